@@ -7,9 +7,14 @@
 pub use anyhow::Result;
 use heck::AsShoutySnakeCase;
 
-use tauri_utils::resources::{external_binaries, resource_relpath, ResourcePaths};
+use tauri_utils::resources::{
+  external_binaries, resource_map_files, resource_relpath, ResourcePaths,
+};
 
-use std::path::{Path, PathBuf};
+use std::{
+  collections::BTreeMap,
+  path::{Path, PathBuf},
+};
 
 #[cfg(feature = "codegen")]
 mod codegen;
@@ -196,7 +201,7 @@ pub fn build() {
 pub fn try_build(attributes: Attributes) -> Result<()> {
   use anyhow::anyhow;
   use cargo_toml::{Dependency, Manifest};
-  use tauri_utils::config::{Config, TauriConfig};
+  use tauri_utils::config::{BundleResources, Config, TauriConfig};
 
   println!("cargo:rerun-if-env-changed=TAURI_CONFIG");
   println!("cargo:rerun-if-changed=tauri.conf.json");
@@ -210,13 +215,35 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   cfg_alias("desktop", !mobile);
   cfg_alias("mobile", mobile);
 
-  let mut config = serde_json::from_value(tauri_utils::config::parse::read_from(
-    std::env::current_dir().unwrap(),
-  )?)?;
-  if let Ok(env) = std::env::var("TAURI_CONFIG") {
-    let merge_config: serde_json::Value = serde_json::from_str(&env)?;
-    json_patch::merge(&mut config, &merge_config);
-  }
+  println!(
+    "cargo:rerun-if-env-changed={}",
+    tauri_utils::config::parse::RESOLVED_CONFIG_ENV
+  );
+  // the configuration resolved by the CLI already includes the merged and interpolated values
+  let config = if let Some((config, path)) = tauri_utils::config::parse::read_resolved()? {
+    println!("cargo:rerun-if-changed={}", path.display());
+    config
+  } else {
+    for base in tauri_utils::config::parse::extended_files(std::env::current_dir().unwrap())? {
+      println!("cargo:rerun-if-changed={}", base.display());
+    }
+
+    let release = tauri_utils::config::parse::is_release_profile();
+    let mut config = serde_json::from_value(tauri_utils::config::parse::read_from(
+      std::env::current_dir().unwrap(),
+    )?)?;
+    tauri_utils::config::parse::apply_profile(&mut config, release);
+    if let Ok(env) = std::env::var("TAURI_CONFIG") {
+      let mut merge_config: serde_json::Value = serde_json::from_str(&env)?;
+      tauri_utils::config::parse::apply_profile(&mut merge_config, release);
+      json_patch::merge(&mut config, &merge_config);
+    }
+    for name in tauri_utils::config::parse::env_variables(&config) {
+      println!("cargo:rerun-if-env-changed={}", name);
+    }
+    tauri_utils::config::parse::interpolate_env(&mut config)?;
+    config
+  };
   let config: Config = serde_json::from_value(config)?;
 
   let s = config.tauri.bundle.identifier.split('.');
@@ -234,7 +261,18 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   cfg_alias("dev", !has_feature("custom-protocol"));
 
   let mut manifest = Manifest::from_path("Cargo.toml")?;
-  if let Some(tauri) = manifest.dependencies.remove("tauri") {
+  // the CLI passes the allowlist features to cargo instead of writing them to the manifest
+  let tauri = if config.build.skip_manifest_rewrite {
+    None
+  } else {
+    take_dependency(&mut manifest.dependencies, "tauri").or_else(|| {
+      manifest
+        .target
+        .values_mut()
+        .find_map(|target| take_dependency(&mut target.dependencies, "tauri"))
+    })
+  };
+  if let Some(tauri) = tauri {
     let features = match tauri {
       Dependency::Simple(_) => Vec::new(),
       Dependency::Detailed(dep) => dep.features,
@@ -299,17 +337,31 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
     .parent()
     .unwrap();
 
-  if let Some(paths) = &config.tauri.bundle.external_bin {
+  let external_bin = config.tauri.bundle.external_bin_for_target(&target_triple);
+  if !external_bin.is_empty() {
     copy_binaries(
-      ResourcePaths::new(external_binaries(paths, &target_triple).as_slice(), true),
+      ResourcePaths::new(
+        external_binaries(&external_bin, &target_triple).as_slice(),
+        true,
+      ),
       &target_triple,
       target_dir,
       manifest.package.as_ref().map(|p| &p.name),
     )?;
   }
 
-  #[allow(unused_mut, clippy::redundant_clone)]
-  let mut resources = config.tauri.bundle.resources.clone().unwrap_or_default();
+  let mut resources = Vec::new();
+  for target_resources in config.tauri.bundle.resources_for_target(&target_triple) {
+    match target_resources {
+      BundleResources::List(list) => resources.extend(list.iter().cloned()),
+      BundleResources::Map(map) => {
+        for (src, target) in resource_map_files(map)? {
+          println!("cargo:rerun-if-changed={}", src.display());
+          copy_file(&src, &target_dir.join(target))?;
+        }
+      }
+    }
+  }
   #[cfg(windows)]
   if let Some(fixed_webview2_runtime_path) = &config.tauri.bundle.windows.webview_fixed_runtime_path
   {
@@ -350,24 +402,31 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
     if window_icon_path.exists() {
       let mut res = WindowsResource::new();
 
-      res.set_manifest(
-        r#"
-        <assembly xmlns="urn:schemas-microsoft-com:asm.v1" manifestVersion="1.0">
-          <dependency>
-              <dependentAssembly>
-                  <assemblyIdentity
-                      type="win32"
-                      name="Microsoft.Windows.Common-Controls"
-                      version="6.0.0.0"
-                      processorArchitecture="*"
-                      publicKeyToken="6595b64144ccf1df"
-                      language="*"
-                  />
-              </dependentAssembly>
-          </dependency>
-        </assembly>
-        "#,
-      );
+      let resource = config
+        .tauri
+        .bundle
+        .windows
+        .resource
+        .clone()
+        .unwrap_or_default();
+      match &resource.manifest {
+        Some(manifest) => {
+          println!("cargo:rerun-if-changed={}", manifest.display());
+          let manifest = std::fs::read_to_string(manifest).with_context(|| {
+            format!(
+              "failed to read the application manifest `{}`",
+              manifest.display()
+            )
+          })?;
+          res.set_manifest(&manifest);
+        }
+        None => {
+          res.set_manifest(&windows_manifest(&resource));
+        }
+      }
+      if let Some(language) = resource.language {
+        res.set_language(language);
+      }
 
       if let Some(sdk_dir) = &attributes.windows_attributes.sdk_dir {
         if let Some(sdk_dir_str) = sdk_dir.to_str() {
@@ -391,6 +450,19 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
         res.set("ProductName", product_name);
         res.set("FileDescription", product_name);
       }
+      if let Some(file_description) = &resource.file_description {
+        res.set("FileDescription", file_description);
+      }
+      if let Some(company_name) = &resource.company_name {
+        res.set("CompanyName", company_name);
+      }
+      if let Some(legal_copyright) = resource
+        .legal_copyright
+        .as_ref()
+        .or(config.tauri.bundle.copyright.as_ref())
+      {
+        res.set("LegalCopyright", legal_copyright);
+      }
       res.set_icon_with_id(&window_icon_path.display().to_string(), "32512");
       res.compile().with_context(|| {
         format!(
@@ -441,6 +513,73 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   Ok(())
 }
 
+/// The default application manifest of the Windows executable, with the DPI awareness and execution level of the configuration.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn windows_manifest(resource: &tauri_utils::config::WindowsResourceConfig) -> String {
+  let mut manifest = String::from(
+    r#"<assembly xmlns="urn:schemas-microsoft-com:asm.v1" manifestVersion="1.0">
+  <dependency>
+    <dependentAssembly>
+      <assemblyIdentity
+        type="win32"
+        name="Microsoft.Windows.Common-Controls"
+        version="6.0.0.0"
+        processorArchitecture="*"
+        publicKeyToken="6595b64144ccf1df"
+        language="*"
+      />
+    </dependentAssembly>
+  </dependency>
+"#,
+  );
+  if let Some(dpi_awareness) = resource.dpi_awareness {
+    manifest.push_str(&format!(
+      r#"  <application xmlns="urn:schemas-microsoft-com:asm.v3">
+    <windowsSettings>
+      <dpiAware xmlns="http://schemas.microsoft.com/SMI/2005/WindowsSettings">{}</dpiAware>
+      <dpiAwareness xmlns="http://schemas.microsoft.com/SMI/2016/WindowsSettings">{}</dpiAwareness>
+    </windowsSettings>
+  </application>
+"#,
+      dpi_awareness.legacy_manifest_value(),
+      dpi_awareness.manifest_value()
+    ));
+  }
+  if let Some(execution_level) = resource.requested_execution_level {
+    manifest.push_str(&format!(
+      r#"  <trustInfo xmlns="urn:schemas-microsoft-com:asm.v3">
+    <security>
+      <requestedPrivileges>
+        <requestedExecutionLevel level="{}" uiAccess="false" />
+      </requestedPrivileges>
+    </security>
+  </trustInfo>
+"#,
+      execution_level.manifest_value()
+    ));
+  }
+  manifest.push_str("</assembly>\n");
+  manifest
+}
+
+/// Removes the dependency on the `package` crate from the set. The dependency can be renamed,
+/// e.g. `tauri-nightly = { git = "https://github.com/tauri-apps/tauri", package = "tauri" }`.
+fn take_dependency(
+  dependencies: &mut BTreeMap<String, cargo_toml::Dependency>,
+  package: &str,
+) -> Option<cargo_toml::Dependency> {
+  let name = dependencies
+    .iter()
+    .find(|(name, dependency)| match dependency {
+      cargo_toml::Dependency::Detailed(detail) if detail.package.is_some() => {
+        detail.package.as_deref() == Some(package)
+      }
+      _ => name.as_str() == package,
+    })
+    .map(|(name, _)| name.clone())?;
+  dependencies.remove(&name)
+}
+
 #[derive(Debug, Default, PartialEq, Eq)]
 struct Diff {
   remove: Vec<String>,
@@ -468,6 +607,26 @@ fn features_diff(current: &[String], expected: &[String]) -> Diff {
 #[cfg(test)]
 mod tests {
   use super::Diff;
+  use tauri_utils::config::{WindowsDpiAwareness, WindowsExecutionLevel, WindowsResourceConfig};
+
+  #[test]
+  fn windows_manifest() {
+    let manifest = super::windows_manifest(&Default::default());
+    assert!(manifest.contains("Microsoft.Windows.Common-Controls"));
+    assert!(!manifest.contains("dpiAwareness"));
+    assert!(!manifest.contains("requestedExecutionLevel"));
+
+    let manifest = super::windows_manifest(&WindowsResourceConfig {
+      dpi_awareness: Some(WindowsDpiAwareness::PerMonitorV2),
+      requested_execution_level: Some(WindowsExecutionLevel::RequireAdministrator),
+      ..Default::default()
+    });
+    assert!(manifest.contains(">true/pm</dpiAware>"));
+    assert!(manifest.contains(">PerMonitorV2</dpiAwareness>"));
+    assert!(manifest
+      .contains(r#"<requestedExecutionLevel level="requireAdministrator" uiAccess="false" />"#));
+    assert!(manifest.trim_end().ends_with("</assembly>"));
+  }
 
   #[test]
   fn array_diff() {
@@ -57,12 +57,24 @@ pub fn get_config(path: &Path) -> Result<(Config, PathBuf), CodegenConfigError>
   // it is impossible for the content of two separate configs to get mixed up. The chances are
   // already unlikely unless the developer goes out of their way to run the cli on a different
   // project than the target crate.
-  let mut config = serde_json::from_value(tauri_utils::config::parse::read_from(parent.clone())?)?;
-  if let Ok(env) = std::env::var("TAURI_CONFIG") {
-    let merge_config: serde_json::Value =
-      serde_json::from_str(&env).map_err(CodegenConfigError::FormatInline)?;
-    json_patch::merge(&mut config, &merge_config);
-  }
+  let config = match tauri_utils::config::parse::read_resolved()? {
+    // the configuration resolved by the CLI already includes the merged and interpolated values
+    Some((config, _)) => config,
+    None => {
+      let release = tauri_utils::config::parse::is_release_profile();
+      let mut config =
+        serde_json::from_value(tauri_utils::config::parse::read_from(parent.clone())?)?;
+      tauri_utils::config::parse::apply_profile(&mut config, release);
+      if let Ok(env) = std::env::var("TAURI_CONFIG") {
+        let mut merge_config: serde_json::Value =
+          serde_json::from_str(&env).map_err(CodegenConfigError::FormatInline)?;
+        tauri_utils::config::parse::apply_profile(&mut merge_config, release);
+        json_patch::merge(&mut config, &merge_config);
+      }
+      tauri_utils::config::parse::interpolate_env(&mut config)?;
+      config
+    }
+  };
 
   let old_cwd = std::env::current_dir().map_err(CodegenConfigError::CurrentDir)?;
   // Set working directory to where `tauri.config.json` is, so that relative paths in it are parsed correctly.
@@ -2960,6 +2960,11 @@ fn create_webview<T: UserEvent>(
     .build()
     .map_err(|e| Error::CreateWebview(Box::new(e)))?;
 
+  #[cfg(any(debug_assertions, feature = "devtools"))]
+  if std::env::var("TAURI_OPEN_DEVTOOLS").as_deref() == Ok("true") {
+    webview.open_devtools();
+  }
+
   #[cfg(windows)]
   {
     let controller = webview.controller();
@@ -27,7 +27,7 @@ use std::{
   collections::HashMap,
   fmt::{self, Display},
   fs::read_to_string,
-  path::PathBuf,
+  path::{Path, PathBuf},
   str::FromStr,
 };
 
@@ -72,10 +72,18 @@ impl Default for WindowUrl {
 pub enum BundleType {
   /// The debian bundle (.deb).
   Deb,
+  /// The RPM bundle (.rpm).
+  Rpm,
+  /// The Flatpak bundle (.flatpak).
+  Flatpak,
   /// The AppImage bundle (.appimage).
   AppImage,
   /// The Microsoft Installer bundle (.msi).
   Msi,
+  /// The NSIS installer bundle (.exe), which can also be built on Linux and macOS.
+  Nsis,
+  /// The MSIX package (.msix) for the Microsoft Store.
+  Msix,
   /// The macOS application bundle (.app).
   App,
   /// The Apple Disk Image bundle (.dmg).
@@ -91,8 +99,12 @@ impl Display for BundleType {
       "{}",
       match self {
         Self::Deb => "deb",
+        Self::Rpm => "rpm",
+        Self::Flatpak => "flatpak",
         Self::AppImage => "appimage",
         Self::Msi => "msi",
+        Self::Nsis => "nsis",
+        Self::Msix => "msix",
         Self::App => "app",
         Self::Dmg => "dmg",
         Self::Updater => "updater",
@@ -118,8 +130,12 @@ impl<'de> Deserialize<'de> for BundleType {
     let s = String::deserialize(deserializer)?;
     match s.to_lowercase().as_str() {
       "deb" => Ok(Self::Deb),
+      "rpm" => Ok(Self::Rpm),
+      "flatpak" => Ok(Self::Flatpak),
       "appimage" => Ok(Self::AppImage),
       "msi" => Ok(Self::Msi),
+      "nsis" => Ok(Self::Nsis),
+      "msix" => Ok(Self::Msix),
       "app" => Ok(Self::App),
       "dmg" => Ok(Self::Dmg),
       "updater" => Ok(Self::Updater),
@@ -241,6 +257,7 @@ impl BundleTarget {
 }
 
 /// Configuration for AppImage bundles.
+#[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -249,6 +266,20 @@ pub struct AppImageConfig {
   /// This increases the bundle size by ~15-35MB depending on your build system.
   #[serde(default, alias = "bundle-media-framework")]
   pub bundle_media_framework: bool,
+  /// The update information embedded in the AppImage for AppImageUpdate, e.g. `gh-releases-zsync|owner|repo|latest|*x86_64.AppImage.zsync`.
+  ///
+  /// The `.zsync` file generated next to the AppImage must be uploaded along with it.
+  #[serde(alias = "update-information")]
+  pub update_information: Option<String>,
+  /// Additional keys of the desktop entry, e.g. `{ "StartupWMClass": "my-app" }`. Overrides the generated keys with the same name.
+  #[serde(default, alias = "desktop-entry")]
+  pub desktop_entry: HashMap<String, String>,
+  /// Patterns of the libraries that must not be bundled, e.g. `libnss3.so*`, passed to `linuxdeploy --exclude-library`.
+  #[serde(default, alias = "exclude-libraries")]
+  pub exclude_libraries: Vec<String>,
+  /// Path to a custom `AppRun` executable or script, used instead of the AppRun binary of AppImageKit.
+  #[serde(alias = "app-run")]
+  pub app_run: Option<PathBuf>,
 }
 
 /// Configuration for Debian (.deb) bundles.
@@ -259,9 +290,173 @@ pub struct AppImageConfig {
 pub struct DebConfig {
   /// The list of deb dependencies your application relies on.
   pub depends: Option<Vec<String>>,
+  /// The tray icon library the package depends on when the app has a system tray.
+  ///
+  /// Defaults to the library selected by the `TAURI_TRAY` environment variable (`ayatana` or `appindicator`) if set,
+  /// otherwise to either of them, so the package installs on both Debian and Ubuntu derivatives.
+  #[serde(alias = "tray-dependency")]
+  pub tray_dependency: Option<DebTrayDependency>,
+  /// The list of packages recommended alongside your application, installed by default by `apt`.
+  pub recommends: Option<Vec<String>>,
+  /// The list of packages that enhance your application, which are not installed by default.
+  pub suggests: Option<Vec<String>>,
+  /// The list of virtual packages your application provides.
+  pub provides: Option<Vec<String>>,
+  /// The list of packages that cannot be installed alongside your application.
+  pub conflicts: Option<Vec<String>>,
+  /// The list of packages whose files your application replaces, e.g. a previous package name.
+  pub replaces: Option<Vec<String>>,
+  /// The section of the package in the archive, e.g. `utils` or `net`.
+  pub section: Option<String>,
+  /// The priority of the package, e.g. `optional` or `extra`. Defaults to `optional`.
+  pub priority: Option<String>,
   /// The files to include on the package.
   #[serde(default)]
   pub files: HashMap<PathBuf, PathBuf>,
+  /// The paths on the package of the configuration files that must keep the user changes on upgrades.
+  ///
+  /// The files included under `/etc` are always declared as configuration files.
+  #[serde(default)]
+  pub conffiles: Vec<PathBuf>,
+  /// Path to a systemd user unit (`.service` file) installed to `/usr/lib/systemd/user`
+  /// and enabled for all users when the package is installed.
+  #[serde(alias = "systemd-user-unit")]
+  pub systemd_user_unit: Option<PathBuf>,
+  /// Path to the `preinst` maintainer script, run before the package is installed.
+  #[serde(alias = "pre-install-script")]
+  pub pre_install_script: Option<PathBuf>,
+  /// Path to the `postinst` maintainer script, run after the package is installed.
+  ///
+  /// When a systemd user unit is configured, the line `#DEBHELPER#` is replaced with the commands enabling it,
+  /// which are otherwise added after the shebang.
+  #[serde(alias = "post-install-script")]
+  pub post_install_script: Option<PathBuf>,
+  /// Path to the `prerm` maintainer script, run before the package is removed.
+  ///
+  /// When a systemd user unit is configured, the line `#DEBHELPER#` is replaced with the commands disabling it,
+  /// which are otherwise added after the shebang.
+  #[serde(alias = "pre-remove-script")]
+  pub pre_remove_script: Option<PathBuf>,
+  /// Path to the `postrm` maintainer script, run after the package is removed.
+  #[serde(alias = "post-remove-script")]
+  pub post_remove_script: Option<PathBuf>,
+}
+
+/// The tray icon library of the Debian package dependencies.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum DebTrayDependency {
+  /// Depends on `libayatana-appindicator3-1`.
+  Ayatana,
+  /// Depends on `libappindicator3-1`.
+  Appindicator,
+  /// Depends on `libayatana-appindicator3-1 | libappindicator3-1`, satisfied by either library.
+  Either,
+}
+
+/// Configuration for RPM bundles.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RpmConfig {
+  /// The list of RPM dependencies your application relies on.
+  pub depends: Option<Vec<String>>,
+  /// The package release number, to increase when the package changes without a new app version. Defaults to `1`.
+  pub release: Option<String>,
+  /// The package epoch, to increase when the versioning scheme changes so the new versions still upgrade the old ones.
+  pub epoch: Option<u32>,
+  /// The license of the package, e.g. `MIT or ASL 2.0`. Defaults to `Unknown`.
+  pub license: Option<String>,
+  /// Path to a custom desktop file Handlebars template.
+  ///
+  /// Available variables: `categories`, `comment` (optional), `exec`, `icon`, `mime_type` (optional) and `name`.
+  #[serde(alias = "desktop-template")]
+  pub desktop_template: Option<PathBuf>,
+  /// The files to include on the package.
+  #[serde(default)]
+  pub files: HashMap<PathBuf, PathBuf>,
+  /// Path to a shell script run before the package is installed.
+  #[serde(alias = "pre-install-script")]
+  pub pre_install_script: Option<PathBuf>,
+  /// Path to a shell script run after the package is installed.
+  #[serde(alias = "post-install-script")]
+  pub post_install_script: Option<PathBuf>,
+  /// Path to a shell script run before the package is removed.
+  #[serde(alias = "pre-remove-script")]
+  pub pre_remove_script: Option<PathBuf>,
+  /// Path to a shell script run after the package is removed.
+  #[serde(alias = "post-remove-script")]
+  pub post_remove_script: Option<PathBuf>,
+}
+
+/// Configuration for Flatpak bundles.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FlatpakConfig {
+  /// The runtime providing the system libraries, which must include webkit2gtk. Defaults to `org.gnome.Platform`.
+  pub runtime: Option<String>,
+  /// The version of the runtime. Defaults to `43`.
+  #[serde(alias = "runtime-version")]
+  pub runtime_version: Option<String>,
+  /// The SDK used to build the Flatpak. Defaults to `org.gnome.Sdk`.
+  pub sdk: Option<String>,
+  /// Sandbox permissions added to the ones derived from the allowlist, e.g. `--filesystem=xdg-download`.
+  ///
+  /// The app can always use the display, the GPU and IPC.
+  /// The network is shared when the HTTP API or the updater is enabled and the home directory when the file system API is enabled.
+  /// The notification and tray D-Bus services are allowed when the notification API or the system tray are enabled.
+  #[serde(default, alias = "finish-args")]
+  pub finish_args: Vec<String>,
+}
+
+/// An additional action of the desktop entry, launching the app with other arguments.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DesktopActionConfig {
+  /// The action identifier, made of letters, digits and `-`.
+  pub id: String,
+  /// The action name displayed by the launcher.
+  pub name: String,
+  /// The arguments passed to the app.
+  #[serde(default, alias = "exec-args")]
+  pub exec_args: Vec<String>,
+}
+
+/// The desktop entry launching the app on Linux.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DesktopEntryConfig {
+  /// The `Categories` of the entry, e.g. `["Development", "IDE"]`. Defaults to the categories matching `tauri > bundle > category`.
+  pub categories: Option<Vec<String>>,
+  /// The `Keywords` the launchers match when searching for the app.
+  #[serde(default)]
+  pub keywords: Vec<String>,
+  /// The `StartupWMClass` of the entry, the window class of the app windows, so the desktop groups them under the entry.
+  #[serde(alias = "startup-wm-class")]
+  pub startup_wm_class: Option<String>,
+  /// The arguments passed to the app by the `Exec` key of the entry.
+  #[serde(default, alias = "exec-args")]
+  pub exec_args: Vec<String>,
+  /// Additional actions of the entry, listed in the context menu of the launchers.
+  #[serde(default)]
+  pub actions: Vec<DesktopActionConfig>,
+}
+
+/// Configuration for the Linux bundles.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LinuxConfig {
+  /// The desktop entry of the Debian, RPM, AppImage and Flatpak bundles.
+  #[serde(default)]
+  pub desktop: DesktopEntryConfig,
 }
 
 fn de_minimum_system_version<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
@@ -307,10 +502,24 @@ pub struct MacConfig {
   #[serde(alias = "signing-identity")]
   pub signing_identity: Option<String>,
   /// Provider short name for notarization.
+  ///
+  /// Used as the notarization team ID when `notarization > teamId` is not set.
   #[serde(alias = "provider-short-name")]
   pub provider_short_name: Option<String>,
   /// Path to the entitlements file.
   pub entitlements: Option<String>,
+  /// The layout of the DMG window.
+  #[serde(default)]
+  pub dmg: DmgConfig,
+  /// The notarization of the app bundle and the DMG, done after signing them.
+  #[serde(default)]
+  pub notarization: NotarizationConfig,
+  /// Entries deep-merged into the bundle's `Info.plist`, after the `Info.plist` file next to the Tauri configuration.
+  ///
+  /// Dictionaries are merged recursively, a `null` value removes the key and any other value replaces it,
+  /// e.g. `{ "NSCameraUsageDescription": "...", "CFBundleURLTypes": [{ "CFBundleURLSchemes": ["myapp"] }] }`.
+  #[serde(alias = "info-plist")]
+  pub info_plist: Option<serde_json::Map<String, JsonValue>>,
 }
 
 impl Default for MacConfig {
@@ -323,6 +532,9 @@ impl Default for MacConfig {
       signing_identity: None,
       provider_short_name: None,
       entitlements: None,
+      dmg: Default::default(),
+      notarization: Default::default(),
+      info_plist: None,
     }
   }
 }
@@ -331,6 +543,117 @@ fn minimum_system_version() -> Option<String> {
   Some("10.13".into())
 }
 
+/// Position coordinates, in points.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct Position {
+  /// X coordinate.
+  pub x: u32,
+  /// Y coordinate.
+  pub y: u32,
+}
+
+/// Size, in points.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct Size {
+  /// Width.
+  pub width: u32,
+  /// Height.
+  pub height: u32,
+}
+
+/// Configuration for the Apple Disk Image (.dmg) bundle window, where the app is dragged to the Applications folder.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DmgConfig {
+  /// Path to the background image of the window. Accepted formats: `png`, `jpg` and `gif`.
+  pub background: Option<PathBuf>,
+  /// Position of the window on the screen.
+  #[serde(alias = "window-position")]
+  pub window_position: Option<Position>,
+  /// Size of the window. Defaults to 660 × 400.
+  #[serde(default = "dmg_window_size", alias = "window-size")]
+  pub window_size: Size,
+  /// Size of the icons in the window. Defaults to 128.
+  #[serde(alias = "icon-size")]
+  pub icon_size: Option<u32>,
+  /// Position of the app icon in the window. Defaults to (180, 170).
+  #[serde(default = "dmg_app_position", alias = "app-position")]
+  pub app_position: Position,
+  /// Position of the Applications folder link in the window. Defaults to (480, 170).
+  #[serde(
+    default = "dmg_application_folder_position",
+    alias = "application-folder-position"
+  )]
+  pub application_folder_position: Position,
+}
+
+impl Default for DmgConfig {
+  fn default() -> Self {
+    Self {
+      background: None,
+      window_position: None,
+      window_size: dmg_window_size(),
+      icon_size: None,
+      app_position: dmg_app_position(),
+      application_folder_position: dmg_application_folder_position(),
+    }
+  }
+}
+
+fn dmg_window_size() -> Size {
+  Size {
+    width: 660,
+    height: 400,
+  }
+}
+
+fn dmg_app_position() -> Position {
+  Position { x: 180, y: 170 }
+}
+
+fn dmg_application_folder_position() -> Position {
+  Position { x: 480, y: 170 }
+}
+
+/// Credentials for the notarization of the macOS bundles with `notarytool`.
+///
+/// Authenticate either with an Apple ID, whose app-specific password is read from the `APPLE_PASSWORD` environment variable,
+/// or with an App Store Connect API key. The `APPLE_ID`, `APPLE_TEAM_ID`, `APPLE_API_KEY`, `APPLE_API_ISSUER`
+/// and `APPLE_API_KEY_PATH` environment variables take precedence over these values.
+///
+/// The bundles are only notarized when a signing identity is set and credentials are found.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NotarizationConfig {
+  /// The Apple ID used to notarize the app.
+  #[serde(alias = "apple-id")]
+  pub apple_id: Option<String>,
+  /// The team ID of the Apple ID.
+  ///
+  /// Defaults to the provider short name, then to the team ID of the signing identity.
+  /// Notarization is skipped with a warning when the Apple ID team cannot be resolved.
+  #[serde(alias = "team-id")]
+  pub team_id: Option<String>,
+  /// The ID of the App Store Connect API key.
+  #[serde(alias = "api-key")]
+  pub api_key: Option<String>,
+  /// The issuer ID of the App Store Connect API key.
+  #[serde(alias = "api-issuer")]
+  pub api_issuer: Option<String>,
+  /// Path to the `AuthKey_<apiKey>.p8` private key file.
+  /// Defaults to the first one found in the `./private_keys`, `~/private_keys`, `~/.private_keys` and `~/.appstoreconnect/private_keys` directories.
+  #[serde(alias = "api-key-path")]
+  pub api_key_path: Option<PathBuf>,
+}
+
 /// Configuration for a target language for the WiX build.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -368,6 +691,12 @@ pub struct WixConfig {
   /// The installer languages to build. See <https://docs.microsoft.com/en-us/windows/win32/msi/localizing-the-error-and-actiontext-tables>.
   #[serde(default)]
   pub language: WixLanguage,
+  /// Builds a single installer for all the languages instead of one per language.
+  ///
+  /// The installer of the first language embeds a transform for each other language,
+  /// applied by Windows Installer when it matches the user language. Requires the Windows 10 SDK.
+  #[serde(default, alias = "embed-language-transforms")]
+  pub embed_language_transforms: bool,
   /// A custom .wxs template to use.
   pub template: Option<PathBuf>,
   /// A list of paths to .wxs files with WiX fragments to use.
@@ -388,6 +717,11 @@ pub struct WixConfig {
   /// The Merge element ids you want to reference from the fragments.
   #[serde(default, alias = "merge-refs")]
   pub merge_refs: Vec<String>,
+  /// The Dialog element ids from the fragments to show, in order, between the install directory and the install confirmation dialogs.
+  ///
+  /// Each dialog must have a `Back` and a `Next` push button control, which are wired to the previous and next dialogs.
+  #[serde(default, alias = "custom-dialogs")]
+  pub custom_dialogs: Vec<String>,
   /// Disables the Webview2 runtime installation after app install.
   ///
   /// Will be removed in v2, prefer the [`WindowsConfig::webview_install_mode`] option.
@@ -414,6 +748,92 @@ pub struct WixConfig {
   pub dialog_image_path: Option<PathBuf>,
 }
 
+/// Install modes for the NSIS installer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum NsisInstallMode {
+  /// Installs the app in `%LOCALAPPDATA%` for the current user only, without requiring admin privileges.
+  CurrentUser,
+  /// Installs the app in `%PROGRAMFILES%` for all users. Requires admin privileges.
+  PerMachine,
+}
+
+impl Default for NsisInstallMode {
+  fn default() -> Self {
+    Self::CurrentUser
+  }
+}
+
+/// Configuration for the installer generated with NSIS.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NsisConfig {
+  /// A custom .nsi template to use.
+  pub template: Option<PathBuf>,
+  /// The path to the license file rendered on a page of the installer.
+  pub license: Option<PathBuf>,
+  /// Path to a bitmap file displayed on the header of the installer pages.
+  ///
+  /// The recommended dimensions are 150px × 57px.
+  #[serde(alias = "header-image")]
+  pub header_image: Option<PathBuf>,
+  /// Path to a bitmap file displayed on the welcome and finish pages.
+  ///
+  /// The recommended dimensions are 164px × 314px.
+  #[serde(alias = "sidebar-image")]
+  pub sidebar_image: Option<PathBuf>,
+  /// Whether the app is installed for the current user or for all users. Defaults to `currentUser`.
+  #[serde(default, alias = "install-mode")]
+  pub install_mode: NsisInstallMode,
+  /// The installer languages, using the NSIS language names e.g. `English` or `SimpChinese`.
+  /// See <https://github.com/kichik/nsis/tree/master/Contrib/Language%20files>.
+  ///
+  /// Defaults to `["English"]`. The first language is the default one.
+  pub languages: Option<Vec<String>>,
+  /// Whether to ask the user to choose the installer language when more than one is configured.
+  #[serde(default, alias = "display-language-selector")]
+  pub display_language_selector: bool,
+  /// A list of paths to NSIS scripts adding custom pages to the installer.
+  ///
+  /// Each script is included before the installation page and must declare its page
+  /// with `Page custom <create function> [<leave function>]` along with the page functions.
+  #[serde(default, alias = "custom-pages")]
+  pub custom_pages: Vec<PathBuf>,
+}
+
+/// Configuration for the MSIX package.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MsixConfig {
+  /// The package identity name. Defaults to the bundle identifier.
+  ///
+  /// The Microsoft Store assigns this value when the app is reserved in Partner Center.
+  #[serde(alias = "identity-name")]
+  pub identity_name: Option<String>,
+  /// The publisher of the package, which must match the subject of the signing certificate
+  /// e.g. `CN=Contoso Software, O=Contoso Corporation, C=US`.
+  ///
+  /// Defaults to `CN=<second part of the bundle identifier>`.
+  pub publisher: Option<String>,
+  /// The publisher name displayed to users. Defaults to the second part of the bundle identifier.
+  #[serde(alias = "publisher-display-name")]
+  pub publisher_display_name: Option<String>,
+  /// The minimum Windows version supported by the package. Defaults to `10.0.17763.0`.
+  #[serde(alias = "min-version")]
+  pub min_version: Option<String>,
+  /// Capabilities added to the ones derived from the allowlist, e.g. `webcam` or `documentsLibrary`.
+  ///
+  /// The package always declares the `runFullTrust` capability required by desktop apps,
+  /// and `internetClient` when the HTTP API or the updater is enabled.
+  #[serde(default)]
+  pub capabilities: Vec<String>,
+}
+
 /// Install modes for the Webview2 runtime.
 /// Note that for the updater bundle [`Self::DownloadBootstrapper`] is used.
 ///
@@ -471,6 +891,130 @@ impl Default for WebviewInstallMode {
   }
 }
 
+/// A code signing method for the Windows bundles, used in CI environments where the certificate lives in a cloud HSM.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WindowsSignMethod {
+  /// Signs with [AzureSignTool](https://github.com/vcsjones/AzureSignTool) and a certificate stored in Azure Key Vault.
+  ///
+  /// The client ID and tenant ID can also be set with the `AZURE_CLIENT_ID` and `AZURE_TENANT_ID` environment variables.
+  /// The client secret is read from the `AZURE_CLIENT_SECRET` environment variable; without it the managed identity of the machine is used.
+  #[serde(rename_all = "camelCase")]
+  AzureKeyVault {
+    /// The URL of the Azure Key Vault, e.g. `https://my-vault.vault.azure.net`.
+    #[serde(alias = "vault-url")]
+    vault_url: String,
+    /// The name of the certificate in the vault.
+    #[serde(alias = "certificate-name")]
+    certificate_name: String,
+    /// The client ID of the application authenticating with the vault.
+    #[serde(alias = "client-id")]
+    client_id: Option<String>,
+    /// The tenant ID of the application authenticating with the vault.
+    #[serde(alias = "tenant-id")]
+    tenant_id: Option<String>,
+  },
+  /// Runs a custom command for each file to sign, e.g. the client of a cloud HSM.
+  Command {
+    /// The program to run.
+    command: String,
+    /// The arguments of the command. `%1` is replaced with the path of the file to sign.
+    #[serde(default)]
+    args: Vec<String>,
+  },
+}
+
+/// The execution level requested by the application manifest of the Windows executable.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WindowsExecutionLevel {
+  /// Runs with the privileges of the process starting the app.
+  AsInvoker,
+  /// Runs with the highest privileges available to the user, prompting administrators for elevation.
+  HighestAvailable,
+  /// Requires administrator privileges, always prompting for elevation.
+  RequireAdministrator,
+}
+
+impl WindowsExecutionLevel {
+  /// The `requestedExecutionLevel` value of the application manifest.
+  pub fn manifest_value(&self) -> &'static str {
+    match self {
+      Self::AsInvoker => "asInvoker",
+      Self::HighestAvailable => "highestAvailable",
+      Self::RequireAdministrator => "requireAdministrator",
+    }
+  }
+}
+
+/// The DPI awareness declared by the application manifest of the Windows executable.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WindowsDpiAwareness {
+  /// The app is scaled by the system.
+  Unaware,
+  /// The app scales to the DPI of the primary display when it starts.
+  System,
+  /// The app scales to the DPI of the display its windows are on.
+  PerMonitor,
+  /// Per monitor scaling, with the non client area and the dialogs scaled by the system. Requires Windows 10 1703.
+  PerMonitorV2,
+}
+
+impl WindowsDpiAwareness {
+  /// The `dpiAwareness` value of the application manifest.
+  pub fn manifest_value(&self) -> &'static str {
+    match self {
+      Self::Unaware => "unaware",
+      Self::System => "system",
+      Self::PerMonitor => "PerMonitor",
+      Self::PerMonitorV2 => "PerMonitorV2",
+    }
+  }
+
+  /// The legacy `dpiAware` value of the application manifest, used before Windows 10 1607.
+  pub fn legacy_manifest_value(&self) -> &'static str {
+    match self {
+      Self::Unaware => "false",
+      Self::System => "true",
+      Self::PerMonitor | Self::PerMonitorV2 => "true/pm",
+    }
+  }
+}
+
+/// The version resource and application manifest embedded in the Windows executable by `tauri-build`.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WindowsResourceConfig {
+  /// The `FileDescription` of the version resource, displayed by the Task Manager. Defaults to the product name.
+  #[serde(alias = "file-description")]
+  pub file_description: Option<String>,
+  /// The `CompanyName` of the version resource.
+  #[serde(alias = "company-name")]
+  pub company_name: Option<String>,
+  /// The `LegalCopyright` of the version resource. Defaults to `tauri > bundle > copyright`.
+  #[serde(alias = "legal-copyright")]
+  pub legal_copyright: Option<String>,
+  /// The language of the version resource, as a Windows language identifier, e.g. `1033` (`0x0409`) for English (United States).
+  pub language: Option<u16>,
+  /// Path to an application manifest replacing the default one, relative to the `src-tauri` folder.
+  ///
+  /// It must declare the dependency on the version 6 of `Microsoft.Windows.Common-Controls` used by the dialogs.
+  pub manifest: Option<PathBuf>,
+  /// The DPI awareness declared in the default application manifest.
+  #[serde(alias = "dpi-awareness")]
+  pub dpi_awareness: Option<WindowsDpiAwareness>,
+  /// The `requestedExecutionLevel` of the default application manifest.
+  #[serde(alias = "requested-execution-level")]
+  pub requested_execution_level: Option<WindowsExecutionLevel>,
+}
+
 /// Windows bundler configuration.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -483,6 +1027,9 @@ pub struct WindowsConfig {
   /// Specifies the SHA1 hash of the signing certificate.
   #[serde(alias = "certificate-thumbprint")]
   pub certificate_thumbprint: Option<String>,
+  /// Signs with a certificate stored in a cloud HSM instead of the local certificate store.
+  /// Overwrites [`Self::certificate_thumbprint`] if set.
+  pub sign: Option<WindowsSignMethod>,
   /// Server to use during timestamping.
   #[serde(alias = "timestamp-url")]
   pub timestamp_url: Option<String>,
@@ -510,6 +1057,12 @@ pub struct WindowsConfig {
   pub allow_downgrades: bool,
   /// Configuration for the MSI generated with WiX.
   pub wix: Option<WixConfig>,
+  /// Configuration for the installer generated with NSIS.
+  pub nsis: Option<NsisConfig>,
+  /// Configuration for the MSIX package.
+  pub msix: Option<MsixConfig>,
+  /// The version resource and application manifest of the executable.
+  pub resource: Option<WindowsResourceConfig>,
 }
 
 impl Default for WindowsConfig {
@@ -517,12 +1070,16 @@ impl Default for WindowsConfig {
     Self {
       digest_algorithm: None,
       certificate_thumbprint: None,
+      sign: None,
       timestamp_url: None,
       tsp: false,
       webview_install_mode: Default::default(),
       webview_fixed_runtime_path: None,
       allow_downgrades: default_allow_downgrades(),
       wix: None,
+      nsis: None,
+      msix: None,
+      resource: None,
     }
   }
 }
@@ -531,6 +1088,95 @@ fn default_allow_downgrades() -> bool {
   true
 }
 
+/// Size optimizations applied to the app binary after release builds.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BundleOptimizeConfig {
+  /// Strip the debug symbols from the binary before it is bundled and print its size breakdown by crate.
+  ///
+  /// Requires the `strip` and `nm` tools on Linux and macOS. Ignored on Windows, where the symbols are stored in a separate `.pdb` file.
+  #[serde(default)]
+  pub strip: bool,
+}
+
+/// The app's role with respect to a file type, used as the `CFBundleTypeRole` on macOS.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum BundleTypeRole {
+  /// The app can read and edit the files.
+  Editor,
+  /// The app can read the files but not edit them.
+  Viewer,
+  /// The app provides runtime services for the files.
+  Shell,
+  /// The app declares the file type but cannot open the files.
+  None,
+}
+
+impl Default for BundleTypeRole {
+  fn default() -> Self {
+    Self::Editor
+  }
+}
+
+impl BundleTypeRole {
+  /// The `CFBundleTypeRole` value of the Info.plist.
+  pub fn plist_value(&self) -> &'static str {
+    match self {
+      Self::Editor => "Editor",
+      Self::Viewer => "Viewer",
+      Self::Shell => "Shell",
+      Self::None => "None",
+    }
+  }
+}
+
+/// A file type opened by the application.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FileAssociation {
+  /// The file extensions, without the leading dot, e.g. `["png", "jpg"]`.
+  pub ext: Vec<String>,
+  /// The name of the file type. Defaults to the first extension.
+  pub name: Option<String>,
+  /// The description of the file type, displayed by the file managers.
+  pub description: Option<String>,
+  /// The app's role with respect to the file type. Only used on macOS.
+  #[serde(default)]
+  pub role: BundleTypeRole,
+  /// The MIME type of the files, e.g. `image/png`.
+  ///
+  /// Required to register the association on Linux, where a shared-mime-info definition
+  /// matching the extensions is installed for it.
+  #[serde(alias = "mime-type")]
+  pub mime_type: Option<String>,
+}
+
+impl FileAssociation {
+  /// The name of the file type: [`Self::name`] or the first extension.
+  pub fn name(&self) -> &str {
+    self
+      .name
+      .as_deref()
+      .or_else(|| self.ext.first().map(String::as_str))
+      .unwrap_or_default()
+  }
+}
+
+/// The URL schemes opening the application.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DeepLinkConfig {
+  /// The URL schemes, without the `://` suffix, e.g. `["myapp"]`.
+  #[serde(default)]
+  pub schemes: Vec<String>,
+}
+
 /// Configuration for tauri-bundler.
 #[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
@@ -540,7 +1186,9 @@ pub struct BundleConfig {
   /// Whether Tauri should bundle your application or just output the executable.
   #[serde(default)]
   pub active: bool,
-  /// The bundle targets, currently supports ["deb", "appimage", "msi", "app", "dmg", "updater"] or "all".
+  /// The bundle targets, currently supports ["deb", "rpm", "flatpak", "appimage", "msi", "nsis", "msix", "app", "dmg", "updater"] or "all".
+  ///
+  /// The `rpm`, `flatpak` and `msix` bundles and the `nsis` bundle on Windows hosts are opt-in, so "all" does not include them.
   #[serde(default)]
   pub targets: BundleTarget,
   /// The application identifier in reverse domain name notation (e.g. `com.tauri.example`).
@@ -555,7 +1203,14 @@ pub struct BundleConfig {
   /// App resources to bundle.
   /// Each resource is a path to a file or directory.
   /// Glob patterns are supported.
-  pub resources: Option<Vec<String>>,
+  ///
+  /// A map of paths or glob patterns to their destination in the resources directory is also accepted,
+  /// e.g. `{ "../assets/**/*.png": "images/" }`.
+  pub resources: Option<BundleResources>,
+  /// Resources only bundled for a target, keyed by platform (`linux`, `macos`, `windows`, `android`, `ios`) or target triple,
+  /// e.g. a DLL only needed on Windows. They are bundled in addition to [`Self::resources`].
+  #[serde(default, alias = "target-resources")]
+  pub target_resources: HashMap<String, BundleResources>,
   /// A copyright string associated with your application.
   pub copyright: Option<String>,
   /// The application kind.
@@ -569,12 +1224,29 @@ pub struct BundleConfig {
   /// A longer, multi-line description of the application.
   #[serde(alias = "long-description")]
   pub long_description: Option<String>,
+  /// The file name of the bundles, without the extension, e.g. `{name}-{version}-{arch}`.
+  ///
+  /// Available placeholders: `{name}` (the main binary name), `{version}`, `{target}` (the target triple),
+  /// `{arch}` (the architecture name used by the bundle format, e.g. `amd64` for Debian packages)
+  /// and `{format}` (the bundle short name, e.g. `deb` or `nsis`).
+  /// MSI installers append `_{language}` when several languages are built, unless the name has a `{language}` placeholder.
+  #[serde(alias = "artifact-name")]
+  pub artifact_name: Option<String>,
   /// Configuration for the AppImage bundle.
   #[serde(default)]
   pub appimage: AppImageConfig,
   /// Configuration for the Debian bundle.
   #[serde(default)]
   pub deb: DebConfig,
+  /// Configuration for the RPM bundle.
+  #[serde(default)]
+  pub rpm: RpmConfig,
+  /// Configuration for the Flatpak bundle.
+  #[serde(default)]
+  pub flatpak: FlatpakConfig,
+  /// Configuration shared by the Linux bundles.
+  #[serde(default)]
+  pub linux: LinuxConfig,
   /// Configuration for the macOS bundles.
   #[serde(rename = "macOS", default)]
   pub macos: MacConfig,
@@ -591,12 +1263,147 @@ pub struct BundleConfig {
   /// so don't forget to provide binaries for all targeted platforms.
   #[serde(alias = "external-bin")]
   pub external_bin: Option<Vec<String>>,
+  /// External binaries only bundled for a target, keyed by platform (`linux`, `macos`, `windows`, `android`, `ios`) or target triple.
+  /// They are bundled in addition to [`Self::external_bin`].
+  #[serde(default, alias = "target-external-bin")]
+  pub target_external_bin: HashMap<String, Vec<String>>,
+  /// Download sources of the external binaries, by external binary path and target triple.
+  ///
+  /// When building for a listed target, the CLI downloads the binary to the target directory,
+  /// verifies its SHA-256 checksum and copies it to the external binary path with the target triple suffix.
+  #[serde(default, alias = "external-bin-sources")]
+  pub external_bin_sources: HashMap<String, HashMap<String, ExternalBinSource>>,
   /// Configuration for the Windows bundle.
   #[serde(default)]
   pub windows: WindowsConfig,
   /// iOS configuration.
   #[serde(rename = "iOS", default)]
   pub ios: IosConfig,
+  /// Android configuration.
+  #[serde(default)]
+  pub android: AndroidConfig,
+  /// Size optimizations applied to the app binary after release builds.
+  #[serde(default)]
+  pub optimize: BundleOptimizeConfig,
+  /// A command invoked to sign the app binaries before they are bundled and every bundle produced,
+  /// in addition to the built-in signers, e.g. for signserver, cosign or osslsigncode.
+  #[serde(alias = "sign-command")]
+  pub sign_command: Option<SignCommandConfig>,
+  /// The file types opened by the application, registered in the Info.plist on macOS,
+  /// the registry by the Windows installers and the desktop file and shared MIME database on Linux.
+  #[serde(alias = "file-associations")]
+  pub file_associations: Option<Vec<FileAssociation>>,
+  /// The URL schemes opening the application, registered like the [`Self::file_associations`].
+  ///
+  /// The URL is passed to the app as a command line argument.
+  #[serde(default, alias = "deep-link")]
+  pub deep_link: DeepLinkConfig,
+}
+
+impl BundleConfig {
+  /// The resources bundled for the target triple: [`Self::resources`] and the matching [`Self::target_resources`].
+  pub fn resources_for_target(&self, target_triple: &str) -> Vec<&BundleResources> {
+    self
+      .resources
+      .iter()
+      .chain(matching_targets(&self.target_resources, target_triple))
+      .collect()
+  }
+
+  /// The external binaries bundled for the target triple: [`Self::external_bin`] and the matching [`Self::target_external_bin`].
+  pub fn external_bin_for_target(&self, target_triple: &str) -> Vec<String> {
+    self
+      .external_bin
+      .iter()
+      .chain(matching_targets(&self.target_external_bin, target_triple))
+      .flatten()
+      .cloned()
+      .collect()
+  }
+}
+
+/// The values of the per-target map matching the target triple, the platform entries before the target triple ones.
+fn matching_targets<'a, T>(map: &'a HashMap<String, T>, target_triple: &str) -> Vec<&'a T> {
+  let platform = if target_triple.contains("windows") {
+    "windows"
+  } else if target_triple.contains("apple-darwin") {
+    "macos"
+  } else if target_triple.contains("apple-ios") {
+    "ios"
+  } else if target_triple.contains("android") {
+    "android"
+  } else if target_triple.contains("linux") {
+    "linux"
+  } else {
+    ""
+  };
+  map
+    .get(platform)
+    .into_iter()
+    .chain(map.get(target_triple))
+    .collect()
+}
+
+/// A download source of an external binary.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExternalBinSource {
+  /// The URL of the binary.
+  pub url: String,
+  /// The SHA-256 checksum of the binary, hex encoded.
+  pub sha256: String,
+}
+
+/// The resources to bundle.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum BundleResources {
+  /// A list of paths or glob patterns.
+  /// Each file keeps its path in the resources directory, with `..` replaced with `_up_`.
+  List(Vec<String>),
+  /// A map of paths or glob patterns to their destination in the resources directory.
+  ///
+  /// A directory or a glob pattern is copied to the destination directory, keeping the file paths relative
+  /// to the directory or to the part of the pattern preceding the first wildcard.
+  /// A file is copied to the destination path, or into it when the destination ends with `/`.
+  Map(HashMap<String, String>),
+}
+
+/// A command signing a file. `%1` in the arguments is replaced with the path of the file.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum SignCommandConfig {
+  /// A command line split on whitespace, e.g. `"cosign sign-blob --yes --output-signature %1.sig %1"`.
+  CommandLine(String),
+  /// The program and its arguments.
+  Command {
+    /// The program to run.
+    command: String,
+    /// The arguments of the program.
+    #[serde(default)]
+    args: Vec<String>,
+  },
+}
+
+impl SignCommandConfig {
+  /// The program to run.
+  pub fn command(&self) -> &str {
+    match self {
+      Self::CommandLine(line) => line.split_whitespace().next().unwrap_or_default(),
+      Self::Command { command, .. } => command,
+    }
+  }
+
+  /// The arguments of the program.
+  pub fn args(&self) -> Vec<String> {
+    match self {
+      Self::CommandLine(line) => line.split_whitespace().skip(1).map(Into::into).collect(),
+      Self::Command { args, .. } => args.clone(),
+    }
+  }
 }
 
 /// A CLI argument definition.
@@ -2187,6 +2994,15 @@ impl WindowsUpdateInstallMode {
       Self::Passive => &["/passive"],
     }
   }
+
+  /// Returns the associated arguments of the NSIS installer.
+  pub fn nsis_args(&self) -> &'static [&'static str] {
+    match self {
+      Self::BasicUi => &[],
+      Self::Quiet => &["/S"],
+      Self::Passive => &["/P"],
+    }
+  }
 }
 
 impl Display for WindowsUpdateInstallMode {
@@ -2247,6 +3063,33 @@ pub struct UpdaterWindowsConfig {
   pub install_mode: WindowsUpdateInstallMode,
 }
 
+/// The `latest.json` manifest of the signed updater archives, written by `tauri build` in the Cargo target directory.
+///
+/// The manifest is in the static JSON format of the updater endpoints and lists the platforms built by the command.
+/// When a manifest of the same version already exists, its other platforms are kept, so the manifest can be completed by the builds of each platform.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct UpdaterManifestConfig {
+  /// The download URL of the updater archives.
+  ///
+  /// The URL can contain the following variables:
+  /// - {version}: The version of the app.
+  /// - {target}: The operating system name (one of `linux`, `windows` or `darwin`).
+  /// - {arch}: The architecture (one of `x86_64`, `i686`, `aarch64` or `armv7`).
+  /// - {file}: The file name of the updater archive.
+  ///
+  /// # Examples
+  /// - "https://github.com/user/repo/releases/download/v{version}/{file}"
+  #[allow(rustdoc::bare_urls)]
+  pub url: String,
+  /// Path to the release notes, e.g. a `CHANGELOG.md` file, relative to the `src-tauri` folder.
+  ///
+  /// When the file has a Markdown heading with the app version, only that section is used.
+  pub notes: Option<PathBuf>,
+}
+
 /// The Updater configuration object.
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Eq, Clone, Serialize)]
@@ -2277,6 +3120,8 @@ pub struct UpdaterConfig {
   /// The Windows configuration for the updater.
   #[serde(default)]
   pub windows: UpdaterWindowsConfig,
+  /// Write a `latest.json` manifest for the signed updater archives when running `tauri build`.
+  pub manifest: Option<UpdaterManifestConfig>,
 }
 
 impl<'de> Deserialize<'de> for UpdaterConfig {
@@ -2294,6 +3139,7 @@ impl<'de> Deserialize<'de> for UpdaterConfig {
       pubkey: Option<String>,
       #[serde(default)]
       windows: UpdaterWindowsConfig,
+      manifest: Option<UpdaterManifestConfig>,
     }
 
     let config = InnerUpdaterConfig::deserialize(deserializer)?;
@@ -2310,6 +3156,7 @@ impl<'de> Deserialize<'de> for UpdaterConfig {
       endpoints: config.endpoints,
       pubkey: config.pubkey.unwrap_or_default(),
       windows: config.windows,
+      manifest: config.manifest,
     })
   }
 }
@@ -2322,6 +3169,7 @@ impl Default for UpdaterConfig {
       endpoints: None,
       pubkey: "".into(),
       windows: Default::default(),
+      manifest: None,
     }
   }
 }
@@ -2369,6 +3217,83 @@ pub struct IosConfig {
   pub development_team: Option<String>,
 }
 
+/// The language of the activity generated for the Android project.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum AndroidLanguage {
+  /// Kotlin.
+  Kotlin,
+  /// Java.
+  Java,
+}
+
+impl Default for AndroidLanguage {
+  fn default() -> Self {
+    Self::Kotlin
+  }
+}
+
+/// General configuration for the Android target, used when generating the Android Studio project.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AndroidConfig {
+  /// The minimum API level required to run the app. Defaults to `24`.
+  #[serde(alias = "min-sdk-version")]
+  pub min_sdk_version: Option<u32>,
+  /// The API level the app targets. Defaults to `33`.
+  #[serde(alias = "target-sdk-version")]
+  pub target_sdk_version: Option<u32>,
+  /// The API level the app is compiled against. Defaults to the target API level.
+  #[serde(alias = "compile-sdk-version")]
+  pub compile_sdk_version: Option<u32>,
+  /// The Gradle version of the Gradle wrapper. Defaults to `7.3.3`.
+  #[serde(alias = "gradle-version")]
+  pub gradle_version: Option<String>,
+  /// The language of the generated `MainActivity`.
+  #[serde(default)]
+  pub language: AndroidLanguage,
+  /// The ABIs the app is built for, e.g. `["arm64-v8a", "x86_64"]`. Defaults to all the ABIs with a Rust target:
+  /// `arm64-v8a`, `armeabi-v7a`, `x86` and `x86_64`.
+  #[serde(alias = "abi-filters")]
+  pub abi_filters: Option<Vec<String>>,
+  /// Additional Gradle dependencies of the app module, e.g. `androidx.biometric:biometric:1.1.0`.
+  #[serde(default)]
+  pub dependencies: Vec<String>,
+  /// The keystore used to sign the release APKs and AABs.
+  #[serde(default)]
+  pub signing: AndroidSigningConfig,
+}
+
+/// The keystore used to sign the Android release builds.
+///
+/// Each value can be overwritten with an environment variable,
+/// which is the recommended way to provide the passwords.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AndroidSigningConfig {
+  /// Path to the keystore file, relative to the `src-tauri` folder.
+  /// The `TAURI_ANDROID_KEYSTORE_PATH` environment variable can be set to overwrite it.
+  #[serde(alias = "keystore-path")]
+  pub keystore_path: Option<PathBuf>,
+  /// The alias of the signing key in the keystore.
+  /// The `TAURI_ANDROID_KEY_ALIAS` environment variable can be set to overwrite it.
+  #[serde(alias = "key-alias")]
+  pub key_alias: Option<String>,
+  /// The password of the keystore.
+  /// The `TAURI_ANDROID_KEYSTORE_PASSWORD` environment variable can be set to overwrite it.
+  #[serde(alias = "keystore-password")]
+  pub keystore_password: Option<String>,
+  /// The password of the signing key. Defaults to the keystore password.
+  /// The `TAURI_ANDROID_KEY_PASSWORD` environment variable can be set to overwrite it.
+  #[serde(alias = "key-password")]
+  pub key_password: Option<String>,
+}
+
 /// Defines the URL or assets to embed in the application.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -2425,6 +3350,110 @@ pub enum HookCommand {
   },
 }
 
+/// The readiness probe `tauri dev` runs against the `devPath` URL before starting the app.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DevServerProbeConfig {
+  /// The number of attempts before giving up. Defaults to `90`.
+  #[serde(default = "default_dev_server_probe_retries")]
+  pub retries: u32,
+  /// The delay between attempts in milliseconds. Defaults to `2000`.
+  #[serde(default = "default_dev_server_probe_interval")]
+  pub interval: u64,
+  /// The timeout of each attempt in milliseconds. Defaults to `1000`.
+  #[serde(default = "default_dev_server_probe_timeout")]
+  pub timeout: u64,
+  /// The HTTP status code the dev server must respond with.
+  ///
+  /// When set, each attempt sends a GET request to the `devPath` URL. Otherwise the dev server is considered ready as soon as it accepts TCP connections.
+  #[serde(alias = "expected-status")]
+  pub expected_status: Option<u16>,
+}
+
+impl Default for DevServerProbeConfig {
+  fn default() -> Self {
+    Self {
+      retries: default_dev_server_probe_retries(),
+      interval: default_dev_server_probe_interval(),
+      timeout: default_dev_server_probe_timeout(),
+      expected_status: None,
+    }
+  }
+}
+
+fn default_dev_server_probe_retries() -> u32 {
+  90
+}
+
+fn default_dev_server_probe_interval() -> u64 {
+  2000
+}
+
+fn default_dev_server_probe_timeout() -> u64 {
+  1000
+}
+
+/// A named `tauri dev` setup selected with `tauri dev --profile <name>`.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DevProfileConfig {
+  /// Overrides `build > devPath`.
+  #[serde(alias = "dev-path")]
+  pub dev_path: Option<AppUrl>,
+  /// Cargo features activated in addition to the `--features` flag.
+  pub features: Option<Vec<String>>,
+  /// Environment variables set for the `beforeDevCommand`, the cargo build and the app.
+  #[serde(default)]
+  pub env: HashMap<String, String>,
+  /// Arguments appended to the `tauri dev` arguments. Arguments after `--` are passed to the application.
+  #[serde(default)]
+  pub args: Vec<String>,
+}
+
+/// The settings of a binary of an app with several `[[bin]]` targets, selected with `--bin <name>`.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BinaryConfig {
+  /// Cargo features activated in addition to the `--features` flag.
+  pub features: Option<Vec<String>>,
+  /// Overrides `tauri > windows`.
+  ///
+  /// The windows are validated when they are merged with the configuration.
+  #[cfg_attr(feature = "schema", schemars(with = "Option<Vec<WindowConfig>>"))]
+  pub windows: Option<Vec<JsonValue>>,
+}
+
+/// An external build system producing the app binary instead of cargo, e.g. Bazel or Nix.
+///
+/// The commands are run from the `src-tauri` folder with the `TAURI_PROFILE` (the cargo profile name, `dev` or `release` by default),
+/// `TAURI_TARGET_TRIPLE` and `TAURI_FEATURES` (comma separated) environment variables.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExternalInterfaceConfig {
+  /// The command building the app binary on `tauri build`.
+  #[serde(alias = "build-command")]
+  pub build_command: String,
+  /// The command building the app binary on `tauri dev`. Defaults to `buildCommand`.
+  #[serde(alias = "dev-command")]
+  pub dev_command: Option<String>,
+  /// Path of the app binary produced by the commands, relative to the `src-tauri` folder.
+  ///
+  /// The `{profile}` and `{target}` placeholders are replaced with the profile and the target triple.
+  #[serde(alias = "binary-path")]
+  pub binary_path: String,
+  /// Glob patterns of the files rebuilding and restarting the app on `tauri dev`, relative to the `src-tauri` folder, e.g. `src/**/*.rs`.
+  #[serde(default)]
+  pub watch: Vec<String>,
+}
+
 /// The Build configuration object.
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
@@ -2432,6 +3461,9 @@ pub enum HookCommand {
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct BuildConfig {
   /// The binary used to build and run the application.
+  ///
+  /// It is invoked with the `build` subcommand (e.g. `cross`), unless a full command such as `cargo zigbuild` is provided.
+  /// The command can be prefixed by `KEY=VALUE` environment variables.
   pub runner: Option<String>,
   /// The path to the application assets or URL to load in development.
   ///
@@ -2463,18 +3495,82 @@ pub struct BuildConfig {
   /// A shell command to run before `tauri build` kicks in.
   ///
   /// The TAURI_PLATFORM, TAURI_ARCH, TAURI_FAMILY, TAURI_PLATFORM_VERSION, TAURI_PLATFORM_TYPE and TAURI_DEBUG environment variables are set if you perform conditional compilation.
+  ///
+  /// The resolved build is described by the TAURI_TARGET_TRIPLE, TAURI_PROFILE, TAURI_OUT_DIR and TAURI_APP_VERSION environment variables,
+  /// so generated files can be written where the bundler looks for them. When building several targets the command runs once per target.
+  /// Mobile builds only set TAURI_PROFILE and TAURI_APP_VERSION.
   #[serde(alias = "before-build-command")]
   pub before_build_command: Option<HookCommand>,
   /// A shell command to run before the bundling phase in `tauri build` kicks in.
   ///
   /// The TAURI_PLATFORM, TAURI_ARCH, TAURI_FAMILY, TAURI_PLATFORM_VERSION, TAURI_PLATFORM_TYPE and TAURI_DEBUG environment variables are set if you perform conditional compilation.
+  /// The TAURI_TARGET_TRIPLE, TAURI_PROFILE, TAURI_OUT_DIR and TAURI_APP_VERSION environment variables describe the target being bundled.
   #[serde(alias = "before-bundle-command")]
   pub before_bundle_command: Option<HookCommand>,
+  /// A shell command to run once `tauri build` bundled every target and feature set.
+  ///
+  /// The TAURI_ARTIFACTS_PATH environment variable is the path of a JSON file listing the `path`, `type` (the bundle short name or `updaterSignature`),
+  /// `target` and `featureSet` of each produced artifact, e.g. to upload or scan them.
+  #[serde(alias = "after-bundle-command")]
+  pub after_bundle_command: Option<HookCommand>,
+  /// A shell command to run after each successful `tauri dev` build, before the app is started.
+  ///
+  /// Useful to regenerate files derived from the Rust code, such as TypeScript bindings, on every change.
+  /// The app is not started if the command fails.
+  #[serde(alias = "after-dev-build-command")]
+  pub after_dev_build_command: Option<HookCommand>,
   /// Features passed to `cargo` commands.
   pub features: Option<Vec<String>>,
   /// Whether we should inject the Tauri API on `window.__TAURI__` or not.
   #[serde(default, alias = "with-global-tauri")]
   pub with_global_tauri: bool,
+  /// Whether `tauri dev` and `tauri build` compile the app through [sccache](https://github.com/mozilla/sccache) by setting `RUSTC_WRAPPER`.
+  ///
+  /// `tauri build` prints the cache statistics after the build.
+  #[serde(default, alias = "use-sccache")]
+  pub use_sccache: bool,
+  /// Whether the CLI leaves Cargo.toml untouched instead of rewriting the features of the `tauri` and `tauri-build` dependencies
+  /// to match the allowlist, passing them to cargo with `--features` instead.
+  ///
+  /// The app must then be built with `tauri dev` and `tauri build`, since a plain `cargo build` doesn't enable the allowlist features.
+  #[serde(default, alias = "skip-manifest-rewrite")]
+  pub skip_manifest_rewrite: bool,
+  /// Delay in milliseconds the `tauri dev` file watcher waits for changes to settle before rebuilding the app.
+  ///
+  /// Defaults to `1000`. Can be overridden with the `--watch-debounce` flag.
+  #[serde(alias = "dev-watcher-debounce")]
+  pub dev_watcher_debounce: Option<u64>,
+  /// Additional paths watched by `tauri dev`, relative to the `src-tauri` folder.
+  ///
+  /// Changes to these paths restart the app, which is useful for crates outside the Cargo workspace or generated assets.
+  #[serde(alias = "watch-paths")]
+  pub watch_paths: Option<Vec<PathBuf>>,
+  /// Configures how `tauri dev` waits for the dev server at `devPath` to be ready before starting the app.
+  #[serde(alias = "dev-server-probe")]
+  pub dev_server_probe: Option<DevServerProbeConfig>,
+  /// Named `tauri dev` setups, selected with `tauri dev --profile <name>`.
+  #[serde(alias = "dev-profiles")]
+  pub dev_profiles: Option<HashMap<String, DevProfileConfig>>,
+  /// Named sets of cargo features built by `tauri build --feature-matrix`, e.g. `{ "free": [], "pro": ["pro"] }`.
+  ///
+  /// Each set is built and bundled separately and its name is appended to the bundle file names.
+  #[serde(alias = "feature-matrix")]
+  pub feature_matrix: Option<HashMap<String, Vec<String>>>,
+  /// Settings of the binaries of an app with several `[[bin]]` targets, keyed by binary name.
+  ///
+  /// The binary selected with `tauri dev --bin <name>` or `tauri build --bin <name>` is built as the main app binary
+  /// with the features of its entry, and its windows replace `tauri > windows`.
+  pub binaries: Option<HashMap<String, BinaryConfig>>,
+  /// Environment variables of the cargo build, keyed by target triple, e.g. `CC`, `AR` or `CARGO_TARGET_<TRIPLE>_LINKER` to cross compile.
+  ///
+  /// The host triple is used when no target is given.
+  #[serde(alias = "target-env")]
+  pub target_env: Option<HashMap<String, HashMap<String, String>>>,
+  /// Builds the app with an external build system instead of cargo, for apps embedding Tauri in a Bazel or Nix build.
+  ///
+  /// The Cargo manifest isn't read or rewritten: the app version is read from `package > version`.
+  #[serde(alias = "external-interface")]
+  pub external_interface: Option<ExternalInterfaceConfig>,
 }
 
 impl Default for BuildConfig {
@@ -2486,8 +3582,20 @@ impl Default for BuildConfig {
       before_dev_command: None,
       before_build_command: None,
       before_bundle_command: None,
+      after_bundle_command: None,
+      after_dev_build_command: None,
       features: None,
       with_global_tauri: false,
+      use_sccache: false,
+      skip_manifest_rewrite: false,
+      dev_watcher_debounce: None,
+      watch_paths: None,
+      dev_server_probe: None,
+      dev_profiles: None,
+      feature_matrix: None,
+      binaries: None,
+      target_env: None,
+      external_interface: None,
     }
   }
 }
@@ -2505,6 +3613,98 @@ fn default_dist_dir() -> AppUrl {
 #[derive(Debug, PartialEq, Eq)]
 struct PackageVersion(String);
 
+/// Reads the version from a `Cargo.toml` file, the `version` field of a JSON file such as `package.json`,
+/// or the content of any other file.
+fn read_version_file(path: &Path) -> Result<String, String> {
+  let content = read_to_string(path)
+    .map_err(|e| format!("failed to read version file {}: {}", path.display(), e))?;
+  if path.file_name() == Some(std::ffi::OsStr::new("Cargo.toml")) {
+    return cargo_manifest_version(path, &content);
+  }
+  if path.extension() == Some(std::ffi::OsStr::new("json")) {
+    let json: JsonValue = serde_json::from_str(&content)
+      .map_err(|e| format!("failed to read version JSON file: {}", e))?;
+    return match json.as_object() {
+      Some(obj) => obj
+        .get("version")
+        .ok_or_else(|| "JSON must contain a `version` field".to_string())?
+        .as_str()
+        .map(ToString::to_string)
+        .ok_or_else(|| format!("`{} > version` must be a string", path.display())),
+      None => Err("`package > version` value is not a path to a JSON object".into()),
+    };
+  }
+  Ok(content.trim().to_string())
+}
+
+/// The version of the `[package]` table of a Cargo manifest,
+/// or of the `[workspace.package]` table of the workspace manifest if the version is inherited.
+///
+/// The manifest is read line by line, so it doesn't depend on the `config-toml` feature.
+fn cargo_manifest_version(path: &Path, manifest: &str) -> Result<String, String> {
+  match toml_table_string(manifest, "package", "version") {
+    Some(TomlValue::String(version)) => Ok(version),
+    Some(TomlValue::Workspace) => path
+      .parent()
+      .into_iter()
+      .flat_map(Path::ancestors)
+      .skip(1)
+      .filter_map(|dir| read_to_string(dir.join("Cargo.toml")).ok())
+      .find_map(
+        |manifest| match toml_table_string(&manifest, "workspace.package", "version") {
+          Some(TomlValue::String(version)) => Some(version),
+          _ => None,
+        },
+      )
+      .ok_or_else(|| {
+        format!(
+          "{} inherits the workspace version, but no `[workspace.package]` version was found",
+          path.display()
+        )
+      }),
+    None => Err(format!("{} has no `package.version`", path.display())),
+  }
+}
+
+enum TomlValue {
+  String(String),
+  /// `key.workspace = true` or `key = { workspace = true }`.
+  Workspace,
+}
+
+fn toml_table_string(manifest: &str, table: &str, key: &str) -> Option<TomlValue> {
+  let mut current_table = String::new();
+  for line in manifest.lines().map(str::trim) {
+    if let Some(header) = line.strip_prefix('[') {
+      current_table = header
+        .split(']')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+      continue;
+    }
+    if current_table != table {
+      continue;
+    }
+    let (line_key, value) = match line.split_once('=') {
+      Some((line_key, value)) => (line_key.trim(), value.trim()),
+      None => continue,
+    };
+    if line_key == format!("{}.workspace", key) || (line_key == key && value.contains("workspace"))
+    {
+      return Some(TomlValue::Workspace);
+    }
+    if line_key == key {
+      return value
+        .strip_prefix('"')
+        .and_then(|v| v.split('"').next())
+        .map(|v| TomlValue::String(v.to_string()));
+    }
+  }
+  None
+}
+
 impl<'d> serde::Deserialize<'d> for PackageVersion {
   fn deserialize<D: Deserializer<'d>>(deserializer: D) -> Result<PackageVersion, D::Error> {
     struct PackageVersionVisitor;
@@ -2515,42 +3715,22 @@ impl<'d> serde::Deserialize<'d> for PackageVersion {
       fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
           formatter,
-          "a semver string or a path to a package.json file"
+          "a semver string or a path to a Cargo.toml, JSON or text file"
         )
       }
 
       fn visit_str<E: DeError>(self, value: &str) -> Result<PackageVersion, E> {
         let path = PathBuf::from(value);
-        if path.exists() {
-          let json_str = read_to_string(&path)
-            .map_err(|e| DeError::custom(format!("failed to read version JSON file: {}", e)))?;
-          let package_json: serde_json::Value = serde_json::from_str(&json_str)
-            .map_err(|e| DeError::custom(format!("failed to read version JSON file: {}", e)))?;
-          if let Some(obj) = package_json.as_object() {
-            let version = obj
-              .get("version")
-              .ok_or_else(|| DeError::custom("JSON must contain a `version` field"))?
-              .as_str()
-              .ok_or_else(|| {
-                DeError::custom(format!("`{} > version` must be a string", path.display()))
-              })?;
-            Ok(PackageVersion(
-              Version::from_str(version)
-                .map_err(|_| DeError::custom("`package > version` must be a semver string"))?
-                .to_string(),
-            ))
-          } else {
-            Err(DeError::custom(
-              "`package > version` value is not a path to a JSON object",
-            ))
-          }
+        let version = if path.exists() {
+          read_version_file(&path).map_err(DeError::custom)?
         } else {
-          Ok(PackageVersion(
-            Version::from_str(value)
-              .map_err(|_| DeError::custom("`package > version` must be a semver string"))?
-              .to_string(),
-          ))
-        }
+          value.to_string()
+        };
+        Ok(PackageVersion(
+          Version::from_str(&version)
+            .map_err(|_| DeError::custom("`package > version` must be a semver string"))?
+            .to_string(),
+        ))
       }
     }
 
@@ -2566,7 +3746,9 @@ pub struct PackageConfig {
   /// App name.
   #[serde(alias = "product-name")]
   pub product_name: Option<String>,
-  /// App version. It is a semver version number or a path to a `package.json` file containing the `version` field.
+  /// App version. It is a semver version number or a path to a file containing the version:
+  /// a `Cargo.toml` file, whose version may be inherited from the workspace, a JSON file such as `package.json` with a `version` field,
+  /// or a text file containing only the version. Relative paths are resolved from the directory of the configuration file.
   #[serde(deserialize_with = "version_deserializer", default)]
   pub version: Option<String>,
 }
@@ -2620,6 +3802,16 @@ impl PackageConfig {
 /// (or `Tauri.linux.toml`, `Tauri.windows.toml`, `Tauri.macos.toml`, `Tauri.android.toml` and `Tauri.ios.toml` if the `Tauri.toml` format is used),
 /// which gets merged with the main configuration object.
 ///
+/// ## Extending a Configuration
+///
+/// The `extends` key merges the configuration over one or several base configuration files,
+/// so the apps of a monorepo can share their bundle, signing and updater settings.
+///
+/// ## Debug and Release Configuration
+///
+/// The `dev` object is merged over the configuration in debug builds and the `prod` object in release builds,
+/// e.g. to enable devtools or allowlist items in development only.
+///
 /// ## Configuration Structure
 ///
 /// The configuration is composed of the following objects:
@@ -2672,6 +3864,20 @@ pub struct Config {
   /// The JSON schema for the Tauri config.
   #[serde(rename = "$schema")]
   pub schema: Option<String>,
+  /// The base configuration files this configuration is merged over.
+  ///
+  /// Each path is a JSON, JSON5 or TOML file, relative to the directory of the configuration file
+  /// or, if prefixed with `workspace:`, to the root of the Cargo workspace. The base files are merged in order,
+  /// objects key by key, and they can extend other files.
+  pub extends: Option<ExtendsConfig>,
+  /// The configuration merged over this configuration in debug builds.
+  ///
+  /// It has the same structure as the configuration, e.g. `{ "tauri": { "allowlist": { "shell": { "open": true } } } }`.
+  pub dev: Option<serde_json::Map<String, JsonValue>>,
+  /// The configuration merged over this configuration in release builds.
+  ///
+  /// It has the same structure as the configuration.
+  pub prod: Option<serde_json::Map<String, JsonValue>>,
   /// Package settings.
   #[serde(default)]
   pub package: PackageConfig,
@@ -2686,6 +3892,17 @@ pub struct Config {
   pub plugins: PluginConfig,
 }
 
+/// The base configuration files of a configuration, see [`Config::extends`].
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum ExtendsConfig {
+  /// A single base configuration file.
+  One(String),
+  /// A list of base configuration files, the later files take precedence.
+  List(Vec<String>),
+}
+
 /// The plugin configs holds a HashMap mapping a plugin name to its configuration object.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -2699,8 +3916,20 @@ fn default_build() -> BuildConfig {
     before_dev_command: None,
     before_build_command: None,
     before_bundle_command: None,
+    after_bundle_command: None,
+    after_dev_build_command: None,
     features: None,
     with_global_tauri: false,
+    use_sccache: false,
+    skip_manifest_rewrite: false,
+    dev_watcher_debounce: None,
+    watch_paths: None,
+    dev_server_probe: None,
+    dev_profiles: None,
+    feature_matrix: None,
+    binaries: None,
+    target_env: None,
+    external_interface: None,
   }
 }
 
@@ -3115,12 +4344,29 @@ mod build {
       let category = quote!(None);
       let short_description = quote!(None);
       let long_description = quote!(None);
+      let artifact_name = quote!(None);
       let appimage = quote!(Default::default());
       let deb = quote!(Default::default());
+      let rpm = quote!(Default::default());
+      let flatpak = quote!(Default::default());
+      let linux = quote!(Default::default());
       let macos = quote!(Default::default());
       let external_bin = opt_vec_str_lit(self.external_bin.as_ref());
+      let external_bin_sources = quote!(Default::default());
+      let target_resources = quote!(Default::default());
+      let target_external_bin = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.target_external_bin,
+        str_lit,
+        |bins| vec_lit(bins, str_lit),
+      );
       let windows = &self.windows;
       let ios = quote!(Default::default());
+      let android = quote!(Default::default());
+      let optimize = quote!(Default::default());
+      let sign_command = quote!(None);
+      let file_associations = quote!(None);
+      let deep_link = quote!(Default::default());
 
       literal_struct!(
         tokens,
@@ -3130,16 +4376,28 @@ mod build {
         icon,
         targets,
         resources,
+        target_resources,
         copyright,
         category,
         short_description,
         long_description,
+        artifact_name,
         appimage,
         deb,
+        rpm,
+        flatpak,
+        linux,
         macos,
         external_bin,
+        target_external_bin,
+        external_bin_sources,
         windows,
-        ios
+        ios,
+        android,
+        optimize,
+        sign_command,
+        file_associations,
+        deep_link
       );
     }
   }
@@ -3165,11 +4423,23 @@ mod build {
       let dev_path = &self.dev_path;
       let dist_dir = &self.dist_dir;
       let with_global_tauri = self.with_global_tauri;
+      let use_sccache = self.use_sccache;
+      let skip_manifest_rewrite = self.skip_manifest_rewrite;
       let runner = quote!(None);
       let before_dev_command = quote!(None);
       let before_build_command = quote!(None);
       let before_bundle_command = quote!(None);
+      let after_bundle_command = quote!(None);
+      let after_dev_build_command = quote!(None);
       let features = quote!(None);
+      let dev_watcher_debounce = quote!(None);
+      let watch_paths = quote!(None);
+      let dev_server_probe = quote!(None);
+      let dev_profiles = quote!(None);
+      let feature_matrix = quote!(None);
+      let binaries = quote!(None);
+      let target_env = quote!(None);
+      let external_interface = quote!(None);
 
       literal_struct!(
         tokens,
@@ -3178,10 +4448,22 @@ mod build {
         dev_path,
         dist_dir,
         with_global_tauri,
+        use_sccache,
+        skip_manifest_rewrite,
         before_dev_command,
         before_build_command,
         before_bundle_command,
-        features
+        after_bundle_command,
+        after_dev_build_command,
+        features,
+        dev_watcher_debounce,
+        watch_paths,
+        dev_server_probe,
+        dev_profiles,
+        feature_matrix,
+        binaries,
+        target_env,
+        external_interface
       );
     }
   }
@@ -3223,6 +4505,8 @@ mod build {
           .as_ref(),
       );
       let windows = &self.windows;
+      // only used by the CLI
+      let manifest = quote!(None);
 
       literal_struct!(
         tokens,
@@ -3231,7 +4515,8 @@ mod build {
         dialog,
         pubkey,
         endpoints,
-        windows
+        windows,
+        manifest
       );
     }
   }
@@ -3501,12 +4786,16 @@ mod build {
   impl ToTokens for Config {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let schema = quote!(None);
+      // the base configurations and the profile overlays are merged when the configuration is read
+      let extends = quote!(None);
+      let dev = quote!(None);
+      let prod = quote!(None);
       let package = &self.package;
       let tauri = &self.tauri;
       let build = &self.build;
       let plugins = &self.plugins;
 
-      literal_struct!(tokens, Config, schema, package, tauri, build, plugins);
+      literal_struct!(tokens, Config, schema, extends, dev, prod, package, tauri, build, plugins);
     }
   }
 }
@@ -3517,6 +4806,31 @@ mod test {
 
   // TODO: create a test that compares a config to a json config
 
+  #[test]
+  fn reads_cargo_manifest_version() {
+    let manifest = "[package]\nname = \"app\"\nversion = \"1.2.3\" # release\n\n[dependencies]\nversion = \"0.1.0\"\n";
+    match toml_table_string(manifest, "package", "version") {
+      Some(TomlValue::String(version)) => assert_eq!(version, "1.2.3"),
+      _ => panic!("expected the package version"),
+    }
+
+    for inherited in ["version.workspace = true", "version = { workspace = true }"] {
+      let manifest = format!("[package]\nname = \"app\"\n{}\n", inherited);
+      assert!(matches!(
+        toml_table_string(&manifest, "package", "version"),
+        Some(TomlValue::Workspace)
+      ));
+    }
+
+    let workspace =
+      "[workspace]\nmembers = [\"app\"]\n\n[workspace.package]\nversion = \"2.0.0\"\n";
+    match toml_table_string(workspace, "workspace.package", "version") {
+      Some(TomlValue::String(version)) => assert_eq!(version, "2.0.0"),
+      _ => panic!("expected the workspace version"),
+    }
+    assert!(toml_table_string(workspace, "package", "version").is_none());
+  }
+
   #[test]
   // test all of the default functions
   fn test_defaults() {
@@ -3543,16 +4857,28 @@ mod test {
         identifier: String::from(""),
         icon: Vec::new(),
         resources: None,
+        target_resources: Default::default(),
         copyright: None,
         category: None,
         short_description: None,
         long_description: None,
+        artifact_name: None,
         appimage: Default::default(),
         deb: Default::default(),
+        rpm: Default::default(),
+        flatpak: Default::default(),
+        linux: Default::default(),
         macos: Default::default(),
         external_bin: None,
+        target_external_bin: Default::default(),
+        external_bin_sources: Default::default(),
         windows: Default::default(),
         ios: Default::default(),
+        android: Default::default(),
+        optimize: Default::default(),
+        sign_command: None,
+        file_associations: None,
+        deep_link: Default::default(),
       },
       cli: None,
       updater: UpdaterConfig {
@@ -3561,6 +4887,7 @@ mod test {
         pubkey: "".into(),
         endpoints: None,
         windows: Default::default(),
+        manifest: None,
       },
       security: SecurityConfig {
         csp: None,
@@ -3583,8 +4910,20 @@ mod test {
       before_dev_command: None,
       before_build_command: None,
       before_bundle_command: None,
+      after_bundle_command: None,
+      after_dev_build_command: None,
       features: None,
       with_global_tauri: false,
+      use_sccache: false,
+      skip_manifest_rewrite: false,
+      dev_watcher_debounce: None,
+      watch_paths: None,
+      dev_server_probe: None,
+      dev_profiles: None,
+      feature_matrix: None,
+      binaries: None,
+      target_env: None,
+      external_interface: None,
     };
 
     // test the configs
@@ -3600,4 +4939,48 @@ mod test {
     );
     assert_eq!(d_windows, tauri.windows);
   }
+
+  #[test]
+  fn target_specific_bundle_entries() {
+    let bundle: BundleConfig = serde_json::from_value(serde_json::json!({
+      "identifier": "com.tauri.test",
+      "externalBin": ["binaries/common"],
+      "targetExternalBin": {
+        "windows": ["binaries/windows"],
+        "aarch64-apple-darwin": ["binaries/apple-silicon"]
+      },
+      "resources": ["assets/*"],
+      "targetResources": {
+        "linux": ["lib/*.so"],
+        "macos": { "frameworks/lib.dylib": "lib/lib.dylib" }
+      }
+    }))
+    .unwrap();
+
+    assert_eq!(
+      bundle.external_bin_for_target("x86_64-pc-windows-msvc"),
+      vec!["binaries/common", "binaries/windows"]
+    );
+    assert_eq!(
+      bundle.external_bin_for_target("aarch64-apple-darwin"),
+      vec!["binaries/common", "binaries/apple-silicon"]
+    );
+    assert_eq!(
+      bundle.external_bin_for_target("x86_64-apple-darwin"),
+      vec!["binaries/common"]
+    );
+
+    assert_eq!(
+      bundle.resources_for_target("x86_64-unknown-linux-gnu"),
+      vec![
+        &BundleResources::List(vec!["assets/*".into()]),
+        &BundleResources::List(vec!["lib/*.so".into()])
+      ]
+    );
+    assert_eq!(
+      bundle.resources_for_target("aarch64-linux-android").len(),
+      1
+    );
+    assert_eq!(bundle.resources_for_target("x86_64-apple-darwin").len(), 2);
+  }
 }
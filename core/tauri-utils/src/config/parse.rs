@@ -47,7 +47,8 @@ impl ConfigFormat {
     }
   }
 
-  fn into_platform_file_name(self) -> &'static str {
+  /// Maps the config format to the file name of the platform-specific configuration of the current platform.
+  pub fn into_platform_file_name(self) -> &'static str {
     match self {
       Self::Json => {
         if cfg!(target_os = "macos") {
@@ -68,9 +69,9 @@ impl ConfigFormat {
         } else if cfg!(windows) {
           "tauri.windows.conf.json5"
         } else if cfg!(target_os = "android") {
-          "tauri.android.conf.json"
+          "tauri.android.conf.json5"
         } else if cfg!(target_os = "ios") {
-          "tauri.ios.conf.json"
+          "tauri.ios.conf.json5"
         } else {
           "tauri.linux.conf.json5"
         }
@@ -81,9 +82,9 @@ impl ConfigFormat {
         } else if cfg!(windows) {
           "Tauri.windows.toml"
         } else if cfg!(target_os = "android") {
-          "tauri.android.toml"
+          "Tauri.android.toml"
         } else if cfg!(target_os = "ios") {
-          "tauri.ios.toml"
+          "Tauri.ios.toml"
         } else {
           "Tauri.linux.toml"
         }
@@ -151,6 +152,28 @@ pub enum ConfigError {
     /// The [`std::io::Error`].
     error: std::io::Error,
   },
+
+  /// The `extends` key of a config file is invalid.
+  #[error("unable to extend the Tauri config file at {path}: {message}")]
+  Extends {
+    /// The path of the config file extending the invalid base.
+    path: PathBuf,
+
+    /// The reason the base config could not be read.
+    message: String,
+  },
+
+  /// An environment variable reference in a config value could not be resolved.
+  #[error(
+    "unable to resolve the environment variables of the Tauri config value at `{key}`: {message}"
+  )]
+  EnvInterpolation {
+    /// The path of the config value, e.g. `package > productName`.
+    key: String,
+
+    /// The reason the reference could not be resolved.
+    message: String,
+  },
 }
 
 /// Reads the configuration from the given root directory.
@@ -163,16 +186,212 @@ pub enum ConfigError {
 /// - `tauri.android.conf.json[5]` or `Tauri.android.toml` on Android
 /// - `tauri.ios.conf.json[5]` or `Tauri.ios.toml` on iOS
 /// Merging the configurations using [JSON Merge Patch (RFC 7396)].
+/// Each configuration is merged over the base files of its `extends` key first, see [`resolve_extends`].
 ///
 /// [JSON Merge Patch (RFC 7396)]: https://datatracker.ietf.org/doc/html/rfc7396.
 pub fn read_from(root_dir: PathBuf) -> Result<Value, ConfigError> {
-  let mut config: Value = parse_value(root_dir.join("tauri.conf.json"))?.0;
-  if let Some((platform_config, _)) = read_platform(root_dir)? {
+  let (mut config, config_path) = parse_value(root_dir.join("tauri.conf.json"))?;
+  resolve_extends(&mut config, &config_path)?;
+  if let Some((mut platform_config, platform_config_path)) = read_platform(root_dir)? {
+    resolve_extends(&mut platform_config, &platform_config_path)?;
     merge(&mut config, &platform_config);
   }
   Ok(config)
 }
 
+/// The keys of the configuration overlays merged in debug and release builds, see [`apply_profile`].
+pub const PROFILE_OVERLAYS: &[&str] = &["dev", "prod"];
+
+/// Merges the `dev` overlay of the configuration over it in debug builds, or the `prod` overlay in release builds,
+/// using [JSON Merge Patch (RFC 7396)], and removes both overlays.
+///
+/// It runs once the configuration files are merged, so the overlays of the base and platform-specific files are
+/// merged together first.
+///
+/// [JSON Merge Patch (RFC 7396)]: https://datatracker.ietf.org/doc/html/rfc7396.
+pub fn apply_profile(config: &mut Value, release: bool) {
+  if let Some(object) = config.as_object_mut() {
+    let dev = object.remove("dev");
+    let prod = object.remove("prod");
+    if let Some(overlay) = if release { prod } else { dev } {
+      merge(config, &overlay);
+    }
+  }
+}
+
+/// Whether the configuration is read for a release build, selecting the overlay of [`apply_profile`].
+///
+/// Build scripts use the Cargo `PROFILE` variable, other crates the profile they are compiled with.
+pub fn is_release_profile() -> bool {
+  std::env::var("PROFILE")
+    .map(|profile| profile == "release")
+    .unwrap_or(!cfg!(debug_assertions))
+}
+
+/// The environment variable set by the Tauri CLI on `tauri dev` and `tauri build`
+/// to the path of the configuration it resolved, after merging the platform-specific configuration,
+/// the base configurations and the `--config` values and interpolating the environment variables.
+pub const RESOLVED_CONFIG_ENV: &str = "TAURI_RESOLVED_CONFIG";
+
+/// Reads the configuration resolved by the Tauri CLI from the file at [`RESOLVED_CONFIG_ENV`], if the variable is set.
+///
+/// Build scripts should prefer it to [`read_from`], which does not know the `--config` values of the CLI.
+pub fn read_resolved() -> Result<Option<(Value, PathBuf)>, ConfigError> {
+  match std::env::var_os(RESOLVED_CONFIG_ENV) {
+    Some(path) => {
+      let path = PathBuf::from(path);
+      let config = do_parse_json(&read_to_string(&path)?, &path)?;
+      Ok(Some((config, path)))
+    }
+    None => Ok(None),
+  }
+}
+
+/// The base configuration files extended by the configurations read by [`read_from`].
+pub fn extended_files(root_dir: PathBuf) -> Result<Vec<PathBuf>, ConfigError> {
+  let (mut config, config_path) = parse_value(root_dir.join("tauri.conf.json"))?;
+  let mut files = resolve_extends(&mut config, &config_path)?;
+  if let Some((mut platform_config, platform_config_path)) = read_platform(root_dir)? {
+    files.extend(resolve_extends(
+      &mut platform_config,
+      &platform_config_path,
+    )?);
+  }
+  Ok(files)
+}
+
+/// Merges the configuration read from `path` over the base configuration files of its `extends` key, removing the key.
+///
+/// `extends` is a path or a list of paths of JSON, JSON5 or TOML files, detected from their extension.
+/// A relative path is resolved from the directory of `path`, and a path prefixed with `workspace:`
+/// from the root of the Cargo workspace, e.g. `workspace:config/tauri.base.json`.
+/// The base configurations, which can extend other files, are merged in order
+/// and the configuration is merged over them using [JSON Merge Patch (RFC 7396)].
+/// The keys are merged as written, so the base and extending files must use the same key style.
+///
+/// Returns the canonical paths of the base configuration files.
+///
+/// [JSON Merge Patch (RFC 7396)]: https://datatracker.ietf.org/doc/html/rfc7396.
+pub fn resolve_extends(config: &mut Value, path: &Path) -> Result<Vec<PathBuf>, ConfigError> {
+  let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+  let mut files = Vec::new();
+  do_resolve_extends(config, &path, &mut vec![path.clone()], &mut files)?;
+  Ok(files)
+}
+
+fn do_resolve_extends(
+  config: &mut Value,
+  path: &Path,
+  chain: &mut Vec<PathBuf>,
+  files: &mut Vec<PathBuf>,
+) -> Result<(), ConfigError> {
+  let extends_error = |message: String| ConfigError::Extends {
+    path: path.into(),
+    message,
+  };
+
+  let bases = match config.as_object_mut().and_then(|c| c.remove("extends")) {
+    None | Some(Value::Null) => return Ok(()),
+    Some(Value::String(base)) => vec![base],
+    Some(Value::Array(bases)) => bases
+      .into_iter()
+      .map(|base| match base {
+        Value::String(base) => Ok(base),
+        _ => Err(extends_error(
+          "`extends` must be a path or a list of paths".into(),
+        )),
+      })
+      .collect::<Result<_, _>>()?,
+    Some(_) => {
+      return Err(extends_error(
+        "`extends` must be a path or a list of paths".into(),
+      ))
+    }
+  };
+
+  let dir = path.parent().unwrap_or_else(|| Path::new("."));
+  let mut extended = Value::Object(Default::default());
+  for base in bases {
+    let base_path = match base.strip_prefix("workspace:") {
+      Some(relative_path) => workspace_dir(dir)
+        .ok_or_else(|| extends_error(format!("`{}` is not in a Cargo workspace", base)))?
+        .join(relative_path),
+      None => dir.join(&base),
+    };
+    let base_path = base_path.canonicalize().map_err(|error| ConfigError::Io {
+      path: base_path,
+      error,
+    })?;
+    if chain.contains(&base_path) {
+      return Err(extends_error(format!(
+        "`{}` extends itself through {}",
+        base,
+        chain
+          .iter()
+          .map(|p| p.display().to_string())
+          .collect::<Vec<_>>()
+          .join(" -> ")
+      )));
+    }
+
+    let mut base_config = parse_file_value(&base_path)?;
+    chain.push(base_path.clone());
+    do_resolve_extends(&mut base_config, &base_path, chain, files)?;
+    chain.pop();
+    merge(&mut extended, &base_config);
+    files.push(base_path);
+  }
+  merge(&mut extended, config);
+  *config = extended;
+  Ok(())
+}
+
+/// The root directory of the Cargo workspace containing `dir`.
+fn workspace_dir(dir: &Path) -> Option<PathBuf> {
+  dir
+    .ancestors()
+    .find(|d| {
+      std::fs::read_to_string(d.join("Cargo.toml")).map_or(false, |manifest| {
+        manifest
+          .lines()
+          .any(|line| line.trim_start().starts_with("[workspace"))
+      })
+    })
+    .map(Path::to_path_buf)
+}
+
+/// Parses the config file at `path` in the format of its extension, JSON by default.
+fn parse_file_value(path: &Path) -> Result<Value, ConfigError> {
+  let raw = read_to_string(path)?;
+  match path.extension().and_then(OsStr::to_str) {
+    Some("json5") => {
+      #[cfg(feature = "config-json5")]
+      {
+        do_parse_json5(&raw, path)
+      }
+
+      #[cfg(not(feature = "config-json5"))]
+      Err(ConfigError::DisabledFormat {
+        extension: ".json5".into(),
+        feature: "config-json5".into(),
+      })
+    }
+    Some("toml") => {
+      #[cfg(feature = "config-toml")]
+      {
+        do_parse_toml(&raw, path)
+      }
+
+      #[cfg(not(feature = "config-toml"))]
+      Err(ConfigError::DisabledFormat {
+        extension: ".toml".into(),
+        feature: "config-toml".into(),
+      })
+    }
+    _ => do_parse_json(&raw, path),
+  }
+}
+
 /// Reads the platform-specific configuration file from the given root directory if it exists.
 ///
 /// Check [`read_from`] for more information.
@@ -217,10 +436,12 @@ pub fn does_supported_file_name_exist(path: impl Into<PathBuf>) -> bool {
 /// 2. Check if `tauri.conf.json5` exists
 ///   a. Parse it with `json5`
 ///   b. Return error if all above steps failed
-/// 3. Check if `Tauri.json` exists
+/// 3. Check if `Tauri.toml` exists
 ///   a. Parse it with `toml`
 ///   b. Return error if all above steps failed
 /// 4. Return error if all above steps failed
+///
+/// The first existing file takes precedence, the other formats are ignored.
 pub fn parse(path: impl Into<PathBuf>) -> Result<(Config, PathBuf), ConfigError> {
   do_parse(path.into())
 }
@@ -366,6 +587,136 @@ fn do_parse_toml<D: DeserializeOwned>(raw: &str, path: &Path) -> Result<D, Confi
   })
 }
 
+/// Replaces the environment variable references in the string values of the configuration.
+///
+/// - `${NAME}` is replaced with the value of `NAME`, which must be set
+/// - `${NAME:-default}` is replaced with `default` if `NAME` is unset or empty
+/// - `${NAME:?message}` fails with `message` if `NAME` is unset or empty
+/// - `$${` is replaced with a literal `${`
+///
+/// The object keys are not interpolated.
+/// It must not run twice on the same value, since the result of an escaped reference is not escaped anymore.
+pub fn interpolate_env(config: &mut Value) -> Result<(), ConfigError> {
+  interpolate_value(config, &mut Vec::new(), &mut |name| {
+    std::env::var(name).ok()
+  })
+}
+
+/// Lists the environment variables referenced by the string values of the configuration, see [`interpolate_env`].
+pub fn env_variables(config: &Value) -> Vec<String> {
+  let mut names = Vec::new();
+  // references can't fail to resolve when every variable is set
+  let _ = interpolate_value(&mut config.clone(), &mut Vec::new(), &mut |name| {
+    names.push(name.to_string());
+    Some("set".into())
+  });
+  names.sort();
+  names.dedup();
+  names
+}
+
+fn interpolate_value(
+  value: &mut Value,
+  key: &mut Vec<String>,
+  lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<(), ConfigError> {
+  match value {
+    Value::String(raw) if raw.contains('$') => {
+      *raw = interpolate_str(raw, lookup).map_err(|message| ConfigError::EnvInterpolation {
+        key: key.join(" > "),
+        message,
+      })?;
+    }
+    Value::Array(items) => {
+      for (i, item) in items.iter_mut().enumerate() {
+        key.push(i.to_string());
+        interpolate_value(item, key, lookup)?;
+        key.pop();
+      }
+    }
+    Value::Object(object) => {
+      for (k, item) in object.iter_mut() {
+        key.push(k.clone());
+        interpolate_value(item, key, lookup)?;
+        key.pop();
+      }
+    }
+    _ => (),
+  }
+  Ok(())
+}
+
+fn interpolate_str(
+  raw: &str,
+  lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<String, String> {
+  let mut interpolated = String::with_capacity(raw.len());
+  let mut rest = raw;
+  while let Some(start) = rest.find('$') {
+    interpolated.push_str(&rest[..start]);
+    rest = &rest[start..];
+    if let Some(escaped) = rest.strip_prefix("$${") {
+      interpolated.push_str("${");
+      rest = escaped;
+    } else if let Some(reference) = rest.strip_prefix("${") {
+      let end = reference
+        .find('}')
+        .ok_or_else(|| format!("`{}` is missing the closing `}}`", rest))?;
+      interpolated.push_str(&resolve_reference(&reference[..end], lookup)?);
+      rest = &reference[end + 1..];
+    } else {
+      interpolated.push('$');
+      rest = &rest[1..];
+    }
+  }
+  interpolated.push_str(rest);
+  Ok(interpolated)
+}
+
+/// Resolves the `NAME`, `NAME:-default` or `NAME:?message` reference.
+fn resolve_reference(
+  reference: &str,
+  lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<String, String> {
+  let (name, modifier) = match reference.find(':') {
+    Some(i) => (&reference[..i], Some(&reference[i + 1..])),
+    None => (reference, None),
+  };
+  if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+    return Err(format!(
+      "`${{{}}}` is not a valid variable reference",
+      reference
+    ));
+  }
+
+  let value = lookup(name);
+  match modifier {
+    None => value.ok_or_else(|| format!("the environment variable `{}` is not set", name)),
+    Some(modifier) => {
+      let value = value.filter(|v| !v.is_empty());
+      if let Some(default) = modifier.strip_prefix('-') {
+        Ok(value.unwrap_or_else(|| default.into()))
+      } else if let Some(message) = modifier.strip_prefix('?') {
+        value.ok_or_else(|| {
+          if message.is_empty() {
+            format!("the environment variable `{}` is required", name)
+          } else {
+            format!(
+              "the environment variable `{}` is required: {}",
+              name, message
+            )
+          }
+        })
+      } else {
+        Err(format!(
+          "`${{{}}}` must use `:-` for a default value or `:?` for a required variable",
+          reference
+        ))
+      }
+    }
+  }
+}
+
 /// Helper function to wrap IO errors from [`std::fs::read_to_string`] into a [`ConfigError`].
 fn read_to_string(path: &Path) -> Result<String, ConfigError> {
   std::fs::read_to_string(path).map_err(|error| ConfigError::Io {
@@ -373,3 +724,129 @@ fn read_to_string(path: &Path) -> Result<String, ConfigError> {
     error,
   })
 }
+
+#[cfg(test)]
+mod test {
+  use super::{apply_profile, interpolate_value, resolve_extends, ConfigError};
+  use serde_json::json;
+
+  fn interpolate(mut value: serde_json::Value) -> Result<serde_json::Value, ConfigError> {
+    interpolate_value(&mut value, &mut Vec::new(), &mut |name| match name {
+      "UPDATER_HOST" => Some("updates.tauri.app".into()),
+      "EMPTY" => Some("".into()),
+      _ => None,
+    })
+    .map(|_| value)
+  }
+
+  #[test]
+  fn interpolates_env_variables() {
+    let config = interpolate(json!({
+      "package": { "productName": "App ${EMPTY:-Dev}", "version": "1.0.0" },
+      "tauri": {
+        "updater": { "endpoints": ["https://${UPDATER_HOST}/{{target}}/$${current_version}"] },
+        "bundle": { "macOS": { "signingIdentity": "${SIGNING_IDENTITY:-}" } }
+      }
+    }))
+    .unwrap();
+    assert_eq!(
+      config,
+      json!({
+        "package": { "productName": "App Dev", "version": "1.0.0" },
+        "tauri": {
+          "updater": { "endpoints": ["https://updates.tauri.app/{{target}}/${current_version}"] },
+          "bundle": { "macOS": { "signingIdentity": "" } }
+        }
+      })
+    );
+
+    match interpolate(
+      json!({ "tauri": { "windows": [{ "title": "${TITLE:?set the window title}" }] } }),
+    ) {
+      Err(ConfigError::EnvInterpolation { key, message }) => {
+        assert_eq!(key, "tauri > windows > 0 > title");
+        assert_eq!(
+          message,
+          "the environment variable `TITLE` is required: set the window title"
+        );
+      }
+      r => panic!("unexpected result {:?}", r),
+    }
+    assert!(interpolate(json!({ "package": { "version": "${VERSION}" } })).is_err());
+    assert!(interpolate(json!({ "package": { "version": "${VERSION" } })).is_err());
+  }
+
+  #[test]
+  fn resolves_extends() {
+    let dir = std::env::temp_dir().join("tauri-utils-resolves-extends");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("shared")).unwrap();
+    std::fs::create_dir_all(dir.join("app").join("src-tauri")).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+    std::fs::write(
+      dir.join("shared").join("base.json"),
+      r#"{ "tauri": { "bundle": { "identifier": "com.base", "publisher": "Base", "targets": ["deb"] } } }"#,
+    )
+    .unwrap();
+    std::fs::write(
+      dir.join("shared").join("signing.json"),
+      r#"{ "extends": "base.json", "tauri": { "bundle": { "publisher": "Signed" } } }"#,
+    )
+    .unwrap();
+    let config_path = dir.join("app").join("src-tauri").join("tauri.conf.json");
+
+    let mut config = json!({
+      "extends": ["../../shared/base.json", "workspace:shared/signing.json"],
+      "tauri": { "bundle": { "identifier": "com.app", "targets": ["appimage"] } }
+    });
+    let files = resolve_extends(&mut config, &config_path).unwrap();
+    assert_eq!(
+      config,
+      json!({
+        "tauri": { "bundle": { "identifier": "com.app", "publisher": "Signed", "targets": ["appimage"] } }
+      })
+    );
+    assert_eq!(files.len(), 3);
+
+    std::fs::write(
+      dir.join("shared").join("base.json"),
+      r#"{ "extends": "signing.json" }"#,
+    )
+    .unwrap();
+    let mut config = json!({ "extends": "workspace:shared/base.json" });
+    match resolve_extends(&mut config, &config_path) {
+      Err(ConfigError::Extends { .. }) => (),
+      r => panic!("unexpected result {:?}", r),
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn applies_profile_overlay() {
+    let config = json!({
+      "tauri": { "allowlist": { "shell": { "open": false } }, "windows": [{ "title": "App" }] },
+      "dev": { "tauri": { "allowlist": { "shell": { "open": true } } } },
+      "prod": { "tauri": { "windows": [{ "title": "App", "devtools": false }] } }
+    });
+
+    let mut debug = config.clone();
+    apply_profile(&mut debug, false);
+    assert_eq!(
+      debug,
+      json!({ "tauri": { "allowlist": { "shell": { "open": true } }, "windows": [{ "title": "App" }] } })
+    );
+
+    let mut release = config;
+    apply_profile(&mut release, true);
+    assert_eq!(
+      release,
+      json!({
+        "tauri": {
+          "allowlist": { "shell": { "open": false } },
+          "windows": [{ "title": "App", "devtools": false }]
+        }
+      })
+    );
+  }
+}
@@ -146,7 +146,7 @@ pub fn target_triple() -> crate::Result<String> {
 /// On Linux, when running in an AppImage the `APPDIR` variable will be set to
 /// the mounted location of the app, and the resource dir will be
 /// `${APPDIR}/usr/lib/${exe_name}`. If not running in an AppImage, the path is
-/// `/usr/lib/${exe_name}`.  When running the app from
+/// `/usr/lib/${exe_name}`, or `/app/lib/${exe_name}` inside a Flatpak sandbox.  When running the app from
 /// `src-tauri/target/(debug|release)/`, the path is
 /// `${exe_dir}/../lib/${exe_name}`.
 ///
@@ -173,6 +173,12 @@ pub fn resource_dir(package_info: &PackageInfo, env: &Env) -> crate::Result<Path
     res = if curr_dir.ends_with("/data/usr/bin") {
       // running from the deb bundle dir
       Ok(exe_dir.join(format!("../lib/{}", package_info.package_name())))
+    } else if curr_dir == "/app/bin" {
+      // running from the Flatpak sandbox
+      Ok(PathBuf::from(format!(
+        "/app/lib/{}",
+        package_info.package_name()
+      )))
     } else if let Some(appdir) = &env.appdir {
       let appdir: &std::path::Path = appdir.as_ref();
       Ok(PathBuf::from(format!(
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::path::{Component, Path, PathBuf};
+use std::{
+  collections::{BTreeMap, HashMap},
+  path::{Component, Path, PathBuf},
+};
 
 /// Given a path (absolute or relative) to a resource file, returns the
 /// relative path from the bundle resources directory where that resource
@@ -21,6 +24,81 @@ pub fn resource_relpath(path: &Path) -> PathBuf {
   dest
 }
 
+/// Expands a map of resource paths or glob patterns to their destination in the bundle resources directory,
+/// returning each resource file with its path relative to that directory.
+///
+/// A directory or a glob pattern is copied to the destination directory, keeping the file paths relative
+/// to the directory or to the part of the pattern preceding the first wildcard.
+/// A file is copied to the destination path, or into it when the destination ends with `/` or is empty.
+///
+/// Fails if a source does not match any file.
+pub fn resource_map_files(map: &HashMap<String, String>) -> crate::Result<Vec<(PathBuf, PathBuf)>> {
+  let mut files = BTreeMap::new();
+  for (source, target) in map {
+    let target_dir = target.is_empty() || target.ends_with('/');
+    let target = resource_relpath(Path::new(target));
+
+    if source.contains(is_wildcard) {
+      // the components before the first wildcard are not part of the destination
+      let base = Path::new(source)
+        .components()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(is_wildcard))
+        .collect::<PathBuf>();
+      let mut found = false;
+      for path in glob::glob(source)? {
+        let path = path?;
+        found = true;
+        for file in walk_files(&path)? {
+          let relative = file.strip_prefix(&base).unwrap_or(&file);
+          files.insert(target.join(relative), file.clone());
+        }
+      }
+      if !found {
+        return Err(crate::Error::GlobPathNotFound(source.clone()));
+      }
+    } else {
+      let path = Path::new(source);
+      if path.is_dir() {
+        for file in walk_files(path)? {
+          let relative = file.strip_prefix(path).unwrap_or(&file);
+          files.insert(target.join(relative), file.clone());
+        }
+      } else if path.is_file() {
+        let file_target = if target_dir {
+          target.join(path.file_name().unwrap_or_default())
+        } else {
+          target
+        };
+        files.insert(file_target, path.to_path_buf());
+      } else {
+        return Err(crate::Error::GlobPathNotFound(source.clone()));
+      }
+    }
+  }
+  Ok(
+    files
+      .into_iter()
+      .map(|(target, file)| (file, target))
+      .collect(),
+  )
+}
+
+fn is_wildcard(c: char) -> bool {
+  matches!(c, '*' | '?' | '[')
+}
+
+/// The files of a directory and its subdirectories, or the path itself if it is a file.
+fn walk_files(path: &Path) -> crate::Result<Vec<PathBuf>> {
+  let mut files = Vec::new();
+  for entry in walkdir::WalkDir::new(path) {
+    let entry = entry?;
+    if !entry.file_type().is_dir() {
+      files.push(entry.into_path());
+    }
+  }
+  Ok(files)
+}
+
 /// Parses the external binaries to bundle, adding the target triple suffix to each of them.
 pub fn external_binaries(external_binaries: &[String], target_triple: &str) -> Vec<String> {
   let mut paths = Vec::new();
@@ -128,3 +206,55 @@ impl<'a> Iterator for ResourcePaths<'a> {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::resource_map_files;
+  use std::{
+    fs::{create_dir_all, File},
+    path::PathBuf,
+  };
+
+  #[test]
+  fn resource_map_destinations() {
+    let tmp = tempfile::tempdir().expect("unable to create tempdir");
+    for file in [
+      "assets/logo.png",
+      "assets/icons/app.png",
+      "assets/notes.txt",
+      "licenses/MIT",
+    ] {
+      let path = tmp.path().join(file);
+      create_dir_all(path.parent().unwrap()).expect("Unable to create dir");
+      File::create(path).expect("Unable to create file");
+    }
+    let source = |path: &str| tmp.path().join(path).display().to_string();
+    let map = [
+      (source("assets/**/*.png"), "images/".to_string()),
+      (source("licenses"), "legal".to_string()),
+      (source("assets/notes.txt"), "docs/README.txt".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    let files = resource_map_files(&map).expect("failed to expand the resource map");
+    let targets = files
+      .iter()
+      .map(|(_, target)| target.clone())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      targets,
+      vec![
+        PathBuf::from("docs/README.txt"),
+        PathBuf::from("images/icons/app.png"),
+        PathBuf::from("images/logo.png"),
+        PathBuf::from("legal/MIT"),
+      ]
+    );
+
+    let missing = [(source("missing/*.png"), "images/".to_string())]
+      .into_iter()
+      .collect();
+    assert!(resource_map_files(&missing).is_err());
+  }
+}
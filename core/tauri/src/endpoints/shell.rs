@@ -99,24 +99,20 @@ impl Cmd {
         let program = PathBuf::from(program);
         let program_as_string = program.display().to_string();
         let program_no_ext_as_string = program.with_extension("").display().to_string();
-        let configured_sidecar = context
-          .config
-          .tauri
-          .bundle
-          .external_bin
-          .as_ref()
-          .map(|bins| {
-            bins
-              .iter()
-              .find(|b| b == &&program_as_string || b == &&program_no_ext_as_string)
-          })
-          .unwrap_or_default();
+        let bundle = &context.config.tauri.bundle;
+        let external_bin = match crate::utils::platform::target_triple() {
+          Ok(target_triple) => bundle.external_bin_for_target(&target_triple),
+          Err(_) => bundle.external_bin.clone().unwrap_or_default(),
+        };
+        let configured_sidecar = external_bin
+          .into_iter()
+          .find(|b| b == &program_as_string || b == &program_no_ext_as_string);
         if let Some(sidecar) = configured_sidecar {
           context
             .window
             .state::<Scopes>()
             .shell
-            .prepare_sidecar(&program.to_string_lossy(), sidecar, args)
+            .prepare_sidecar(&program.to_string_lossy(), &sidecar, args)
             .map_err(crate::error::into_anyhow)?
         } else {
           return Err(crate::Error::SidecarNotAllowed(program).into_anyhow());
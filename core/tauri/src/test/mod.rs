@@ -43,6 +43,9 @@ pub fn mock_context<A: Assets>(assets: A) -> crate::Context<A> {
   crate::Context {
     config: Config {
       schema: None,
+      extends: None,
+      dev: None,
+      prod: None,
       package: Default::default(),
       tauri: TauriConfig {
         pattern: PatternKind::Brownfield,
@@ -612,15 +612,8 @@ impl<R: Runtime> Update<R> {
         archive_buffer,
         &self.extract_path,
         self.with_elevated_task,
-        self
-          .app
-          .config()
-          .tauri
-          .updater
-          .windows
-          .install_mode
-          .clone()
-          .msiexec_args(),
+        self.app.config().tauri.updater.windows.install_mode.clone(),
+        self.download_url.path().ends_with(".nsis.zip"),
       )?;
       #[cfg(not(target_os = "windows"))]
       copy_files_and_run(archive_buffer, &self.extract_path)?;
@@ -702,6 +695,8 @@ fn copy_files_and_run<R: Read + Seek>(archive_buffer: R, extract_path: &Path) ->
 // ### Expected structure:
 // ├── [AppName]_[version]_x64.msi.zip          # ZIP generated by tauri-bundler
 // │   └──[AppName]_[version]_x64.msi           # Application MSI
+// ├── [AppName]_[version]_x64-setup.nsis.zip   # or ZIP of the NSIS installer
+// │   └──[AppName]_[version]_x64-setup.exe     # Application NSIS installer
 // └── ...
 
 // ## MSI
@@ -711,14 +706,19 @@ fn copy_files_and_run<R: Read + Seek>(archive_buffer: R, extract_path: &Path) ->
 
 // ## EXE
 // Update server can provide a custom EXE (installer) who can run any task.
+// The NSIS installer generated with tauri-bundler (downloaded as a `.nsis.zip`) receives the install mode arguments
+// and relaunches the app when it receives `/UPDATE`. Other EXEs run without arguments.
 #[cfg(target_os = "windows")]
 #[allow(clippy::unnecessary_wraps)]
 fn copy_files_and_run<R: Read + Seek>(
   archive_buffer: R,
   _extract_path: &Path,
   with_elevated_task: bool,
-  msiexec_args: &[&str],
+  install_mode: crate::utils::config::WindowsUpdateInstallMode,
+  is_nsis: bool,
 ) -> Result {
+  let msiexec_args = install_mode.msiexec_args();
+
   // FIXME: We need to create a memory buffer with the MSI and then run it.
   //        (instead of extracting the MSI to a temp path)
   //
@@ -745,9 +745,11 @@ fn copy_files_and_run<R: Read + Seek>(
     // If it's an `exe` we expect an installer not a runtime.
     if found_path.extension() == Some(OsStr::new("exe")) {
       // Run the EXE
-      Command::new(found_path)
-        .spawn()
-        .expect("installer failed to start");
+      let mut installer = Command::new(found_path);
+      if is_nsis {
+        installer.args(install_mode.nsis_args()).arg("/UPDATE");
+      }
+      installer.spawn().expect("installer failed to start");
 
       exit(0);
     } else if found_path.extension() == Some(OsStr::new("msi")) {
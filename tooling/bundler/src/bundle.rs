@@ -13,20 +13,28 @@ mod path_utils;
 mod platform;
 mod settings;
 mod updater_bundle;
-#[cfg(target_os = "windows")]
 mod windows;
 
 pub use self::{
   category::AppCategory,
   settings::{
-    BundleBinary, BundleSettings, DebianSettings, MacOsSettings, PackageSettings, PackageType,
-    Settings, SettingsBuilder, UpdaterSettings,
+    AppImageSettings, BundleBinary, BundleSettings, BundleTypeRole, DebianSettings, DesktopAction,
+    DesktopEntrySettings, DmgSettings, FileAssociation, FlatpakSettings, MacOsSettings,
+    NotarizationSettings, PackageSettings, PackageType, RpmSettings, Settings, SettingsBuilder,
+    SignCommand, UpdaterSettings,
   },
 };
 use log::{info, warn};
-pub use settings::{WindowsSettings, WixLanguage, WixLanguageConfig, WixSettings};
+pub use settings::{
+  MsixSettings, NsisInstallMode, NsisSettings, WindowsSettings, WindowsSignMethod, WixLanguage,
+  WixLanguageConfig, WixSettings,
+};
 
-use std::{fmt::Write, path::PathBuf};
+use std::{
+  fmt::Write,
+  path::PathBuf,
+  time::{Duration, Instant},
+};
 
 /// Generated bundle metadata.
 #[derive(Debug)]
@@ -35,6 +43,8 @@ pub struct Bundle {
   pub package_type: PackageType,
   /// All paths for this package.
   pub bundle_paths: Vec<PathBuf>,
+  /// The time spent creating this package.
+  pub duration: Duration,
 }
 
 /// Bundles the project.
@@ -42,8 +52,26 @@ pub struct Bundle {
 pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
   let mut bundles = Vec::new();
   let package_types = settings.package_types()?;
+  for package_type in settings.unsupported_package_types()? {
+    warn!(
+      "The `{}` bundle is not supported for the {} target, skipping it",
+      package_type.short_name(),
+      settings.target()
+    );
+  }
+
+  if let Some(sign_command) = settings.sign_command() {
+    for bin in settings.binaries() {
+      common::run_sign_command(
+        &sign_command.command,
+        &sign_command.args,
+        &settings.binary_path(bin),
+      )?;
+    }
+  }
 
   for package_type in &package_types {
+    let started_at = Instant::now();
     let bundle_paths = match package_type {
       #[cfg(target_os = "macos")]
       PackageType::MacOsBundle => macos::app::bundle_project(&settings)?,
@@ -51,11 +79,16 @@ pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
       PackageType::IosBundle => macos::ios::bundle_project(&settings)?,
       #[cfg(target_os = "windows")]
       PackageType::WindowsMsi => windows::msi::bundle_project(&settings, false)?,
+      PackageType::Nsis => windows::nsis::bundle_project(&settings)?,
+      #[cfg(target_os = "windows")]
+      PackageType::Msix => windows::msix::bundle_project(&settings)?,
       #[cfg(target_os = "linux")]
       PackageType::Deb => linux::debian::bundle_project(&settings)?,
       #[cfg(target_os = "linux")]
       PackageType::Rpm => linux::rpm::bundle_project(&settings)?,
       #[cfg(target_os = "linux")]
+      PackageType::Flatpak => linux::flatpak::bundle_project(&settings)?,
+      #[cfg(target_os = "linux")]
       PackageType::AppImage => linux::appimage::bundle_project(&settings)?,
       // dmg is dependant of MacOsBundle, we send our bundles to prevent rebuilding
       #[cfg(target_os = "macos")]
@@ -68,9 +101,17 @@ pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
       }
     };
 
+    // sign each bundle before the ones depending on it are created
+    if let Some(sign_command) = settings.sign_command() {
+      for path in &bundle_paths {
+        common::run_sign_command(&sign_command.command, &sign_command.args, path)?;
+      }
+    }
+
     bundles.push(Bundle {
       package_type: package_type.to_owned(),
       bundle_paths,
+      duration: started_at.elapsed(),
     });
   }
 
@@ -96,6 +137,213 @@ pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
   Ok(bundles)
 }
 
+/// Checks the icons, tooling and signing configuration needed by each package type of the settings.
+///
+/// It does not need the app binaries, so it can run before compiling the app to fail early.
+/// The resources are checked separately by [`check_resources`], since the app build or the
+/// `beforeBundleCommand` may create them.
+/// Every problem found is listed in the returned error.
+pub fn check_prerequisites(settings: &Settings) -> crate::Result<()> {
+  let mut problems = Vec::new();
+
+  for icon_path in settings.icon_files() {
+    if let Err(e) = icon_path {
+      problems.push(format!("invalid icon: {}", e));
+    }
+  }
+
+  if let Some(sign_command) = settings.sign_command() {
+    if sign_command.command.is_empty() {
+      problems.push("the sign command must not be empty".into());
+    }
+  }
+
+  for package_type in settings.package_types()? {
+    if let Err(e) = check_package_prerequisites(settings, package_type) {
+      problems.push(format!("{}: {}", package_type.short_name(), e));
+    }
+  }
+
+  if problems.is_empty() {
+    Ok(())
+  } else {
+    Err(crate::Error::GenericError(format!(
+      "the bundle prerequisites are not met:\n  {}",
+      problems.join("\n  ")
+    )))
+  }
+}
+
+/// Checks that every resource of the settings exists.
+///
+/// Every missing resource is listed in the returned error.
+pub fn check_resources(settings: &Settings) -> crate::Result<()> {
+  let mut problems = Vec::new();
+
+  for resource in settings.resources() {
+    match resource {
+      Ok((src, _)) if !src.is_file() => {
+        problems.push(format!("resource {} does not exist", src.display()))
+      }
+      Err(e) => problems.push(format!("invalid resource: {}", e)),
+      _ => {}
+    }
+  }
+
+  if problems.is_empty() {
+    Ok(())
+  } else {
+    Err(crate::Error::GenericError(format!(
+      "the bundle resources are not available:\n  {}",
+      problems.join("\n  ")
+    )))
+  }
+}
+
+fn check_package_prerequisites(
+  settings: &Settings,
+  package_type: PackageType,
+) -> crate::Result<()> {
+  match package_type {
+    PackageType::WindowsMsi | PackageType::Nsis => {
+      windows::icon_path(settings)?;
+      if package_type == PackageType::Nsis {
+        windows::nsis::check_makensis()?;
+        if let Some(nsis) = &settings.windows().nsis {
+          for path in [
+            &nsis.template,
+            &nsis.license,
+            &nsis.header_image,
+            &nsis.sidebar_image,
+          ]
+          .into_iter()
+          .flatten()
+          .chain(&nsis.custom_pages)
+          {
+            if !path.is_file() {
+              return Err(crate::Error::GenericError(format!(
+                "{} does not exist",
+                path.display()
+              )));
+            }
+          }
+        }
+      }
+      #[cfg(target_os = "windows")]
+      if package_type == PackageType::WindowsMsi
+        && settings
+          .windows()
+          .wix
+          .as_ref()
+          .map_or(false, |w| w.embed_language_transforms)
+      {
+        windows::sign::locate_sdk_tool("msidb.exe")?;
+        windows::sign::locate_sdk_tool("msiinfo.exe")?;
+      }
+      // WiX is downloaded on the first MSI build, but signtool must come with the Windows SDK
+      windows::sign::check_sign_method(settings)?;
+    }
+    #[cfg(target_os = "windows")]
+    PackageType::Msix => {
+      windows::sign::locate_sdk_tool("makeappx.exe")?;
+      windows::msix::logo_source(settings)?;
+      windows::sign::check_sign_method(settings)?;
+    }
+    PackageType::MacOsBundle | PackageType::Dmg => {
+      // the certificate is only imported to a keychain when both variables are set
+      if settings.macos().signing_identity.is_some()
+        && std::env::var_os("APPLE_CERTIFICATE").is_some()
+        && std::env::var_os("APPLE_CERTIFICATE_PASSWORD").is_none()
+      {
+        return Err(crate::Error::GenericError(
+          "`APPLE_CERTIFICATE` is set but `APPLE_CERTIFICATE_PASSWORD` is missing".into(),
+        ));
+      }
+      // fails early on incomplete notarization credentials
+      #[cfg(target_os = "macos")]
+      if settings.macos().signing_identity.is_some() {
+        macos::sign::notarize_auth(settings)?;
+      }
+      if let Some(background) = &settings.macos().dmg.background {
+        if package_type == PackageType::Dmg && !background.is_file() {
+          return Err(crate::Error::GenericError(format!(
+            "{} does not exist",
+            background.display()
+          )));
+        }
+      }
+    }
+    #[cfg(target_os = "linux")]
+    PackageType::Deb => {
+      let deb = settings.deb();
+      for path in [
+        &deb.systemd_user_unit,
+        &deb.pre_install_script,
+        &deb.post_install_script,
+        &deb.pre_remove_script,
+        &deb.post_remove_script,
+      ]
+      .into_iter()
+      .flatten()
+      {
+        if !path.is_file() {
+          return Err(crate::Error::GenericError(format!(
+            "{} does not exist",
+            path.display()
+          )));
+        }
+      }
+    }
+    #[cfg(target_os = "linux")]
+    PackageType::Rpm => {
+      linux::rpm::check_rpmbuild()?;
+      let rpm = settings.rpm();
+      for path in [
+        &rpm.desktop_template,
+        &rpm.pre_install_script,
+        &rpm.post_install_script,
+        &rpm.pre_remove_script,
+        &rpm.post_remove_script,
+      ]
+      .into_iter()
+      .flatten()
+      {
+        if !path.is_file() {
+          return Err(crate::Error::GenericError(format!(
+            "{} does not exist",
+            path.display()
+          )));
+        }
+      }
+    }
+    #[cfg(target_os = "linux")]
+    PackageType::AppImage => {
+      if let Some(app_run) = &settings.appimage().app_run {
+        if !app_run.is_file() {
+          return Err(crate::Error::GenericError(format!(
+            "{} does not exist",
+            app_run.display()
+          )));
+        }
+      }
+      let mut has_square_icon = false;
+      for icon_path in settings.icon_files().flatten() {
+        if icon_path.extension().map_or(false, |e| e == "png") {
+          let (width, height) = image::image_dimensions(&icon_path)?;
+          has_square_icon |= width == height;
+        }
+      }
+      if !has_square_icon {
+        return Err(crate::Error::GenericError(
+          "the bundle config must have a square `.png` icon".into(),
+        ));
+      }
+    }
+    _ => {}
+  }
+  Ok(())
+}
+
 /// Check to see if there are icons in the settings struct
 pub fn check_icons(settings: &Settings) -> crate::Result<bool> {
   // make a peekable iterator of the icon_files
@@ -237,6 +237,12 @@ impl AppCategory {
   }
 }
 
+impl serde::Serialize for AppCategory {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(self.macos_application_category_type())
+  }
+}
+
 impl<'d> serde::Deserialize<'d> for AppCategory {
   fn deserialize<D: serde::Deserializer<'d>>(deserializer: D) -> Result<AppCategory, D::Error> {
     deserializer.deserialize_str(AppCategoryVisitor { did_you_mean: None })
@@ -3,12 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use log::debug;
+use anyhow::Context;
+use log::{debug, info};
 
 use std::{
   ffi::OsStr,
   fs::{self, File},
-  io::{self, BufReader, BufWriter},
+  io::{self, BufReader, BufWriter, Write},
   path::Path,
   process::{Command, Output, Stdio},
   sync::{Arc, Mutex},
@@ -28,6 +29,59 @@ pub fn is_retina<P: AsRef<Path>>(path: P) -> bool {
     .unwrap_or(false)
 }
 
+/// The `SOURCE_DATE_EPOCH` environment variable, set by `tauri build --reproducible`.
+///
+/// See <https://reproducible-builds.org/specs/source-date-epoch/>.
+pub fn source_date_epoch() -> Option<u64> {
+  std::env::var("SOURCE_DATE_EPOCH")
+    .ok()
+    .and_then(|epoch| epoch.parse().ok())
+}
+
+/// Appends a file or directory to a tar archive.
+///
+/// When [`source_date_epoch`] is set, the entry is written with that modification time and without ownership.
+#[allow(dead_code)]
+pub fn append_to_tar<W: Write>(
+  builder: &mut tar::Builder<W>,
+  path: &Path,
+  src_path: &Path,
+) -> crate::Result<()> {
+  match source_date_epoch() {
+    Some(epoch) => {
+      let metadata = fs::metadata(src_path)?;
+      let mut header = tar::Header::new_gnu();
+      header.set_metadata_in_mode(&metadata, tar::HeaderMode::Deterministic);
+      header.set_mtime(epoch);
+      if metadata.is_dir() {
+        builder.append_data(&mut header, path, io::empty())?;
+      } else {
+        builder.append_data(&mut header, path, File::open(src_path)?)?;
+      }
+    }
+    None if src_path.is_dir() => builder.append_dir(path, src_path)?,
+    None => builder.append_file(path, &mut File::open(src_path)?)?,
+  }
+  Ok(())
+}
+
+/// Creates a gzip encoder, whose header timestamp is [`source_date_epoch`] instead of the current time when set.
+#[allow(dead_code)]
+pub fn gzip_encoder<W: Write>(writer: W) -> io::Result<libflate::gzip::Encoder<W>> {
+  use libflate::gzip::{EncodeOptions, Encoder, HeaderBuilder};
+  match source_date_epoch() {
+    Some(epoch) => Encoder::with_options(
+      writer,
+      EncodeOptions::new().header(
+        HeaderBuilder::new()
+          .modification_time(epoch as u32)
+          .finish(),
+      ),
+    ),
+    None => Encoder::new(writer),
+  }
+}
+
 /// Creates a new file at the given path, creating any parent directories as
 /// needed.
 pub fn create_file(path: &Path) -> crate::Result<BufWriter<File>> {
@@ -135,6 +189,23 @@ pub fn copy_dir(from: &Path, to: &Path) -> crate::Result<()> {
   Ok(())
 }
 
+/// Runs `command`, with `%1` in the arguments replaced with the path of the file to sign.
+pub fn run_sign_command(command: &str, args: &[String], path: &Path) -> crate::Result<()> {
+  info!(action = "Signing"; "{} with `{}`", path.display(), command);
+  let mut cmd = Command::new(command);
+  for arg in args {
+    if arg == "%1" {
+      cmd.arg(path);
+    } else {
+      cmd.arg(arg.replace("%1", &path.to_string_lossy()));
+    }
+  }
+  cmd
+    .output_ok()
+    .with_context(|| format!("failed to sign {}", path.display()))?;
+  Ok(())
+}
+
 pub trait CommandExt {
   fn output_ok(&mut self) -> crate::Result<Output>;
 }
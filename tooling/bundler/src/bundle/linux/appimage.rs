@@ -13,8 +13,8 @@ use handlebars::Handlebars;
 use log::info;
 use std::{
   collections::BTreeMap,
-  fs::{remove_dir_all, write},
-  path::PathBuf,
+  fs::{read_to_string, remove_dir_all, write},
+  path::{Path, PathBuf},
   process::{Command, Stdio},
 };
 
@@ -25,13 +25,26 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   let arch = match settings.binary_arch() {
     "x86" => "i386",
     "x86_64" => "amd64",
+    "arm" => "armhf",
+    other => other,
+  };
+  // the AppImage runtime matches the target, while linuxdeploy must run on the host
+  let runtime_arch = match settings.binary_arch() {
+    "x86" => "i686",
+    "arm" => "armhf",
+    other => other,
+  };
+  let linuxdeploy_arch = match std::env::consts::ARCH {
+    "x86" => "i386",
+    "arm" => "armhf",
     other => other,
   };
   let package_dir = settings.project_out_directory().join("bundle/appimage_deb");
 
   // generate deb_folder structure
-  let (_, icons) = debian::generate_data(settings, &package_dir)?;
+  let (data_dir, icons) = debian::generate_data(settings, &package_dir)?;
   let icons: Vec<debian::DebIcon> = icons.into_iter().collect();
+  customize_desktop_file(settings, &data_dir).with_context(|| "Failed to edit desktop file")?;
 
   let output_path = settings.project_out_directory().join("bundle/appimage");
   if output_path.exists() {
@@ -40,10 +53,15 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   std::fs::create_dir_all(output_path.clone())?;
   let app_dir_path = output_path.join(format!("{}.AppDir", settings.main_binary_name()));
   let appimage_filename = format!(
-    "{}_{}_{}.AppImage",
-    settings.main_binary_name(),
-    settings.version_string(),
-    arch
+    "{}.AppImage",
+    settings
+      .artifact_name(crate::PackageType::AppImage, arch)
+      .unwrap_or_else(|| format!(
+        "{}_{}_{}",
+        settings.main_binary_name(),
+        settings.version_string(),
+        arch
+      ))
   );
   let appimage_path = output_path.join(&appimage_filename);
   path_utils::create(app_dir_path, true)?;
@@ -52,7 +70,8 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   // setup data to insert into shell script
   let mut sh_map = BTreeMap::new();
-  sh_map.insert("arch", settings.target().split('-').next().unwrap());
+  sh_map.insert("arch", runtime_arch);
+  sh_map.insert("linuxdeploy_arch", linuxdeploy_arch);
   sh_map.insert("app_name", settings.main_binary_name());
   sh_map.insert("app_name_uppercase", &upcase_app_name);
   sh_map.insert("appimage_filename", &appimage_filename);
@@ -78,6 +97,27 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .to_string_lossy()
     .to_string();
   sh_map.insert("icon_path", &larger_icon_path);
+  let appimage_settings = settings.appimage();
+  let app_run = match &appimage_settings.app_run {
+    Some(app_run) => std::env::current_dir()?
+      .join(app_run)
+      .to_string_lossy()
+      .to_string(),
+    None => String::new(),
+  };
+  sh_map.insert("app_run", &app_run);
+  let update_information = appimage_settings
+    .update_information
+    .clone()
+    .unwrap_or_default();
+  sh_map.insert("update_information", &update_information);
+  let exclude_libraries = appimage_settings
+    .exclude_libraries
+    .iter()
+    .map(|pattern| format!("--exclude-library \"{}\"", pattern))
+    .collect::<Vec<_>>()
+    .join(" ");
+  sh_map.insert("exclude_libraries", &exclude_libraries);
 
   // initialize shell script template.
   let mut handlebars = Handlebars::new();
@@ -105,10 +145,55 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   // execute the shell script to build the appimage.
   Command::new(&sh_file)
-    .current_dir(output_path)
+    .current_dir(&output_path)
     .output_ok()
     .context("error running appimage.sh")?;
 
   remove_dir_all(&package_dir)?;
-  Ok(vec![appimage_path])
+
+  // AppImageUpdate downloads the delta of the new version from this file
+  let zsync_path = output_path.join(format!("{}.zsync", appimage_filename));
+  if zsync_path.exists() {
+    Ok(vec![appimage_path, zsync_path])
+  } else {
+    Ok(vec![appimage_path])
+  }
+}
+
+/// Adds the `desktop_entry` keys to the desktop file generated for the Debian package.
+fn customize_desktop_file(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
+  let desktop_entry = &settings.appimage().desktop_entry;
+  if desktop_entry.is_empty() {
+    return Ok(());
+  }
+
+  let desktop_file_path = data_dir
+    .join("usr/share/applications")
+    .join(format!("{}.desktop", settings.main_binary_name()));
+  let contents = read_to_string(&desktop_file_path)?;
+  // the keys belong to the main group, which the action groups follow
+  let (entry, actions) = contents
+    .find("\n[Desktop Action ")
+    .map_or((contents.as_str(), ""), |i| contents.split_at(i + 1));
+  let mut desktop_file = String::new();
+  for line in entry.lines() {
+    let overridden = line
+      .split_once('=')
+      .map_or(false, |(key, _)| desktop_entry.contains_key(key));
+    if !overridden && !line.is_empty() {
+      desktop_file.push_str(line);
+      desktop_file.push('\n');
+    }
+  }
+  let mut keys = desktop_entry.keys().collect::<Vec<_>>();
+  keys.sort();
+  for key in keys {
+    desktop_file.push_str(&format!("{}={}\n", key, desktop_entry[key]));
+  }
+  if !actions.is_empty() {
+    desktop_file.push('\n');
+    desktop_file.push_str(actions);
+  }
+  write(&desktop_file_path, desktop_file)?;
+  Ok(())
 }
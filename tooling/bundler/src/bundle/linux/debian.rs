@@ -10,36 +10,40 @@
 //     control.tar.gz          # Contains files controlling the installation:
 //         control                  # Basic package metadata
 //         md5sums                  # Checksums for files in data.tar.gz below
-//         postinst                 # Post-installation script (optional)
-//         prerm                    # Pre-uninstallation script (optional)
+//         conffiles                # Configuration files kept on upgrades (optional)
+//         preinst, postinst        # Installation scripts (optional)
+//         prerm, postrm            # Uninstallation scripts (optional)
 //     data.tar.gz             # Contains files to be installed:
 //         usr/bin/foobar                            # Binary executable file
 //         usr/share/applications/foobar.desktop     # Desktop file (for apps)
 //         usr/share/icons/hicolor/...               # Icon files (for apps)
+//         usr/share/mime/packages/foobar.xml        # MIME types of the file associations (optional)
 //         usr/lib/foobar/...                        # Other resource files
 //
 // For cargo-bundle, we put bundle resource files under /usr/lib/package_name/,
 // and then generate the desktop file and control file from the bundle
-// metadata, as well as generating the md5sums file.  Currently we do not
-// generate postinst or prerm files.
+// metadata, as well as generating the md5sums file.  The maintainer scripts
+// are the configured ones, extended to enable the systemd user unit if any.
 
 use super::super::common;
 use crate::Settings;
 use anyhow::Context;
 use heck::AsKebabCase;
 use image::{self, codecs::png::PngDecoder, ImageDecoder};
-use libflate::gzip;
 use log::info;
 use walkdir::WalkDir;
 
 use std::{
-  collections::BTreeSet,
+  collections::{BTreeSet, HashMap},
   ffi::OsStr,
   fs::{self, File},
   io::{self, Write},
+  os::unix::fs::PermissionsExt,
   path::{Path, PathBuf},
 };
 
+const SYSTEMD_USER_UNIT_DIR: &str = "usr/lib/systemd/user";
+
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 pub struct DebIcon {
   pub width: u32,
@@ -59,12 +63,16 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     "aarch64" => "arm64",
     other => other,
   };
-  let package_base_name = format!(
-    "{}_{}_{}",
-    settings.main_binary_name(),
-    settings.version_string(),
-    arch
-  );
+  let package_base_name = settings
+    .artifact_name(crate::PackageType::Deb, arch)
+    .unwrap_or_else(|| {
+      format!(
+        "{}_{}_{}",
+        settings.main_binary_name(),
+        settings.version_string(),
+        arch
+      )
+    });
   let package_name = format!("{}.deb", package_base_name);
 
   let base_dir = settings.project_out_directory().join("bundle/deb");
@@ -79,12 +87,24 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   let (data_dir, _) = generate_data(settings, &package_dir)
     .with_context(|| "Failed to build data folders and files")?;
-  copy_custom_files(settings, &data_dir).with_context(|| "Failed to copy custom files")?;
+  copy_custom_files(&settings.deb().files, &data_dir)
+    .with_context(|| "Failed to copy custom files")?;
+  if let Some(unit) = &settings.deb().systemd_user_unit {
+    let unit_name = unit
+      .file_name()
+      .ok_or_else(|| anyhow::anyhow!("Invalid systemd user unit path {}", unit.display()))?;
+    common::copy_file(unit, data_dir.join(SYSTEMD_USER_UNIT_DIR).join(unit_name))
+      .with_context(|| "Failed to copy systemd user unit")?;
+  }
 
   // Generate control files.
   let control_dir = package_dir.join("control");
   generate_control_file(settings, arch, &control_dir, &data_dir)
     .with_context(|| "Failed to create control file")?;
+  generate_conffiles(settings, &control_dir, &data_dir)
+    .with_context(|| "Failed to create conffiles file")?;
+  generate_scripts(settings, &control_dir)
+    .with_context(|| "Failed to create maintainer scripts")?;
   generate_md5sums(&control_dir, &data_dir).with_context(|| "Failed to create md5sums file")?;
 
   // Generate `debian-binary` file; see
@@ -130,6 +150,7 @@ pub fn generate_data(
   let icons =
     generate_icon_files(settings, &data_dir).with_context(|| "Failed to create icon files")?;
   generate_desktop_file(settings, &data_dir).with_context(|| "Failed to create desktop file")?;
+  generate_mime_info(settings, &data_dir).with_context(|| "Failed to create MIME info file")?;
 
   Ok((data_dir, icons))
 }
@@ -137,6 +158,7 @@ pub fn generate_data(
 /// Generate the application desktop file and store it under the `data_dir`.
 fn generate_desktop_file(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
   let bin_name = settings.main_binary_name();
+  let desktop = settings.desktop();
   let desktop_file_name = format!("{}.desktop", bin_name);
   let desktop_file_path = data_dir
     .join("usr/share/applications")
@@ -145,7 +167,17 @@ fn generate_desktop_file(settings: &Settings, data_dir: &Path) -> crate::Result<
   // For more information about the format of this file, see
   // https://developer.gnome.org/integration-guide/stable/desktop-files.html.en
   writeln!(file, "[Desktop Entry]")?;
-  if let Some(category) = settings.app_category() {
+  if !desktop.actions.is_empty() {
+    let ids = desktop
+      .actions
+      .iter()
+      .map(|action| action.id.clone())
+      .collect::<Vec<_>>();
+    writeln!(file, "Actions={}", desktop_list(&ids))?;
+  }
+  if let Some(categories) = &desktop.categories {
+    writeln!(file, "Categories={}", desktop_list(categories))?;
+  } else if let Some(category) = settings.app_category() {
     writeln!(file, "Categories={}", category.gnome_desktop_categories())?;
   } else {
     writeln!(file, "Categories=")?;
@@ -153,14 +185,148 @@ fn generate_desktop_file(settings: &Settings, data_dir: &Path) -> crate::Result<
   if !settings.short_description().is_empty() {
     writeln!(file, "Comment={}", settings.short_description())?;
   }
-  writeln!(file, "Exec={}", bin_name)?;
+  writeln!(
+    file,
+    "Exec={}{}",
+    exec(bin_name, &desktop.exec_args),
+    exec_field_code(settings)
+  )?;
   writeln!(file, "Icon={}", bin_name)?;
+  if !desktop.keywords.is_empty() {
+    writeln!(file, "Keywords={}", desktop_list(&desktop.keywords))?;
+  }
+  let mime_types = mime_types(settings);
+  if !mime_types.is_empty() {
+    writeln!(file, "MimeType={};", mime_types.join(";"))?;
+  }
   writeln!(file, "Name={}", settings.product_name())?;
+  if let Some(startup_wm_class) = &desktop.startup_wm_class {
+    writeln!(file, "StartupWMClass={}", startup_wm_class)?;
+  }
   writeln!(file, "Terminal=false")?;
   writeln!(file, "Type=Application")?;
+  for action in &desktop.actions {
+    writeln!(file)?;
+    writeln!(file, "[Desktop Action {}]", action.id)?;
+    writeln!(file, "Exec={}", exec(bin_name, &action.exec_args))?;
+    writeln!(file, "Name={}", action.name)?;
+  }
   Ok(())
 }
 
+/// The value of a desktop file list key, e.g. `Keywords`.
+fn desktop_list(values: &[String]) -> String {
+  values
+    .iter()
+    .map(|value| format!("{};", value.replace(';', "\\;")))
+    .collect()
+}
+
+/// The value of a desktop file `Exec` key launching the binary with the arguments.
+fn exec(bin_name: &str, args: &[String]) -> String {
+  std::iter::once(bin_name.to_string())
+    .chain(args.iter().map(|arg| exec_arg(arg)))
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// Quotes an argument of the `Exec` key following the desktop entry specification:
+/// arguments with reserved characters are quoted, `%` is doubled
+/// and the backslashes are escaped again since the key is a string value.
+fn exec_arg(arg: &str) -> String {
+  let reserved = |c: char| c.is_whitespace() || "\"'\\><~|&;$*?#()`".contains(c);
+  let arg = if arg.is_empty() || arg.contains(reserved) {
+    let mut quoted = String::from('"');
+    for c in arg.chars() {
+      if "\"`$\\".contains(c) {
+        quoted.push('\\');
+      }
+      quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+  } else {
+    arg.to_string()
+  };
+  arg.replace('%', "%%").replace('\\', "\\\\")
+}
+
+/// The MIME types opened by the app: the types of the file associations and the URL scheme handlers.
+pub fn mime_types(settings: &Settings) -> Vec<String> {
+  settings
+    .file_associations()
+    .iter()
+    .filter_map(|association| association.mime_type.clone())
+    .chain(
+      settings
+        .deep_link_schemes()
+        .iter()
+        .map(|scheme| format!("x-scheme-handler/{}", scheme)),
+    )
+    .collect()
+}
+
+/// The field code of the desktop file `Exec` key passing the opened files or URLs to the app.
+fn exec_field_code(settings: &Settings) -> &'static str {
+  if !settings.deep_link_schemes().is_empty() {
+    " %U"
+  } else if settings
+    .file_associations()
+    .iter()
+    .any(|association| association.mime_type.is_some())
+  {
+    " %F"
+  } else {
+    ""
+  }
+}
+
+/// Generate the shared-mime-info package defining the MIME types of the file associations.
+fn generate_mime_info(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
+  let mut mime_info = String::new();
+  for association in settings.file_associations() {
+    if let Some(mime_type) = &association.mime_type {
+      mime_info.push_str(&format!(
+        "  <mime-type type=\"{}\">\n",
+        xml_escape(mime_type)
+      ));
+      let comment = association
+        .description
+        .as_deref()
+        .unwrap_or_else(|| association.name());
+      mime_info.push_str(&format!("    <comment>{}</comment>\n", xml_escape(comment)));
+      for ext in &association.ext {
+        mime_info.push_str(&format!("    <glob pattern=\"*.{}\"/>\n", xml_escape(ext)));
+      }
+      mime_info.push_str("  </mime-type>\n");
+    }
+  }
+  if mime_info.is_empty() {
+    return Ok(());
+  }
+
+  let mime_info_path = data_dir
+    .join("usr/share/mime/packages")
+    .join(format!("{}.xml", settings.bundle_identifier()));
+  let file = &mut common::create_file(&mime_info_path)?;
+  writeln!(file, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+  writeln!(
+    file,
+    "<mime-info xmlns=\"http://www.freedesktop.org/standards/shared-mime-info\">"
+  )?;
+  write!(file, "{}", mime_info)?;
+  writeln!(file, "</mime-info>")?;
+  Ok(())
+}
+
+fn xml_escape(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
 /// Generates the debian control file and stores it under the `control_dir`.
 fn generate_control_file(
   settings: &Settings,
@@ -175,6 +341,14 @@ fn generate_control_file(
   writeln!(file, "Package: {}", AsKebabCase(settings.product_name()))?;
   writeln!(file, "Version: {}", settings.version_string())?;
   writeln!(file, "Architecture: {}", arch)?;
+  if let Some(section) = &settings.deb().section {
+    writeln!(file, "Section: {}", section)?;
+  }
+  writeln!(
+    file,
+    "Priority: {}",
+    settings.deb().priority.as_deref().unwrap_or("optional")
+  )?;
   // Installed-Size must be divided by 1024, see https://www.debian.org/doc/debian-policy/ch-controlfields.html#installed-size
   writeln!(file, "Installed-Size: {}", total_dir_size(data_dir)? / 1024)?;
   let authors = settings.authors_comma_separated().unwrap_or_default();
@@ -186,6 +360,17 @@ fn generate_control_file(
   if !dependencies.is_empty() {
     writeln!(file, "Depends: {}", dependencies.join(", "))?;
   }
+  for (field, packages) in [
+    ("Recommends", &settings.deb().recommends),
+    ("Suggests", &settings.deb().suggests),
+    ("Provides", &settings.deb().provides),
+    ("Conflicts", &settings.deb().conflicts),
+    ("Replaces", &settings.deb().replaces),
+  ] {
+    if let Some(packages) = packages.as_ref().filter(|p| !p.is_empty()) {
+      writeln!(file, "{}: {}", field, packages.join(", "))?;
+    }
+  }
   let mut short_description = settings.short_description().trim();
   if short_description.is_empty() {
     short_description = "(none)";
@@ -203,11 +388,115 @@ fn generate_control_file(
       writeln!(file, " {}", line)?;
     }
   }
-  writeln!(file, "Priority: optional")?;
   file.flush()?;
   Ok(())
 }
 
+/// Declares the files under `/etc` and the configured conffiles in the `conffiles` file of the `control_dir`.
+fn generate_conffiles(
+  settings: &Settings,
+  control_dir: &Path,
+  data_dir: &Path,
+) -> crate::Result<()> {
+  let mut conffiles = BTreeSet::new();
+
+  let etc_dir = data_dir.join("etc");
+  if etc_dir.is_dir() {
+    for entry in WalkDir::new(&etc_dir) {
+      let entry = entry?;
+      if entry.file_type().is_file() {
+        conffiles.insert(Path::new("/").join(entry.path().strip_prefix(data_dir)?));
+      }
+    }
+  }
+
+  for path in &settings.deb().conffiles {
+    let path = Path::new("/").join(path);
+    if !data_dir.join(path.strip_prefix("/")?).is_file() {
+      return Err(crate::Error::GenericError(format!(
+        "the conffile {} is not included in the package",
+        path.display()
+      )));
+    }
+    conffiles.insert(path);
+  }
+
+  if conffiles.is_empty() {
+    return Ok(());
+  }
+  let mut file = common::create_file(&control_dir.join("conffiles"))?;
+  for path in conffiles {
+    writeln!(file, "{}", path.display())?;
+  }
+  file.flush()?;
+  Ok(())
+}
+
+/// Writes the configured maintainer scripts to the `control_dir`,
+/// along with the commands enabling and disabling the systemd user unit.
+fn generate_scripts(settings: &Settings, control_dir: &Path) -> crate::Result<()> {
+  let deb = settings.deb();
+  let unit_name = deb
+    .systemd_user_unit
+    .as_ref()
+    .and_then(|unit| unit.file_name())
+    .map(|name| name.to_string_lossy().into_owned());
+  let unit_command = |action: &str, when: &str| {
+    unit_name.as_ref().map(|unit| {
+      format!(
+        "if [ \"$1\" = \"{}\" ]; then\n  systemctl --global {} {} >/dev/null || true\nfi\n",
+        when, action, unit
+      )
+    })
+  };
+
+  for (name, script, snippet) in [
+    ("preinst", &deb.pre_install_script, None),
+    (
+      "postinst",
+      &deb.post_install_script,
+      unit_command("enable", "configure"),
+    ),
+    (
+      "prerm",
+      &deb.pre_remove_script,
+      unit_command("disable", "remove"),
+    ),
+    ("postrm", &deb.post_remove_script, None),
+  ] {
+    let content = match (script, snippet) {
+      (Some(path), snippet) => {
+        let script =
+          fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        match snippet {
+          Some(snippet) => insert_snippet(&script, &snippet),
+          None => script,
+        }
+      }
+      (None, Some(snippet)) => format!("#!/bin/sh\nset -e\n{}", snippet),
+      (None, None) => continue,
+    };
+    let dest_path = control_dir.join(name);
+    create_file_with_data(&dest_path, &content)?;
+    fs::set_permissions(&dest_path, fs::Permissions::from_mode(0o755))?;
+  }
+  Ok(())
+}
+
+/// Replaces the `#DEBHELPER#` token of the script with the snippet, like debhelper does,
+/// or inserts the snippet after the shebang.
+fn insert_snippet(script: &str, snippet: &str) -> String {
+  if script.contains("#DEBHELPER#") {
+    return script.replacen("#DEBHELPER#", snippet.trim_end(), 1);
+  }
+  match script.split_once('\n') {
+    Some((shebang, rest)) if shebang.starts_with("#!") => {
+      format!("{}\n{}{}", shebang, snippet, rest)
+    }
+    _ => format!("{}{}", snippet, script),
+  }
+}
+
 /// Create an `md5sums` file in the `control_dir` containing the MD5 checksums
 /// for each file within the `data_dir`.
 fn generate_md5sums(control_dir: &Path, data_dir: &Path) -> crate::Result<()> {
@@ -242,9 +531,9 @@ fn copy_resource_files(settings: &Settings, data_dir: &Path) -> crate::Result<()
   settings.copy_resources(&resource_dir)
 }
 
-/// Copies user-defined files to the deb package.
-fn copy_custom_files(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
-  for (deb_path, path) in settings.deb().files.iter() {
+/// Copies user-defined files to the deb or rpm package.
+pub fn copy_custom_files(files: &HashMap<PathBuf, PathBuf>, data_dir: &Path) -> crate::Result<()> {
+  for (deb_path, path) in files.iter() {
     let deb_path = if deb_path.is_absolute() {
       deb_path.strip_prefix("/").unwrap()
     } else {
@@ -329,19 +618,15 @@ fn total_dir_size(dir: &Path) -> crate::Result<u64> {
 fn create_tar_from_dir<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> crate::Result<W> {
   let src_dir = src_dir.as_ref();
   let mut tar_builder = tar::Builder::new(dest_file);
-  for entry in WalkDir::new(&src_dir) {
+  // sorted so the archive does not depend on the file system order
+  for entry in WalkDir::new(&src_dir).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
     let entry = entry?;
     let src_path = entry.path();
     if src_path == src_dir {
       continue;
     }
     let dest_path = src_path.strip_prefix(&src_dir)?;
-    if entry.file_type().is_dir() {
-      tar_builder.append_dir(dest_path, src_path)?;
-    } else {
-      let mut src_file = fs::File::open(src_path)?;
-      tar_builder.append_file(dest_path, &mut src_file)?;
-    }
+    common::append_to_tar(&mut tar_builder, dest_path, src_path)?;
   }
   let dest_file = tar_builder.into_inner()?;
   Ok(dest_file)
@@ -354,7 +639,7 @@ fn tar_and_gzip_dir<P: AsRef<Path>>(src_dir: P) -> crate::Result<PathBuf> {
   let src_dir = src_dir.as_ref();
   let dest_path = src_dir.with_extension("tar.gz");
   let dest_file = common::create_file(&dest_path)?;
-  let gzip_encoder = gzip::Encoder::new(dest_file)?;
+  let gzip_encoder = common::gzip_encoder(dest_file)?;
   let gzip_encoder = create_tar_from_dir(src_dir, gzip_encoder)?;
   let mut dest_file = gzip_encoder.finish().into_result()?;
   dest_file.flush()?;
@@ -366,8 +651,56 @@ fn tar_and_gzip_dir<P: AsRef<Path>>(src_dir: P) -> crate::Result<PathBuf> {
 fn create_archive(srcs: Vec<PathBuf>, dest: &Path) -> crate::Result<()> {
   let mut builder = ar::Builder::new(common::create_file(dest)?);
   for path in &srcs {
-    builder.append_path(path)?;
+    let mut file = File::open(path)?;
+    let mut header = ar::Header::from_metadata(
+      path
+        .file_name()
+        .expect("archive member must have a file name")
+        .to_string_lossy()
+        .as_bytes()
+        .to_vec(),
+      &file.metadata()?,
+    );
+    if let Some(epoch) = common::source_date_epoch() {
+      header.set_mtime(epoch);
+      header.set_uid(0);
+      header.set_gid(0);
+      header.set_mode(0o100644);
+    }
+    builder.append(&header, &mut file)?;
   }
   builder.into_inner()?.flush()?;
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{exec_arg, insert_snippet};
+
+  #[test]
+  fn snippet_replaces_debhelper_token() {
+    assert_eq!(
+      insert_snippet("#!/bin/sh\necho a\n#DEBHELPER#\nexit 0\n", "echo b\n"),
+      "#!/bin/sh\necho a\necho b\nexit 0\n"
+    );
+  }
+
+  #[test]
+  fn snippet_follows_shebang() {
+    assert_eq!(
+      insert_snippet("#!/bin/sh\nexit 0\n", "echo b\n"),
+      "#!/bin/sh\necho b\nexit 0\n"
+    );
+    assert_eq!(insert_snippet("exit 0\n", "echo b\n"), "echo b\nexit 0\n");
+  }
+
+  #[test]
+  fn quotes_exec_args() {
+    assert_eq!(exec_arg("--hidden"), "--hidden");
+    assert_eq!(exec_arg("--name=a b"), "\"--name=a b\"");
+    assert_eq!(exec_arg(""), "\"\"");
+    assert_eq!(exec_arg("100%"), "100%%");
+    assert_eq!(exec_arg("say \"hi\""), "\"say \\\\\"hi\\\\\"\"");
+    assert_eq!(exec_arg("$HOME"), "\"\\\\$HOME\"");
+  }
+}
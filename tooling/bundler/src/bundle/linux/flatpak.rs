@@ -0,0 +1,135 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Flatpak manifest generated from the same file tree as the Debian package,
+//! built into a single-file bundle when `flatpak-builder` is installed.
+
+use super::{super::common::CommandExt, debian};
+use crate::Settings;
+use anyhow::Context;
+use log::{info, warn};
+use serde_json::json;
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+const DEFAULT_RUNTIME: &str = "org.gnome.Platform";
+const DEFAULT_RUNTIME_VERSION: &str = "43";
+const DEFAULT_SDK: &str = "org.gnome.Sdk";
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the manifest and the Flatpak bundle were created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86" => "i386",
+    other => other,
+  };
+  let app_id = settings.bundle_identifier();
+
+  let base_dir = settings.project_out_directory().join("bundle/flatpak");
+  let package_dir = base_dir.join(app_id);
+  if package_dir.exists() {
+    fs::remove_dir_all(&package_dir)
+      .with_context(|| format!("Failed to remove old {}", package_dir.display()))?;
+  }
+
+  let (data_dir, _) = debian::generate_data(settings, &package_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+
+  let manifest_path = base_dir.join(format!("{}.json", app_id));
+  info!(action = "Bundling"; "{}", manifest_path.display());
+  fs::write(
+    &manifest_path,
+    serde_json::to_string_pretty(&manifest(settings, &data_dir))?,
+  )
+  .with_context(|| "Failed to create Flatpak manifest")?;
+
+  if Command::new("flatpak-builder")
+    .arg("--version")
+    .output_ok()
+    .is_err()
+  {
+    warn!("flatpak-builder was not found, only the Flatpak manifest was generated");
+    return Ok(vec![manifest_path]);
+  }
+
+  let bundle_path = base_dir.join(format!(
+    "{}.flatpak",
+    settings
+      .artifact_name(crate::PackageType::Flatpak, arch)
+      .unwrap_or_else(|| format!(
+        "{}_{}_{}",
+        settings.main_binary_name(),
+        settings.version_string(),
+        arch
+      ))
+  ));
+  info!(action = "Bundling"; "{}", bundle_path.display());
+
+  let repo_dir = package_dir.join("repo");
+  Command::new("flatpak-builder")
+    .arg("--force-clean")
+    .arg(format!("--arch={}", arch))
+    .arg(format!("--repo={}", repo_dir.display()))
+    .arg(package_dir.join("build"))
+    .arg(&manifest_path)
+    .current_dir(&package_dir)
+    .output_ok()
+    .context("error running flatpak-builder")?;
+  Command::new("flatpak")
+    .arg("build-bundle")
+    .arg(format!("--arch={}", arch))
+    .arg(&repo_dir)
+    .arg(&bundle_path)
+    .arg(app_id)
+    .output_ok()
+    .context("error running flatpak build-bundle")?;
+
+  Ok(vec![manifest_path, bundle_path])
+}
+
+/// The manifest installing the files of `data_dir` to `/app`.
+fn manifest(settings: &Settings, data_dir: &Path) -> serde_json::Value {
+  let flatpak = settings.flatpak();
+  let app_id = settings.bundle_identifier();
+  let bin_name = settings.main_binary_name();
+
+  // the exported desktop file and icons must be named after the app id
+  let build_commands = vec![
+    "cp -a usr/. /app/".to_string(),
+    format!(
+      "mv /app/share/applications/{}.desktop /app/share/applications/{}.desktop",
+      bin_name, app_id
+    ),
+    format!(
+      "sed -i 's/^Icon=.*/Icon={}/' /app/share/applications/{}.desktop",
+      app_id, app_id
+    ),
+    format!(
+      "for icon in /app/share/icons/hicolor/*/apps/{}.png; do mv \"$icon\" \"$(dirname \"$icon\")/{}.png\"; done",
+      bin_name, app_id
+    ),
+  ];
+
+  json!({
+    "app-id": app_id,
+    "runtime": flatpak.runtime.as_deref().unwrap_or(DEFAULT_RUNTIME),
+    "runtime-version": flatpak.runtime_version.as_deref().unwrap_or(DEFAULT_RUNTIME_VERSION),
+    "sdk": flatpak.sdk.as_deref().unwrap_or(DEFAULT_SDK),
+    "command": bin_name,
+    "finish-args": flatpak.finish_args,
+    "modules": [{
+      "name": bin_name,
+      "buildsystem": "simple",
+      "build-commands": build_commands,
+      "sources": [{
+        "type": "dir",
+        "path": data_dir,
+      }],
+    }],
+  })
+}
@@ -3,12 +3,230 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+//! RPM package built with `rpmbuild` from the same file tree as the Debian package.
+
+use super::{
+  super::common::{self, CommandExt},
+  debian,
+};
 use crate::Settings;
+use anyhow::Context;
+use handlebars::{to_json, Handlebars};
+use log::info;
+use walkdir::WalkDir;
 
-use std::path::PathBuf;
+use std::{
+  collections::BTreeMap,
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
 
 /// Bundles the project.
-/// Not implemented yet.
-pub fn bundle_project(_settings: &Settings) -> crate::Result<Vec<PathBuf>> {
-  unimplemented!();
+/// Returns a vector of PathBuf that shows where the RPM was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  check_rpmbuild()?;
+
+  let arch = match settings.binary_arch() {
+    "x86" => "i386",
+    "arm" => "armhfp",
+    other => other,
+  };
+  let release = settings.rpm().release.as_deref().unwrap_or("1");
+  // `-` separates the version from the release, while `~` sorts pre-releases before the release
+  let version = settings.version_string().replace('-', "~");
+  let package_base_name = settings
+    .artifact_name(crate::PackageType::Rpm, arch)
+    .unwrap_or_else(|| {
+      format!(
+        "{}-{}-{}.{}",
+        settings.main_binary_name(),
+        version,
+        release,
+        arch
+      )
+    });
+  let package_name = format!("{}.rpm", package_base_name);
+
+  let base_dir = settings.project_out_directory().join("bundle/rpm");
+  let package_dir = base_dir.join(&package_base_name);
+  if package_dir.exists() {
+    fs::remove_dir_all(&package_dir)
+      .with_context(|| format!("Failed to remove old {}", package_base_name))?;
+  }
+  let package_path = base_dir.join(&package_name);
+
+  info!(action = "Bundling"; "{} ({})", package_name, package_path.display());
+
+  let (data_dir, _) = debian::generate_data(settings, &package_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+  debian::copy_custom_files(&settings.rpm().files, &data_dir)
+    .with_context(|| "Failed to copy custom files")?;
+  if let Some(template) = &settings.rpm().desktop_template {
+    generate_desktop_file(settings, template, &data_dir)
+      .with_context(|| "Failed to create desktop file")?;
+  }
+
+  let spec_path = package_dir.join(format!("{}.spec", settings.main_binary_name()));
+  fs::write(
+    &spec_path,
+    generate_spec(settings, &version, release, &data_dir)?,
+  )
+  .with_context(|| "Failed to create spec file")?;
+
+  let mut cmd = Command::new("rpmbuild");
+  cmd
+    .arg("-bb")
+    .args(["--target", arch])
+    .arg("--define")
+    .arg(format!(
+      "_topdir {}",
+      package_dir.join("rpmbuild").display()
+    ))
+    .arg("--define")
+    .arg(format!("_rpmdir {}", base_dir.display()))
+    .arg("--define")
+    .arg(format!("_build_name_fmt {}", package_name));
+  if common::source_date_epoch().is_some() {
+    // rpmbuild reads SOURCE_DATE_EPOCH from the environment
+    cmd
+      .args(["--define", "use_source_date_epoch_as_buildtime 1"])
+      .args(["--define", "clamp_mtime_to_source_date_epoch 1"]);
+  }
+  cmd
+    .arg(&spec_path)
+    .output_ok()
+    .context("error running rpmbuild")?;
+
+  Ok(vec![package_path])
+}
+
+/// Fails if `rpmbuild` cannot be executed.
+pub fn check_rpmbuild() -> crate::Result<()> {
+  Command::new("rpmbuild")
+    .arg("--version")
+    .output_ok()
+    .map_err(|_| {
+      crate::Error::GenericError(
+        "rpmbuild was not found, install it (e.g. the `rpm` package of your distribution) to bundle the RPM package".into(),
+      )
+    })?;
+  Ok(())
+}
+
+/// Renders the spec file installing the files of `data_dir`.
+fn generate_spec(
+  settings: &Settings,
+  version: &str,
+  release: &str,
+  data_dir: &Path,
+) -> crate::Result<String> {
+  let rpm = settings.rpm();
+
+  let mut files = Vec::new();
+  for entry in WalkDir::new(data_dir).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+    let entry = entry?;
+    if !entry.file_type().is_dir() {
+      let path = entry.path().strip_prefix(data_dir)?;
+      files.push(escape(&format!("/{}", path.display())));
+    }
+  }
+
+  let mut summary = settings.short_description().trim();
+  if summary.is_empty() {
+    summary = "(none)";
+  }
+  let description = settings
+    .long_description()
+    .map(str::trim)
+    .filter(|d| !d.is_empty())
+    .unwrap_or(summary);
+
+  let mut data = BTreeMap::new();
+  data.insert(
+    "name",
+    to_json(heck::AsKebabCase(settings.product_name()).to_string()),
+  );
+  data.insert("version", to_json(version));
+  data.insert("release", to_json(release));
+  data.insert("epoch", to_json(rpm.epoch));
+  data.insert("summary", to_json(escape(summary)));
+  data.insert(
+    "license",
+    to_json(escape(rpm.license.as_deref().unwrap_or("Unknown"))),
+  );
+  if !settings.homepage_url().is_empty() {
+    data.insert("url", to_json(settings.homepage_url()));
+  }
+  data.insert("depends", to_json(rpm.depends.clone().unwrap_or_default()));
+  data.insert("description", to_json(escape(description)));
+  data.insert("data_dir", to_json(data_dir));
+  data.insert("files", to_json(files));
+  for (name, script) in [
+    ("pre_install_script", &rpm.pre_install_script),
+    ("post_install_script", &rpm.post_install_script),
+    ("pre_remove_script", &rpm.pre_remove_script),
+    ("post_remove_script", &rpm.post_remove_script),
+  ] {
+    if let Some(path) = script {
+      let script =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+      data.insert(name, to_json(escape(script.trim_end())));
+    }
+  }
+
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(handlebars::no_escape);
+  handlebars
+    .register_template_string("rpm.spec", include_str!("templates/rpm.spec"))
+    .expect("Failed to setup handlebar template");
+  Ok(handlebars.render("rpm.spec", &data)?)
+}
+
+/// Replaces the desktop file generated for the Debian package with the rendered template.
+fn generate_desktop_file(
+  settings: &Settings,
+  template: &Path,
+  data_dir: &Path,
+) -> crate::Result<()> {
+  let template = fs::read_to_string(template)
+    .with_context(|| format!("Failed to read {}", template.display()))?;
+  let bin_name = settings.main_binary_name();
+
+  let mut data = BTreeMap::new();
+  data.insert(
+    "categories",
+    to_json(
+      settings
+        .app_category()
+        .map(|c| c.gnome_desktop_categories())
+        .unwrap_or_default(),
+    ),
+  );
+  if !settings.short_description().is_empty() {
+    data.insert("comment", to_json(settings.short_description()));
+  }
+  data.insert("exec", to_json(bin_name));
+  let mime_types = debian::mime_types(settings);
+  if !mime_types.is_empty() {
+    data.insert("mime_type", to_json(format!("{};", mime_types.join(";"))));
+  }
+  data.insert("icon", to_json(bin_name));
+  data.insert("name", to_json(settings.product_name()));
+
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(handlebars::no_escape);
+  let desktop_file = handlebars.render_template(&template, &data)?;
+  fs::write(
+    data_dir
+      .join("usr/share/applications")
+      .join(format!("{}.desktop", bin_name)),
+    desktop_file,
+  )?;
+  Ok(())
+}
+
+/// Escapes the macros of a value used in the spec file.
+fn escape(value: &str) -> String {
+  value.replace('%', "%%")
 }
@@ -0,0 +1,48 @@
+%global debug_package %{nil}
+%define _build_id_links none
+
+Name: {{name}}
+Version: {{version}}
+Release: {{release}}
+{{#if epoch}}
+Epoch: {{epoch}}
+{{/if}}
+Summary: {{summary}}
+License: {{license}}
+{{#if url}}
+URL: {{url}}
+{{/if}}
+{{#each depends}}
+Requires: {{this}}
+{{/each}}
+
+%description
+{{description}}
+
+%install
+cp -a "{{data_dir}}/." "%{buildroot}/"
+
+%files
+{{#each files}}
+"{{this}}"
+{{/each}}
+{{#if pre_install_script}}
+
+%pre
+{{pre_install_script}}
+{{/if}}
+{{#if post_install_script}}
+
+%post
+{{post_install_script}}
+{{/if}}
+{{#if pre_remove_script}}
+
+%preun
+{{pre_remove_script}}
+{{/if}}
+{{#if post_remove_script}}
+
+%postun
+{{post_remove_script}}
+{{/if}}
@@ -25,7 +25,7 @@
 use super::{
   super::common,
   icon::create_icns_file,
-  sign::{notarize, notarize_auth_args, sign},
+  sign::{notarize, notarize_auth, sign},
 };
 use crate::Settings;
 
@@ -87,13 +87,9 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     // sign application
     sign(app_bundle_path.clone(), identity, settings, true)?;
     // notarization is required for distribution
-    match notarize_auth_args() {
-      Ok(args) => {
-        notarize(app_bundle_path.clone(), args, settings)?;
-      }
-      Err(e) => {
-        warn!("skipping app notarization, {}", e.to_string());
-      }
+    match notarize_auth(settings)? {
+      Some(auth) => notarize(app_bundle_path.clone(), &auth, settings)?,
+      None => warn!("skipping app notarization, no APPLE_ID & APPLE_PASSWORD & APPLE_TEAM_ID or APPLE_API_KEY & APPLE_API_ISSUER environment variables found"),
     }
   }
 
@@ -126,6 +122,36 @@ fn create_info_plist(
   let mut plist = plist::Dictionary::new();
   plist.insert("CFBundleDevelopmentRegion".into(), "English".into());
   plist.insert("CFBundleDisplayName".into(), settings.product_name().into());
+  if !settings.file_associations().is_empty() {
+    plist.insert(
+      "CFBundleDocumentTypes".into(),
+      plist::Value::Array(
+        settings
+          .file_associations()
+          .iter()
+          .map(|association| {
+            let mut document_type = plist::Dictionary::new();
+            document_type.insert(
+              "CFBundleTypeExtensions".into(),
+              plist::Value::Array(association.ext.iter().map(|e| e.clone().into()).collect()),
+            );
+            if let Some(mime_type) = &association.mime_type {
+              document_type.insert(
+                "CFBundleTypeMIMETypes".into(),
+                plist::Value::Array(vec![mime_type.clone().into()]),
+              );
+            }
+            document_type.insert("CFBundleTypeName".into(), association.name().into());
+            document_type.insert(
+              "CFBundleTypeRole".into(),
+              association.role.plist_value().into(),
+            );
+            document_type.into()
+          })
+          .collect(),
+      ),
+    );
+  }
   plist.insert(
     "CFBundleExecutable".into(),
     settings.main_binary_name().into(),
@@ -152,6 +178,27 @@ fn create_info_plist(
     "CFBundleShortVersionString".into(),
     settings.version_string().into(),
   );
+  if !settings.deep_link_schemes().is_empty() {
+    let mut url_type = plist::Dictionary::new();
+    url_type.insert(
+      "CFBundleURLName".into(),
+      settings.bundle_identifier().into(),
+    );
+    url_type.insert(
+      "CFBundleURLSchemes".into(),
+      plist::Value::Array(
+        settings
+          .deep_link_schemes()
+          .iter()
+          .map(|scheme| scheme.clone().into())
+          .collect(),
+      ),
+    );
+    plist.insert(
+      "CFBundleURLTypes".into(),
+      plist::Value::Array(vec![url_type.into()]),
+    );
+  }
   plist.insert("CFBundleVersion".into(), build_number.into());
   plist.insert("CSResourcesFileMapped".into(), true.into());
   if let Some(category) = settings.app_category() {
@@ -190,11 +237,71 @@ fn create_info_plist(
     }
   }
 
+  if let Some(info_plist) = &settings.macos().info_plist {
+    merge_info_plist(&mut plist, info_plist.clone());
+  }
+
   plist::Value::Dictionary(plist).to_file_xml(bundle_dir.join("Info.plist"))?;
 
   Ok(())
 }
 
+// Deep merges the `infoPlist` configuration into the Info.plist dictionary:
+// dictionaries are merged recursively, `null` removes the key and any other value replaces it.
+fn merge_info_plist(
+  plist: &mut plist::Dictionary,
+  config: serde_json::Map<String, serde_json::Value>,
+) {
+  for (key, value) in config {
+    match value {
+      serde_json::Value::Null => {
+        plist.remove(&key);
+      }
+      serde_json::Value::Object(map) => {
+        if let Some(dict) = plist
+          .get_mut(&key)
+          .and_then(plist::Value::as_dictionary_mut)
+        {
+          merge_info_plist(dict, map);
+        } else {
+          let mut dict = plist::Dictionary::new();
+          merge_info_plist(&mut dict, map);
+          plist.insert(key, dict.into());
+        }
+      }
+      value => {
+        if let Some(value) = json_to_plist(value) {
+          plist.insert(key, value);
+        }
+      }
+    }
+  }
+}
+
+// Converts a JSON value to its plist representation, dropping `null` values.
+fn json_to_plist(value: serde_json::Value) -> Option<plist::Value> {
+  match value {
+    serde_json::Value::Null => None,
+    serde_json::Value::Bool(b) => Some(b.into()),
+    serde_json::Value::Number(n) => Some(if let Some(i) = n.as_i64() {
+      i.into()
+    } else if let Some(u) = n.as_u64() {
+      u.into()
+    } else {
+      n.as_f64().unwrap_or_default().into()
+    }),
+    serde_json::Value::String(s) => Some(s.into()),
+    serde_json::Value::Array(values) => Some(plist::Value::Array(
+      values.into_iter().filter_map(json_to_plist).collect(),
+    )),
+    serde_json::Value::Object(map) => {
+      let mut dict = plist::Dictionary::new();
+      merge_info_plist(&mut dict, map);
+      Some(dict.into())
+    }
+  }
+}
+
 // Copies the framework under `{src_dir}/{framework}.framework` to `{dest_dir}/{framework}.framework`.
 fn copy_framework_from(dest_dir: &Path, framework: &str, src_dir: &Path) -> crate::Result<bool> {
   let src_name = format!("{}.framework", framework);
@@ -256,3 +363,73 @@ fn copy_frameworks_to_bundle(bundle_directory: &Path, settings: &Settings) -> cr
   }
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::merge_info_plist;
+
+  #[test]
+  fn deep_merges_info_plist() {
+    let mut plist = plist::Dictionary::new();
+    plist.insert("CFBundleName".into(), "app".into());
+    plist.insert("LSMinimumSystemVersion".into(), "10.13".into());
+    let mut security = plist::Dictionary::new();
+    security.insert("NSAllowsArbitraryLoads".into(), false.into());
+    security.insert("NSExceptionDomains".into(), plist::Dictionary::new().into());
+    plist.insert("NSAppTransportSecurity".into(), security.into());
+
+    let config = serde_json::json!({
+      "NSCameraUsageDescription": "Scan QR codes",
+      "LSMinimumSystemVersion": null,
+      "NSAppTransportSecurity": { "NSAllowsArbitraryLoads": true },
+      "CFBundleURLTypes": [{ "CFBundleURLName": "app", "CFBundleURLSchemes": ["app"] }],
+      "CFBundleDocumentTypes": [{ "CFBundleTypeExtensions": ["txt"], "LSHandlerRank": "Owner", "LSItemContentTypes": null }]
+    });
+    merge_info_plist(&mut plist, config.as_object().unwrap().clone());
+
+    assert_eq!(
+      plist.get("CFBundleName").and_then(|v| v.as_string()),
+      Some("app")
+    );
+    assert!(plist.get("LSMinimumSystemVersion").is_none());
+    assert_eq!(
+      plist
+        .get("NSCameraUsageDescription")
+        .and_then(|v| v.as_string()),
+      Some("Scan QR codes")
+    );
+    let security = plist
+      .get("NSAppTransportSecurity")
+      .and_then(|v| v.as_dictionary())
+      .unwrap();
+    assert_eq!(
+      security
+        .get("NSAllowsArbitraryLoads")
+        .and_then(|v| v.as_boolean()),
+      Some(true)
+    );
+    assert!(security.get("NSExceptionDomains").is_some());
+    let url_types = plist
+      .get("CFBundleURLTypes")
+      .and_then(|v| v.as_array())
+      .unwrap();
+    let schemes = url_types[0]
+      .as_dictionary()
+      .and_then(|d| d.get("CFBundleURLSchemes"))
+      .and_then(|v| v.as_array())
+      .unwrap();
+    assert_eq!(schemes[0].as_string(), Some("app"));
+    let document_type = plist
+      .get("CFBundleDocumentTypes")
+      .and_then(|v| v.as_array())
+      .and_then(|a| a[0].as_dictionary())
+      .unwrap();
+    assert_eq!(
+      document_type
+        .get("LSHandlerRank")
+        .and_then(|v| v.as_string()),
+      Some("Owner")
+    );
+    assert!(document_type.get("LSItemContentTypes").is_none());
+  }
+}
@@ -35,15 +35,20 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
 
   // get the target path
   let output_path = settings.project_out_directory().join("bundle/dmg");
-  let package_base_name = format!(
-    "{}_{}_{}",
-    settings.main_binary_name(),
-    settings.version_string(),
-    match settings.binary_arch() {
-      "x86_64" => "x64",
-      other => other,
-    }
-  );
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    other => other,
+  };
+  let package_base_name = settings
+    .artifact_name(crate::PackageType::Dmg, arch)
+    .unwrap_or_else(|| {
+      format!(
+        "{}_{}_{}",
+        settings.main_binary_name(),
+        settings.version_string(),
+        arch
+      )
+    });
   let dmg_name = format!("{}.dmg", &package_base_name);
   let dmg_path = output_path.join(&dmg_name);
 
@@ -92,46 +97,67 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
     .output()
     .expect("Failed to chmod script");
 
+  let dmg = &settings.macos().dmg;
   let mut args = vec![
-    "--volname",
-    product_name,
-    "--icon",
-    product_name,
-    "180",
-    "170",
-    "--app-drop-link",
-    "480",
-    "170",
-    "--window-size",
-    "660",
-    "400",
-    "--hide-extension",
-    &bundle_file_name,
+    "--volname".to_string(),
+    product_name.to_string(),
+    "--icon".to_string(),
+    product_name.to_string(),
+    dmg.app_position.x.to_string(),
+    dmg.app_position.y.to_string(),
+    "--app-drop-link".to_string(),
+    dmg.application_folder_position.x.to_string(),
+    dmg.application_folder_position.y.to_string(),
+    "--window-size".to_string(),
+    dmg.window_size.width.to_string(),
+    dmg.window_size.height.to_string(),
+    "--hide-extension".to_string(),
+    bundle_file_name.clone(),
   ];
 
+  if let Some(position) = &dmg.window_position {
+    args.push("--window-pos".into());
+    args.push(position.x.to_string());
+    args.push(position.y.to_string());
+  }
+
+  if let Some(icon_size) = dmg.icon_size {
+    args.push("--icon-size".into());
+    args.push(icon_size.to_string());
+  }
+
+  if let Some(background) = &dmg.background {
+    args.push("--background".into());
+    args.push(
+      env::current_dir()?
+        .join(background)
+        .to_string_lossy()
+        .to_string(),
+    );
+  }
+
   let icns_icon_path =
     create_icns_file(&output_path, settings)?.map(|path| path.to_string_lossy().to_string());
-  if let Some(icon) = &icns_icon_path {
-    args.push("--volicon");
+  if let Some(icon) = icns_icon_path {
+    args.push("--volicon".into());
     args.push(icon);
   }
 
-  #[allow(unused_assignments)]
-  let mut license_path_ref = "".to_string();
   if let Some(license_path) = &settings.macos().license {
-    args.push("--eula");
-    license_path_ref = env::current_dir()?
-      .join(license_path)
-      .to_string_lossy()
-      .to_string();
-    args.push(&license_path_ref);
+    args.push("--eula".into());
+    args.push(
+      env::current_dir()?
+        .join(license_path)
+        .to_string_lossy()
+        .to_string(),
+    );
   }
 
   // Issue #592 - Building MacOS dmg files on CI
   // https://github.com/tauri-apps/tauri/issues/592
   if let Some(value) = env::var_os("CI") {
     if value == "true" {
-      args.push("--skip-jenkins");
+      args.push("--skip-jenkins".into());
     }
   }
 
@@ -150,6 +176,10 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
   // Sign DMG if needed
   if let Some(identity) = &settings.macos().signing_identity {
     super::sign::sign(dmg_path.clone(), identity, settings, false)?;
+    // the app inside is already notarized, but the DMG needs its own ticket
+    if let Some(auth) = super::sign::notarize_auth(settings)? {
+      super::sign::notarize(dmg_path.clone(), &auth, settings)?;
+    }
   }
   Ok(vec![dmg_path])
 }
@@ -4,12 +4,18 @@
 // SPDX-License-Identifier: MIT
 
 use std::ffi::OsString;
-use std::{fs::File, io::prelude::*, path::PathBuf, process::Command};
+use std::{
+  fs::File,
+  io::prelude::*,
+  path::{Path, PathBuf},
+  process::Command,
+  time::{Duration, Instant},
+};
 
 use crate::{bundle::common::CommandExt, Settings};
 use anyhow::Context;
-use log::info;
-use regex::Regex;
+use log::{info, warn};
+use serde::Deserialize;
 
 const KEYCHAIN_ID: &str = "tauri-build.keychain";
 const KEYCHAIN_PWD: &str = "tauri-build";
@@ -212,197 +218,303 @@ fn try_sign(
   Ok(())
 }
 
-pub fn notarize(
-  app_bundle_path: PathBuf,
-  auth_args: Vec<String>,
-  settings: &Settings,
-) -> crate::Result<()> {
-  let identifier = settings.bundle_identifier();
+/// The credentials used to authenticate with Apple's notary service.
+pub enum NotarizeAuth {
+  AppleId {
+    apple_id: String,
+    password: String,
+    team_id: String,
+  },
+  ApiKey {
+    key: String,
+    key_path: PathBuf,
+    issuer: String,
+  },
+}
 
-  let bundle_stem = app_bundle_path
-    .file_stem()
-    .expect("failed to get bundle filename");
+impl NotarizeAuth {
+  fn args(&self) -> Vec<OsString> {
+    match self {
+      Self::AppleId {
+        apple_id,
+        password,
+        team_id,
+      } => vec![
+        "--apple-id".into(),
+        apple_id.into(),
+        "--password".into(),
+        password.into(),
+        "--team-id".into(),
+        team_id.into(),
+      ],
+      Self::ApiKey {
+        key,
+        key_path,
+        issuer,
+      } => vec![
+        "--key-id".into(),
+        key.into(),
+        "--key".into(),
+        key_path.into(),
+        "--issuer".into(),
+        issuer.into(),
+      ],
+    }
+  }
+}
 
-  let tmp_dir = tempfile::tempdir()?;
-  let zip_path = tmp_dir
-    .path()
-    .join(format!("{}.zip", bundle_stem.to_string_lossy()));
-  let zip_args = vec![
-    "-c",
-    "-k",
-    "--keepParent",
-    "--sequesterRsrc",
-    app_bundle_path
-      .to_str()
-      .expect("failed to convert bundle_path to string"),
-    zip_path
-      .to_str()
-      .expect("failed to convert zip_path to string"),
-  ];
-
-  // use ditto to create a PKZip almost identical to Finder
-  // this remove almost 99% of false alarm in notarization
-  Command::new("ditto")
-    .args(zip_args)
-    .output_ok()
-    .context("failed to zip app with ditto")?;
+/// How often the notarization status is polled.
+const NOTARIZATION_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// How long to wait for Apple to process a notarization submission.
+const NOTARIZATION_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+/// How many notarization status polls can fail in a row.
+const NOTARIZATION_MAX_POLL_ERRORS: u32 = 10;
 
-  // sign the zip file
-  if let Some(identity) = &settings.macos().signing_identity {
-    sign(zip_path.clone(), identity, settings, false)?;
-  };
+#[derive(Deserialize)]
+struct NotarytoolSubmission {
+  id: String,
+}
 
-  let mut notarize_args = vec![
-    "altool",
-    "--notarize-app",
-    "-f",
+#[derive(Deserialize)]
+struct NotarytoolInfo {
+  status: String,
+}
+
+/// Submits the app bundle or the DMG to Apple's notary service, waits for the result
+/// and staples the notarization ticket to it.
+///
+/// On rejection the error contains the log of the submission.
+pub fn notarize(path: PathBuf, auth: &NotarizeAuth, settings: &Settings) -> crate::Result<()> {
+  let tmp_dir = tempfile::tempdir()?;
+
+  // the app bundle is a directory, so it must be uploaded as a zip
+  let upload_path = if path.is_dir() {
+    let bundle_stem = path.file_stem().expect("failed to get bundle filename");
+    let zip_path = tmp_dir
+      .path()
+      .join(format!("{}.zip", bundle_stem.to_string_lossy()));
+
+    // use ditto to create a PKZip almost identical to Finder
+    // this remove almost 99% of false alarm in notarization
+    Command::new("ditto")
+      .args(["-c", "-k", "--keepParent", "--sequesterRsrc"])
+      .arg(&path)
+      .arg(&zip_path)
+      .output_ok()
+      .context("failed to zip app with ditto")?;
+
+    // sign the zip file
+    if let Some(identity) = &settings.macos().signing_identity {
+      sign(zip_path.clone(), identity, settings, false)?;
+    };
     zip_path
-      .to_str()
-      .expect("failed to convert zip_path to string"),
-    "--primary-bundle-id",
-    identifier,
-  ];
-
-  if let Some(provider_short_name) = &settings.macos().provider_short_name {
-    notarize_args.push("--asc-provider");
-    notarize_args.push(provider_short_name);
-  }
+  } else {
+    path.clone()
+  };
 
-  info!(action = "Notarizing"; "{}", app_bundle_path.display());
+  info!(action = "Notarizing"; "{}", path.display());
 
   let output = Command::new("xcrun")
-    .args(notarize_args)
-    .args(auth_args.clone())
+    .args(["notarytool", "submit", "--output-format", "json"])
+    .arg(&upload_path)
+    .args(auth.args())
     .output_ok()
     .context("failed to upload app to Apple's notarization servers.")?;
-
-  // combine both stdout and stderr to support macOS below 10.15
-  let mut notarize_response = std::str::from_utf8(&output.stdout)?.to_string();
-  notarize_response.push('\n');
-  notarize_response.push_str(std::str::from_utf8(&output.stderr)?);
-  notarize_response.push('\n');
-  if let Some(uuid) = Regex::new(r"\nRequestUUID = (.+?)\n")?
-    .captures_iter(&notarize_response)
-    .next()
-  {
-    info!("notarization started; waiting for Apple response...");
-
-    let uuid = uuid[1].to_string();
-    get_notarization_status(uuid, auth_args, settings)?;
-    staple_app(app_bundle_path.clone())?;
-  } else {
+  let submission: NotarytoolSubmission = serde_json::from_slice(&output.stdout).map_err(|e| {
+    anyhow::anyhow!(
+      "failed to parse the notarytool submission: {}. {}",
+      e,
+      String::from_utf8_lossy(&output.stdout)
+    )
+  })?;
+
+  info!(
+    "notarization started with submission id {}; waiting for Apple response...",
+    submission.id
+  );
+  let status = notarization_status(&submission.id, auth)?;
+  if status != "Accepted" {
+    let log = Command::new("xcrun")
+      .args(["notarytool", "log"])
+      .arg(&submission.id)
+      .args(auth.args())
+      .output_ok()
+      .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+      .unwrap_or_else(|e| format!("failed to fetch the notarization log: {}", e));
     return Err(
       anyhow::anyhow!(
-        "failed to parse RequestUUID from upload output. {}",
-        notarize_response
+        "Apple failed to notarize {} (status: {}).\n{}",
+        path.display(),
+        status,
+        log
       )
       .into(),
     );
   }
 
-  Ok(())
+  staple(&path)
 }
 
-fn staple_app(mut app_bundle_path: PathBuf) -> crate::Result<()> {
-  let app_bundle_path_clone = app_bundle_path.clone();
-  let filename = app_bundle_path_clone
-    .file_name()
-    .expect("failed to get bundle filename")
-    .to_str()
-    .expect("failed to convert bundle filename to string");
+/// Polls the notary service until the submission is processed and returns its status.
+///
+/// Fails when the submission is still in progress after [`NOTARIZATION_TIMEOUT`]
+/// or when [`NOTARIZATION_MAX_POLL_ERRORS`] polls in a row fail.
+fn notarization_status(submission_id: &str, auth: &NotarizeAuth) -> crate::Result<String> {
+  let started = Instant::now();
+  let mut poll_errors = 0;
+  loop {
+    std::thread::sleep(NOTARIZATION_POLL_INTERVAL);
+    let info = Command::new("xcrun")
+      .args(["notarytool", "info", "--output-format", "json"])
+      .arg(submission_id)
+      .args(auth.args())
+      .output_ok()
+      .map_err(|e| e.to_string())
+      .and_then(|output| {
+        serde_json::from_slice::<NotarytoolInfo>(&output.stdout).map_err(|e| e.to_string())
+      });
+
+    match info {
+      Ok(info) if info.status != "In Progress" => return Ok(info.status),
+      Ok(_) => poll_errors = 0,
+      // network errors are transient, so keep polling
+      Err(e) => {
+        poll_errors += 1;
+        if poll_errors >= NOTARIZATION_MAX_POLL_ERRORS {
+          return Err(
+            anyhow::anyhow!(
+              "failed to get the status of the notarization submission {}: {}",
+              submission_id,
+              e
+            )
+            .into(),
+          );
+        }
+      }
+    }
 
-  app_bundle_path.pop();
+    if started.elapsed() > NOTARIZATION_TIMEOUT {
+      return Err(
+        anyhow::anyhow!(
+          "the notarization submission {} is still in progress after {} minutes. Check its status with `xcrun notarytool info {}`",
+          submission_id,
+          NOTARIZATION_TIMEOUT.as_secs() / 60,
+          submission_id
+        )
+        .into(),
+      );
+    }
+  }
+}
+
+/// The team ID of a `Developer ID Application: Name (TEAMID)` signing identity.
+fn signing_identity_team_id(identity: &str) -> Option<String> {
+  let team_id = identity.strip_suffix(')')?.rsplit_once('(')?.1;
+  if team_id.len() == 10 && team_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+    Some(team_id.to_string())
+  } else {
+    None
+  }
+}
+
+fn staple(path: &Path) -> crate::Result<()> {
+  let filename = path.file_name().expect("failed to get bundle filename");
 
   Command::new("xcrun")
-    .args(vec!["stapler", "staple", "-v", filename])
-    .current_dir(app_bundle_path)
+    .args(["stapler", "staple", "-v"])
+    .arg(filename)
+    .current_dir(path.parent().expect("failed to get bundle directory"))
     .output_ok()
     .context("failed to staple app.")?;
 
   Ok(())
 }
 
-fn get_notarization_status(
-  uuid: String,
-  auth_args: Vec<String>,
-  settings: &Settings,
-) -> crate::Result<()> {
-  std::thread::sleep(std::time::Duration::from_secs(10));
-  let result = Command::new("xcrun")
-    .args(vec!["altool", "--notarization-info", &uuid])
-    .args(auth_args.clone())
-    .output_ok();
-
-  if let Ok(output) = result {
-    // combine both stdout and stderr to support macOS below 10.15
-    let mut notarize_status = std::str::from_utf8(&output.stdout)?.to_string();
-    notarize_status.push('\n');
-    notarize_status.push_str(std::str::from_utf8(&output.stderr)?);
-    notarize_status.push('\n');
-    if let Some(status) = Regex::new(r"\n *Status: (.+?)\n")?
-      .captures_iter(&notarize_status)
-      .next()
-    {
-      let status = status[1].to_string();
-      if status == "in progress" {
-        get_notarization_status(uuid, auth_args, settings)
-      } else if status == "invalid" {
-        Err(
-          anyhow::anyhow!(format!(
-            "Apple failed to notarize your app. {}",
-            notarize_status
-          ))
-          .into(),
-        )
-      } else if status != "success" {
-        Err(
-          anyhow::anyhow!(format!(
-            "Unknown notarize status {}. {}",
-            status, notarize_status
-          ))
-          .into(),
-        )
-      } else {
-        Ok(())
+/// Reads the notarization credentials from the environment variables, falling back to the
+/// `notarization` settings.
+///
+/// Returns `None` when no credentials are configured and fails when they are incomplete.
+pub fn notarize_auth(settings: &Settings) -> crate::Result<Option<NotarizeAuth>> {
+  let notarization = &settings.macos().notarization;
+  let var =
+    |name: &str, fallback: &Option<String>| std::env::var(name).ok().or_else(|| fallback.clone());
+
+  if let Some(apple_id) = var("APPLE_ID", &notarization.apple_id) {
+    let password = std::env::var("APPLE_PASSWORD").map_err(|_| {
+      crate::Error::GenericError(
+        "an Apple ID is configured for notarization but the `APPLE_PASSWORD` environment variable is missing".into(),
+      )
+    })?;
+    // `notarytool` requires the team, which the provider short name and the signing identity usually name
+    let team_id = var("APPLE_TEAM_ID", &notarization.team_id)
+      .or_else(|| settings.macos().provider_short_name.clone())
+      .or_else(|| {
+        settings
+          .macos()
+          .signing_identity
+          .as_deref()
+          .and_then(signing_identity_team_id)
+      });
+    return match team_id {
+      Some(team_id) => Ok(Some(NotarizeAuth::AppleId {
+        apple_id,
+        password,
+        team_id,
+      })),
+      None => {
+        warn!("an Apple ID is configured for notarization but no team ID is set, skipping notarization. Set `tauri > bundle > macOS > notarization > teamId` or the `APPLE_TEAM_ID` environment variable");
+        Ok(None)
       }
-    } else {
-      get_notarization_status(uuid, auth_args, settings)
+    };
+  }
+
+  if let Some(key) = var("APPLE_API_KEY", &notarization.api_key) {
+    let issuer = var("APPLE_API_ISSUER", &notarization.api_issuer).ok_or_else(|| {
+      crate::Error::GenericError(
+        "an API key is configured for notarization but no issuer is set. Set `tauri > bundle > macOS > notarization > apiIssuer` or the `APPLE_API_ISSUER` environment variable".into(),
+      )
+    })?;
+    let key_path = std::env::var_os("APPLE_API_KEY_PATH")
+      .map(PathBuf::from)
+      .or_else(|| notarization.api_key_path.clone())
+      .or_else(|| find_api_key(&key))
+      .ok_or_else(|| {
+        crate::Error::GenericError(format!(
+          "could not find AuthKey_{}.p8 in the private_keys directories. Set `tauri > bundle > macOS > notarization > apiKeyPath` or the `APPLE_API_KEY_PATH` environment variable",
+          key
+        ))
+      })?;
+    if !key_path.is_file() {
+      return Err(crate::Error::GenericError(format!(
+        "{} does not exist",
+        key_path.display()
+      )));
     }
-  } else {
-    get_notarization_status(uuid, auth_args, settings)
+    return Ok(Some(NotarizeAuth::ApiKey {
+      key,
+      key_path,
+      issuer,
+    }));
   }
+
+  Ok(None)
 }
 
-pub fn notarize_auth_args() -> crate::Result<Vec<String>> {
-  match (
-    std::env::var_os("APPLE_ID"),
-    std::env::var_os("APPLE_PASSWORD"),
-  ) {
-    (Some(apple_id), Some(apple_password)) => {
-      let apple_id = apple_id
-        .to_str()
-        .expect("failed to convert APPLE_ID to string")
-        .to_string();
-      let apple_password = apple_password
-        .to_str()
-        .expect("failed to convert APPLE_PASSWORD to string")
-        .to_string();
-      Ok(vec![
-        "-u".to_string(),
-        apple_id,
-        "-p".to_string(),
-        apple_password,
-      ])
-    }
-    _ => {
-      match (std::env::var_os("APPLE_API_KEY"), std::env::var_os("APPLE_API_ISSUER")) {
-        (Some(api_key), Some(api_issuer)) => {
-          let api_key = api_key.to_str().expect("failed to convert APPLE_API_KEY to string").to_string();
-          let api_issuer = api_issuer.to_str().expect("failed to convert APPLE_API_ISSUER to string").to_string();
-          Ok(vec!["--apiKey".to_string(), api_key, "--apiIssuer".to_string(), api_issuer])
-        },
-        _ => Err(anyhow::anyhow!("no APPLE_ID & APPLE_PASSWORD or APPLE_API_KEY & APPLE_API_ISSUER environment variables found").into())
-      }
+/// Looks for the private key file in the directories searched by `altool`.
+fn find_api_key(key: &str) -> Option<PathBuf> {
+  let file_name = format!("AuthKey_{}.p8", key);
+  let mut dirs = vec![std::env::current_dir().ok()?.join("private_keys")];
+  if let Some(home) = dirs_next::home_dir() {
+    for dir in [
+      "private_keys",
+      ".private_keys",
+      ".appstoreconnect/private_keys",
+    ] {
+      dirs.push(home.join(dir));
     }
   }
+  dirs
+    .into_iter()
+    .map(|dir| dir.join(&file_name))
+    .find(|path| path.is_file())
 }
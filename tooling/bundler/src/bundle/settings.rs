@@ -5,7 +5,11 @@
 
 use super::category::AppCategory;
 use crate::bundle::{common, platform::target_triple};
-pub use tauri_utils::config::WebviewInstallMode;
+use serde::Serialize;
+pub use tauri_utils::config::{
+  BundleTypeRole, FileAssociation, NsisInstallMode, Position, Size, WebviewInstallMode,
+  WindowsSignMethod,
+};
 use tauri_utils::{
   config::BundleType,
   resources::{external_binaries, ResourcePaths},
@@ -26,10 +30,16 @@ pub enum PackageType {
   IosBundle,
   /// The Windows bundle (.msi).
   WindowsMsi,
+  /// The Windows NSIS installer (.exe).
+  Nsis,
+  /// The Windows MSIX package (.msix).
+  Msix,
   /// The Linux Debian package bundle (.deb).
   Deb,
   /// The Linux RPM bundle (.rpm).
   Rpm,
+  /// The Linux Flatpak bundle (.flatpak).
+  Flatpak,
   /// The Linux AppImage bundle (.AppImage).
   AppImage,
   /// The macOS DMG bundle (.dmg).
@@ -42,8 +52,12 @@ impl From<BundleType> for PackageType {
   fn from(bundle: BundleType) -> Self {
     match bundle {
       BundleType::Deb => Self::Deb,
+      BundleType::Rpm => Self::Rpm,
+      BundleType::Flatpak => Self::Flatpak,
       BundleType::AppImage => Self::AppImage,
       BundleType::Msi => Self::WindowsMsi,
+      BundleType::Nsis => Self::Nsis,
+      BundleType::Msix => Self::Msix,
       BundleType::App => Self::MacOsBundle,
       BundleType::Dmg => Self::Dmg,
       BundleType::Updater => Self::Updater,
@@ -53,15 +67,18 @@ impl From<BundleType> for PackageType {
 
 impl PackageType {
   /// Maps a short name to a PackageType.
-  /// Possible values are "deb", "ios", "msi", "app", "rpm", "appimage", "dmg", "updater".
+  /// Possible values are "deb", "ios", "msi", "nsis", "msix", "app", "rpm", "flatpak", "appimage", "dmg", "updater".
   pub fn from_short_name(name: &str) -> Option<PackageType> {
     // Other types we may eventually want to support: apk.
     match name {
       "deb" => Some(PackageType::Deb),
       "ios" => Some(PackageType::IosBundle),
       "msi" => Some(PackageType::WindowsMsi),
+      "nsis" => Some(PackageType::Nsis),
+      "msix" => Some(PackageType::Msix),
       "app" => Some(PackageType::MacOsBundle),
       "rpm" => Some(PackageType::Rpm),
+      "flatpak" => Some(PackageType::Flatpak),
       "appimage" => Some(PackageType::AppImage),
       "dmg" => Some(PackageType::Dmg),
       "updater" => Some(PackageType::Updater),
@@ -76,8 +93,11 @@ impl PackageType {
       PackageType::Deb => "deb",
       PackageType::IosBundle => "ios",
       PackageType::WindowsMsi => "msi",
+      PackageType::Nsis => "nsis",
+      PackageType::Msix => "msix",
       PackageType::MacOsBundle => "app",
       PackageType::Rpm => "rpm",
+      PackageType::Flatpak => "flatpak",
       PackageType::AppImage => "appimage",
       PackageType::Dmg => "dmg",
       PackageType::Updater => "updater",
@@ -97,10 +117,15 @@ const ALL_PACKAGE_TYPES: &[PackageType] = &[
   PackageType::IosBundle,
   #[cfg(target_os = "windows")]
   PackageType::WindowsMsi,
+  PackageType::Nsis,
+  #[cfg(target_os = "windows")]
+  PackageType::Msix,
   #[cfg(target_os = "macos")]
   PackageType::MacOsBundle,
   #[cfg(target_os = "linux")]
   PackageType::Rpm,
+  #[cfg(target_os = "linux")]
+  PackageType::Flatpak,
   #[cfg(target_os = "macos")]
   PackageType::Dmg,
   #[cfg(target_os = "linux")]
@@ -109,7 +134,8 @@ const ALL_PACKAGE_TYPES: &[PackageType] = &[
 ];
 
 /// The package settings.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PackageSettings {
   /// the package's product name.
   pub product_name: String,
@@ -126,7 +152,8 @@ pub struct PackageSettings {
 }
 
 /// The updater settings.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdaterSettings {
   /// Whether the updater is active or not.
   pub active: bool,
@@ -141,18 +168,129 @@ pub struct UpdaterSettings {
 }
 
 /// The Linux debian bundle settings.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DebianSettings {
   // OS-specific settings:
   /// the list of debian dependencies.
   pub depends: Option<Vec<String>>,
+  /// the list of recommended packages.
+  pub recommends: Option<Vec<String>>,
+  /// the list of suggested packages.
+  pub suggests: Option<Vec<String>>,
+  /// the list of virtual packages provided by the package.
+  pub provides: Option<Vec<String>>,
+  /// the list of conflicting packages.
+  pub conflicts: Option<Vec<String>>,
+  /// the list of packages replaced by the package.
+  pub replaces: Option<Vec<String>>,
+  /// the section of the package.
+  pub section: Option<String>,
+  /// the priority of the package, `optional` by default.
+  pub priority: Option<String>,
   /// List of custom files to add to the deb package.
   /// Maps the path on the debian package to the path of the file to include (relative to the current working directory).
   pub files: HashMap<PathBuf, PathBuf>,
+  /// The paths on the package of the configuration files, in addition to the files under `/etc`.
+  pub conffiles: Vec<PathBuf>,
+  /// Path to a systemd user unit installed to `/usr/lib/systemd/user` and enabled on install.
+  pub systemd_user_unit: Option<PathBuf>,
+  /// Path to the `preinst` maintainer script.
+  pub pre_install_script: Option<PathBuf>,
+  /// Path to the `postinst` maintainer script.
+  pub post_install_script: Option<PathBuf>,
+  /// Path to the `prerm` maintainer script.
+  pub pre_remove_script: Option<PathBuf>,
+  /// Path to the `postrm` maintainer script.
+  pub post_remove_script: Option<PathBuf>,
+}
+
+/// The Linux AppImage bundle settings.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppImageSettings {
+  /// The update information embedded in the AppImage for AppImageUpdate.
+  pub update_information: Option<String>,
+  /// Additional keys of the desktop entry, overriding the generated ones.
+  pub desktop_entry: HashMap<String, String>,
+  /// Patterns of the libraries that linuxdeploy must not bundle.
+  pub exclude_libraries: Vec<String>,
+  /// Path to the `AppRun` used instead of the one of AppImageKit (relative to the current working directory).
+  pub app_run: Option<PathBuf>,
+}
+
+/// An additional action of the Linux desktop entry.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopAction {
+  /// The action identifier.
+  pub id: String,
+  /// The action name displayed by the launcher.
+  pub name: String,
+  /// The arguments passed to the app.
+  pub exec_args: Vec<String>,
+}
+
+/// The Linux desktop entry settings, used by the Debian, RPM, AppImage and Flatpak bundles.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopEntrySettings {
+  /// The categories of the entry, overriding the ones of the app category.
+  pub categories: Option<Vec<String>>,
+  /// The keywords of the entry.
+  pub keywords: Vec<String>,
+  /// The window class of the app windows.
+  pub startup_wm_class: Option<String>,
+  /// The arguments passed to the app by the `Exec` key.
+  pub exec_args: Vec<String>,
+  /// The additional actions of the entry.
+  pub actions: Vec<DesktopAction>,
+}
+
+/// The Linux Flatpak bundle settings.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatpakSettings {
+  /// The runtime, defaults to `org.gnome.Platform`.
+  pub runtime: Option<String>,
+  /// The runtime version, defaults to `43`.
+  pub runtime_version: Option<String>,
+  /// The SDK, defaults to `org.gnome.Sdk`.
+  pub sdk: Option<String>,
+  /// The sandbox permissions of the app.
+  pub finish_args: Vec<String>,
+}
+
+/// The Linux RPM bundle settings.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpmSettings {
+  /// The list of RPM dependencies.
+  pub depends: Option<Vec<String>>,
+  /// The package release number, defaults to `1`.
+  pub release: Option<String>,
+  /// The package epoch.
+  pub epoch: Option<u32>,
+  /// The license of the package, defaults to `Unknown`.
+  pub license: Option<String>,
+  /// Path to a Handlebars template replacing the generated desktop file.
+  pub desktop_template: Option<PathBuf>,
+  /// List of custom files to add to the RPM package.
+  /// Maps the path on the RPM package to the path of the file to include (relative to the current working directory).
+  pub files: HashMap<PathBuf, PathBuf>,
+  /// Path to the script run before the package is installed.
+  pub pre_install_script: Option<PathBuf>,
+  /// Path to the script run after the package is installed.
+  pub post_install_script: Option<PathBuf>,
+  /// Path to the script run before the package is removed.
+  pub pre_remove_script: Option<PathBuf>,
+  /// Path to the script run after the package is removed.
+  pub post_remove_script: Option<PathBuf>,
 }
 
 /// The macOS bundle settings.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct MacOsSettings {
   /// MacOS frameworks that need to be bundled with the app.
   ///
@@ -178,22 +316,85 @@ pub struct MacOsSettings {
   /// Code signing identity.
   pub signing_identity: Option<String>,
   /// Provider short name for notarization.
+  ///
+  /// Used as the `notarytool` team when [`NotarizationSettings::team_id`] is not set.
   pub provider_short_name: Option<String>,
   /// Path to the entitlements.plist file.
   pub entitlements: Option<String>,
   /// Path to the Info.plist file for the bundle.
   pub info_plist_path: Option<PathBuf>,
+  /// Entries deep-merged into the Info.plist after the `info_plist_path` file.
+  ///
+  /// Dictionaries are merged recursively, `null` removes the key and any other value replaces it.
+  pub info_plist: Option<serde_json::Map<String, serde_json::Value>>,
+  /// The layout of the DMG window.
+  pub dmg: DmgSettings,
+  /// The notarization credentials, overridden by the environment variables.
+  pub notarization: NotarizationSettings,
+}
+
+/// The layout of the DMG window.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DmgSettings {
+  /// Path to the background image of the window (relative to the current working directory).
+  pub background: Option<PathBuf>,
+  /// Position of the window on the screen.
+  pub window_position: Option<Position>,
+  /// Size of the window.
+  pub window_size: Size,
+  /// Size of the icons in the window.
+  pub icon_size: Option<u32>,
+  /// Position of the app icon in the window.
+  pub app_position: Position,
+  /// Position of the Applications folder link in the window.
+  pub application_folder_position: Position,
+}
+
+impl Default for DmgSettings {
+  fn default() -> Self {
+    Self {
+      background: None,
+      window_position: None,
+      window_size: Size {
+        width: 660,
+        height: 400,
+      },
+      icon_size: None,
+      app_position: Position { x: 180, y: 170 },
+      application_folder_position: Position { x: 480, y: 170 },
+    }
+  }
+}
+
+/// The credentials used to notarize the app bundle and the DMG with `notarytool`.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotarizationSettings {
+  /// The Apple ID, authenticated with the `APPLE_PASSWORD` environment variable.
+  pub apple_id: Option<String>,
+  /// The team ID of the Apple ID.
+  ///
+  /// Defaults to [`MacOsSettings::provider_short_name`], then to the team ID of the signing identity.
+  pub team_id: Option<String>,
+  /// The ID of the App Store Connect API key.
+  pub api_key: Option<String>,
+  /// The issuer ID of the App Store Connect API key.
+  pub api_issuer: Option<String>,
+  /// Path to the private key file of the API key.
+  pub api_key_path: Option<PathBuf>,
 }
 
 /// Configuration for a target language for the WiX build.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WixLanguageConfig {
   /// The path to a locale (`.wxl`) file. See <https://wixtoolset.org/documentation/manual/v3/howtos/ui_and_localization/build_a_localized_version.html>.
   pub locale_path: Option<PathBuf>,
 }
 
 /// The languages to build using WiX.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct WixLanguage(pub Vec<(String, WixLanguageConfig)>);
 
 impl Default for WixLanguage {
@@ -203,10 +404,13 @@ impl Default for WixLanguage {
 }
 
 /// Settings specific to the WiX implementation.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WixSettings {
   /// The app languages to build. See <https://docs.microsoft.com/en-us/windows/win32/msi/localizing-the-error-and-actiontext-tables>.
   pub language: WixLanguage,
+  /// Whether to build a single installer embedding a transform for each language.
+  pub embed_language_transforms: bool,
   /// By default, the bundler uses an internal template.
   /// This option allows you to define your own wix file.
   pub template: Option<PathBuf>,
@@ -222,6 +426,8 @@ pub struct WixSettings {
   pub feature_refs: Vec<String>,
   /// The Merge element ids you want to reference from the fragments.
   pub merge_refs: Vec<String>,
+  /// The Dialog element ids from the fragments shown after the install directory dialog, in order.
+  pub custom_dialogs: Vec<String>,
   /// Disables the Webview2 runtime installation after app install. Will be removed in v2, use [`WindowsSettings::webview_install_mode`] instead.
   pub skip_webview_install: bool,
   /// The path to the LICENSE file.
@@ -242,13 +448,57 @@ pub struct WixSettings {
   pub fips_compliant: bool,
 }
 
+/// Settings specific to the NSIS implementation.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NsisSettings {
+  /// By default, the bundler uses an internal template.
+  /// This option allows you to define your own .nsi file.
+  pub template: Option<PathBuf>,
+  /// The path to the license file rendered on a page of the installer.
+  pub license: Option<PathBuf>,
+  /// Path to a bitmap file displayed on the header of the installer pages.
+  pub header_image: Option<PathBuf>,
+  /// Path to a bitmap file displayed on the welcome and finish pages.
+  pub sidebar_image: Option<PathBuf>,
+  /// Whether the app is installed for the current user or for all users.
+  pub install_mode: NsisInstallMode,
+  /// The installer languages, using the NSIS language names. Defaults to `["English"]`.
+  pub languages: Option<Vec<String>>,
+  /// Whether to ask the user to choose the installer language when more than one is configured.
+  pub display_language_selector: bool,
+  /// Paths to NSIS scripts declaring custom pages, included before the installation page.
+  pub custom_pages: Vec<PathBuf>,
+}
+
+/// Settings specific to the MSIX package.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MsixSettings {
+  /// The package identity name. Defaults to the bundle identifier.
+  pub identity_name: Option<String>,
+  /// The publisher of the package, which must match the subject of the signing certificate.
+  pub publisher: Option<String>,
+  /// The publisher name displayed to users.
+  pub publisher_display_name: Option<String>,
+  /// The minimum Windows version supported by the package. Defaults to `10.0.17763.0`.
+  pub min_version: Option<String>,
+  /// The capabilities declared by the package.
+  pub capabilities: Vec<String>,
+}
+
 /// The Windows bundle settings.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WindowsSettings {
   /// The file digest algorithm to use for creating file signatures. Required for code signing. SHA-256 is recommended.
   pub digest_algorithm: Option<String>,
   /// The SHA1 hash of the signing certificate.
   pub certificate_thumbprint: Option<String>,
+  /// Signs with a cloud HSM instead of the local certificate store.
+  ///
+  /// Overwrites [`Self::certificate_thumbprint`] if set.
+  pub sign: Option<WindowsSignMethod>,
   /// Server to use during timestamping.
   pub timestamp_url: Option<String>,
   /// Whether to use Time-Stamp Protocol (TSP, a.k.a. RFC 3161) for the timestamp server. Your code signing provider may
@@ -256,6 +506,10 @@ pub struct WindowsSettings {
   pub tsp: bool,
   /// WiX configuration.
   pub wix: Option<WixSettings>,
+  /// NSIS configuration.
+  pub nsis: Option<NsisSettings>,
+  /// MSIX configuration.
+  pub msix: MsixSettings,
   /// The path to the application icon. Defaults to `./icons/icon.ico`.
   pub icon_path: PathBuf,
   /// The installation mode for the Webview2 runtime.
@@ -279,9 +533,12 @@ impl Default for WindowsSettings {
     Self {
       digest_algorithm: None,
       certificate_thumbprint: None,
+      sign: None,
       timestamp_url: None,
       tsp: false,
       wix: None,
+      nsis: None,
+      msix: Default::default(),
       icon_path: PathBuf::from("icons/icon.ico"),
       webview_install_mode: Default::default(),
       webview_fixed_runtime_path: None,
@@ -291,7 +548,8 @@ impl Default for WindowsSettings {
 }
 
 /// The bundle settings of the BuildArtifact we're bundling.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BundleSettings {
   /// the app's identifier.
   pub identifier: Option<String>,
@@ -303,6 +561,8 @@ pub struct BundleSettings {
   ///
   /// supports glob patterns.
   pub resources: Option<Vec<String>>,
+  /// the app's resource files mapped to their path in the resources directory.
+  pub resources_map: Option<HashMap<PathBuf, PathBuf>>,
   /// the app's copyright.
   pub copyright: Option<String>,
   /// the app's category.
@@ -311,6 +571,10 @@ pub struct BundleSettings {
   pub short_description: Option<String>,
   /// the app's long description.
   pub long_description: Option<String>,
+  /// the file name template of the bundles, without the extension.
+  ///
+  /// The `{name}`, `{version}`, `{target}`, `{arch}` and `{format}` placeholders are replaced.
+  pub artifact_name: Option<String>,
   // Bundles for other binaries:
   /// Configuration map for the apps to bundle.
   pub bin: Option<HashMap<String, BundleSettings>>,
@@ -331,16 +595,41 @@ pub struct BundleSettings {
   pub external_bin: Option<Vec<String>>,
   /// Debian-specific settings.
   pub deb: DebianSettings,
+  /// AppImage-specific settings.
+  pub appimage: AppImageSettings,
+  /// RPM-specific settings.
+  pub rpm: RpmSettings,
+  /// Flatpak-specific settings.
+  pub flatpak: FlatpakSettings,
+  /// Linux desktop entry settings.
+  pub desktop: DesktopEntrySettings,
   /// MacOS-specific settings.
   pub macos: MacOsSettings,
   /// Updater configuration.
   pub updater: Option<UpdaterSettings>,
   /// Windows-specific settings.
   pub windows: WindowsSettings,
+  /// A command signing the app binaries and every bundle produced.
+  pub sign_command: Option<SignCommand>,
+  /// The file types opened by the app.
+  pub file_associations: Option<Vec<FileAssociation>>,
+  /// The URL schemes opening the app.
+  pub deep_link_schemes: Option<Vec<String>>,
+}
+
+/// A command signing a file, invoked in addition to the built-in signers.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignCommand {
+  /// The program to run.
+  pub command: String,
+  /// The arguments of the program. `%1` is replaced with the path of the file to sign.
+  pub args: Vec<String>,
 }
 
 /// A binary to bundle.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BundleBinary {
   name: String,
   src_path: Option<String>,
@@ -503,6 +792,16 @@ impl SettingsBuilder {
 }
 
 impl Settings {
+  /// Returns the package settings.
+  pub fn package_settings(&self) -> &PackageSettings {
+    &self.package
+  }
+
+  /// Returns the bundle settings.
+  pub fn bundle_settings(&self) -> &BundleSettings {
+    &self.bundle_settings
+  }
+
   /// Returns the directory where the bundle should be placed.
   pub fn project_out_directory(&self) -> &Path {
     &self.project_out_directory
@@ -563,11 +862,18 @@ impl Settings {
   ///
   /// Fails if the host/target's native package type is not supported.
   pub fn package_types(&self) -> crate::Result<Vec<PackageType>> {
-    let target_os = std::env::consts::OS;
+    let cross_windows = self.target.contains("windows") && !cfg!(target_os = "windows");
+    // Windows apps can be bundled from other hosts with NSIS
+    let target_os = if cross_windows {
+      "windows"
+    } else {
+      std::env::consts::OS
+    };
     let mut platform_types = match target_os {
       "macos" => vec![PackageType::MacOsBundle, PackageType::Dmg],
       "ios" => vec![PackageType::IosBundle],
       "linux" => vec![PackageType::Deb, PackageType::AppImage],
+      "windows" if cross_windows => vec![PackageType::Nsis],
       "windows" => vec![PackageType::WindowsMsi],
       os => {
         return Err(crate::Error::GenericError(format!(
@@ -579,27 +885,46 @@ impl Settings {
 
     // add updater if needed
     if self.is_update_enabled() {
-      platform_types.push(PackageType::Updater)
+      if cross_windows {
+        log::warn!("The updater bundle is not supported when cross compiling to Windows");
+      } else {
+        platform_types.push(PackageType::Updater)
+      }
     }
 
     if let Some(package_types) = &self.package_types {
-      let mut types = vec![];
-      for package_type in package_types {
-        let package_type = *package_type;
-        if platform_types
-          .clone()
-          .into_iter()
-          .any(|t| t == package_type)
-        {
-          types.push(package_type);
-        }
+      // the NSIS installer and the MSIX package are opt-in on Windows hosts and the RPM and Flatpak packages on Linux
+      if target_os == "windows" && !cross_windows {
+        platform_types.insert(1, PackageType::Nsis);
+        platform_types.insert(2, PackageType::Msix);
+      } else if target_os == "linux" {
+        platform_types.insert(1, PackageType::Rpm);
+        platform_types.insert(2, PackageType::Flatpak);
       }
-      Ok(types)
+      // keep the platform order so the dmg and updater bundles reuse the bundles they depend on
+      Ok(
+        platform_types
+          .into_iter()
+          .filter(|package_type| package_types.contains(package_type))
+          .collect(),
+      )
     } else {
       Ok(platform_types)
     }
   }
 
+  /// Returns the requested package types that cannot be bundled for the target.
+  pub fn unsupported_package_types(&self) -> crate::Result<Vec<PackageType>> {
+    let supported = self.package_types()?;
+    let mut unsupported = Vec::new();
+    for package_type in self.package_types.iter().flatten() {
+      if !supported.contains(package_type) && !unsupported.contains(package_type) {
+        unsupported.push(*package_type);
+      }
+    }
+    Ok(unsupported)
+  }
+
   /// Returns the product name.
   pub fn product_name(&self) -> &str {
     &self.package.product_name
@@ -627,6 +952,26 @@ impl Settings {
     }
   }
 
+  /// Returns the resource files to be included in this bundle, with their path
+  /// relative to the resources directory.
+  pub fn resources(&self) -> impl Iterator<Item = crate::Result<(PathBuf, PathBuf)>> + '_ {
+    self
+      .resource_files()
+      .map(|src| {
+        let src = src?;
+        let target = tauri_utils::resources::resource_relpath(&src);
+        Ok((src, target))
+      })
+      .chain(
+        self
+          .bundle_settings
+          .resources_map
+          .iter()
+          .flatten()
+          .map(|(src, target)| Ok((src.clone(), target.clone()))),
+      )
+  }
+
   /// Returns an iterator over the external binaries to be included in this
   /// bundle.
   pub fn external_binaries(&self) -> ResourcePaths<'_> {
@@ -654,10 +999,9 @@ impl Settings {
 
   /// Copies resources to a path.
   pub fn copy_resources(&self, path: &Path) -> crate::Result<()> {
-    for src in self.resource_files() {
-      let src = src?;
-      let dest = path.join(tauri_utils::resources::resource_relpath(&src));
-      common::copy_file(&src, &dest)?;
+    for resource in self.resources() {
+      let (src, target) = resource?;
+      common::copy_file(&src, &path.join(target))?;
     }
     Ok(())
   }
@@ -714,11 +1058,45 @@ impl Settings {
     self.bundle_settings.long_description.as_deref()
   }
 
+  /// Returns the file name of a bundle without extension from the artifact name template, if it is set.
+  ///
+  /// `arch` is the architecture name used by the bundle format.
+  pub fn artifact_name(&self, package_type: PackageType, arch: &str) -> Option<String> {
+    self.bundle_settings.artifact_name.as_ref().map(|template| {
+      template
+        .replace("{name}", &self.main_binary_name().replace(".exe", ""))
+        .replace("{version}", self.version_string())
+        .replace("{target}", &self.target)
+        .replace("{arch}", arch)
+        .replace("{format}", package_type.short_name())
+    })
+  }
+
   /// Returns the debian settings.
   pub fn deb(&self) -> &DebianSettings {
     &self.bundle_settings.deb
   }
 
+  /// Returns the AppImage settings.
+  pub fn appimage(&self) -> &AppImageSettings {
+    &self.bundle_settings.appimage
+  }
+
+  /// Returns the RPM settings.
+  pub fn rpm(&self) -> &RpmSettings {
+    &self.bundle_settings.rpm
+  }
+
+  /// Returns the Flatpak settings.
+  pub fn flatpak(&self) -> &FlatpakSettings {
+    &self.bundle_settings.flatpak
+  }
+
+  /// Returns the Linux desktop entry settings.
+  pub fn desktop(&self) -> &DesktopEntrySettings {
+    &self.bundle_settings.desktop
+  }
+
   /// Returns the MacOS settings.
   pub fn macos(&self) -> &MacOsSettings {
     &self.bundle_settings.macos
@@ -734,6 +1112,29 @@ impl Settings {
     self.bundle_settings.updater.as_ref()
   }
 
+  /// Returns the command signing the app binaries and the bundles.
+  pub fn sign_command(&self) -> Option<&SignCommand> {
+    self.bundle_settings.sign_command.as_ref()
+  }
+
+  /// Returns the file types opened by the app.
+  pub fn file_associations(&self) -> &[FileAssociation] {
+    self
+      .bundle_settings
+      .file_associations
+      .as_deref()
+      .unwrap_or_default()
+  }
+
+  /// Returns the URL schemes opening the app.
+  pub fn deep_link_schemes(&self) -> &[String] {
+    self
+      .bundle_settings
+      .deep_link_schemes
+      .as_deref()
+      .unwrap_or_default()
+  }
+
   /// Is update enabled
   pub fn is_update_enabled(&self) -> bool {
     match &self.bundle_settings.updater {
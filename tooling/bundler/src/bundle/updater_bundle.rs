@@ -128,6 +128,27 @@ fn bundle_update(settings: &Settings, bundles: &[Bundle]) -> crate::Result<Vec<P
 fn bundle_update(settings: &Settings, bundles: &[Bundle]) -> crate::Result<Vec<PathBuf>> {
   use crate::bundle::settings::WebviewInstallMode;
 
+  // the NSIS installer is used when it is the only installer built
+  if !bundles
+    .iter()
+    .any(|bundle| bundle.package_type == crate::PackageType::WindowsMsi)
+  {
+    if let Some(bundle) = bundles
+      .iter()
+      .find(|bundle| bundle.package_type == crate::PackageType::Nsis)
+    {
+      let mut nsis_archived_paths = Vec::new();
+      for source_path in &bundle.bundle_paths {
+        let nsis_archived_path = source_path.with_extension("nsis.zip");
+        info!(action = "Bundling"; "{}", nsis_archived_path.display());
+        create_zip(source_path, &nsis_archived_path)
+          .with_context(|| "Failed to zip update NSIS installer")?;
+        nsis_archived_paths.push(nsis_archived_path);
+      }
+      return Ok(nsis_archived_paths);
+    }
+  }
+
   // find our .msi or rebuild
   let bundle_paths = if matches!(
     settings.windows().webview_install_mode,
@@ -189,9 +210,13 @@ pub fn create_zip(src_file: &Path, dst_file: &Path) -> crate::Result<PathBuf> {
     .expect("Can't extract file name from path");
 
   let mut zip = zip::ZipWriter::new(writer);
-  let options = FileOptions::default()
+  let mut options = FileOptions::default()
     .compression_method(zip::CompressionMethod::Stored)
     .unix_permissions(0o755);
+  if common::source_date_epoch().is_some() {
+    // zip timestamps are local times without a time zone, so the oldest one is used instead of the epoch
+    options = options.last_modified_time(zip::DateTime::default());
+  }
 
   zip.start_file(file_name.to_string_lossy(), options)?;
   let mut f = File::open(src_file)?;
@@ -206,7 +231,7 @@ pub fn create_zip(src_file: &Path, dst_file: &Path) -> crate::Result<PathBuf> {
 #[cfg(not(target_os = "windows"))]
 fn create_tar(src_dir: &Path, dest_path: &Path) -> crate::Result<PathBuf> {
   let dest_file = common::create_file(dest_path)?;
-  let gzip_encoder = libflate::gzip::Encoder::new(dest_file)?;
+  let gzip_encoder = common::gzip_encoder(dest_file)?;
 
   let gzip_encoder = create_tar_from_src(src_dir, gzip_encoder)?;
   let mut dest_file = gzip_encoder.finish().into_result()?;
@@ -223,14 +248,14 @@ fn create_tar_from_src<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> cr
   let file_type = fs::metadata(src_dir).expect("Can't read source directory");
   // if it's a file don't need to walkdir
   if file_type.is_file() {
-    let mut src_file = fs::File::open(src_dir)?;
     let file_name = src_dir
       .file_name()
       .expect("Can't extract file name from path");
 
-    tar_builder.append_file(file_name, &mut src_file)?;
+    common::append_to_tar(&mut tar_builder, Path::new(file_name), src_dir)?;
   } else {
-    for entry in walkdir::WalkDir::new(&src_dir) {
+    // sorted so the archive does not depend on the file system order
+    for entry in walkdir::WalkDir::new(&src_dir).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
       let entry = entry?;
       let src_path = entry.path();
       if src_path == src_dir {
@@ -242,12 +267,7 @@ fn create_tar_from_src<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> cr
       // We need a tar with app.app/<...> (source root folder should be included)
       // safe to unwrap: the path has a parent
       let dest_path = src_path.strip_prefix(&src_dir.parent().unwrap())?;
-      if entry.file_type().is_dir() {
-        tar_builder.append_dir(dest_path, src_path)?;
-      } else {
-        let mut src_file = fs::File::open(src_path)?;
-        tar_builder.append_file(dest_path, &mut src_file)?;
-      }
+      common::append_to_tar(&mut tar_builder, dest_path, src_path)?;
     }
   }
   let dest_file = tar_builder.into_inner()?;
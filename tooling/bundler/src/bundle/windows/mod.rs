@@ -3,5 +3,73 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+#[cfg(target_os = "windows")]
 pub mod msi;
+#[cfg(target_os = "windows")]
+pub mod msix;
+pub mod nsis;
 pub mod sign;
+
+use crate::Settings;
+use serde::Serialize;
+use std::path::PathBuf;
+
+pub const WEBVIEW2_BOOTSTRAPPER_URL: &str = "https://go.microsoft.com/fwlink/p/?LinkId=2124703";
+
+/// The path of the `.ico` icon used by the installers.
+pub fn icon_path(settings: &Settings) -> crate::Result<PathBuf> {
+  let icon_path = std::env::current_dir()?.join(&settings.windows().icon_path);
+  if icon_path.extension().map_or(true, |e| e != "ico") || !icon_path.is_file() {
+    return Err(crate::Error::GenericError(
+      "the bundle config must have a `.ico` icon".into(),
+    ));
+  }
+  Ok(icon_path)
+}
+
+/// A file extension registered by the installers.
+#[derive(Serialize)]
+pub struct FileExtension {
+  /// The extension, without the leading dot.
+  pub ext: String,
+  /// The ProgID of the file type, made of the bundle identifier and the extension.
+  pub prog_id: String,
+  /// The description of the file type.
+  pub description: String,
+}
+
+/// The extensions of the file associations, registered under a ProgID opening them with the main binary.
+pub fn file_extensions(settings: &Settings) -> Vec<FileExtension> {
+  settings
+    .file_associations()
+    .iter()
+    .flat_map(|association| {
+      let description = association
+        .description
+        .clone()
+        .unwrap_or_else(|| association.name().to_string());
+      association.ext.iter().map(move |ext| FileExtension {
+        ext: ext.clone(),
+        prog_id: format!("{}.{}", settings.bundle_identifier(), ext),
+        description: description.clone(),
+      })
+    })
+    .collect()
+}
+
+/// The NSIS and MSIX version info must be made of four numbers, so the pre-release and build metadata are dropped.
+pub fn file_version(version: &str) -> String {
+  let mut numbers = version
+    .split(|c| c == '-' || c == '+')
+    .next()
+    .unwrap_or_default()
+    .split('.')
+    .map(|n| n.parse::<u16>().unwrap_or_default())
+    .collect::<Vec<_>>();
+  numbers.resize(4, 0);
+  numbers
+    .iter()
+    .map(|n| n.to_string())
+    .collect::<Vec<_>>()
+    .join(".")
+}
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use super::super::sign::{sign, SignParams};
+use super::super::{file_extensions, icon_path, sign, WEBVIEW2_BOOTSTRAPPER_URL};
 use crate::bundle::{
   common::CommandExt,
   path_utils::{copy_file, FileOpts},
@@ -22,7 +22,7 @@ use std::{
   path::{Path, PathBuf},
   process::Command,
 };
-use tauri_utils::{config::WebviewInstallMode, resources::resource_relpath};
+use tauri_utils::config::WebviewInstallMode;
 use uuid::Uuid;
 use zip::ZipArchive;
 
@@ -32,7 +32,6 @@ pub const WIX_URL: &str =
 pub const WIX_SHA256: &str = "2c1888d5d1dba377fc7fa14444cf556963747ff9a0a289a3599cf09da03b9e2e";
 pub const MSI_FOLDER_NAME: &str = "msi";
 pub const MSI_UPDATER_FOLDER_NAME: &str = "msi-updater";
-const WEBVIEW2_BOOTSTRAPPER_URL: &str = "https://go.microsoft.com/fwlink/p/?LinkId=2124703";
 const WEBVIEW2_X86_INSTALLER_GUID: &str = "a17bde80-b5ab-47b5-8bbb-1cbe93fc6ec9";
 const WEBVIEW2_X64_INSTALLER_GUID: &str = "aa5fd9b3-dc11-4cbc-8343-a50f57b311e1";
 
@@ -90,6 +89,8 @@ struct ResourceFile {
   id: String,
   /// the file path.
   path: String,
+  /// the file name in the resources directory.
+  name: String,
 }
 
 /// A resource directory to bundle with WIX.
@@ -120,10 +121,11 @@ impl ResourceDirectory {
       file_ids.push(file.id.clone());
       files.push_str(
         format!(
-          r#"<Component Id="{id}" Guid="{guid}" Win64="$(var.Win64)" KeyPath="yes"><File Id="PathFile_{id}" Source="{path}" /></Component>"#,
+          r#"<Component Id="{id}" Guid="{guid}" Win64="$(var.Win64)" KeyPath="yes"><File Id="PathFile_{id}" Source="{path}" Name="{name}" /></Component>"#,
           id = file.id,
           guid = file.guid,
-          path = file.path
+          path = file.path,
+          name = file.name
         ).as_str()
       );
     }
@@ -215,13 +217,28 @@ fn app_installer_output_path(
     }
   };
 
-  let package_base_name = format!(
-    "{}_{}_{}_{}",
-    settings.main_binary_name().replace(".exe", ""),
-    settings.version_string(),
-    arch,
-    language,
-  );
+  let package_base_name = match settings.artifact_name(crate::PackageType::WindowsMsi, arch) {
+    Some(name) if name.contains("{language}") => name.replace("{language}", language),
+    Some(name) => {
+      let several_languages = settings
+        .windows()
+        .wix
+        .as_ref()
+        .map_or(false, |w| w.language.0.len() > 1);
+      if several_languages {
+        format!("{}_{}", name, language)
+      } else {
+        name
+      }
+    }
+    None => format!(
+      "{}_{}_{}_{}",
+      settings.main_binary_name().replace(".exe", ""),
+      settings.version_string(),
+      arch,
+      language,
+    ),
+  };
 
   Ok(settings.project_out_directory().to_path_buf().join(format!(
     "bundle/{}/{}.msi",
@@ -428,31 +445,7 @@ pub fn build_wix_app_installer(
     .find(|bin| bin.main())
     .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
   let app_exe_source = settings.binary_path(main_binary);
-  let try_sign = |file_path: &PathBuf| -> crate::Result<()> {
-    if let Some(certificate_thumbprint) = &settings.windows().certificate_thumbprint {
-      info!(action = "Signing"; "{}", file_path.display());
-      sign(
-        &file_path,
-        &SignParams {
-          product_name: settings.product_name().into(),
-          digest_algorithm: settings
-            .windows()
-            .digest_algorithm
-            .as_ref()
-            .map(|algorithm| algorithm.to_string())
-            .unwrap_or_else(|| "sha256".to_string()),
-          certificate_thumbprint: certificate_thumbprint.to_string(),
-          timestamp_url: settings
-            .windows()
-            .timestamp_url
-            .as_ref()
-            .map(|url| url.to_string()),
-          tsp: settings.windows().tsp,
-        },
-      )?;
-    }
-    Ok(())
-  };
+  let try_sign = |file_path: &PathBuf| sign::try_sign(settings, file_path);
 
   try_sign(&app_exe_source)?;
 
@@ -641,9 +634,11 @@ pub fn build_wix_app_installer(
   data.insert("merge_modules", to_json(merge_modules));
 
   data.insert("app_exe_source", to_json(&app_exe_source));
+  data.insert("file_extensions", to_json(file_extensions(settings)));
+  data.insert("deep_link_schemes", to_json(settings.deep_link_schemes()));
 
   // copy icon from `settings.windows().icon_path` folder to resource folder near msi
-  let icon_path = copy_icon(settings, "icon.ico", &settings.windows().icon_path)?;
+  let icon_path = copy_icon(settings, "icon.ico", &icon_path(settings)?)?;
 
   data.insert("icon_path", to_json(icon_path));
 
@@ -658,6 +653,10 @@ pub fn build_wix_app_installer(
     data.insert("feature_group_refs", to_json(&wix.feature_group_refs));
     data.insert("feature_refs", to_json(&wix.feature_refs));
     data.insert("merge_refs", to_json(&wix.merge_refs));
+    data.insert(
+      "custom_dialogs",
+      to_json(custom_dialogs(&wix.custom_dialogs)),
+    );
     fragment_paths = wix.fragment_paths.clone();
     enable_elevated_update_task = wix.enable_elevated_update_task;
 
@@ -773,7 +772,14 @@ pub fn build_wix_app_installer(
     run_candle(settings, wix_toolset_path, &output_path, path, extensions)?;
   }
 
+  let embed_language_transforms = configured_languages.0.len() > 1
+    && settings
+      .windows()
+      .wix
+      .as_ref()
+      .map_or(false, |w| w.embed_language_transforms);
   let mut output_paths = Vec::new();
+  let mut language_installers = Vec::new();
 
   for (language, language_config) in configured_languages.0 {
     let language_metadata = language_map.get(&language).unwrap_or_else(|| {
@@ -852,6 +858,25 @@ pub fn build_wix_app_installer(
       &msi_output_path,
     )?;
     rename(&msi_output_path, &msi_path)?;
+    if embed_language_transforms {
+      language_installers.push((language_metadata.lang_id, msi_path));
+    } else {
+      try_sign(&msi_path)?;
+      output_paths.push(msi_path);
+    }
+  }
+
+  if embed_language_transforms {
+    let msi_path = app_installer_output_path(settings, "multilang", updater)?;
+    info!(action = "Embedding"; "the language transforms in {}", msi_path.display());
+    let platform = if arch == "x86" { "Intel" } else { arch };
+    embed_transforms(
+      wix_toolset_path,
+      &output_path,
+      &language_installers,
+      platform,
+      &msi_path,
+    )?;
     try_sign(&msi_path)?;
     output_paths.push(msi_path);
   }
@@ -859,6 +884,61 @@ pub fn build_wix_app_installer(
   Ok(output_paths)
 }
 
+/// Builds a single installer from the installers of each language, the first one being the default language.
+///
+/// The transforms are embedded as substorages named after the language ids and listed in the summary information,
+/// so Windows Installer applies the one matching the user language.
+fn embed_transforms(
+  wix_toolset_path: &Path,
+  build_path: &Path,
+  installers: &[(usize, PathBuf)],
+  platform: &str,
+  output_path: &Path,
+) -> crate::Result<()> {
+  let msidb = sign::locate_sdk_tool("msidb.exe")?;
+  let msiinfo = sign::locate_sdk_tool("msiinfo.exe")?;
+
+  let (_, base_installer) = &installers[0];
+  std::fs::copy(base_installer, output_path)?;
+  for (lang_id, installer) in &installers[1..] {
+    // msidb names the substorage after the transform file
+    let transform = lang_id.to_string();
+    Command::new(wix_toolset_path.join("torch.exe"))
+      .args(["-t", "language"])
+      .arg(base_installer)
+      .arg(installer)
+      .arg("-out")
+      .arg(&transform)
+      .current_dir(build_path)
+      .output_ok()
+      .context("error running torch.exe")?;
+    Command::new(&msidb)
+      .arg("-d")
+      .arg(output_path)
+      .arg("-r")
+      .arg(&transform)
+      .current_dir(build_path)
+      .output_ok()
+      .context("error running msidb.exe")?;
+  }
+
+  let language_ids = installers
+    .iter()
+    .map(|(lang_id, _)| lang_id.to_string())
+    .collect::<Vec<_>>();
+  Command::new(msiinfo)
+    .arg(output_path)
+    .arg("/p")
+    .arg(format!("{};{}", platform, language_ids.join(",")))
+    .output_ok()
+    .context("error running msiinfo.exe")?;
+
+  for (_, installer) in installers {
+    std::fs::remove_file(installer)?;
+  }
+  Ok(())
+}
+
 /// Generates the data required for the external binaries and extra binaries bundling.
 fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
   let mut binaries = Vec::new();
@@ -907,6 +987,31 @@ fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
   Ok(binaries)
 }
 
+/// A custom dialog of the installer UI sequence, with the dialogs its `Back` and `Next` buttons open.
+#[derive(Serialize)]
+struct CustomDialog<'a> {
+  id: &'a str,
+  back: &'a str,
+  next: &'a str,
+}
+
+/// Chains the custom dialogs between the install directory and the install confirmation dialogs.
+fn custom_dialogs(ids: &[String]) -> Vec<CustomDialog<'_>> {
+  ids
+    .iter()
+    .enumerate()
+    .map(|(i, id)| CustomDialog {
+      id,
+      back: if i == 0 {
+        "InstallDirDlg"
+      } else {
+        ids[i - 1].as_str()
+      },
+      next: ids.get(i + 1).map_or("VerifyReadyDlg", String::as_str),
+    })
+    .collect()
+}
+
 #[derive(Serialize)]
 struct MergeModule {
   name: String,
@@ -946,11 +1051,11 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourceMap> {
 
   let mut added_resources = Vec::new();
 
-  for src in settings.resource_files() {
-    let src = src?;
+  for resource in settings.resources() {
+    let (src, target_path) = resource?;
 
     let resource_path = cwd
-      .join(src.clone())
+      .join(src)
       .into_os_string()
       .into_string()
       .expect("failed to read resource path");
@@ -958,20 +1063,24 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourceMap> {
     // In some glob resource paths like `assets/**/*` a file might appear twice
     // because the `tauri_utils::resources::ResourcePaths` iterator also reads a directory
     // when it finds one. So we must check it before processing the file.
-    if added_resources.contains(&resource_path) {
+    if added_resources.contains(&target_path) {
       continue;
     }
 
-    added_resources.push(resource_path.clone());
+    added_resources.push(target_path.clone());
 
     let resource_entry = ResourceFile {
       id: format!("I{}", Uuid::new_v4().as_simple()),
       guid: Uuid::new_v4().to_string(),
       path: resource_path,
+      name: target_path
+        .file_name()
+        .expect("failed to read resource file name")
+        .to_string_lossy()
+        .into_owned(),
     };
 
     // split the resource path directories
-    let target_path = resource_relpath(&src);
     let components_count = target_path.components().count();
     let directories = target_path
       .components()
@@ -0,0 +1,284 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! MSIX package for the Microsoft Store, built with `makeappx` from the Windows SDK.
+
+use super::sign::{locate_sdk_tool, try_sign};
+use crate::bundle::{
+  common::{self, CommandExt},
+  settings::Settings,
+};
+use anyhow::Context;
+use handlebars::{to_json, Handlebars};
+use image::imageops::FilterType;
+use log::info;
+use serde::Serialize;
+
+use std::{
+  collections::BTreeMap,
+  fs::{create_dir_all, remove_dir_all, write},
+  path::PathBuf,
+  process::Command,
+};
+
+pub const MSIX_FOLDER_NAME: &str = "msix";
+
+const DEFAULT_MIN_VERSION: &str = "10.0.17763.0";
+
+/// The logos required by the manifest, generated from the app icon.
+const LOGOS: &[(&str, u32)] = &[
+  ("Square150x150Logo.png", 150),
+  ("Square44x44Logo.png", 44),
+  ("StoreLogo.png", 50),
+];
+
+const BASE_CAPABILITIES: &[&str] = &[
+  "internetClient",
+  "internetClientServer",
+  "privateNetworkClientServer",
+  "allJoyn",
+  "codeGeneration",
+];
+const RESTRICTED_CAPABILITIES: &[&str] = &[
+  "runFullTrust",
+  "allowElevation",
+  "broadFileSystemAccess",
+  "confirmAppClose",
+  "unvirtualizedResources",
+];
+const DEVICE_CAPABILITIES: &[&str] = &[
+  "bluetooth",
+  "humaninterfacedevice",
+  "location",
+  "microphone",
+  "proximity",
+  "serialcommunication",
+  "usb",
+  "webcam",
+];
+
+#[derive(Serialize)]
+struct Capability<'a> {
+  element: &'static str,
+  name: &'a str,
+}
+
+#[derive(Serialize)]
+struct FileType<'a> {
+  name: String,
+  ext: &'a [String],
+}
+
+/// Runs `makeappx` to build the package.
+/// Returns a vector of PathBuf that shows where the package was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    "x86" => "x86",
+    "aarch64" => "arm64",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "unsupported target: {}",
+        target
+      )))
+    }
+  };
+
+  let makeappx = locate_sdk_tool("makeappx.exe")?;
+
+  let output_path = settings
+    .project_out_directory()
+    .join(MSIX_FOLDER_NAME)
+    .join(arch);
+  if output_path.exists() {
+    remove_dir_all(&output_path)?;
+  }
+  let package_dir = output_path.join("package");
+  let assets_dir = package_dir.join("Assets");
+  create_dir_all(&assets_dir)?;
+
+  let main_binary = settings
+    .binaries()
+    .iter()
+    .find(|bin| bin.main())
+    .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
+
+  for bin in settings.binaries() {
+    let bin_path = settings.binary_path(bin);
+    common::copy_file(&bin_path, &package_dir.join(bin.name()))
+      .with_context(|| format!("failed to copy {}", bin_path.display()))?;
+  }
+  settings.copy_binaries(&package_dir)?;
+  settings.copy_resources(&package_dir)?;
+
+  let logo = image::open(logo_source(settings)?)?;
+  for (name, size) in LOGOS {
+    logo
+      .resize_exact(*size, *size, FilterType::Lanczos3)
+      .save(assets_dir.join(name))?;
+  }
+
+  let msix = &settings.windows().msix;
+  let bundle_id = settings.bundle_identifier();
+  let manufacturer = bundle_id.split('.').nth(1).unwrap_or(bundle_id);
+  let description = if settings.short_description().is_empty() {
+    settings.product_name()
+  } else {
+    settings.short_description()
+  };
+
+  let mut data = BTreeMap::new();
+  data.insert(
+    "identity_name",
+    to_json(msix.identity_name.as_deref().unwrap_or(bundle_id)),
+  );
+  data.insert(
+    "publisher",
+    to_json(
+      msix
+        .publisher
+        .clone()
+        .unwrap_or_else(|| format!("CN={}", manufacturer)),
+    ),
+  );
+  data.insert(
+    "publisher_display_name",
+    to_json(
+      msix
+        .publisher_display_name
+        .as_deref()
+        .unwrap_or(manufacturer),
+    ),
+  );
+  data.insert(
+    "version",
+    to_json(super::file_version(settings.version_string())),
+  );
+  data.insert("arch", to_json(arch));
+  data.insert("product_name", to_json(settings.product_name()));
+  data.insert("description", to_json(description));
+  data.insert("main_binary", to_json(main_binary.name()));
+  data.insert(
+    "min_version",
+    to_json(msix.min_version.as_deref().unwrap_or(DEFAULT_MIN_VERSION)),
+  );
+  data.insert("capabilities", to_json(capabilities(&msix.capabilities)));
+  data.insert("file_types", to_json(file_types(settings)));
+  data.insert(
+    "deep_link_schemes",
+    to_json(
+      settings
+        .deep_link_schemes()
+        .iter()
+        .map(|scheme| scheme.to_lowercase())
+        .collect::<Vec<_>>(),
+    ),
+  );
+
+  // the default HTML escaping of handlebars is valid for XML
+  let mut handlebars = Handlebars::new();
+  handlebars
+    .register_template_string(
+      "AppxManifest.xml",
+      include_str!("templates/AppxManifest.xml"),
+    )
+    .expect("Failed to setup handlebar template");
+  write(
+    package_dir.join("AppxManifest.xml"),
+    handlebars.render("AppxManifest.xml", &data)?,
+  )?;
+
+  let package_path = settings.project_out_directory().join(format!(
+    "bundle/{}/{}.msix",
+    MSIX_FOLDER_NAME,
+    settings
+      .artifact_name(crate::PackageType::Msix, arch)
+      .unwrap_or_else(|| format!(
+        "{}_{}_{}",
+        settings.main_binary_name().replace(".exe", ""),
+        settings.version_string(),
+        arch
+      ))
+  ));
+  create_dir_all(package_path.parent().unwrap())?;
+
+  info!(action = "Running"; "makeappx to produce {}", package_path.display());
+  Command::new(makeappx)
+    .arg("pack")
+    .arg("/o")
+    .arg("/d")
+    .arg(&package_dir)
+    .arg("/p")
+    .arg(&package_path)
+    .output_ok()
+    .context("error running makeappx")?;
+
+  try_sign(settings, &package_path)?;
+
+  Ok(vec![package_path])
+}
+
+/// The largest `.png` icon, used to generate the logos of the package.
+pub fn logo_source(settings: &Settings) -> crate::Result<PathBuf> {
+  let mut largest = None;
+  for icon_path in settings.icon_files().flatten() {
+    if icon_path.extension().map_or(false, |e| e == "png") {
+      let (width, _) = image::image_dimensions(&icon_path)?;
+      if largest.as_ref().map_or(true, |(w, _)| width > *w) {
+        largest = Some((width, icon_path));
+      }
+    }
+  }
+  largest
+    .map(|(_, path)| path)
+    .ok_or_else(|| crate::Error::GenericError("the bundle config must have a `.png` icon".into()))
+}
+
+/// The file type associations, named with the lowercase characters, digits, `.`, `_` and `-` the manifest allows.
+fn file_types(settings: &Settings) -> Vec<FileType<'_>> {
+  settings
+    .file_associations()
+    .iter()
+    .map(|association| FileType {
+      name: association
+        .name()
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+          if c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-' {
+            c
+          } else {
+            '-'
+          }
+        })
+        .collect(),
+      ext: &association.ext,
+    })
+    .collect()
+}
+
+/// Declares each capability with the manifest element matching its namespace.
+/// The manifest schema requires the device capabilities to come last.
+fn capabilities(names: &[String]) -> Vec<Capability<'_>> {
+  let element = |name: &str| {
+    if BASE_CAPABILITIES.contains(&name) {
+      "Capability"
+    } else if RESTRICTED_CAPABILITIES.contains(&name) {
+      "rescap:Capability"
+    } else if DEVICE_CAPABILITIES.contains(&name) {
+      "DeviceCapability"
+    } else {
+      "uap:Capability"
+    }
+  };
+  let mut capabilities = names
+    .iter()
+    .map(|name| Capability {
+      element: element(name.as_str()),
+      name,
+    })
+    .collect::<Vec<_>>();
+  capabilities.sort_by_key(|c| c.element == "DeviceCapability");
+  capabilities
+}
@@ -0,0 +1,247 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Windows installer built with [NSIS](https://nsis.sourceforge.io).
+//!
+//! Unlike WiX, `makensis` is also available on Linux and macOS so this installer can be produced when cross compiling.
+
+use super::{sign::try_sign, FileExtension, WEBVIEW2_BOOTSTRAPPER_URL};
+use crate::bundle::{
+  common::{self, CommandExt},
+  settings::{NsisInstallMode, NsisSettings, Settings},
+};
+use anyhow::Context;
+use handlebars::{to_json, Handlebars};
+use log::info;
+use tauri_utils::config::WebviewInstallMode;
+
+use std::{
+  collections::BTreeMap,
+  fs::{create_dir_all, read_to_string, remove_dir_all, write},
+  path::PathBuf,
+  process::Command,
+};
+
+pub const NSIS_FOLDER_NAME: &str = "nsis";
+
+/// Runs `makensis` to build the installer.
+/// Returns a vector of PathBuf that shows where the installer was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    "x86" => "x86",
+    "aarch64" => "arm64",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "unsupported target: {}",
+        target
+      )))
+    }
+  };
+
+  check_makensis()?;
+
+  let icon_path = super::icon_path(settings)?;
+
+  let output_path = settings
+    .project_out_directory()
+    .join(NSIS_FOLDER_NAME)
+    .join(arch);
+  if output_path.exists() {
+    remove_dir_all(&output_path)?;
+  }
+  let files_path = output_path.join("files");
+  create_dir_all(&files_path)?;
+
+  let main_binary = settings
+    .binaries()
+    .iter()
+    .find(|bin| bin.main())
+    .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
+  try_sign(settings, &settings.binary_path(main_binary))?;
+
+  // the installer copies the content of this folder to the install directory
+  for bin in settings.binaries() {
+    let bin_path = settings.binary_path(bin);
+    common::copy_file(&bin_path, &files_path.join(bin.name()))
+      .with_context(|| format!("failed to copy {}", bin_path.display()))?;
+  }
+  settings.copy_binaries(&files_path)?;
+  settings.copy_resources(&files_path)?;
+
+  let installer_path = settings.project_out_directory().join(format!(
+    "bundle/{}/{}.exe",
+    NSIS_FOLDER_NAME,
+    settings
+      .artifact_name(crate::PackageType::Nsis, arch)
+      .unwrap_or_else(|| format!(
+        "{}_{}_{}-setup",
+        settings.main_binary_name().replace(".exe", ""),
+        settings.version_string(),
+        arch
+      ))
+  ));
+  create_dir_all(installer_path.parent().unwrap())?;
+
+  let bundle_id = settings.bundle_identifier();
+  let mut data = BTreeMap::new();
+  data.insert("product_name", to_json(escape(settings.product_name())));
+  data.insert("version", to_json(escape(settings.version_string())));
+  data.insert(
+    "file_version",
+    to_json(super::file_version(settings.version_string())),
+  );
+  data.insert(
+    "manufacturer",
+    to_json(escape(bundle_id.split('.').nth(1).unwrap_or(bundle_id))),
+  );
+  data.insert("bundle_id", to_json(escape(bundle_id)));
+  data.insert(
+    "copyright",
+    to_json(settings.copyright_string().map(escape)),
+  );
+  data.insert("main_binary", to_json(escape(main_binary.name())));
+  data.insert("icon_path", to_json(&icon_path));
+  data.insert("files_glob", to_json(files_path.join("*")));
+  data.insert("out_file", to_json(&installer_path));
+  data.insert(
+    "file_extensions",
+    to_json(
+      super::file_extensions(settings)
+        .into_iter()
+        .map(|e| FileExtension {
+          ext: escape(&e.ext),
+          prog_id: escape(&e.prog_id),
+          description: escape(&e.description),
+        })
+        .collect::<Vec<_>>(),
+    ),
+  );
+  data.insert(
+    "deep_link_schemes",
+    to_json(
+      settings
+        .deep_link_schemes()
+        .iter()
+        .map(|scheme| escape(scheme))
+        .collect::<Vec<_>>(),
+    ),
+  );
+
+  let nsis = settings.windows().nsis.clone().unwrap_or_default();
+  let cwd = std::env::current_dir()?;
+  data.insert(
+    "per_machine",
+    to_json(nsis.install_mode == NsisInstallMode::PerMachine),
+  );
+  data.insert(
+    "program_files",
+    to_json(if arch == "x86" {
+      "$PROGRAMFILES"
+    } else {
+      "$PROGRAMFILES64"
+    }),
+  );
+  for (name, path) in [
+    ("license", &nsis.license),
+    ("header_image", &nsis.header_image),
+    ("sidebar_image", &nsis.sidebar_image),
+  ] {
+    if let Some(path) = path {
+      data.insert(name, to_json(cwd.join(path)));
+    }
+  }
+  data.insert(
+    "custom_pages",
+    to_json(
+      nsis
+        .custom_pages
+        .iter()
+        .map(|p| cwd.join(p))
+        .collect::<Vec<_>>(),
+    ),
+  );
+  let languages = languages(&nsis);
+  data.insert(
+    "display_language_selector",
+    to_json(nsis.display_language_selector && languages.len() > 1),
+  );
+  data.insert("languages", to_json(languages));
+
+  // the bootstrapper is always downloaded at install time since the embedded and offline installers
+  // are only fetched by the WiX backend, which requires a Windows host
+  let (install_webview, silent) = if settings.windows().webview_fixed_runtime_path.is_some() {
+    (false, true)
+  } else {
+    match settings.windows().webview_install_mode {
+      WebviewInstallMode::Skip | WebviewInstallMode::FixedRuntime { .. } => (false, true),
+      WebviewInstallMode::DownloadBootstrapper { silent }
+      | WebviewInstallMode::EmbedBootstrapper { silent }
+      | WebviewInstallMode::OfflineInstaller { silent } => (true, silent),
+    }
+  };
+  data.insert("install_webview", to_json(install_webview));
+  data.insert(
+    "webview2_bootstrapper_url",
+    to_json(WEBVIEW2_BOOTSTRAPPER_URL),
+  );
+  data.insert(
+    "webview_installer_args",
+    to_json(if silent { "/silent" } else { "" }),
+  );
+
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(handlebars::no_escape);
+  if let Some(template) = &nsis.template {
+    let template =
+      read_to_string(template).with_context(|| format!("failed to read {}", template.display()))?;
+    handlebars
+      .register_template_string("installer.nsi", template)
+      .map_err(|e| crate::Error::GenericError(format!("invalid NSIS template: {}", e)))?;
+  } else {
+    handlebars
+      .register_template_string("installer.nsi", include_str!("templates/installer.nsi"))
+      .expect("Failed to setup handlebar template");
+  }
+  let script_path = output_path.join("installer.nsi");
+  write(&script_path, handlebars.render("installer.nsi", &data)?)?;
+
+  info!(action = "Running"; "makensis to produce {}", installer_path.display());
+  Command::new("makensis")
+    .arg("-V2")
+    .arg(&script_path)
+    .current_dir(&output_path)
+    .output_ok()
+    .context("error running makensis")?;
+
+  try_sign(settings, &installer_path)?;
+
+  Ok(vec![installer_path])
+}
+
+/// Fails if `makensis` cannot be executed.
+pub fn check_makensis() -> crate::Result<()> {
+  Command::new("makensis")
+    .arg("-VERSION")
+    .output_ok()
+    .map_err(|_| {
+      crate::Error::GenericError(
+        "makensis was not found, install NSIS (e.g. the `nsis` package of your distribution or `brew install makensis`) to bundle the Windows installer".into(),
+      )
+    })?;
+  Ok(())
+}
+
+/// The configured installer languages, defaulting to English.
+fn languages(nsis: &NsisSettings) -> Vec<String> {
+  match &nsis.languages {
+    Some(languages) if !languages.is_empty() => languages.clone(),
+    _ => vec!["English".into()],
+  }
+}
+
+/// Escapes a value used in a quoted NSIS string.
+fn escape(value: &str) -> String {
+  value.replace('$', "$$").replace('"', "$\\\"")
+}
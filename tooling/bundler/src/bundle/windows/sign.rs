@@ -3,18 +3,26 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::bundle::common::CommandExt;
-use bitness::{self, Bitness};
-use log::{debug, info};
-use std::{
-  path::{Path, PathBuf},
-  process::Command,
+use crate::bundle::{
+  common::{self, CommandExt},
+  settings::{Settings, WindowsSignMethod},
 };
+use anyhow::Context;
+#[cfg(target_os = "windows")]
+use bitness::{self, Bitness};
+#[cfg(target_os = "windows")]
+use log::debug;
+use log::info;
+#[cfg(target_os = "windows")]
+use std::path::PathBuf;
+use std::{path::Path, process::Command};
+#[cfg(target_os = "windows")]
 use winreg::{
   enums::{HKEY_LOCAL_MACHINE, KEY_READ, KEY_WOW64_32KEY},
   RegKey,
 };
 
+#[cfg(target_os = "windows")]
 pub struct SignParams {
   pub product_name: String,
   pub digest_algorithm: String,
@@ -23,8 +31,132 @@ pub struct SignParams {
   pub tsp: bool,
 }
 
+/// Signs the file with the configured [`WindowsSignMethod`], or with signtool and the certificate thumbprint.
+///
+/// Signing with a certificate thumbprint is only possible on Windows hosts.
+pub fn try_sign(settings: &Settings, path: &Path) -> crate::Result<()> {
+  let windows = settings.windows();
+  let digest_algorithm = windows.digest_algorithm.as_deref().unwrap_or("sha256");
+
+  if let Some(method) = &windows.sign {
+    match method {
+      WindowsSignMethod::AzureKeyVault {
+        vault_url,
+        certificate_name,
+        client_id,
+        tenant_id,
+      } => {
+        info!(action = "Signing"; "{} with Azure Key Vault certificate \"{}\"", path.display(), certificate_name);
+        let mut cmd = Command::new("azuresigntool");
+        cmd
+          .arg("sign")
+          .args(["-kvu", vault_url])
+          .args(["-kvc", certificate_name]);
+        if let Some(client_id) = env_or("AZURE_CLIENT_ID", client_id) {
+          cmd.arg("-kvi").arg(client_id);
+        }
+        if let Some(tenant_id) = env_or("AZURE_TENANT_ID", tenant_id) {
+          cmd.arg("-kvt").arg(tenant_id);
+        }
+        match std::env::var_os("AZURE_CLIENT_SECRET") {
+          Some(client_secret) => cmd.arg("-kvs").arg(client_secret),
+          // authenticate with the managed identity of the CI machine
+          None => cmd.arg("-kvm"),
+        };
+        cmd
+          .args(["-fd", digest_algorithm])
+          .args(["-d", settings.product_name()]);
+        if let Some(timestamp_url) = &windows.timestamp_url {
+          if windows.tsp {
+            cmd
+              .args(["-tr", timestamp_url])
+              .args(["-td", digest_algorithm]);
+          } else {
+            cmd.args(["-t", timestamp_url]);
+          }
+        }
+        cmd
+          .arg(path)
+          .output_ok()
+          .with_context(|| format!("failed to sign {}", path.display()))?;
+      }
+      WindowsSignMethod::Command { command, args } => {
+        common::run_sign_command(command, args, path)?;
+      }
+    }
+  } else if let Some(certificate_thumbprint) = &windows.certificate_thumbprint {
+    #[cfg(target_os = "windows")]
+    sign(
+      path,
+      &SignParams {
+        product_name: settings.product_name().into(),
+        digest_algorithm: digest_algorithm.into(),
+        certificate_thumbprint: certificate_thumbprint.to_string(),
+        timestamp_url: windows.timestamp_url.clone(),
+        tsp: windows.tsp,
+      },
+    )?;
+    #[cfg(not(target_os = "windows"))]
+    log::warn!(
+      "Skipping the signature of {} with certificate {}, signing requires a Windows host",
+      path.display(),
+      certificate_thumbprint
+    );
+  }
+  Ok(())
+}
+
+/// Fails if the tool of the configured [`WindowsSignMethod`] cannot be executed.
+pub fn check_sign_method(settings: &Settings) -> crate::Result<()> {
+  match &settings.windows().sign {
+    Some(WindowsSignMethod::AzureKeyVault { .. }) => {
+      // only spawning matters, AzureSignTool exits with an error without a command
+      Command::new("azuresigntool")
+        .arg("--help")
+        .output()
+        .map_err(|_| {
+          crate::Error::GenericError(
+            "AzureSignTool was not found, install it with `dotnet tool install --global AzureSignTool`".into(),
+          )
+        })?;
+    }
+    Some(WindowsSignMethod::Command { command, .. }) if command.is_empty() => {
+      return Err(crate::Error::GenericError(
+        "the sign command must not be empty".into(),
+      ));
+    }
+    #[cfg(target_os = "windows")]
+    None if settings.windows().certificate_thumbprint.is_some() => {
+      locate_signtool()?;
+    }
+    _ => {}
+  }
+  Ok(())
+}
+
+fn env_or(name: &str, value: &Option<String>) -> Option<String> {
+  std::env::var(name).ok().or_else(|| value.clone())
+}
+
+#[cfg(target_os = "windows")]
+pub fn locate_signtool() -> crate::Result<PathBuf> {
+  find_sdk_tool("signtool.exe")?.ok_or(crate::Error::SignToolNotFound)
+}
+
+/// Locates a tool of the Windows SDK, e.g. `makeappx.exe`.
+#[cfg(target_os = "windows")]
+pub fn locate_sdk_tool(name: &str) -> crate::Result<PathBuf> {
+  find_sdk_tool(name)?.ok_or_else(|| {
+    crate::Error::GenericError(format!(
+      "{} not found, install the Windows 10 SDK to provide it",
+      name
+    ))
+  })
+}
+
 // sign code forked from https://github.com/forbjok/rust-codesign
-fn locate_signtool() -> crate::Result<PathBuf> {
+#[cfg(target_os = "windows")]
+fn find_sdk_tool(name: &str) -> crate::Result<Option<PathBuf>> {
   const INSTALLED_ROOTS_REGKEY_PATH: &str = r"SOFTWARE\Microsoft\Windows Kits\Installed Roots";
   const KITS_ROOT_REGVALUE_NAME: &str = r"KitsRoot10";
 
@@ -68,28 +200,29 @@ fn locate_signtool() -> crate::Result<PathBuf> {
   For Windows SDK 10 versions earlier than v10.0.15063.468, signtool will be located there. */
   kit_bin_paths.push(kits_root_10_bin_path);
 
-  // Choose which version of SignTool to use based on OS bitness
+  // Choose which version of the tool to use based on OS bitness
   let arch_dir = match bitness::os_bitness().expect("failed to get os bitness") {
     Bitness::X86_32 => "x86",
     Bitness::X86_64 => "x64",
     _ => return Err(crate::Error::UnsupportedBitness),
   };
 
-  /* Iterate through all bin paths, checking for existence of a SignTool executable. */
+  /* Iterate through all bin paths, checking for existence of the tool executable. */
   for kit_bin_path in &kit_bin_paths {
-    /* Construct SignTool path. */
-    let signtool_path = kit_bin_path.join(arch_dir).join("signtool.exe");
+    /* Construct the tool path. */
+    let tool_path = kit_bin_path.join(arch_dir).join(name);
 
-    /* Check if SignTool exists at this location. */
-    if signtool_path.exists() {
-      // SignTool found. Return it.
-      return Ok(signtool_path);
+    /* Check if the tool exists at this location. */
+    if tool_path.exists() {
+      // Tool found. Return it.
+      return Ok(Some(tool_path));
     }
   }
 
-  Err(crate::Error::SignToolNotFound)
+  Ok(None)
 }
 
+#[cfg(target_os = "windows")]
 pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
   // Convert path to string reference, as we need to pass it as a command-line parameter to signtool
   let path_str = path.as_ref().to_str().unwrap();
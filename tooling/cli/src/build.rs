@@ -5,11 +5,17 @@
 use crate::{
   helpers::{
     app_paths::{app_dir, tauri_dir},
-    command_env,
-    config::{get as get_config, AppUrl, HookCommand, WindowUrl, MERGE_CONFIG_EXTENSION_NAME},
-    updater_signature::{read_key_from_file, secret_key as updater_secret_key, sign_file},
+    config::{
+      apply_binary_config, get as get_config, merge_config_args, reload as reload_config, AppUrl,
+      Config, WindowUrl, MERGE_CONFIG_EXTENSION_NAME,
+    },
+    env_file,
+    hook::{run_hook, HookContext},
+    updater_signature::{
+      read_key_from_file, secret_key as updater_secret_key, verify_file, Signer,
+    },
   },
-  interface::{AppInterface, AppSettings, Interface},
+  interface::{manifest::check_manifest, AppInterface, Interface},
   CommandExt, Result,
 };
 use anyhow::{bail, Context};
@@ -20,108 +26,297 @@ use std::{
   env::{set_current_dir, var_os},
   path::{Path, PathBuf},
   process::Command,
+  time::Instant,
 };
-use tauri_bundler::bundle::{bundle_project, Bundle, PackageType};
+use tauri_bundler::bundle::{
+  bundle_project, check_prerequisites, check_resources, Bundle, PackageType, Settings,
+};
+
+mod artifacts;
+mod checks;
+mod checksum;
+mod delta;
+mod feature_matrix;
+mod sbom;
+mod sidecars;
+mod strip;
+mod timings;
+mod updater_manifest;
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Tauri build")]
 pub struct Options {
-  /// Binary to use to build the application, defaults to `cargo`
+  /// Binary to use to build the application, defaults to `cargo`. Can also be a full build command such as `cargo zigbuild`, prefixed by `KEY=VALUE` environment variables
   #[clap(short, long)]
   pub runner: Option<String>,
   /// Builds with the debug flag
   #[clap(short, long)]
   pub debug: bool,
-  /// Target triple to build against.
+  /// Builds with the given cargo profile instead of `release`, e.g. a `dist` or `release-lto` profile defined in Cargo.toml
+  #[clap(long, value_name = "PROFILE", conflicts_with = "debug")]
+  pub profile: Option<String>,
+  /// Target triple to build against. Use a comma separated list or repeat the flag to build for several targets, each bundled in its own `target/<triple>` folder.
   ///
   /// It must be one of the values outputted by `$rustc --print target-list` or `universal-apple-darwin` for an universal macOS application.
   ///
   /// Note that compiling an universal macOS application requires both `aarch64-apple-darwin` and `x86_64-apple-darwin` targets to be installed.
-  #[clap(short, long)]
-  pub target: Option<String>,
+  #[clap(short, long, multiple_occurrences(true), use_value_delimiter(true))]
+  pub target: Vec<String>,
   /// Space or comma separated list of features to activate
   #[clap(short, long, multiple_occurrences(true), multiple_values(true))]
   pub features: Option<Vec<String>>,
-  /// Space or comma separated list of bundles to package.
+  /// The `[[bin]]` target bundled as the main app binary of an app with several binaries, instead of the default one.
+  /// Its `build > binaries` features and windows are applied
+  #[clap(long, value_name = "NAME")]
+  pub bin: Option<String>,
+  /// Build and bundle the given cargo example as the app, e.g. a demo app in the `examples` folder of a plugin or library crate
+  #[clap(long, value_name = "NAME", conflicts_with = "bin")]
+  pub example: Option<String>,
+  /// Space or comma separated list of bundles to package, e.g. `--bundles deb,appimage`. Overrides `tauri > bundle > targets`.
   ///
-  /// Each bundle must be one of `deb`, `appimage`, `msi`, `app` or `dmg` on MacOS and `updater` on all platforms.
+  /// Each bundle must be one of `deb`, `rpm`, `flatpak`, `appimage`, `msi`, `nsis`, `msix`, `app` or `dmg` on MacOS and `updater` on all platforms.
+  /// The `nsis` installer can also be bundled on Linux and macOS when building for a Windows target.
+  /// Bundles that are not supported on the target are skipped with a warning.
   /// If `none` is specified, the bundler will be skipped.
   ///
   /// Note that the `updater` bundle is not automatically added so you must specify it if the updater is enabled.
   #[clap(short, long, multiple_occurrences(true), multiple_values(true))]
   pub bundles: Option<Vec<String>>,
-  /// JSON string or path to JSON file to merge with tauri.conf.json
-  #[clap(short, long)]
-  pub config: Option<String>,
+  /// Compile the app without bundling it, printing the path of the app binary as the last line of the standard output
+  #[clap(long, conflicts_with = "bundles")]
+  pub no_bundle: bool,
+  /// JSON string or path to a JSON, JSON5 or TOML file to merge with tauri.conf.json. Can be used multiple times, the later values take precedence
+  #[clap(short, long, multiple_occurrences(true))]
+  pub config: Vec<String>,
+  /// Skip the given pre-flight release check. Can be used multiple times.
+  ///
+  /// Available checks: `devtools`, `dangerous-settings`, `csp`, `updater-pubkey` and `signing`.
+  #[clap(long = "skip-check", multiple_occurrences(true))]
+  pub skip_checks: Vec<String>,
+  /// Load environment variables from the given file, in addition to the `.env` and `.env.local` files of the app directory. Can be used multiple times.
+  ///
+  /// Variables already set on the environment are not overridden.
+  #[clap(long = "env-file", multiple_occurrences(true))]
+  pub env_files: Vec<PathBuf>,
+  /// Skip prompting for values. Missing rustup targets are installed automatically
+  #[clap(long)]
+  pub ci: bool,
+  /// Compile the app through sccache by setting `RUSTC_WRAPPER`, printing the cache statistics after the build. Overrides `build > useSccache`
+  #[clap(long)]
+  pub use_sccache: bool,
+  /// Make the build reproducible: sets `SOURCE_DATE_EPOCH` to the last commit time (unless already set), remaps the local paths with `--remap-path-prefix` and normalizes the timestamps of the deb and updater archives
+  #[clap(long)]
+  pub reproducible: bool,
+  /// Build and bundle the app once for each feature set of `build > featureMatrix`, appending the set name to the bundle file names
+  #[clap(long)]
+  pub feature_matrix: bool,
+  /// Number of feature sets built at once with `--feature-matrix`. Each parallel build uses its own `target/feature-matrix/<name>` directory
+  #[clap(long, value_name = "N", requires = "feature-matrix")]
+  pub matrix_jobs: Option<usize>,
+  /// Write a checksum file next to each bundle, e.g. `app_1.0.0_amd64.deb.sha256`, in the format of `sha256sum`
+  #[clap(
+    long,
+    value_name = "ALGORITHM",
+    value_parser(clap::builder::PossibleValuesParser::new(checksum::ALGORITHMS))
+  )]
+  pub checksum: Option<String>,
+  /// Write a software bill of materials of the compiled crates and bundled resources to the bundle directory, in the CycloneDX or SPDX JSON format
+  #[clap(
+    long,
+    value_name = "FORMAT",
+    value_parser(clap::builder::PossibleValuesParser::new(sbom::FORMATS))
+  )]
+  pub sbom: Option<String>,
+  /// Directory of the previous release with its updater archives and `latest.json` manifest.
+  /// A delta updater package is written next to each updater archive of the previous release found in the directory, and listed in the updater manifest
+  #[clap(long, value_name = "DIR")]
+  pub delta_from: Option<PathBuf>,
+  /// Write the duration of each build phase to the given path as JSON, in addition to the summary printed after the build
+  #[clap(long, value_name = "PATH")]
+  pub timings: Option<PathBuf>,
+  /// Print the changes to the features of the `tauri` and `tauri-build` dependencies that would be made to Cargo.toml, as a unified diff, and exit without building the app
+  #[clap(long)]
+  pub dry_run: bool,
+  /// Fail instead of rewriting Cargo.toml when the features of the `tauri` and `tauri-build` dependencies don't match the configuration
+  #[clap(long)]
+  pub frozen_manifest: bool,
   /// Command line arguments passed to the runner
   pub args: Vec<String>,
 }
 
 pub fn command(mut options: Options) -> Result<()> {
-  let mut interface = setup(&mut options)?;
+  timings::start();
+  let mut interface = setup(&mut options, false)?;
 
-  let config = get_config(options.config.as_deref())?;
-  let config_guard = config.lock().unwrap();
-  let config_ = config_guard.as_ref().unwrap();
+  // the configuration is not kept locked: the interface reads it while building
+  let config = get_config(!options.debug, options.config.first().map(String::as_str))?;
+  let config_ = &Config::clone(config.lock().unwrap().as_ref().unwrap());
 
-  let app_settings = interface.app_settings();
-  let interface_options = options.clone().into();
+  if options.feature_matrix {
+    feature_matrix::run(&mut interface, &options, config_)?;
+  } else {
+    build_targets(&mut interface, &options, config_, None)?;
+  }
 
-  let bin_path = app_settings.app_binary_path(&interface_options)?;
-  let out_dir = bin_path.parent().unwrap();
+  let version = interface.app_settings().get_package_settings().version;
+  if let Some(manifest) = &config_.tauri.updater.manifest {
+    if !updater_manifest::is_empty() {
+      let manifest_path = interface
+        .app_settings()
+        .target_dir(&options.clone().into())?
+        .join("latest.json");
+      updater_manifest::write(&manifest_path, manifest, &version)?;
+      info!(action = "Finished"; "updater manifest at {}", manifest_path.display());
+      artifacts::record(artifacts::Artifact {
+        path: manifest_path,
+        kind: "updaterManifest".into(),
+        target: None,
+        feature_set: None,
+      });
+    }
+  }
 
-  interface.build(interface_options)?;
+  if let Some(after_bundle) = config_.build.after_bundle_command.clone() {
+    if !artifacts::is_empty() {
+      let artifacts_path = interface
+        .app_settings()
+        .target_dir(&options.clone().into())?
+        .join("bundle-artifacts.json");
+      artifacts::write(&artifacts_path)?;
+      let context = HookContext {
+        version: Some(version),
+        artifacts_path: Some(artifacts_path),
+        ..Default::default()
+      };
+      timings::measure("afterBundleCommand", || {
+        run_hook("afterBundleCommand", after_bundle, options.debug, &context)
+      })?;
+    }
+  }
 
-  let app_settings = interface.app_settings();
+  timings::report(options.timings.as_deref())
+}
 
-  if config_.tauri.bundle.active {
-    let package_types = if let Some(names) = &options.bundles {
-      let mut types = vec![];
-      for name in names
-        .iter()
-        .flat_map(|n| n.split(',').map(|s| s.to_string()).collect::<Vec<String>>())
-      {
-        if name == "none" {
-          break;
-        }
-        match PackageType::from_short_name(&name) {
-          Some(package_type) => {
-            types.push(package_type);
-          }
-          None => {
-            return Err(anyhow::anyhow!(format!(
-              "Unsupported bundle format: {}",
-              name
-            )));
-          }
-        }
-      }
-      Some(types)
-    } else {
-      let targets = config_.tauri.bundle.targets.to_vec();
-      if targets.is_empty() {
-        None
-      } else {
-        Some(targets.into_iter().map(Into::into).collect())
-      }
+/// Runs the release checks, then builds and bundles the app for each target.
+///
+/// The bundles are renamed after the feature set, if any.
+fn build_targets(
+  interface: &mut AppInterface,
+  options: &Options,
+  config_: &Config,
+  feature_set: Option<&str>,
+) -> Result<()> {
+  if !options.debug {
+    checks::run(
+      &checks::CheckContext {
+        config: config_,
+        manifest: interface.app_settings().manifest(),
+        features: options.features.as_deref().unwrap_or_default(),
+      },
+      &options.skip_checks,
+    )?;
+  }
+
+  let targets = if options.target.is_empty() {
+    vec![None]
+  } else {
+    options.target.iter().cloned().map(Some).collect()
+  };
+  let multiple_targets = targets.len() > 1;
+  for target in targets {
+    if multiple_targets {
+      info!(action = "Building"; "target {}", target.as_deref().unwrap_or_default());
+    }
+    let mut target_options = options.clone();
+    target_options.target = target.into_iter().collect();
+    build_target(interface, target_options, config_, feature_set)?;
+  }
+
+  Ok(())
+}
+
+/// Builds and bundles the app for a single target triple (or the host when `options.target` is empty).
+fn build_target(
+  interface: &mut AppInterface,
+  options: Options,
+  config_: &Config,
+  feature_set: Option<&str>,
+) -> Result<()> {
+  // the phases of each target and feature set are reported separately
+  let phase_suffix = match (options.target.first().map(String::as_str), feature_set) {
+    (Some(target), Some(set)) => format!(" ({}, {})", target, set),
+    (Some(label), None) | (None, Some(label)) => format!(" ({})", label),
+    (None, None) => String::new(),
+  };
+
+  let package_types = package_types(&options, config_)?;
+  let bundle = !options.no_bundle
+    && config_.tauri.bundle.active
+    && package_types.as_ref().map_or(true, |p| !p.is_empty());
+
+  // tauri-build copies the external binaries, so they must be downloaded before compiling the app
+  if !config_.tauri.bundle.external_bin_sources.is_empty() {
+    let interface_options: crate::interface::Options = options.clone().into();
+    let target_triple = match &interface_options.target {
+      Some(target) => crate::interface::rust::target_dir_name(target).to_string(),
+      None => tauri_utils::platform::target_triple()?,
     };
+    let target_dir = interface.app_settings().target_dir(&interface_options)?;
+    sidecars::download(&config_.tauri.bundle, &target_triple, &target_dir)?;
+  }
 
+  // a missing icon or installer tool would otherwise only be reported after compiling the app
+  if bundle {
+    let app_settings = interface.app_settings();
+    let interface_options = options.clone().into();
+    let out_dir = app_settings.out_dir(&interface_options)?;
+    let settings = app_settings
+      .get_bundler_settings(&interface_options, config_, &out_dir, package_types.clone())
+      .with_context(|| "failed to build bundler settings")?;
+    check_prerequisites(&settings)?;
+    check_updater_private_key(config_, &settings)?;
+  }
+
+  let bin_path = timings::measure(format!("cargo build{}", phase_suffix), || {
+    interface.build(options.clone().into())
+  })?;
+  let out_dir = bin_path.parent().unwrap();
+
+  if config_.tauri.bundle.optimize.strip && !options.debug {
+    timings::measure(format!("strip{}", phase_suffix), || strip::run(&bin_path))?;
+  }
+
+  // logs are written to stderr, so scripts can read the path from the last line of stdout
+  if options.no_bundle {
+    println!("{}", bin_path.display());
+  }
+
+  let app_settings = interface.app_settings();
+
+  if bundle {
     if let Some(types) = &package_types {
       if config_.tauri.updater.active && !types.contains(&PackageType::Updater) {
         warn!("The updater is enabled but the bundle target list does not contain `updater`, so the updater artifacts won't be generated.");
       }
     }
 
-    // if we have a package to bundle, let's run the `before_bundle_command`.
-    if package_types.as_ref().map_or(true, |p| !p.is_empty()) {
-      if let Some(before_bundle) = config_.build.before_bundle_command.clone() {
-        run_hook("beforeBundleCommand", before_bundle, options.debug)?;
-      }
+    if let Some(before_bundle) = config_.build.before_bundle_command.clone() {
+      let context = resolve_hook_context(app_settings, &options, Some(out_dir))?;
+      timings::measure(format!("beforeBundleCommand{}", phase_suffix), || {
+        run_hook(
+          "beforeBundleCommand",
+          before_bundle,
+          options.debug,
+          &context,
+        )
+      })?;
     }
 
     let settings = app_settings
-      .get_bundler_settings(&options.into(), config_, out_dir, package_types)
+      .get_bundler_settings(&options.clone().into(), config_, out_dir, package_types)
       .with_context(|| "failed to build bundler settings")?;
+    // resources may be created by the app build or the beforeBundleCommand
+    check_resources(&settings)?;
 
     // set env vars used by the bundler
     #[cfg(target_os = "linux")]
@@ -134,36 +329,94 @@ pub fn command(mut options: Options) -> Result<()> {
         std::env::set_var("APPIMAGE_BUNDLE_XDG_OPEN", "1");
       }
       if config_.tauri.system_tray.is_some() {
-        if let Ok(tray) = std::env::var("TAURI_TRAY") {
-          std::env::set_var(
-            "TRAY_LIBRARY_PATH",
-            if tray == "ayatana" {
-              format!(
-                "{}/libayatana-appindicator3.so.1",
-                pkgconfig_utils::get_library_path("ayatana-appindicator3-0.1")
-                  .expect("failed to get ayatana-appindicator library path using pkg-config.")
-              )
-            } else {
-              format!(
-                "{}/libappindicator3.so.1",
-                pkgconfig_utils::get_library_path("appindicator3-0.1")
-                  .expect("failed to get libappindicator-gtk library path using pkg-config.")
-              )
-            },
-          );
-        } else {
-          std::env::set_var(
-            "TRAY_LIBRARY_PATH",
-            pkgconfig_utils::get_appindicator_library_path(),
-          );
-        }
+        use crate::{helpers::config::DebTrayDependency, interface::rust::deb_tray_dependency};
+        std::env::set_var(
+          "TRAY_LIBRARY_PATH",
+          match deb_tray_dependency(&config_.tauri.bundle.deb) {
+            DebTrayDependency::Ayatana => format!(
+              "{}/libayatana-appindicator3.so.1",
+              pkgconfig_utils::get_library_path("ayatana-appindicator3-0.1")
+                .expect("failed to get ayatana-appindicator library path using pkg-config.")
+            )
+            .into(),
+            DebTrayDependency::Appindicator => format!(
+              "{}/libappindicator3.so.1",
+              pkgconfig_utils::get_library_path("appindicator3-0.1")
+                .expect("failed to get libappindicator-gtk library path using pkg-config.")
+            )
+            .into(),
+            DebTrayDependency::Either => pkgconfig_utils::get_appindicator_library_path(),
+          },
+        );
       }
       if config_.tauri.bundle.appimage.bundle_media_framework {
         std::env::set_var("APPIMAGE_BUNDLE_GSTREAMER", "1");
       }
     }
 
-    let bundles = bundle_project(settings).with_context(|| "failed to bundle project")?;
+    let target = settings.target().to_string();
+    let resources = if options.sbom.is_some() {
+      settings
+        .resources()
+        .collect::<std::result::Result<Vec<_>, _>>()?
+    } else {
+      Vec::new()
+    };
+    let mut bundles = bundle_project(settings).with_context(|| "failed to bundle project")?;
+    for bundle in &bundles {
+      timings::record(
+        format!(
+          "bundle {}{}",
+          bundle.package_type.short_name(),
+          phase_suffix
+        ),
+        bundle.duration,
+      );
+    }
+    if let Some(name) = feature_set {
+      bundles = feature_matrix::rename_bundles(bundles, name)?;
+    }
+    let record_artifact = |path: &Path, kind: &str| {
+      artifacts::record(artifacts::Artifact {
+        path: path.to_path_buf(),
+        kind: kind.into(),
+        target: Some(target.clone()),
+        feature_set: feature_set.map(Into::into),
+      })
+    };
+    for bundle in &bundles {
+      for path in &bundle.bundle_paths {
+        record_artifact(path, bundle.package_type.short_name());
+      }
+    }
+
+    let mut sbom_path = None;
+    if let Some(format) = &options.sbom {
+      let extension = if format == "spdx" { "spdx" } else { "cdx" };
+      let path = out_dir.join("bundle").join(match feature_set {
+        Some(name) => format!("sbom_{}.{}.json", name, extension),
+        None => format!("sbom.{}.json", extension),
+      });
+      timings::measure(format!("sbom{}", phase_suffix), || {
+        sbom::write(&path, format, &target, &options, &resources)
+      })?;
+      info!(action = "Finished"; "software bill of materials at {}", path.display());
+      record_artifact(&path, "sbom");
+      sbom_path = Some(path);
+    }
+
+    if let Some(algorithm) = &options.checksum {
+      // the app bundles of macOS are directories
+      for path in bundles
+        .iter()
+        .flat_map(|bundle| &bundle.bundle_paths)
+        .chain(&sbom_path)
+        .filter(|path| path.is_file())
+      {
+        let checksum_path = checksum::write(path, algorithm)?;
+        record_artifact(&checksum_path, "checksum");
+      }
+    }
 
     let updater_bundles: Vec<&Bundle> = bundles
       .iter()
@@ -171,24 +424,14 @@ pub fn command(mut options: Options) -> Result<()> {
       .collect();
     // If updater is active and we bundled it
     if config_.tauri.updater.active && !updater_bundles.is_empty() {
-      // if no password provided we use an empty string
-      let password = var_os("TAURI_KEY_PASSWORD").map(|v| v.to_str().unwrap().to_string());
-      // get the private key
-      let secret_key = if let Some(mut private_key) =
-        var_os("TAURI_PRIVATE_KEY").map(|v| v.to_str().unwrap().to_string())
-      {
-        // check if env var points to a file..
-        let pk_dir = Path::new(&private_key);
-        // Check if user provided a path or a key
-        // We validate if the path exist or not.
-        if pk_dir.exists() {
-          // read file content and use it as private key
-          private_key = read_key_from_file(pk_dir)?;
-        }
-        updater_secret_key(private_key, password)
-      } else {
-        Err(anyhow::anyhow!("A public key has been found, but no private key. Make sure to set `TAURI_PRIVATE_KEY` environment variable."))
-      }?;
+      let signing_started_at = Instant::now();
+      let signer = updater_signer_from_env()?;
+      let version = app_settings.get_package_settings().version;
+      let previous_version = options
+        .delta_from
+        .as_deref()
+        .map(delta::previous_version)
+        .transpose()?;
 
       let pubkey = base64::decode(&config_.tauri.updater.pubkey)?;
       let pub_key_decoded = String::from_utf8_lossy(&pubkey);
@@ -201,16 +444,43 @@ pub fn command(mut options: Options) -> Result<()> {
         // another type of updater package who require multiple file signature
         for path in elem.bundle_paths.iter() {
           // sign our path from environment variables
-          let (signature_path, signature) = sign_file(&secret_key, path)?;
-          if signature.keynum() != public_key.keynum() {
-            return Err(anyhow::anyhow!(
-              "The updater secret key from `TAURI_PRIVATE_KEY` does not match the public key defined in `tauri.conf.json > tauri > updater > pubkey`."
-            ));
+          let signature_path = sign_updater_file(&signer, &public_key, path)?;
+          record_artifact(&signature_path, "updaterSignature");
+          // the manifest cannot tell the archives of the feature sets apart
+          let manifest = match (&config_.tauri.updater.manifest, feature_set) {
+            (Some(manifest), None) => {
+              updater_manifest::record(manifest, &version, &target, path, &signature_path)?
+                .then(|| manifest)
+            }
+            _ => None,
+          };
+          signed_paths.push(signature_path);
+
+          if let (Some(dir), Some(previous_version)) = (&options.delta_from, &previous_version) {
+            if let Some(delta_path) = delta::write(path, dir, &version, previous_version)? {
+              let signature_path = sign_updater_file(&signer, &public_key, &delta_path)?;
+              record_artifact(&delta_path, "updaterDelta");
+              record_artifact(&signature_path, "updaterSignature");
+              if let Some(manifest) = manifest {
+                updater_manifest::record_delta(
+                  manifest,
+                  &version,
+                  &target,
+                  previous_version,
+                  &delta_path,
+                  &signature_path,
+                )?;
+              }
+              signed_paths.push(signature_path);
+            }
           }
-          signed_paths.append(&mut vec![signature_path]);
         }
       }
 
+      timings::record(
+        format!("updater signing{}", phase_suffix),
+        signing_started_at.elapsed(),
+      );
       print_signed_updater_archive(&signed_paths)?;
     }
   }
@@ -218,32 +488,151 @@ pub fn command(mut options: Options) -> Result<()> {
   Ok(())
 }
 
-pub fn setup(options: &mut Options) -> Result<AppInterface> {
-  let (merge_config, merge_config_path) = if let Some(config) = &options.config {
-    if config.starts_with('{') {
-      (Some(config.to_string()), None)
-    } else {
-      (
-        Some(
-          std::fs::read_to_string(&config)
-            .with_context(|| "failed to read custom configuration")?,
-        ),
-        Some(config.clone()),
+/// Signs an updater file, checking the signature against the public key of the updater config.
+fn sign_updater_file(
+  signer: &Signer,
+  public_key: &minisign::PublicKey,
+  path: &Path,
+) -> Result<PathBuf> {
+  let (signature_path, signature) = signer.sign(path)?;
+  match signer {
+    Signer::SecretKey(_) if signature.keynum() != public_key.keynum() => {
+      return Err(anyhow::anyhow!(
+        "The updater secret key from `TAURI_PRIVATE_KEY` does not match the public key defined in `tauri.conf.json > tauri > updater > pubkey`."
+      ));
+    }
+    // the agent output is not trusted, it must be a valid signature of the file
+    Signer::Agent(_) => verify_file(public_key, &signature, path).with_context(|| {
+      format!(
+        "The signature from `TAURI_SIGNING_AGENT` of {} cannot be verified with the public key defined in `tauri.conf.json > tauri > updater > pubkey`.",
+        path.display()
       )
+    })?,
+    _ => (),
+  }
+  Ok(signature_path)
+}
+
+/// The signing agent from `TAURI_SIGNING_AGENT`, or else the private key from the environment.
+fn updater_signer_from_env() -> Result<Signer> {
+  match var_os("TAURI_SIGNING_AGENT").filter(|agent| !agent.is_empty()) {
+    Some(agent) => Ok(Signer::Agent(agent.to_string_lossy().into_owned())),
+    None => updater_secret_key_from_env().map(Signer::SecretKey),
+  }
+}
+
+/// Reads the updater private key from `TAURI_PRIVATE_KEY`, decrypted with `TAURI_KEY_PASSWORD`.
+fn updater_secret_key_from_env() -> Result<minisign::SecretKey> {
+  // if no password provided we use an empty string
+  let password = var_os("TAURI_KEY_PASSWORD").map(|v| v.to_str().unwrap().to_string());
+  // get the private key
+  if let Some(mut private_key) =
+    var_os("TAURI_PRIVATE_KEY").map(|v| v.to_str().unwrap().to_string())
+  {
+    // check if env var points to a file..
+    let pk_dir = Path::new(&private_key);
+    // Check if user provided a path or a key
+    // We validate if the path exist or not.
+    if pk_dir.exists() {
+      // read file content and use it as private key
+      private_key = read_key_from_file(pk_dir)?;
     }
+    updater_secret_key(private_key, password)
   } else {
-    (None, None)
-  };
-  options.config = merge_config;
+    Err(anyhow::anyhow!("A public key has been found, but no private key. Make sure to set the `TAURI_PRIVATE_KEY` or `TAURI_SIGNING_AGENT` environment variable."))
+  }
+}
+
+/// Fails if the updater bundle will be generated but cannot be signed.
+fn check_updater_private_key(config_: &Config, settings: &Settings) -> Result<()> {
+  if config_.tauri.updater.active && settings.package_types()?.contains(&PackageType::Updater) {
+    updater_signer_from_env().with_context(|| "the updater bundle cannot be signed")?;
+  }
+  Ok(())
+}
+
+/// Parses the `--bundles` option, falling back to `tauri > bundle > targets`.
+pub fn package_types(options: &Options, config_: &Config) -> Result<Option<Vec<PackageType>>> {
+  if let Some(names) = &options.bundles {
+    let mut types = vec![];
+    for name in names
+      .iter()
+      .flat_map(|n| n.split(','))
+      .map(str::trim)
+      .filter(|n| !n.is_empty())
+    {
+      if name == "none" {
+        break;
+      }
+      match PackageType::from_short_name(name) {
+        Some(package_type) => {
+          if !types.contains(&package_type) {
+            types.push(package_type);
+          }
+        }
+        None => {
+          return Err(anyhow::anyhow!(format!(
+            "Unsupported bundle format: {} (possible values: {})",
+            name,
+            PackageType::all()
+              .iter()
+              .map(|t| t.short_name())
+              .collect::<Vec<_>>()
+              .join(", ")
+          )));
+        }
+      }
+    }
+    Ok(Some(types))
+  } else {
+    let targets = config_.tauri.bundle.targets.to_vec();
+    if targets.is_empty() {
+      Ok(None)
+    } else {
+      Ok(Some(targets.into_iter().map(Into::into).collect()))
+    }
+  }
+}
+
+/// Loads the configuration and runs the `beforeBuildCommand`.
+///
+/// Mobile builds compile several targets at once, so their hooks only receive the profile and app version.
+pub fn setup(options: &mut Options, mobile: bool) -> Result<AppInterface> {
+  options.ci = options.ci || var_os("CI").is_some();
+  env_file::load(&options.env_files)?;
+
+  let (merge_config, merge_config_path) = read_merge_config(&options.config)?;
+  // the options forwarded to the interface hold the merged configuration
+  options.config = merge_config.into_iter().collect();
 
   let tauri_path = tauri_dir();
   set_current_dir(&tauri_path).with_context(|| "failed to change current working directory")?;
 
-  let config = get_config(options.config.as_deref())?;
+  if options.reproducible {
+    set_source_date_epoch()?;
+  }
+
+  let mut config = get_config(!options.debug, options.config.first().map(String::as_str))?;
+  if let Some(bin) = options.bin.clone() {
+    let reload = apply_binary_config(
+      config.lock().unwrap().as_ref().unwrap(),
+      &bin,
+      &mut options.features,
+      &mut options.config,
+    )?;
+    if reload {
+      config = reload_config(!options.debug, options.config.first().map(String::as_str))?;
+    }
+  }
 
   let config_guard = config.lock().unwrap();
   let config_ = config_guard.as_ref().unwrap();
 
+  check_manifest(config_, options.dry_run, options.frozen_manifest)?;
+  if options.dry_run {
+    std::process::exit(0);
+  }
+
   let interface = AppInterface::new(config_)?;
 
   let bundle_identifier_source = match config_.find_bundle_identifier_overwriter() {
@@ -275,8 +664,39 @@ pub fn setup(options: &mut Options) -> Result<AppInterface> {
     std::process::exit(1);
   }
 
+  // the runner may change the out dir exposed to the hook
+  if options.runner.is_none() {
+    options.runner = config_.build.runner.clone();
+  }
+
   if let Some(before_build) = config_.build.before_build_command.clone() {
-    run_hook("beforeBuildCommand", before_build, options.debug)?;
+    let targets = if mobile || options.target.is_empty() {
+      vec![None]
+    } else {
+      options.target.iter().cloned().map(Some).collect()
+    };
+    // each target has its own out dir, so the hook runs once per target
+    for target in targets {
+      let mut target_options = options.clone();
+      target_options.target = target.into_iter().collect();
+      let mut context = resolve_hook_context(interface.app_settings(), &target_options, None)?;
+      if mobile {
+        context.target_triple = None;
+        context.out_dir = None;
+      }
+      let phase = match &context.target_triple {
+        Some(target) if options.target.len() > 1 => format!("beforeBuildCommand ({})", target),
+        _ => "beforeBuildCommand".into(),
+      };
+      timings::measure(phase, || {
+        run_hook(
+          "beforeBuildCommand",
+          before_build.clone(),
+          options.debug,
+          &context,
+        )
+      })?;
+    }
   }
 
   if let AppUrl::Url(WindowUrl::App(web_asset_path)) = &config_.build.dist_dir {
@@ -309,9 +729,7 @@ pub fn setup(options: &mut Options) -> Result<AppInterface> {
     }
   }
 
-  if options.runner.is_none() {
-    options.runner = config_.build.runner.clone();
-  }
+  options.use_sccache = options.use_sccache || config_.build.use_sccache;
 
   if let Some(list) = options.features.as_mut() {
     list.extend(config_.build.features.clone().unwrap_or_default());
@@ -320,46 +738,70 @@ pub fn setup(options: &mut Options) -> Result<AppInterface> {
   Ok(interface)
 }
 
-fn run_hook(name: &str, hook: HookCommand, debug: bool) -> Result<()> {
-  let (script, script_cwd) = match hook {
-    HookCommand::Script(s) if s.is_empty() => (None, None),
-    HookCommand::Script(s) => (Some(s), None),
-    HookCommand::ScriptWithOptions { script, cwd } => (Some(script), cwd.map(Into::into)),
-  };
-  let cwd = script_cwd.unwrap_or_else(|| app_dir().clone());
-  if let Some(script) = script {
-    info!(action = "Running"; "{} `{}`", name, script);
-    #[cfg(target_os = "windows")]
-    let status = Command::new("cmd")
-      .arg("/S")
-      .arg("/C")
-      .arg(&script)
-      .current_dir(cwd)
-      .envs(command_env(debug))
-      .piped()
-      .with_context(|| format!("failed to run `{}` with `cmd /C`", script))?;
-    #[cfg(not(target_os = "windows"))]
-    let status = Command::new("sh")
-      .arg("-c")
-      .arg(&script)
-      .current_dir(cwd)
-      .envs(command_env(debug))
-      .piped()
-      .with_context(|| format!("failed to run `{}` with `sh -c`", script))?;
-
-    if !status.success() {
+/// Reads the `--config` values, returning the merged JSON and the paths of the files, see [`merge_config_args`].
+pub fn read_merge_config(configs: &[String]) -> Result<(Option<String>, Option<String>)> {
+  let paths = configs
+    .iter()
+    .filter(|config| !config.trim_start().starts_with('{'))
+    .map(String::as_str)
+    .collect::<Vec<_>>();
+  Ok((
+    merge_config_args(configs)?,
+    Some(paths.join(", ")).filter(|paths| !paths.is_empty()),
+  ))
+}
+
+/// Sets `SOURCE_DATE_EPOCH` to the time of the last commit unless it is already set,
+/// so the hooks and the bundler use the same timestamp on every build of a commit.
+fn set_source_date_epoch() -> Result<()> {
+  if let Some(epoch) = var_os("SOURCE_DATE_EPOCH") {
+    if epoch.to_string_lossy().parse::<u64>().is_err() {
       bail!(
-        "{} `{}` failed with exit code {}",
-        name,
-        script,
-        status.code().unwrap_or_default()
+        "SOURCE_DATE_EPOCH must be a UNIX timestamp, found `{}`",
+        epoch.to_string_lossy()
       );
     }
+    return Ok(());
   }
 
+  let output = Command::new("git")
+    .args(["log", "-1", "--pretty=%ct"])
+    .current_dir(app_dir())
+    .output_ok()
+    .with_context(|| "failed to run `git log`")?;
+  let epoch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+  if !output.status.success() || epoch.parse::<u64>().is_err() {
+    bail!("`--reproducible` uses the time of the last commit, but it could not be read with `git log`. Set the SOURCE_DATE_EPOCH environment variable instead.");
+  }
+  info!("Using the last commit time {} as SOURCE_DATE_EPOCH", epoch);
+  std::env::set_var("SOURCE_DATE_EPOCH", epoch);
   Ok(())
 }
 
+/// Resolves the hook context of a single target build.
+fn resolve_hook_context(
+  app_settings: &<AppInterface as Interface>::AppSettings,
+  options: &Options,
+  out_dir: Option<&Path>,
+) -> Result<HookContext> {
+  let interface_options: crate::interface::Options = options.clone().into();
+  let target_triple = match &interface_options.target {
+    Some(target) => target.clone(),
+    None => tauri_utils::platform::target_triple()?,
+  };
+  let out_dir = match out_dir {
+    Some(out_dir) => out_dir.to_path_buf(),
+    None => app_settings.out_dir(&interface_options)?,
+  };
+  Ok(HookContext {
+    target_triple: Some(target_triple),
+    profile: Some(interface_options.cargo_profile().into()),
+    out_dir: Some(out_dir),
+    version: Some(app_settings.get_package_settings().version),
+    artifacts_path: None,
+  })
+}
+
 fn print_signed_updater_archive(output_paths: &[PathBuf]) -> crate::Result<()> {
   let pluralised = if output_paths.len() == 1 {
     "updater archive"
@@ -0,0 +1,48 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Collects the artifacts of `tauri build`, listed in a JSON file for `build > afterBundleCommand`.
+
+use crate::Result;
+use anyhow::Context;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use std::{
+  path::{Path, PathBuf},
+  sync::Mutex,
+};
+
+static ARTIFACTS: Lazy<Mutex<Vec<Artifact>>> = Lazy::new(Default::default);
+
+/// A file produced by the build.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+  pub path: PathBuf,
+  /// The bundle short name, e.g. `deb` or `nsis`, `updaterSignature` or `updaterManifest`.
+  #[serde(rename = "type")]
+  pub kind: String,
+  /// The target triple, `None` for the files of all targets such as the updater manifest.
+  pub target: Option<String>,
+  /// The feature set of `tauri build --feature-matrix`.
+  pub feature_set: Option<String>,
+}
+
+/// Records an artifact.
+pub fn record(artifact: Artifact) {
+  ARTIFACTS.lock().unwrap().push(artifact);
+}
+
+/// Whether no artifact was recorded.
+pub fn is_empty() -> bool {
+  ARTIFACTS.lock().unwrap().is_empty()
+}
+
+/// Writes the recorded artifacts to `path` as a JSON array.
+pub fn write(path: &Path) -> Result<()> {
+  let artifacts = ARTIFACTS.lock().unwrap();
+  std::fs::write(path, serde_json::to_string_pretty(&*artifacts)?)
+    .with_context(|| format!("failed to write the artifact list to {}", path.display()))
+}
@@ -0,0 +1,97 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Pre-flight checks executed before a release build produces any artifact.
+
+use crate::{helpers::config::Config, interface::manifest::Manifest, Result};
+use anyhow::bail;
+use log::{error, info, warn};
+
+mod csp;
+mod dangerous;
+mod devtools;
+mod signing;
+mod updater;
+
+/// The data a check has access to.
+pub struct CheckContext<'a> {
+  /// The resolved Tauri configuration.
+  pub config: &'a Config,
+  /// The (rewritten) Cargo manifest.
+  pub manifest: &'a Manifest,
+  /// The cargo features that will be enabled on the build.
+  pub features: &'a [String],
+}
+
+/// How a failed check affects the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+  /// The failure is reported but the build continues.
+  Warning,
+  /// The failure stops the build.
+  Error,
+}
+
+/// A validator that runs against the resolved configuration and manifest.
+pub trait Check {
+  /// The identifier used to skip this check with `--skip-check`.
+  fn id(&self) -> &'static str;
+  /// Severity of a failure.
+  fn severity(&self) -> Severity;
+  /// Runs the check, returning a description of the problem when it fails.
+  fn run(&self, context: &CheckContext<'_>) -> Option<String>;
+}
+
+fn all() -> Vec<Box<dyn Check>> {
+  vec![
+    Box::new(devtools::DevtoolsCheck),
+    Box::new(dangerous::DangerousSettingsCheck),
+    Box::new(csp::CspCheck),
+    Box::new(updater::UpdaterPubkeyCheck),
+    Box::new(signing::SigningCheck),
+  ]
+}
+
+/// Runs all checks that were not skipped and fails if any check with [`Severity::Error`] failed.
+pub fn run(context: &CheckContext<'_>, skip: &[String]) -> Result<()> {
+  let checks = all();
+
+  for id in skip {
+    if !checks.iter().any(|c| c.id() == id) {
+      warn!(
+        "Unknown release check `{}` passed to `--skip-check` (available checks: {})",
+        id,
+        checks.iter().map(|c| c.id()).collect::<Vec<_>>().join(", ")
+      );
+    }
+  }
+
+  let mut failed = Vec::new();
+  for check in checks {
+    if skip.iter().any(|s| s == check.id()) {
+      info!(action = "Skipping"; "release check `{}`", check.id());
+      continue;
+    }
+    if let Some(message) = check.run(context) {
+      match check.severity() {
+        Severity::Warning => warn!("[{}] {}", check.id(), message),
+        Severity::Error => {
+          error!("[{}] {}", check.id(), message);
+          failed.push(check.id());
+        }
+      }
+    }
+  }
+
+  if !failed.is_empty() {
+    bail!(
+      "{} release {} failed: {}. Fix the reported issues or skip them with `--skip-check <id>`.",
+      failed.len(),
+      if failed.len() == 1 { "check" } else { "checks" },
+      failed.join(", ")
+    );
+  }
+
+  Ok(())
+}
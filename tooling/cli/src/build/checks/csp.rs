@@ -0,0 +1,26 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{Check, CheckContext, Severity};
+
+/// Warns when no Content Security Policy is defined.
+pub struct CspCheck;
+
+impl Check for CspCheck {
+  fn id(&self) -> &'static str {
+    "csp"
+  }
+
+  fn severity(&self) -> Severity {
+    Severity::Warning
+  }
+
+  fn run(&self, context: &CheckContext<'_>) -> Option<String> {
+    if context.config.tauri.security.csp.is_none() {
+      Some("`tauri > security > csp` is not set. Define a Content Security Policy to protect your application against XSS attacks.".into())
+    } else {
+      None
+    }
+  }
+}
@@ -0,0 +1,55 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{Check, CheckContext, Severity};
+use crate::helpers::config::DisabledCspModificationKind;
+
+/// Warns about configuration options flagged as dangerous.
+pub struct DangerousSettingsCheck;
+
+impl Check for DangerousSettingsCheck {
+  fn id(&self) -> &'static str {
+    "dangerous-settings"
+  }
+
+  fn severity(&self) -> Severity {
+    Severity::Warning
+  }
+
+  fn run(&self, context: &CheckContext<'_>) -> Option<String> {
+    let mut enabled = Vec::new();
+
+    let csp_modification_disabled = match &context
+      .config
+      .tauri
+      .security
+      .dangerous_disable_asset_csp_modification
+    {
+      DisabledCspModificationKind::Flag(disabled) => *disabled,
+      DisabledCspModificationKind::List(directives) => !directives.is_empty(),
+    };
+    if csp_modification_disabled {
+      enabled.push("tauri > security > dangerousDisableAssetCspModification");
+    }
+
+    if context
+      .config
+      .tauri
+      .allowlist
+      .process
+      .relaunch_dangerous_allow_symlink_macos
+    {
+      enabled.push("tauri > allowlist > process > relaunchDangerousAllowSymlinkMacos");
+    }
+
+    if enabled.is_empty() {
+      None
+    } else {
+      Some(format!(
+        "dangerous configuration options are enabled: {}",
+        enabled.join(", ")
+      ))
+    }
+  }
+}
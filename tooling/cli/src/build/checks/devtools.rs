@@ -0,0 +1,34 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{Check, CheckContext, Severity};
+
+/// Fails if the `devtools` feature of the `tauri` crate is enabled on the release build.
+pub struct DevtoolsCheck;
+
+impl Check for DevtoolsCheck {
+  fn id(&self) -> &'static str {
+    "devtools"
+  }
+
+  fn severity(&self) -> Severity {
+    Severity::Error
+  }
+
+  fn run(&self, context: &CheckContext<'_>) -> Option<String> {
+    // includes the features of a `tauri` dependency inherited from the workspace
+    let dependency_enables_devtools = context.manifest.tauri_features.contains("devtools");
+
+    let feature_enables_devtools = context
+      .manifest
+      .all_enabled_features(context.features)
+      .contains(&"tauri/devtools".into());
+
+    if dependency_enables_devtools || feature_enables_devtools {
+      Some("the `devtools` feature of the `tauri` crate is enabled. The webview inspector must not be shipped on release builds.".into())
+    } else {
+      None
+    }
+  }
+}
@@ -0,0 +1,43 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{Check, CheckContext, Severity};
+
+/// Warns when code signing is not configured for the current platform.
+pub struct SigningCheck;
+
+impl Check for SigningCheck {
+  fn id(&self) -> &'static str {
+    "signing"
+  }
+
+  fn severity(&self) -> Severity {
+    Severity::Warning
+  }
+
+  #[allow(unused_variables)]
+  fn run(&self, context: &CheckContext<'_>) -> Option<String> {
+    #[cfg(target_os = "macos")]
+    if context.config.tauri.bundle.macos.signing_identity.is_none()
+      && std::env::var_os("APPLE_SIGNING_IDENTITY").is_none()
+    {
+      return Some("no signing identity configured. Set `tauri > bundle > macOS > signingIdentity` or the `APPLE_SIGNING_IDENTITY` environment variable.".into());
+    }
+
+    #[cfg(windows)]
+    if context
+      .config
+      .tauri
+      .bundle
+      .windows
+      .certificate_thumbprint
+      .is_none()
+      && context.config.tauri.bundle.windows.sign.is_none()
+    {
+      return Some("no signing certificate configured. Set `tauri > bundle > windows > certificateThumbprint` or `tauri > bundle > windows > sign`.".into());
+    }
+
+    None
+  }
+}
@@ -0,0 +1,27 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{Check, CheckContext, Severity};
+
+/// Fails if the updater is active but no public key was configured.
+pub struct UpdaterPubkeyCheck;
+
+impl Check for UpdaterPubkeyCheck {
+  fn id(&self) -> &'static str {
+    "updater-pubkey"
+  }
+
+  fn severity(&self) -> Severity {
+    Severity::Error
+  }
+
+  fn run(&self, context: &CheckContext<'_>) -> Option<String> {
+    let updater = &context.config.tauri.updater;
+    if updater.active && updater.pubkey.trim().is_empty() {
+      Some("the updater is active but `tauri > updater > pubkey` is empty. Generate a key pair with `tauri signer generate`.".into())
+    } else {
+      None
+    }
+  }
+}
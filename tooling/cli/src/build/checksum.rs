@@ -0,0 +1,50 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Checksum files of the bundles, written by `tauri build --checksum <ALGORITHM>`.
+
+use crate::Result;
+use anyhow::Context;
+use sha2::{Digest, Sha256, Sha512};
+
+use std::{
+  fs::File,
+  io,
+  path::{Path, PathBuf},
+};
+
+/// The values of `--checksum`.
+pub const ALGORITHMS: &[&str] = &["sha256", "sha512"];
+
+/// Writes `<path>.<algorithm>` in the `sha256sum` format, so it can be verified with `sha256sum -c`.
+pub fn write(path: &Path, algorithm: &str) -> Result<PathBuf> {
+  let digest = file_digest(path, algorithm)?;
+  let file_name = path
+    .file_name()
+    .map(|n| n.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  let checksum_path = path.with_file_name(format!("{}.{}", file_name, algorithm));
+  std::fs::write(&checksum_path, format!("{}  {}\n", digest, file_name))
+    .with_context(|| format!("failed to write {}", checksum_path.display()))?;
+  Ok(checksum_path)
+}
+
+/// The hex encoded digest of the file.
+pub fn file_digest(path: &Path, algorithm: &str) -> Result<String> {
+  let mut file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+  let digest = match algorithm {
+    "sha256" => {
+      let mut hasher = Sha256::new();
+      io::copy(&mut file, &mut hasher)?;
+      format!("{:x}", hasher.finalize())
+    }
+    "sha512" => {
+      let mut hasher = Sha512::new();
+      io::copy(&mut file, &mut hasher)?;
+      format!("{:x}", hasher.finalize())
+    }
+    _ => anyhow::bail!("unsupported checksum algorithm `{}`", algorithm),
+  };
+  Ok(digest)
+}
@@ -0,0 +1,235 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Delta updater packages, written by `tauri build --delta-from <DIR>`.
+//!
+//! A delta is the binary diff between the payloads of the previous and the current updater archives,
+//! i.e. the tar of a `.tar.gz` archive or the stored `.zip` archive.
+//! It is a gzip stream starting with [`MAGIC`], followed by the operations rebuilding the current payload:
+//! - `C`, a little endian `u64` offset and `u64` length: copies a range of the previous payload.
+//! - `I`, a little endian `u64` length and the bytes: inserts new bytes.
+//! - `E`: the end of the delta.
+
+use crate::Result;
+use anyhow::Context;
+use libflate::gzip::{Decoder, EncodeOptions, Encoder, HeaderBuilder};
+
+use std::{
+  collections::HashMap,
+  fs::File,
+  io::{BufReader, BufWriter, Read, Write},
+  path::{Path, PathBuf},
+};
+
+const MAGIC: &[u8] = b"TAURIDELTA1\n";
+/// The size of the indexed blocks of the previous payload, the shortest copied range.
+const BLOCK_SIZE: usize = 32;
+const HASH_BASE: u32 = 257;
+
+/// The version of the previous release, read from the `latest.json` updater manifest of the directory.
+pub fn previous_version(dir: &Path) -> Result<String> {
+  let manifest_path = dir.join("latest.json");
+  let manifest: serde_json::Value = serde_json::from_str(
+    &std::fs::read_to_string(&manifest_path)
+      .with_context(|| format!("failed to read {}", manifest_path.display()))?,
+  )
+  .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+  manifest["version"]
+    .as_str()
+    .map(Into::into)
+    .ok_or_else(|| anyhow::anyhow!("{} has no `version`", manifest_path.display()))
+}
+
+/// Writes the delta between the archive of the previous release in `dir` and `archive`.
+///
+/// The previous archive has the name of `archive` with the previous version, `None` is returned when it does not exist.
+pub fn write(
+  archive: &Path,
+  dir: &Path,
+  version: &str,
+  previous_version: &str,
+) -> Result<Option<PathBuf>> {
+  let file_name = archive
+    .file_name()
+    .map(|n| n.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  let previous_archive = dir.join(file_name.replace(version, previous_version));
+  if !previous_archive.is_file() {
+    return Ok(None);
+  }
+
+  let old = read_payload(&previous_archive)?;
+  let new = read_payload(archive)?;
+  let delta_path = archive.with_file_name(format!("{}.from_{}.delta", file_name, previous_version));
+  let file = File::create(&delta_path)
+    .with_context(|| format!("failed to create {}", delta_path.display()))?;
+  // a zero modification time keeps the delta reproducible
+  let mut encoder = Encoder::with_options(
+    BufWriter::new(file),
+    EncodeOptions::new().header(HeaderBuilder::new().modification_time(0).finish()),
+  )?;
+  diff(&old, &new, &mut encoder)?;
+  encoder.finish().into_result()?.flush()?;
+  Ok(Some(delta_path))
+}
+
+/// The bytes of the archive, decompressed if it is a gzip file.
+fn read_payload(path: &Path) -> Result<Vec<u8>> {
+  let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+  let mut payload = Vec::new();
+  if path.extension().map_or(false, |ext| ext == "gz") {
+    Decoder::new(BufReader::new(file))?.read_to_end(&mut payload)?;
+  } else {
+    BufReader::new(file).read_to_end(&mut payload)?;
+  }
+  Ok(payload)
+}
+
+/// Writes the operations rebuilding `new` from `old`.
+///
+/// The blocks of `old` are indexed by their rolling hash, so the matching ranges are found in a single pass over `new`.
+fn diff<W: Write>(old: &[u8], new: &[u8], out: &mut W) -> Result<()> {
+  out.write_all(MAGIC)?;
+
+  let mut index = HashMap::new();
+  for (i, block) in old.chunks_exact(BLOCK_SIZE).enumerate() {
+    index.entry(hash(block)).or_insert(i * BLOCK_SIZE);
+  }
+  // the weight of the byte leaving the rolling window
+  let leaving_weight = (1..BLOCK_SIZE).fold(1u32, |w, _| w.wrapping_mul(HASH_BASE));
+
+  let mut literal_start = 0;
+  let mut i = 0;
+  let mut h = new.get(..BLOCK_SIZE).map(hash).unwrap_or_default();
+  while i + BLOCK_SIZE <= new.len() {
+    let matched = index
+      .get(&h)
+      .copied()
+      .filter(|&o| old[o..o + BLOCK_SIZE] == new[i..i + BLOCK_SIZE]);
+    if let Some(o) = matched {
+      // extends the match backwards into the pending literal, then forwards
+      let (mut start, mut old_start) = (i, o);
+      while start > literal_start && old_start > 0 && new[start - 1] == old[old_start - 1] {
+        start -= 1;
+        old_start -= 1;
+      }
+      let mut len = i + BLOCK_SIZE - start;
+      while start + len < new.len()
+        && old_start + len < old.len()
+        && new[start + len] == old[old_start + len]
+      {
+        len += 1;
+      }
+
+      write_insert(out, &new[literal_start..start])?;
+      out.write_all(b"C")?;
+      out.write_all(&(old_start as u64).to_le_bytes())?;
+      out.write_all(&(len as u64).to_le_bytes())?;
+
+      i = start + len;
+      literal_start = i;
+      if let Some(block) = new.get(i..i + BLOCK_SIZE) {
+        h = hash(block);
+      }
+    } else {
+      if let Some(&next) = new.get(i + BLOCK_SIZE) {
+        h = h
+          .wrapping_sub(u32::from(new[i]).wrapping_mul(leaving_weight))
+          .wrapping_mul(HASH_BASE)
+          .wrapping_add(u32::from(next));
+      }
+      i += 1;
+    }
+  }
+
+  write_insert(out, &new[literal_start..])?;
+  out.write_all(b"E")?;
+  Ok(())
+}
+
+fn write_insert<W: Write>(out: &mut W, bytes: &[u8]) -> Result<()> {
+  if !bytes.is_empty() {
+    out.write_all(b"I")?;
+    out.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    out.write_all(bytes)?;
+  }
+  Ok(())
+}
+
+fn hash(block: &[u8]) -> u32 {
+  block.iter().fold(0u32, |h, b| {
+    h.wrapping_mul(HASH_BASE).wrapping_add(u32::from(*b))
+  })
+}
+
+/// Rebuilds the current payload from the previous one and the (decompressed) delta operations.
+#[cfg(test)]
+fn apply<R: Read>(old: &[u8], delta: &mut R) -> Result<Vec<u8>> {
+  let read_u64 = |delta: &mut R| -> Result<usize> {
+    let mut bytes = [0; 8];
+    delta.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes) as usize)
+  };
+
+  let mut magic = [0; MAGIC.len()];
+  delta.read_exact(&mut magic)?;
+  anyhow::ensure!(magic == MAGIC, "not a delta updater package");
+
+  let mut new = Vec::new();
+  loop {
+    let mut op = [0];
+    delta.read_exact(&mut op)?;
+    match &op {
+      b"C" => {
+        let offset = read_u64(delta)?;
+        let len = read_u64(delta)?;
+        let range = old
+          .get(offset..offset + len)
+          .context("the delta does not match the previous payload")?;
+        new.extend_from_slice(range);
+      }
+      b"I" => {
+        let len = read_u64(delta)?;
+        delta.take(len as u64).read_to_end(&mut new)?;
+      }
+      b"E" => return Ok(new),
+      _ => anyhow::bail!("invalid delta operation"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{apply, diff};
+
+  fn pseudo_random_bytes(len: usize, mut seed: u32) -> Vec<u8> {
+    (0..len)
+      .map(|_| {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as u8
+      })
+      .collect()
+  }
+
+  #[test]
+  fn rebuilds_the_new_payload() {
+    let old = pseudo_random_bytes(64 * 1024, 7);
+    let mut new = old.clone();
+    new[1000..1010].copy_from_slice(b"0123456789");
+    new.splice(20_000..20_000, pseudo_random_bytes(500, 11));
+    new.drain(40_000..41_000);
+    new.extend(pseudo_random_bytes(100, 13));
+
+    let mut delta = Vec::new();
+    diff(&old, &new, &mut delta).unwrap();
+    assert!(delta.len() < 1024, "the delta has {} bytes", delta.len());
+    assert_eq!(apply(&old, &mut delta.as_slice()).unwrap(), new);
+
+    let mut delta = Vec::new();
+    diff(&[], b"short", &mut delta).unwrap();
+    assert_eq!(apply(&[], &mut delta.as_slice()).unwrap(), b"short");
+  }
+}
@@ -0,0 +1,251 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Builds each feature set of `build > featureMatrix`, enabled by `tauri build --feature-matrix`.
+
+use super::{build_targets, Options};
+use crate::{
+  helpers::config::Config,
+  interface::{AppInterface, Interface},
+  Result,
+};
+use anyhow::{bail, Context};
+use log::{error, info};
+use tauri_bundler::bundle::Bundle;
+
+use std::{
+  collections::VecDeque,
+  path::{Path, PathBuf},
+  sync::{Arc, Mutex},
+};
+
+/// The folder of the target directory containing the target directory of each feature set on parallel builds.
+const TARGET_DIR_NAME: &str = "feature-matrix";
+
+/// Builds and bundles the app once per feature set, running up to `options.matrix_jobs` builds at once.
+pub fn run(interface: &mut AppInterface, options: &Options, config: &Config) -> Result<()> {
+  let mut sets = match &config.build.feature_matrix {
+    Some(matrix) if !matrix.is_empty() => matrix
+      .iter()
+      .map(|(name, features)| (name.clone(), features.clone()))
+      .collect::<Vec<_>>(),
+    _ => bail!(
+      "`--feature-matrix` requires at least one feature set in `tauri.conf.json > build > featureMatrix`"
+    ),
+  };
+  sets.sort();
+
+  for (name, _) in &sets {
+    if name.is_empty()
+      || !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+      bail!(
+        "Invalid feature set name `{}` in `tauri.conf.json > build > featureMatrix`, it is used in the bundle file names so it must only contain alphanumeric characters, hyphens and underscores",
+        name
+      );
+    }
+  }
+
+  let jobs = options.matrix_jobs.unwrap_or(1);
+  if jobs == 0 {
+    bail!("`--matrix-jobs` must be at least 1");
+  }
+
+  if jobs == 1 {
+    // the sets share the target directory, so only the crates depending on the features are rebuilt
+    for (name, features) in sets {
+      info!(action = "Building"; "feature set `{}`", name);
+      build_targets(
+        interface,
+        &set_options(options, &features),
+        config,
+        Some(&name),
+      )?;
+    }
+    return Ok(());
+  }
+
+  // cargo locks the target directory, so each parallel build needs its own
+  let target_dir = interface
+    .app_settings()
+    .target_dir(&options.clone().into())?
+    .join(TARGET_DIR_NAME);
+
+  let workers = jobs.min(sets.len());
+  let queue = Arc::new(Mutex::new(sets.into_iter().collect::<VecDeque<_>>()));
+  let config = Arc::new(config.clone());
+  let mut handles = Vec::new();
+  for _ in 0..workers {
+    let queue = queue.clone();
+    let config = config.clone();
+    let options = options.clone();
+    let target_dir = target_dir.clone();
+    handles.push(std::thread::spawn(move || -> Result<Vec<String>> {
+      let mut failures = Vec::new();
+      let mut interface = AppInterface::new(&config)?;
+      loop {
+        let next = queue.lock().unwrap().pop_front();
+        let (name, features) = match next {
+          Some(set) => set,
+          None => break,
+        };
+        info!(action = "Building"; "feature set `{}`", name);
+        let mut set_options = set_options(&options, &features);
+        set_options.args = with_target_dir(&set_options.args, &target_dir.join(&name));
+        if let Err(e) = build_targets(&mut interface, &set_options, &config, Some(&name)) {
+          error!("Feature set `{}` failed: {:#}", name, e);
+          failures.push(name);
+        }
+      }
+      Ok(failures)
+    }));
+  }
+
+  let mut failed = Vec::new();
+  for handle in handles {
+    failed.extend(
+      handle
+        .join()
+        .map_err(|_| anyhow::anyhow!("feature set build thread panicked"))??,
+    );
+  }
+  if !failed.is_empty() {
+    failed.sort();
+    bail!("failed to build the feature sets: {}", failed.join(", "));
+  }
+
+  Ok(())
+}
+
+/// The options of a feature set build, with its features added to the ones of the command line.
+fn set_options(options: &Options, features: &[String]) -> Options {
+  let mut set_options = options.clone();
+  set_options
+    .features
+    .get_or_insert_with(Vec::new)
+    .extend(features.iter().cloned());
+  set_options
+}
+
+/// Replaces the `--target-dir` of the cargo arguments, which cargo rejects if given twice.
+fn with_target_dir(args: &[String], target_dir: &Path) -> Vec<String> {
+  let mut new_args = vec!["--target-dir".to_string(), target_dir.display().to_string()];
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    if arg == "--" {
+      new_args.push(arg.clone());
+      new_args.extend(args.cloned());
+      break;
+    } else if arg == "--target-dir" {
+      args.next();
+    } else if !arg.starts_with("--target-dir=") {
+      new_args.push(arg.clone());
+    }
+  }
+  new_args
+}
+
+/// Appends the feature set name to the file name of each bundle, e.g. `app_1.0.0_amd64_pro.deb`.
+pub fn rename_bundles(mut bundles: Vec<Bundle>, name: &str) -> Result<Vec<Bundle>> {
+  for bundle in &mut bundles {
+    for path in &mut bundle.bundle_paths {
+      let renamed = suffixed_path(path, name);
+      if renamed.is_dir() {
+        std::fs::remove_dir_all(&renamed)?;
+      } else if renamed.exists() {
+        std::fs::remove_file(&renamed)?;
+      }
+      std::fs::rename(&*path, &renamed)
+        .with_context(|| format!("failed to rename {}", path.display()))?;
+      *path = renamed;
+    }
+  }
+  info!("Bundles of feature set `{}`:", name);
+  for path in bundles.iter().flat_map(|b| &b.bundle_paths) {
+    info!("        {}", path.display());
+  }
+  Ok(bundles)
+}
+
+/// Inserts `_<suffix>` before the extension, keeping the inner extension of updater archives such as `.app.tar.gz`.
+fn suffixed_path(path: &Path, suffix: &str) -> PathBuf {
+  let file_name = path
+    .file_name()
+    .map(|n| n.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  let (file_name, archive_extension) = [".tar.gz", ".zip"]
+    .iter()
+    .find_map(|ext| file_name.strip_suffix(ext).map(|stem| (stem, *ext)))
+    .unwrap_or((file_name.as_str(), ""));
+  let file_name = Path::new(file_name);
+  let stem = file_name
+    .file_stem()
+    .map(|s| s.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  let extension = file_name
+    .extension()
+    .map(|e| format!(".{}", e.to_string_lossy()))
+    .unwrap_or_default();
+  path.with_file_name(format!(
+    "{}_{}{}{}",
+    stem, suffix, extension, archive_extension
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{suffixed_path, with_target_dir};
+  use std::path::{Path, PathBuf};
+
+  #[test]
+  fn suffix_before_extension() {
+    for (path, expected) in [
+      (
+        "bundle/deb/app_1.0.0_amd64.deb",
+        "bundle/deb/app_1.0.0_amd64_pro.deb",
+      ),
+      ("bundle/macos/App.app", "bundle/macos/App_pro.app"),
+      (
+        "bundle/macos/App.app.tar.gz",
+        "bundle/macos/App_pro.app.tar.gz",
+      ),
+      (
+        "bundle/msi/App_1.0.0_x64_en-US.msi.zip",
+        "bundle/msi/App_1.0.0_x64_en-US_pro.msi.zip",
+      ),
+    ] {
+      assert_eq!(
+        suffixed_path(Path::new(path), "pro"),
+        PathBuf::from(expected)
+      );
+    }
+  }
+
+  #[test]
+  fn replaces_target_dir() {
+    let args = [
+      "--locked",
+      "--target-dir",
+      "out",
+      "--target-dir=out",
+      "--",
+      "--target-dir",
+    ]
+    .iter()
+    .map(|a| a.to_string())
+    .collect::<Vec<_>>();
+    assert_eq!(
+      with_target_dir(&args, Path::new("target/pro")),
+      vec![
+        "--target-dir",
+        "target/pro",
+        "--locked",
+        "--",
+        "--target-dir"
+      ]
+    );
+  }
+}
@@ -0,0 +1,430 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Software bill of materials of the app, written by `tauri build --sbom <FORMAT>`.
+//!
+//! It lists the crates compiled into the app, resolved by `cargo metadata` for the target and features,
+//! and the bundled resources with their SHA-256 checksum.
+
+use super::{checksum::file_digest, Options};
+use crate::{helpers::app_paths::tauri_dir, Result};
+use anyhow::{bail, Context};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use std::{
+  collections::{BTreeSet, HashMap},
+  path::{Path, PathBuf},
+  process::Command,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The values of `--sbom`.
+pub const FORMATS: &[&str] = &["cyclonedx", "spdx"];
+
+const CRATES_IO_SOURCE: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+#[derive(Deserialize)]
+struct Metadata {
+  packages: Vec<Package>,
+  resolve: Option<Resolve>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+  id: String,
+  name: String,
+  version: String,
+  license: Option<String>,
+  source: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Resolve {
+  root: Option<String>,
+  nodes: Vec<Node>,
+}
+
+#[derive(Deserialize)]
+struct Node {
+  id: String,
+  deps: Vec<NodeDep>,
+}
+
+#[derive(Deserialize)]
+struct NodeDep {
+  pkg: String,
+  dep_kinds: Vec<DepKind>,
+}
+
+#[derive(Deserialize)]
+struct DepKind {
+  kind: Option<String>,
+}
+
+/// A bundled resource.
+struct Resource {
+  target: PathBuf,
+  sha256: String,
+}
+
+/// The resolved crates of the app, the root crate first.
+struct Crates<'a> {
+  packages: Vec<&'a Package>,
+  /// The dependencies of each crate, by package id.
+  dependencies: HashMap<&'a str, Vec<&'a str>>,
+}
+
+/// Writes the bill of materials of the app built with the features of `options` for the `target` triple.
+pub fn write(
+  path: &Path,
+  format: &str,
+  target: &str,
+  options: &Options,
+  resources: &[(PathBuf, PathBuf)],
+) -> Result<()> {
+  let mut feature_args = Vec::new();
+  if let Some(features) = options.features.as_ref().filter(|f| !f.is_empty()) {
+    feature_args.push("--features".to_string());
+    feature_args.push(features.join(","));
+  }
+  for flag in ["--no-default-features", "--all-features"] {
+    if options.args.iter().any(|arg| arg == flag) {
+      feature_args.push(flag.to_string());
+    }
+  }
+
+  let output = Command::new("cargo")
+    .args([
+      "metadata",
+      "--format-version",
+      "1",
+      "--filter-platform",
+      target,
+    ])
+    .args(feature_args)
+    .current_dir(tauri_dir())
+    .output()
+    .with_context(|| "failed to run `cargo metadata`")?;
+  if !output.status.success() {
+    bail!(
+      "cargo metadata command exited with a non zero exit code: {}",
+      String::from_utf8_lossy(&output.stderr)
+    );
+  }
+  let metadata: Metadata = serde_json::from_slice(&output.stdout)?;
+  let crates = resolve(&metadata)?;
+
+  let resources = resources
+    .iter()
+    .map(|(src, target)| {
+      Ok(Resource {
+        target: target.clone(),
+        sha256: file_digest(src, "sha256")?,
+      })
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  let document = match format {
+    "cyclonedx" => cyclonedx(&crates, &resources),
+    "spdx" => spdx(&crates, &resources),
+    _ => bail!("unsupported SBOM format `{}`", format),
+  };
+  std::fs::write(path, serde_json::to_string_pretty(&document)?)
+    .with_context(|| format!("failed to write {}", path.display()))?;
+  Ok(())
+}
+
+/// The crates reachable from the root crate, without the development dependencies.
+fn resolve(metadata: &Metadata) -> Result<Crates<'_>> {
+  let resolve = match &metadata.resolve {
+    Some(resolve) => resolve,
+    None => bail!("`cargo metadata` did not resolve the dependencies"),
+  };
+  let root = match &resolve.root {
+    Some(root) => root.as_str(),
+    None => bail!("`cargo metadata` did not resolve the app crate"),
+  };
+  let nodes = resolve
+    .nodes
+    .iter()
+    .map(|node| (node.id.as_str(), node))
+    .collect::<HashMap<_, _>>();
+  let packages = metadata
+    .packages
+    .iter()
+    .map(|package| (package.id.as_str(), package))
+    .collect::<HashMap<_, _>>();
+
+  let mut dependencies = HashMap::new();
+  let mut visited = BTreeSet::new();
+  let mut queue = vec![root];
+  while let Some(id) = queue.pop() {
+    if !visited.insert(id) {
+      continue;
+    }
+    let deps = nodes
+      .get(id)
+      .map(|node| {
+        node
+          .deps
+          .iter()
+          .filter(|dep| {
+            dep
+              .dep_kinds
+              .iter()
+              .any(|k| k.kind.as_deref() != Some("dev"))
+          })
+          .map(|dep| dep.pkg.as_str())
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+    queue.extend(&deps);
+    dependencies.insert(id, deps);
+  }
+
+  let mut crates = visited
+    .into_iter()
+    .filter(|id| *id != root)
+    .filter_map(|id| packages.get(id).copied())
+    .collect::<Vec<_>>();
+  crates.insert(
+    0,
+    packages
+      .get(root)
+      .copied()
+      .ok_or_else(|| anyhow::anyhow!("the app crate is missing from `cargo metadata`"))?,
+  );
+  Ok(Crates {
+    packages: crates,
+    dependencies,
+  })
+}
+
+/// The package URL of the crates published on crates.io.
+fn purl(package: &Package) -> Option<String> {
+  (package.source.as_deref() == Some(CRATES_IO_SOURCE))
+    .then(|| format!("pkg:cargo/{}@{}", package.name, package.version))
+}
+
+/// A CycloneDX 1.4 document.
+fn cyclonedx(crates: &Crates<'_>, resources: &[Resource]) -> Value {
+  let app = crates.packages[0];
+  let components = crates.packages[1..]
+    .iter()
+    .map(|package| {
+      let mut component = json!({
+        "type": "library",
+        "bom-ref": package.id,
+        "name": package.name,
+        "version": package.version,
+      });
+      if let Some(purl) = purl(package) {
+        component["purl"] = json!(purl);
+      }
+      if let Some(license) = &package.license {
+        component["licenses"] = json!([{ "expression": license }]);
+      }
+      component
+    })
+    .chain(resources.iter().map(|resource| {
+      json!({
+        "type": "file",
+        "bom-ref": format!("file:{}", resource.target.display()),
+        "name": resource.target.display().to_string(),
+        "hashes": [{ "alg": "SHA-256", "content": resource.sha256 }],
+      })
+    }))
+    .collect::<Vec<_>>();
+  let dependencies = crates
+    .packages
+    .iter()
+    .map(|package| {
+      json!({
+        "ref": package.id,
+        "dependsOn": crates.dependencies.get(package.id.as_str()).cloned().unwrap_or_default(),
+      })
+    })
+    .collect::<Vec<_>>();
+
+  json!({
+    "bomFormat": "CycloneDX",
+    "specVersion": "1.4",
+    "version": 1,
+    "metadata": {
+      "timestamp": timestamp(),
+      "tools": [{ "vendor": "Tauri", "name": "tauri-cli", "version": env!("CARGO_PKG_VERSION") }],
+      "component": {
+        "type": "application",
+        "bom-ref": app.id,
+        "name": app.name,
+        "version": app.version,
+      },
+    },
+    "components": components,
+    "dependencies": dependencies,
+  })
+}
+
+/// An SPDX 2.3 document.
+fn spdx(crates: &Crates<'_>, resources: &[Resource]) -> Value {
+  let app = crates.packages[0];
+  let spdx_ids = crates
+    .packages
+    .iter()
+    .enumerate()
+    .map(|(i, package)| (package.id.as_str(), format!("SPDXRef-Package-{}", i)))
+    .collect::<HashMap<_, _>>();
+
+  let packages = crates
+    .packages
+    .iter()
+    .map(|package| {
+      let mut spdx_package = json!({
+        "SPDXID": spdx_ids[package.id.as_str()],
+        "name": package.name,
+        "versionInfo": package.version,
+        "downloadLocation": "NOASSERTION",
+        "licenseConcluded": "NOASSERTION",
+        "licenseDeclared": package.license.as_deref().unwrap_or("NOASSERTION"),
+        "filesAnalyzed": false,
+      });
+      if let Some(purl) = purl(package) {
+        spdx_package["downloadLocation"] = json!(format!(
+          "https://crates.io/api/v1/crates/{}/{}/download",
+          package.name, package.version
+        ));
+        spdx_package["externalRefs"] = json!([{
+          "referenceCategory": "PACKAGE-MANAGER",
+          "referenceType": "purl",
+          "referenceLocator": purl,
+        }]);
+      }
+      spdx_package
+    })
+    .collect::<Vec<_>>();
+  let files = resources
+    .iter()
+    .enumerate()
+    .map(|(i, resource)| {
+      json!({
+        "SPDXID": format!("SPDXRef-File-{}", i),
+        "fileName": format!("./{}", resource.target.display()),
+        "checksums": [{ "algorithm": "SHA256", "checksumValue": resource.sha256 }],
+      })
+    })
+    .collect::<Vec<_>>();
+
+  let app_spdx_id = &spdx_ids[app.id.as_str()];
+  let mut relationships = vec![json!({
+    "spdxElementId": "SPDXRef-DOCUMENT",
+    "relationshipType": "DESCRIBES",
+    "relatedSpdxElement": app_spdx_id,
+  })];
+  for package in &crates.packages {
+    for dependency in crates
+      .dependencies
+      .get(package.id.as_str())
+      .into_iter()
+      .flatten()
+    {
+      if let Some(dependency_spdx_id) = spdx_ids.get(dependency) {
+        relationships.push(json!({
+          "spdxElementId": spdx_ids[package.id.as_str()],
+          "relationshipType": "DEPENDS_ON",
+          "relatedSpdxElement": dependency_spdx_id,
+        }));
+      }
+    }
+  }
+  for i in 0..resources.len() {
+    relationships.push(json!({
+      "spdxElementId": app_spdx_id,
+      "relationshipType": "CONTAINS",
+      "relatedSpdxElement": format!("SPDXRef-File-{}", i),
+    }));
+  }
+
+  // the namespace must be unique to the document content
+  let mut hasher = Sha256::new();
+  for package in &crates.packages {
+    hasher.update(package.id.as_bytes());
+  }
+  for resource in resources {
+    hasher.update(resource.sha256.as_bytes());
+  }
+
+  json!({
+    "spdxVersion": "SPDX-2.3",
+    "dataLicense": "CC0-1.0",
+    "SPDXID": "SPDXRef-DOCUMENT",
+    "name": format!("{}-{}", app.name, app.version),
+    "documentNamespace": format!(
+      "https://spdx.org/spdxdocs/{}-{}-{:x}",
+      app.name,
+      app.version,
+      hasher.finalize()
+    ),
+    "creationInfo": {
+      "created": timestamp(),
+      "creators": [format!("Tool: tauri-cli-{}", env!("CARGO_PKG_VERSION"))],
+    },
+    "packages": packages,
+    "files": files,
+    "relationships": relationships,
+  })
+}
+
+/// The current UTC date time, `SOURCE_DATE_EPOCH` if set for reproducible builds.
+pub fn timestamp() -> String {
+  let seconds = std::env::var("SOURCE_DATE_EPOCH")
+    .ok()
+    .and_then(|epoch| epoch.parse().ok())
+    .unwrap_or_else(|| {
+      SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+    });
+  format_utc(seconds)
+}
+
+/// Formats the seconds since the Unix epoch as an ISO 8601 UTC date time, e.g. `2022-09-01T12:00:00Z`.
+fn format_utc(seconds: u64) -> String {
+  let days = (seconds / 86400) as i64;
+  let time = seconds % 86400;
+  // converts the days to a civil date, see <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+  let z = days + 719468;
+  let era = z.div_euclid(146097);
+  let day_of_era = z.rem_euclid(146097);
+  let year_of_era =
+    (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let mp = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = year_of_era + era * 400 + i64::from(month <= 2);
+  format!(
+    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+    year,
+    month,
+    day,
+    time / 3600,
+    time % 3600 / 60,
+    time % 60
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::format_utc;
+
+  #[test]
+  fn formats_utc_date_time() {
+    assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+    assert_eq!(format_utc(951_782_400), "2000-02-29T00:00:00Z");
+    assert_eq!(format_utc(1_662_033_600), "2022-09-01T12:00:00Z");
+  }
+}
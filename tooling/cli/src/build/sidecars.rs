@@ -0,0 +1,93 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Downloads the external binaries configured in `bundle > externalBinSources`.
+
+use crate::{
+  helpers::{app_paths::tauri_dir, config::BundleConfig},
+  Result,
+};
+use anyhow::{bail, Context};
+use log::{info, warn};
+use sha2::{Digest, Sha256};
+use tauri_utils::resources::external_binaries;
+
+use std::{fs, io::Read, path::Path};
+
+/// Provides the external binaries with a download source for the target triple.
+///
+/// The binaries are cached under `<target_dir>/sidecars`, so they are only downloaded once per checksum.
+/// A binary is copied to its external binary path unless the file there already has the expected checksum.
+pub fn download(config: &BundleConfig, target_triple: &str, target_dir: &Path) -> Result<()> {
+  let external_bin = config
+    .external_bin
+    .iter()
+    .chain(config.target_external_bin.values())
+    .flatten()
+    .collect::<Vec<_>>();
+  for (name, sources) in &config.external_bin_sources {
+    if !external_bin.contains(&name) {
+      bail!(
+        "`{}` has a download source but is not listed in `tauri > bundle > externalBin` or `targetExternalBin`",
+        name
+      );
+    }
+    let source = match sources.get(target_triple) {
+      Some(source) => source,
+      None => continue,
+    };
+    let expected = source.sha256.to_lowercase();
+
+    let dest = tauri_dir().join(&external_binaries(std::slice::from_ref(name), target_triple)[0]);
+    if dest.is_file() {
+      if sha256(&fs::read(&dest)?) == expected {
+        continue;
+      }
+      warn!(
+        "{} does not match the checksum of its download source, replacing it",
+        dest.display()
+      );
+    }
+
+    let file_name = dest.file_name().expect("external binary without file name");
+    let cached = target_dir.join("sidecars").join(&expected).join(file_name);
+    if !cached.is_file() {
+      info!(action = "Downloading"; "{} from {}", file_name.to_string_lossy(), source.url);
+      let mut bytes = Vec::new();
+      ureq::get(&source.url)
+        .call()
+        .with_context(|| format!("failed to download {}", source.url))?
+        .into_reader()
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("failed to download {}", source.url))?;
+      let actual = sha256(&bytes);
+      if actual != expected {
+        bail!(
+          "the checksum of {} does not match: expected {}, got {}",
+          source.url,
+          expected,
+          actual
+        );
+      }
+      fs::create_dir_all(cached.parent().unwrap())?;
+      fs::write(&cached, bytes)?;
+    }
+
+    if let Some(parent) = dest.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    fs::copy(&cached, &dest)
+      .with_context(|| format!("failed to copy the external binary to {}", dest.display()))?;
+    #[cfg(unix)]
+    {
+      use std::os::unix::fs::PermissionsExt;
+      fs::set_permissions(&dest, fs::Permissions::from_mode(0o755))?;
+    }
+  }
+  Ok(())
+}
+
+fn sha256(bytes: &[u8]) -> String {
+  format!("{:x}", Sha256::digest(bytes))
+}
@@ -0,0 +1,137 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Strips the release binary and reports its size, enabled by `bundle > optimize > strip`.
+
+use crate::{CommandExt, Result};
+use anyhow::{bail, Context};
+use log::{info, warn};
+
+use std::{collections::HashMap, path::Path, process::Command};
+
+/// Number of crates listed in the size breakdown.
+const REPORTED_CRATES: usize = 10;
+
+/// Strips the symbols of the binary and prints its size before and after, along with the size of the largest crates.
+pub fn run(bin_path: &Path) -> Result<()> {
+  if bin_path.extension().map_or(false, |e| e == "exe") {
+    info!("Skipping symbol stripping of Windows binaries, their debug symbols are stored in a separate .pdb file");
+    return Ok(());
+  }
+
+  let size_before = file_size(bin_path)?;
+  // the breakdown relies on the symbols, so it must be computed before stripping them
+  let crate_sizes = match crate_sizes(bin_path) {
+    Ok(sizes) => Some(sizes),
+    Err(e) => {
+      warn!("Failed to compute the size breakdown by crate: {:#}", e);
+      None
+    }
+  };
+
+  info!(action = "Stripping"; "{}", bin_path.display());
+  let output = Command::new("strip")
+    .arg(bin_path)
+    .output_ok()
+    .with_context(|| "failed to run `strip`, make sure it is installed")?;
+  if !output.status.success() {
+    bail!(
+      "`strip` failed ({}): {}",
+      output.status,
+      String::from_utf8_lossy(&output.stderr).trim()
+    );
+  }
+  let size_after = file_size(bin_path)?;
+
+  info!(
+    "Binary size: {} -> {} ({:.1}% smaller)",
+    format_size(size_before),
+    format_size(size_after),
+    100.0 * size_before.saturating_sub(size_after) as f64 / size_before.max(1) as f64
+  );
+
+  if let Some(crate_sizes) = crate_sizes {
+    let total: u64 = crate_sizes.iter().map(|(_, size)| size).sum();
+    info!("Largest crates by symbol size:");
+    for (name, size) in crate_sizes.iter().take(REPORTED_CRATES) {
+      info!(
+        "  {:>10} {:>5.1}%  {}",
+        format_size(*size),
+        100.0 * *size as f64 / total.max(1) as f64,
+        name
+      );
+    }
+  }
+
+  Ok(())
+}
+
+fn file_size(path: &Path) -> Result<u64> {
+  Ok(
+    std::fs::metadata(path)
+      .with_context(|| format!("failed to read metadata of {}", path.display()))?
+      .len(),
+  )
+}
+
+/// Sums the size of the symbols of each crate using `nm`, sorted by the largest crate first.
+fn crate_sizes(bin_path: &Path) -> Result<Vec<(String, u64)>> {
+  let output = Command::new("nm")
+    .args(["--print-size", "--size-sort", "--demangle"])
+    .arg(bin_path)
+    .output_ok()
+    .with_context(|| "failed to run `nm`")?;
+  if !output.status.success() {
+    bail!(
+      "`nm` failed: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    );
+  }
+
+  let mut sizes = HashMap::<String, u64>::new();
+  for line in String::from_utf8_lossy(&output.stdout).lines() {
+    // <address> <size> <type> <name>
+    let mut parts = line.splitn(4, ' ');
+    let (size, name) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+      (Some(_), Some(size), Some(_), Some(name)) => (size, name),
+      _ => continue,
+    };
+    if let Ok(size) = u64::from_str_radix(size, 16) {
+      *sizes.entry(symbol_crate(name).to_string()).or_default() += size;
+    }
+  }
+
+  let mut sizes = sizes.into_iter().collect::<Vec<_>>();
+  sizes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+  Ok(sizes)
+}
+
+/// The crate a demangled symbol belongs to, e.g. `std` for `<std::fs::File as std::io::Read>::read`.
+fn symbol_crate(symbol: &str) -> &str {
+  let symbol = symbol.trim_start_matches('<');
+  match symbol.find("::") {
+    Some(index) if !symbol[..index].contains(|c: char| c.is_whitespace() || c == '<') => {
+      &symbol[..index]
+    }
+    // C and other non Rust symbols
+    _ => "[unknown]",
+  }
+}
+
+fn format_size(bytes: u64) -> String {
+  const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+  if bytes < 1024 {
+    return format!("{} B", bytes);
+  }
+  let mut size = bytes as f64 / 1024.0;
+  let mut unit = UNITS[0];
+  for next in &UNITS[1..] {
+    if size < 1024.0 {
+      break;
+    }
+    size /= 1024.0;
+    unit = next;
+  }
+  format!("{:.1} {}", size, unit)
+}
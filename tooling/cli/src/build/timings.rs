@@ -0,0 +1,81 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Measures the phases of `tauri build`, summarized at the end of the build and written to `--timings <PATH>`.
+
+use crate::Result;
+use anyhow::Context;
+use log::info;
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Serialize;
+
+use std::{
+  path::Path,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+static STARTED_AT: OnceCell<Instant> = OnceCell::new();
+static PHASES: Lazy<Mutex<Vec<Phase>>> = Lazy::new(Default::default);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Phase {
+  name: String,
+  duration_ms: u128,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Report<'a> {
+  total_ms: u128,
+  phases: &'a [Phase],
+}
+
+/// Starts the clock of the total build duration.
+pub fn start() {
+  STARTED_AT.get_or_init(Instant::now);
+}
+
+/// Records the duration of a phase.
+pub fn record(name: impl Into<String>, duration: Duration) {
+  PHASES.lock().unwrap().push(Phase {
+    name: name.into(),
+    duration_ms: duration.as_millis(),
+  });
+}
+
+/// Runs `f` and records its duration, even if it fails.
+pub fn measure<T>(name: impl Into<String>, f: impl FnOnce() -> T) -> T {
+  let started_at = Instant::now();
+  let result = f();
+  record(name, started_at.elapsed());
+  result
+}
+
+/// Prints the duration of each phase and writes the JSON report to `path`.
+pub fn report(path: Option<&Path>) -> Result<()> {
+  let total = STARTED_AT.get_or_init(Instant::now).elapsed();
+  let phases = PHASES.lock().unwrap();
+
+  info!(action = "Timings"; "{:.1}s in total", total.as_secs_f64());
+  for phase in phases.iter() {
+    info!(
+      "  {:>8.1}s  {}",
+      phase.duration_ms as f64 / 1000.0,
+      phase.name
+    );
+  }
+
+  if let Some(path) = path {
+    let report = Report {
+      total_ms: total.as_millis(),
+      phases: &phases,
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&report)?)
+      .with_context(|| format!("failed to write the timings report to {}", path.display()))?;
+  }
+
+  Ok(())
+}
@@ -0,0 +1,219 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The `latest.json` updater manifest, written by `tauri build` when `tauri > updater > manifest` is set.
+
+use crate::{
+  helpers::{config::UpdaterManifestConfig, updater_platform},
+  Result,
+};
+use anyhow::Context;
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use std::{collections::BTreeMap, path::Path, sync::Mutex};
+
+static PLATFORMS: Lazy<Mutex<BTreeMap<String, Platform>>> = Lazy::new(Default::default);
+
+/// The manifest in the static JSON format of the updater endpoints.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+  version: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  notes: Option<String>,
+  #[serde(default)]
+  pub_date: String,
+  #[serde(default)]
+  platforms: BTreeMap<String, Platform>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Platform {
+  signature: String,
+  url: String,
+  /// The delta updater packages, by previous version.
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  deltas: BTreeMap<String, Delta>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Delta {
+  signature: String,
+  url: String,
+}
+
+/// Records the signed updater archive of a target.
+///
+/// The manifest has a single archive per platform, so the following archives of the same platform are skipped.
+/// Returns whether the archive was recorded.
+pub fn record(
+  config: &UpdaterManifestConfig,
+  version: &str,
+  target: &str,
+  archive: &Path,
+  signature_path: &Path,
+) -> Result<bool> {
+  let (os, arch) = match updater_platform(target) {
+    Some(platform) => platform,
+    None => {
+      warn!(
+        "The updater does not support the `{}` target, so it is not listed in the updater manifest.",
+        target
+      );
+      return Ok(false);
+    }
+  };
+  let key = format!("{}-{}", os, arch);
+  let mut platforms = PLATFORMS.lock().unwrap();
+  if platforms.contains_key(&key) {
+    warn!(
+      "The updater manifest already has an archive for `{}`, skipping {}.",
+      key,
+      archive.display()
+    );
+    return Ok(false);
+  }
+
+  let (signature, url) = signed_url(config, version, (os, arch), archive, signature_path)?;
+  platforms.insert(
+    key,
+    Platform {
+      signature,
+      url,
+      deltas: Default::default(),
+    },
+  );
+  Ok(true)
+}
+
+/// Records the signed delta updater package of the archive recorded for the target.
+pub fn record_delta(
+  config: &UpdaterManifestConfig,
+  version: &str,
+  target: &str,
+  previous_version: &str,
+  delta: &Path,
+  signature_path: &Path,
+) -> Result<()> {
+  if let Some((os, arch)) = updater_platform(target) {
+    let (signature, url) = signed_url(config, version, (os, arch), delta, signature_path)?;
+    if let Some(platform) = PLATFORMS
+      .lock()
+      .unwrap()
+      .get_mut(&format!("{}-{}", os, arch))
+    {
+      platform
+        .deltas
+        .insert(previous_version.into(), Delta { signature, url });
+    }
+  }
+  Ok(())
+}
+
+/// The signature and the templated download URL of a file.
+fn signed_url(
+  config: &UpdaterManifestConfig,
+  version: &str,
+  (os, arch): (&str, &str),
+  path: &Path,
+  signature_path: &Path,
+) -> Result<(String, String)> {
+  let file = path
+    .file_name()
+    .map(|n| n.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  let url = config
+    .url
+    .replace("{version}", version)
+    .replace("{target}", os)
+    .replace("{arch}", arch)
+    .replace("{file}", &file);
+  // also percent-encodes the spaces of the product name
+  let url = url::Url::parse(&url)
+    .with_context(|| format!("invalid updater manifest URL `{}`", url))?
+    .to_string();
+  let signature = std::fs::read_to_string(signature_path)
+    .with_context(|| format!("failed to read {}", signature_path.display()))?;
+  Ok((signature, url))
+}
+
+/// Whether no updater archive was recorded.
+pub fn is_empty() -> bool {
+  PLATFORMS.lock().unwrap().is_empty()
+}
+
+/// Writes the manifest of the recorded archives to `path`.
+///
+/// The platforms of an existing manifest of the same version are kept.
+pub fn write(path: &Path, config: &UpdaterManifestConfig, version: &str) -> Result<()> {
+  let notes = match &config.notes {
+    Some(notes_path) => {
+      let changelog = std::fs::read_to_string(notes_path)
+        .with_context(|| format!("failed to read the release notes {}", notes_path.display()))?;
+      Some(release_notes(&changelog, version))
+    }
+    None => None,
+  };
+
+  let mut platforms = std::fs::read_to_string(path)
+    .ok()
+    .and_then(|manifest| serde_json::from_str::<Manifest>(&manifest).ok())
+    .filter(|manifest| manifest.version == version)
+    .map(|manifest| manifest.platforms)
+    .unwrap_or_default();
+  platforms.append(&mut PLATFORMS.lock().unwrap());
+
+  let manifest = Manifest {
+    version: version.into(),
+    notes,
+    pub_date: super::sbom::timestamp(),
+    platforms,
+  };
+  std::fs::write(path, serde_json::to_string_pretty(&manifest)?)
+    .with_context(|| format!("failed to write the updater manifest to {}", path.display()))
+}
+
+/// The section of the changelog with a heading for `version`, or the whole file.
+fn release_notes(changelog: &str, version: &str) -> String {
+  let lines = changelog.lines().collect::<Vec<_>>();
+  let heading_level = |line: &str| {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    (level > 0 && line[level..].starts_with(' ')).then(|| level)
+  };
+  let is_version_heading = |line: &str| {
+    heading_level(line).is_some()
+      && line
+        .split(|c: char| c.is_whitespace() || "[]()".contains(c))
+        .any(|word| word.trim_start_matches('v') == version)
+  };
+
+  match lines.iter().position(|line| is_version_heading(line)) {
+    Some(start) => {
+      let level = heading_level(lines[start]).unwrap();
+      let end = lines[start + 1..]
+        .iter()
+        .position(|line| heading_level(line).map_or(false, |l| l <= level))
+        .map_or(lines.len(), |i| start + 1 + i);
+      lines[start + 1..end].join("\n").trim().into()
+    }
+    None => changelog.trim().into(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::release_notes;
+
+  #[test]
+  fn extracts_the_version_section() {
+    let changelog = "# Changelog\n\n## [1.1.0] - 2022-10-01\n\n### Added\n\n- Dark mode\n\n## [1.0.0]\n\n- Initial release\n";
+    assert_eq!(
+      release_notes(changelog, "1.1.0"),
+      "### Added\n\n- Dark mode"
+    );
+    assert_eq!(release_notes(changelog, "1.0.0"), "- Initial release");
+    assert_eq!(release_notes("Bug fixes\n", "1.0.0"), "Bug fixes");
+  }
+}
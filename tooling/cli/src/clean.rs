@@ -0,0 +1,114 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  helpers::{
+    app_paths::tauri_dir,
+    config::{get as get_config, merge_config_args},
+  },
+  interface::{AppInterface, Interface},
+  Result,
+};
+use anyhow::Context;
+use clap::Parser;
+use log::info;
+
+use std::{
+  fs::read_dir,
+  path::{Path, PathBuf},
+};
+
+#[derive(Debug, Parser)]
+#[clap(about = "Remove the build outputs of the app and the files cached by the CLI")]
+pub struct Options {
+  /// Only remove the bundles, the updater manifest and the bundle artifacts list, keeping the compiled artifacts
+  #[clap(long)]
+  bundles_only: bool,
+  /// Also remove the generated Android and iOS projects in `gen/android` and `gen/apple`
+  #[clap(long)]
+  mobile: bool,
+  /// List the paths to remove without removing them
+  #[clap(long)]
+  dry_run: bool,
+  /// JSON string or path to a JSON, JSON5 or TOML file to merge with tauri.conf.json. Can be used multiple times, the later values take precedence
+  #[clap(short, long, multiple_occurrences(true))]
+  config: Vec<String>,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let merge_config = merge_config_args(&options.config)?;
+  let tauri_dir = tauri_dir();
+  std::env::set_current_dir(&tauri_dir)
+    .with_context(|| "failed to change current working directory")?;
+  let config = get_config(true, merge_config.as_deref())?;
+  let config_guard = config.lock().unwrap();
+  let config_ = config_guard.as_ref().unwrap();
+
+  let interface = AppInterface::new(config_)?;
+  let target_dir = interface
+    .app_settings()
+    .target_dir(&crate::interface::Options::default())?;
+
+  let mut paths = if options.bundles_only {
+    bundle_outputs(&target_dir)
+  } else {
+    vec![target_dir]
+  };
+  if options.mobile {
+    paths.push(tauri_dir.join("gen").join("android"));
+    paths.push(tauri_dir.join("gen").join("apple"));
+  }
+  // the options forwarded from `tauri android dev` and `tauri ios dev` to the Xcode and Gradle builds
+  paths.push(
+    std::env::temp_dir()
+      .join(".tauri")
+      .join(&config_.tauri.bundle.identifier),
+  );
+
+  let paths = paths.into_iter().filter(|p| p.exists()).collect::<Vec<_>>();
+  if paths.is_empty() {
+    info!("Nothing to clean.");
+    return Ok(());
+  }
+  for path in paths {
+    if options.dry_run {
+      info!(action = "Would remove"; "{}", path.display());
+      continue;
+    }
+    info!(action = "Removing"; "{}", path.display());
+    if path.is_dir() {
+      std::fs::remove_dir_all(&path)
+    } else {
+      std::fs::remove_file(&path)
+    }
+    .with_context(|| format!("failed to remove {}", path.display()))?;
+  }
+  Ok(())
+}
+
+/// The outputs of `tauri build` in the target directory: the `bundle` folder of each profile and target triple,
+/// the updater manifest and the bundle artifacts list.
+fn bundle_outputs(target_dir: &Path) -> Vec<PathBuf> {
+  let mut paths = vec![
+    target_dir.join("latest.json"),
+    target_dir.join("bundle-artifacts.json"),
+  ];
+  // `target/<profile>/bundle` and `target/<triple>/<profile>/bundle`
+  for dir in subdirectories(target_dir) {
+    paths.push(dir.join("bundle"));
+    paths.extend(subdirectories(&dir).into_iter().map(|d| d.join("bundle")));
+  }
+  paths
+}
+
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+  read_dir(dir)
+    .map(|entries| {
+      entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_dir())
+        .collect()
+    })
+    .unwrap_or_default()
+}
@@ -0,0 +1,99 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{Cli, Result};
+use anyhow::Context;
+use clap::{Command, IntoApp, Parser};
+use clap_complete::{generate, Shell};
+use log::info;
+use tauri_bundler::PackageType;
+
+use std::{io::Write, path::PathBuf};
+
+#[derive(Debug, Parser)]
+#[clap(about = "Generate the shell completions of the Tauri CLI")]
+pub struct Options {
+  /// Shell to generate the completions for
+  #[clap(short, long, value_enum)]
+  shell: Shell,
+  /// Write the completions to the given file instead of the standard output
+  #[clap(short, long)]
+  output: Option<PathBuf>,
+}
+
+pub fn command(options: Options, bin_name: Option<&str>) -> Result<()> {
+  // `cargo tauri` is completed by the completions of the `cargo-tauri` binary
+  let bin_name = match bin_name {
+    Some(name) if name.starts_with("cargo") => "cargo-tauri",
+    _ => "tauri",
+  };
+
+  // the values are listed when the completions are generated,
+  // so the script must be generated again after installing a Rust target
+  let bundles = PackageType::all()
+    .iter()
+    .map(|t| t.short_name())
+    .chain(std::iter::once("none"))
+    .collect::<Vec<_>>();
+  let targets = installed_targets();
+  let targets = targets.iter().map(String::as_str).collect::<Vec<_>>();
+
+  let mut cmd = Cli::command()
+    .bin_name(bin_name)
+    .mut_subcommand("build", |build| {
+      complete_values(
+        complete_values(build, "bundles", &bundles),
+        "target",
+        &targets,
+      )
+    })
+    .mut_subcommand("dev", |dev| complete_values(dev, "target", &targets))
+    .mut_subcommand("inspect", |inspect| {
+      inspect.mut_subcommand("bundle-config", |bundle_config| {
+        complete_values(
+          complete_values(bundle_config, "bundles", &bundles),
+          "target",
+          &targets,
+        )
+      })
+    });
+
+  let mut completions = Vec::new();
+  generate(options.shell, &mut cmd, bin_name, &mut completions);
+  match options.output {
+    Some(path) => {
+      std::fs::write(&path, completions)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+      info!(action = "Generated"; "{} completions at {}", options.shell, path.display());
+    }
+    None => std::io::stdout().write_all(&completions)?,
+  }
+  Ok(())
+}
+
+/// Completes the values of the argument of the command, which must exist.
+fn complete_values<'a>(cmd: Command<'a>, arg: &'static str, values: &[&'a str]) -> Command<'a> {
+  debug_assert!(cmd.get_arguments().any(|a| a.get_id() == arg));
+  if values.is_empty() {
+    return cmd;
+  }
+  cmd.mut_arg(arg, |a| a.possible_values(values.iter().copied()))
+}
+
+/// The Rust targets installed with rustup, empty if rustup is not available.
+fn installed_targets() -> Vec<String> {
+  std::process::Command::new("rustup")
+    .args(["target", "list", "--installed"])
+    .output()
+    .ok()
+    .filter(|output| output.status.success())
+    .map(|output| {
+      String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+    })
+    .unwrap_or_default()
+}
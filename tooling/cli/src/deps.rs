@@ -0,0 +1,309 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! `tauri deps`, auditing the Tauri dependencies of the Cargo manifest and the package.json.
+//!
+//! The Tauri crates and the `@tauri-apps` packages are released together, so they must share their minor version,
+//! and the plugins must target the same major version.
+
+use crate::{
+  helpers::app_paths::{app_dir, tauri_dir},
+  interface::rust::{
+    manifest::{read_manifest, set_dependency_version},
+    CargoSettings,
+  },
+  Result,
+};
+use anyhow::Context;
+use clap::Parser;
+use colored::Colorize;
+use log::{info, warn};
+use semver::VersionReq;
+use serde_json::Value;
+
+use std::path::Path;
+
+/// The crates released with the CLI.
+const CORE_CRATES: &[&str] = &["tauri", "tauri-build"];
+/// The npm packages released with the CLI.
+const CORE_PACKAGES: &[&str] = &["@tauri-apps/api", "@tauri-apps/cli"];
+
+#[derive(Debug, Parser)]
+#[clap(
+  about = "Lists the Tauri dependencies of the project and reports the versions that do not match"
+)]
+pub struct Options {
+  /// Update the mismatched Tauri dependencies in place to the newest version used by the project
+  #[clap(long)]
+  update: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+  Cargo,
+  Npm,
+}
+
+#[derive(Debug)]
+struct Dependency {
+  name: String,
+  source: Source,
+  /// The table declaring the dependency, e.g. `build-dependencies` or `devDependencies`.
+  table: &'static str,
+  /// The version requirement, `None` for path and git dependencies.
+  requirement: Option<String>,
+}
+
+impl Dependency {
+  fn is_core(&self) -> bool {
+    match self.source {
+      Source::Cargo => CORE_CRATES.contains(&self.name.as_str()),
+      Source::Npm => CORE_PACKAGES.contains(&self.name.as_str()),
+    }
+  }
+
+  fn is_plugin(&self) -> bool {
+    self.name.starts_with("tauri-plugin-") || self.name.starts_with("@tauri-apps/plugin-")
+  }
+
+  /// The `major.minor` version of the lowest version matching the requirement.
+  fn minor_version(&self) -> Option<(u64, u64)> {
+    let requirement = VersionReq::parse(self.requirement.as_deref()?).ok()?;
+    let comparator = requirement.comparators.first()?;
+    Some((comparator.major, comparator.minor.unwrap_or_default()))
+  }
+
+  /// Whether the dependency does not match the `major.minor` version of the core dependencies.
+  fn is_mismatched(&self, (major, minor): (u64, u64)) -> bool {
+    match self.minor_version() {
+      Some(version) if self.is_core() => version != (major, minor),
+      Some((plugin_major, _)) if self.is_plugin() => plugin_major != major,
+      _ => false,
+    }
+  }
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let tauri_dir = tauri_dir();
+  let package_json_path = app_dir().join("package.json");
+
+  let mut dependencies = cargo_dependencies(&tauri_dir)?;
+  if package_json_path.exists() {
+    dependencies.extend(npm_dependencies(&read_package_json(&package_json_path)?));
+  }
+
+  let version = target_version(&dependencies);
+  for source in [Source::Cargo, Source::Npm] {
+    let mut dependencies = dependencies
+      .iter()
+      .filter(|d| d.source == source)
+      .peekable();
+    if dependencies.peek().is_none() {
+      continue;
+    }
+    println!();
+    println!(
+      "{}",
+      match source {
+        Source::Cargo => "Cargo.toml",
+        Source::Npm => "package.json",
+      }
+      .yellow()
+      .bold()
+    );
+    for dependency in dependencies {
+      print!("{} {}: ", "›".cyan(), dependency.name.bold());
+      print!(
+        "{}",
+        dependency.requirement.as_deref().unwrap_or("path or git")
+      );
+      if version.map_or(false, |v| dependency.is_mismatched(v)) {
+        print!(" ({})", "mismatched".red());
+      }
+      println!();
+    }
+  }
+  println!();
+
+  let version = match version {
+    Some(version) => version,
+    None => {
+      warn!("No versioned Tauri dependency found.");
+      return Ok(());
+    }
+  };
+  let mismatched = dependencies
+    .iter()
+    .filter(|d| d.is_mismatched(version))
+    .collect::<Vec<_>>();
+  if mismatched.is_empty() {
+    info!(
+      "The Tauri dependencies match version {}.{}.",
+      version.0, version.1
+    );
+    return Ok(());
+  }
+
+  for dependency in &mismatched {
+    warn!(
+      "`{}` {} does not match the Tauri version {}.{}.",
+      dependency.name,
+      dependency.requirement.as_deref().unwrap_or_default(),
+      version.0,
+      version.1
+    );
+  }
+  if !options.update {
+    info!("Run `tauri deps --update` to update the Tauri dependencies.");
+    return Ok(());
+  }
+
+  let core = mismatched
+    .iter()
+    .filter(|d| d.is_core())
+    .collect::<Vec<_>>();
+  if core.iter().any(|d| d.source == Source::Cargo) {
+    let manifest_path = tauri_dir.join("Cargo.toml");
+    let mut manifest = read_manifest(&manifest_path)?;
+    for dependency in core.iter().filter(|d| d.source == Source::Cargo) {
+      let requirement = format!("{}.{}", version.0, version.1);
+      set_dependency_version(
+        &mut manifest,
+        dependency.table,
+        &dependency.name,
+        &requirement,
+      );
+      info!(action = "Updated"; "`{}` to {} in Cargo.toml", dependency.name, requirement);
+    }
+    std::fs::write(&manifest_path, manifest.to_string())
+      .with_context(|| format!("failed to write {}", manifest_path.display()))?;
+  }
+  if core.iter().any(|d| d.source == Source::Npm) {
+    let mut package_json = read_package_json(&package_json_path)?;
+    for dependency in core.iter().filter(|d| d.source == Source::Npm) {
+      let requirement = format!("^{}.{}.0", version.0, version.1);
+      package_json[dependency.table][&dependency.name] = Value::String(requirement.clone());
+      info!(action = "Updated"; "`{}` to {} in package.json", dependency.name, requirement);
+    }
+    std::fs::write(
+      &package_json_path,
+      serde_json::to_string_pretty(&package_json)? + "\n",
+    )
+    .with_context(|| format!("failed to write {}", package_json_path.display()))?;
+  }
+  for plugin in mismatched.iter().filter(|d| !d.is_core()) {
+    warn!(
+      "Update `{}` to a version targeting Tauri {} manually.",
+      plugin.name, version.0
+    );
+  }
+  if !core.is_empty() {
+    info!("Run `cargo update` and install the frontend dependencies to apply the new versions.");
+  }
+
+  Ok(())
+}
+
+/// The newest `major.minor` version of the core dependencies, which the other dependencies must match.
+fn target_version(dependencies: &[Dependency]) -> Option<(u64, u64)> {
+  dependencies
+    .iter()
+    .filter(|d| d.is_core())
+    .filter_map(Dependency::minor_version)
+    .max()
+}
+
+fn cargo_dependencies(tauri_dir: &Path) -> Result<Vec<Dependency>> {
+  let settings = CargoSettings::load(tauri_dir)?;
+  let mut dependencies = Vec::new();
+  for (table, table_dependencies) in [
+    ("dependencies", settings.dependencies),
+    ("build-dependencies", settings.build_dependencies),
+  ] {
+    let mut table_dependencies = table_dependencies
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|(name, _)| CORE_CRATES.contains(&name.as_str()) || name.starts_with("tauri-plugin-"))
+      .collect::<Vec<_>>();
+    table_dependencies.sort_by(|(a, _), (b, _)| a.cmp(b));
+    dependencies.extend(
+      table_dependencies
+        .into_iter()
+        .map(|(name, dependency)| Dependency {
+          requirement: dependency.version().map(Into::into),
+          name,
+          source: Source::Cargo,
+          table,
+        }),
+    );
+  }
+  Ok(dependencies)
+}
+
+fn read_package_json(path: &Path) -> Result<Value> {
+  serde_json::from_str(
+    &std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?,
+  )
+  .with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn npm_dependencies(package_json: &Value) -> Vec<Dependency> {
+  let mut dependencies = Vec::new();
+  for table in ["dependencies", "devDependencies"] {
+    if let Some(table_dependencies) = package_json.get(table).and_then(Value::as_object) {
+      for (name, requirement) in table_dependencies {
+        let dependency = Dependency {
+          name: name.clone(),
+          source: Source::Npm,
+          table,
+          // `file:`, `link:` and git requirements are not semver ranges
+          requirement: requirement
+            .as_str()
+            .filter(|r| VersionReq::parse(r).is_ok())
+            .map(Into::into),
+        };
+        if dependency.is_core() || dependency.is_plugin() {
+          dependencies.push(dependency);
+        }
+      }
+    }
+  }
+  dependencies
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{npm_dependencies, target_version, Dependency, Source};
+  use serde_json::json;
+
+  #[test]
+  fn reports_mismatched_dependencies() {
+    let mut dependencies = npm_dependencies(&json!({
+      "dependencies": { "@tauri-apps/api": "^1.0.0", "tauri-plugin-store-api": "github:tauri-apps/tauri-plugin-store" },
+      "devDependencies": { "@tauri-apps/cli": "^1.2.1", "vite": "^3.0.0" }
+    }));
+    dependencies.push(Dependency {
+      name: "tauri".into(),
+      source: Source::Cargo,
+      table: "dependencies",
+      requirement: Some("1.2".into()),
+    });
+    dependencies.push(Dependency {
+      name: "tauri-plugin-sql".into(),
+      source: Source::Cargo,
+      table: "dependencies",
+      requirement: Some("0.1".into()),
+    });
+    assert_eq!(dependencies.len(), 5);
+
+    let version = target_version(&dependencies).unwrap();
+    assert_eq!(version, (1, 2));
+    let mismatched = dependencies
+      .iter()
+      .filter(|d| d.is_mismatched(version))
+      .map(|d| d.name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(mismatched, ["@tauri-apps/api", "tauri-plugin-sql"]);
+  }
+}
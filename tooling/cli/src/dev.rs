@@ -6,39 +6,56 @@ use crate::{
   helpers::{
     app_paths::{app_dir, tauri_dir},
     command_env,
-    config::{get as get_config, AppUrl, BeforeDevCommand, WindowUrl},
+    config::{
+      apply_binary_config, get as get_config, merge_config_args, reload as reload_config, AppUrl,
+      BeforeDevCommand, DevProfileConfig, DevServerProbeConfig, WindowUrl,
+    },
+    env_file,
   },
-  interface::{AppInterface, DevProcess, ExitReason, Interface},
+  interface::{manifest::check_manifest, AppInterface, DevProcess, ExitReason, Interface},
   CommandExt, Result,
 };
 use clap::Parser;
+use output::{Source, Stream};
 
 use anyhow::{bail, Context};
-use log::{error, info, warn};
+use log::{debug, error, info, warn};
 use once_cell::sync::OnceCell;
 use shared_child::SharedChild;
 
 use std::{
   env::set_current_dir,
+  net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+  path::PathBuf,
   process::{exit, Command, ExitStatus, Stdio},
   sync::{
     atomic::{AtomicBool, Ordering},
     Arc, Mutex,
   },
+  time::{Duration, Instant},
 };
 
 static BEFORE_DEV: OnceCell<Mutex<Arc<SharedChild>>> = OnceCell::new();
 static KILL_BEFORE_DEV_FLAG: OnceCell<AtomicBool> = OnceCell::new();
 
+/// Default number of consecutive crashes after which `tauri dev` stops restarting a process.
+const DEFAULT_CRASH_RESTART_LIMIT: u32 = 3;
+/// A crash counts towards the restart limit when it happens this soon after the previous (re)start.
+const CRASH_LOOP_WINDOW: Duration = Duration::from_secs(10);
+const BEFORE_DEV_RESTART_DELAY: Duration = Duration::from_secs(1);
+
 #[cfg(unix)]
 const KILL_CHILDREN_SCRIPT: &[u8] = include_bytes!("../scripts/kill-children.sh");
 
+pub mod events;
+pub mod output;
+
 pub const TAURI_DEV_WATCHER_GITIGNORE: &[u8] = include_bytes!("../tauri-dev-watcher.gitignore");
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Tauri dev", trailing_var_arg(true))]
 pub struct Options {
-  /// Binary to use to run the application
+  /// Binary to use to run the application, defaults to `cargo`. Can also be a full build command such as `cargo zigbuild`, prefixed by `KEY=VALUE` environment variables
   #[clap(short, long)]
   pub runner: Option<String>,
   /// Target triple to build against
@@ -47,33 +64,101 @@ pub struct Options {
   /// List of cargo features to activate
   #[clap(short, long, multiple_occurrences(true), multiple_values(true))]
   pub features: Option<Vec<String>>,
+  /// Build and run the given `[[bin]]` target of an app with several binaries instead of the default one.
+  /// Its `build > binaries` features and windows are applied
+  #[clap(long, value_name = "NAME")]
+  pub bin: Option<String>,
+  /// Build and run the given cargo example as the app, e.g. a demo app in the `examples` folder of a plugin or library crate
+  #[clap(long, value_name = "NAME", conflicts_with = "bin")]
+  pub example: Option<String>,
   /// Exit on panic
   #[clap(short, long)]
   pub exit_on_panic: bool,
-  /// JSON string or path to JSON file to merge with tauri.conf.json
-  #[clap(short, long)]
-  pub config: Option<String>,
+  /// JSON string or path to a JSON, JSON5 or TOML file to merge with tauri.conf.json. Can be used multiple times, the later values take precedence
+  #[clap(short, long, multiple_occurrences(true))]
+  pub config: Vec<String>,
   /// Run the code in release mode
   #[clap(long = "release")]
   pub release_mode: bool,
+  /// Build the app with the given cargo profile instead of `dev`, e.g. a `dev-opt` profile defined in Cargo.toml
+  #[clap(long, value_name = "PROFILE", conflicts_with = "release-mode")]
+  pub cargo_profile: Option<String>,
   /// Command line arguments passed to the runner. Arguments after `--` are passed to the application.
   pub args: Vec<String>,
   /// Disable the file watcher
   #[clap(long)]
   pub no_watch: bool,
+  /// Delay in milliseconds used to debounce file changes before restarting the app. Overrides `build > devWatcherDebounce`.
+  #[clap(long, value_name = "MS")]
+  pub watch_debounce: Option<u64>,
+  /// Use a polling file watcher instead of native file system events. Useful on Docker, WSL and network file systems.
+  #[clap(long)]
+  pub watch_poll: bool,
+  /// Time in milliseconds the app has to exit when the watcher restarts it before being killed. On Unix the app receives SIGTERM first. Defaults to 3000, use 0 to kill it immediately.
+  #[clap(long, value_name = "MS")]
+  pub restart_grace_period: Option<u64>,
+  /// Start the app without waiting for the dev server at `devPath` to be ready
+  #[clap(long)]
+  pub no_dev_server_wait: bool,
+  /// Open the devtools when the app windows are created. Requires a debug build or the `devtools` feature
+  #[clap(long)]
+  pub open_devtools: bool,
+  /// The output format of the dev messages. `json` prints the dev lifecycle events to stdout as JSON lines
+  #[clap(long, value_enum, default_value = "human")]
+  pub message_format: events::MessageFormat,
+  /// Use the next free port when the `devPath` port is already in use before the beforeDevCommand starts the dev server.
+  /// The port is exposed to the beforeDevCommand as the `TAURI_DEV_SERVER_PORT` environment variable
+  #[clap(long)]
+  pub auto_port: bool,
+  /// Number of times in a row the app or the beforeDevCommand is restarted after crashing right after starting. Defaults to 3.
+  ///
+  /// The watcher then waits for a file change before restarting the app, and a crashing beforeDevCommand stops `tauri dev`
+  #[clap(long, value_name = "N")]
+  pub crash_restart_limit: Option<u32>,
+  /// Load environment variables from the given file, in addition to the `.env` and `.env.local` files of the app directory. Can be used multiple times.
+  ///
+  /// Variables already set on the environment are not overridden.
+  #[clap(long = "env-file", multiple_occurrences(true))]
+  pub env_files: Vec<PathBuf>,
+  /// Name of the `build > devProfiles` entry to use. Its devPath, features, environment variables and arguments are applied on top of the current options
+  #[clap(long)]
+  pub profile: Option<String>,
+  /// Prefix the output of the build, the app and the beforeDevCommand with the time elapsed since `tauri dev` started
+  #[clap(long)]
+  pub log_timestamps: bool,
+  /// Shell command to run after each successful build, before the app is started. Overrides `build > afterDevBuildCommand`
+  #[clap(long)]
+  pub after_build_command: Option<String>,
+  /// Expose the webview remote debugging server on this port so the app can be inspected from a browser.
+  /// Uses `WEBKIT_INSPECTOR_SERVER` on Linux and `--remote-debugging-port` for WebView2 on Windows. Not supported on macOS
+  #[clap(long, value_name = "PORT")]
+  pub remote_debugging_port: Option<u16>,
+  /// The address the remote debugging server listens on. Use `0.0.0.0` to inspect the app from another machine. Only supported on Linux
+  #[clap(long, value_name = "ADDRESS", requires = "remote-debugging-port")]
+  pub remote_debugging_address: Option<String>,
+  /// Compile the app through sccache by setting `RUSTC_WRAPPER`. Overrides `build > useSccache`
+  #[clap(long)]
+  pub use_sccache: bool,
+  /// Print the changes to the features of the `tauri` and `tauri-build` dependencies that would be made to Cargo.toml, as a unified diff, and exit without running the app
+  #[clap(long)]
+  pub dry_run: bool,
+  /// Fail instead of rewriting Cargo.toml when the features of the `tauri` and `tauri-build` dependencies don't match the configuration
+  #[clap(long)]
+  pub frozen_manifest: bool,
 }
 
 pub fn command(options: Options) -> Result<()> {
   let r = command_internal(options);
-  if r.is_err() {
-    kill_before_dev_process();
-    #[cfg(not(debug_assertions))]
-    let _ = check_for_updates();
-  }
+  // the dev command only returns on failure or when the user quits
+  kill_before_dev_process();
+  #[cfg(not(debug_assertions))]
+  let _ = check_for_updates();
   r
 }
 
 fn command_internal(mut options: Options) -> Result<()> {
+  events::set_message_format(options.message_format);
+  output::set_timestamps(options.log_timestamps);
   let mut interface = setup(&mut options)?;
   let exit_on_panic = options.exit_on_panic;
   let no_watch = options.no_watch;
@@ -83,20 +168,117 @@ fn command_internal(mut options: Options) -> Result<()> {
 }
 
 pub fn setup(options: &mut Options) -> Result<AppInterface> {
+  env_file::load(&options.env_files)?;
+
   let tauri_path = tauri_dir();
-  options.config = if let Some(config) = &options.config {
-    Some(if config.starts_with('{') {
-      config.to_string()
-    } else {
-      std::fs::read_to_string(&config).with_context(|| "failed to read custom configuration")?
-    })
-  } else {
-    None
-  };
+  // the options forwarded to the interface hold the merged configuration
+  options.config = merge_config_args(&options.config)?.into_iter().collect();
 
   set_current_dir(&tauri_path).with_context(|| "failed to change current working directory")?;
 
-  let config = get_config(options.config.as_deref())?;
+  let mut config = get_config(
+    options.release_mode,
+    options.config.first().map(String::as_str),
+  )?;
+
+  {
+    let config = config.lock().unwrap();
+    check_manifest(
+      config.as_ref().unwrap(),
+      options.dry_run,
+      options.frozen_manifest,
+    )?;
+  }
+  if options.dry_run {
+    exit(0);
+  }
+
+  if let Some(name) = options.profile.clone() {
+    let profile = {
+      let config = config.lock().unwrap();
+      let profiles = config
+        .as_ref()
+        .unwrap()
+        .build
+        .dev_profiles
+        .clone()
+        .unwrap_or_default();
+      match profiles.get(&name) {
+        Some(profile) => profile.clone(),
+        None => {
+          let mut available = profiles.keys().map(|k| k.as_str()).collect::<Vec<_>>();
+          available.sort_unstable();
+          bail!(
+            "dev profile `{}` not found in `build > devProfiles` (available profiles: {})",
+            name,
+            if available.is_empty() {
+              "none".into()
+            } else {
+              available.join(", ")
+            }
+          );
+        }
+      }
+    };
+    info!(action = "Using"; "dev profile `{}`", name);
+    let dev_path = profile.dev_path.clone();
+    apply_dev_profile(options, profile);
+    if let Some(dev_path) = dev_path {
+      merge_build_config(options, "devPath", serde_json::to_value(dev_path)?)?;
+      config = reload_config(
+        options.release_mode,
+        options.config.first().map(String::as_str),
+      )?;
+    }
+  }
+
+  if let Some(bin) = options.bin.clone() {
+    let reload = apply_binary_config(
+      config.lock().unwrap().as_ref().unwrap(),
+      &bin,
+      &mut options.features,
+      &mut options.config,
+    )?;
+    if reload {
+      config = reload_config(
+        options.release_mode,
+        options.config.first().map(String::as_str),
+      )?;
+    }
+  }
+
+  let dev_server_url = {
+    let config = config.lock().unwrap();
+    let build = &config.as_ref().unwrap().build;
+    match (&build.before_dev_command, &build.dev_path) {
+      (Some(_), AppUrl::Url(WindowUrl::External(url))) => Some(url.clone()),
+      _ => None,
+    }
+  };
+  if let Some(dev_server_url) = dev_server_url {
+    let dev_server_url = match check_dev_server_port(&dev_server_url, options.auto_port)? {
+      Some(url) => {
+        merge_build_config(options, "devPath", url.to_string().into())?;
+        config = reload_config(
+          options.release_mode,
+          options.config.first().map(String::as_str),
+        )?;
+        url
+      }
+      None => dev_server_url,
+    };
+    if let Some(port) = dev_server_url.port_or_known_default() {
+      std::env::set_var("TAURI_DEV_SERVER_PORT", port.to_string());
+    }
+  }
+
+  if options.remote_debugging_port.is_some() {
+    if cfg!(target_os = "macos") {
+      warn!("The webview remote debugging port is not supported on macOS, use the Develop menu of Safari to inspect the app instead.");
+    } else if cfg!(windows) && options.remote_debugging_address.is_some() {
+      warn!("WebView2 only exposes the remote debugging port on localhost, `--remote-debugging-address` is ignored.");
+    }
+  }
 
   let interface = AppInterface::new(config.lock().unwrap().as_ref().unwrap())?;
 
@@ -158,27 +340,16 @@ pub fn setup(options: &mut Options) -> Result<AppInterface> {
         }
       } else {
         command.stdin(Stdio::piped());
-        command.stdout(os_pipe::dup_stdout()?);
-        command.stderr(os_pipe::dup_stderr()?);
-
-        let child = SharedChild::spawn(&mut command)
-          .unwrap_or_else(|_| panic!("failed to run `{}`", before_dev));
-        let child = Arc::new(child);
-        let child_ = child.clone();
-
-        std::thread::spawn(move || {
-          let status = child_
-            .wait()
-            .expect("failed to wait on \"beforeDevCommand\"");
-          if !(status.success() || KILL_BEFORE_DEV_FLAG.get().unwrap().load(Ordering::Relaxed)) {
-            error!("The \"beforeDevCommand\" terminated with a non-zero status code.");
-            exit(status.code().unwrap_or(1));
-          }
-        });
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::piped());
 
+        let child = spawn_before_dev(&mut command, &before_dev)?;
         BEFORE_DEV.set(Mutex::new(child)).unwrap();
         KILL_BEFORE_DEV_FLAG.set(AtomicBool::default()).unwrap();
 
+        let crash_loop = CrashLoop::new(options.crash_restart_limit);
+        std::thread::spawn(move || supervise_before_dev(command, before_dev, crash_loop));
+
         let _ = ctrlc::set_handler(move || {
           kill_before_dev_process();
           #[cfg(not(debug_assertions))]
@@ -200,6 +371,9 @@ pub fn setup(options: &mut Options) -> Result<AppInterface> {
       .clone();
   }
 
+  options.use_sccache =
+    options.use_sccache || config.lock().unwrap().as_ref().unwrap().build.use_sccache;
+
   let mut cargo_features = config
     .lock()
     .unwrap()
@@ -213,66 +387,148 @@ pub fn setup(options: &mut Options) -> Result<AppInterface> {
     cargo_features.extend(features.clone());
   }
 
-  if std::env::var_os("TAURI_SKIP_DEVSERVER_CHECK") != Some("true".into()) {
-    if let AppUrl::Url(WindowUrl::External(dev_server_url)) = config
-      .lock()
-      .unwrap()
-      .as_ref()
-      .unwrap()
-      .build
-      .dev_path
-      .clone()
-    {
-      let host = dev_server_url
-        .host()
-        .unwrap_or_else(|| panic!("No host name in the URL"));
-      let port = dev_server_url
-        .port_or_known_default()
-        .unwrap_or_else(|| panic!("No port number in the URL"));
-      let addrs;
-      let addr;
-      let addrs = match host {
-        url::Host::Domain(domain) => {
-          use std::net::ToSocketAddrs;
-          addrs = (domain, port).to_socket_addrs()?;
-          addrs.as_slice()
-        }
-        url::Host::Ipv4(ip) => {
-          addr = (ip, port).into();
-          std::slice::from_ref(&addr)
-        }
-        url::Host::Ipv6(ip) => {
-          addr = (ip, port).into();
-          std::slice::from_ref(&addr)
-        }
-      };
-      let mut i = 0;
-      let sleep_interval = std::time::Duration::from_secs(2);
-      let max_attempts = 90;
-      loop {
-        if std::net::TcpStream::connect(addrs).is_ok() {
-          break;
-        }
-        if i % 3 == 1 {
-          warn!(
-            "Waiting for your frontend dev server to start on {}...",
-            dev_server_url
-          );
+  if !options.no_dev_server_wait
+    && std::env::var_os("TAURI_SKIP_DEVSERVER_CHECK") != Some("true".into())
+  {
+    let (dev_path, probe) = {
+      let config = config.lock().unwrap();
+      let build = &config.as_ref().unwrap().build;
+      (
+        build.dev_path.clone(),
+        build.dev_server_probe.clone().unwrap_or_default(),
+      )
+    };
+    if let AppUrl::Url(WindowUrl::External(dev_server_url)) = dev_path {
+      wait_dev_server(&dev_server_url, &probe)?;
+    }
+  }
+
+  Ok(interface)
+}
+
+/// Checks whether the port of a local dev server is already in use before the `beforeDevCommand` starts it.
+///
+/// Returns the URL updated with the next free port when `auto_port` is set.
+fn check_dev_server_port(dev_server_url: &url::Url, auto_port: bool) -> Result<Option<url::Url>> {
+  let is_local = match dev_server_url.host() {
+    Some(url::Host::Domain(domain)) => domain == "localhost",
+    Some(url::Host::Ipv4(ip)) => ip.is_loopback() || ip.is_unspecified(),
+    Some(url::Host::Ipv6(ip)) => ip.is_loopback() || ip.is_unspecified(),
+    None => false,
+  };
+  let port = match dev_server_url.port_or_known_default() {
+    Some(port) if is_local => port,
+    _ => return Ok(None),
+  };
+  if !port_in_use(port) {
+    return Ok(None);
+  }
+
+  if !auto_port {
+    warn!(
+      "Port {} is already in use, so the app might load another server instead of the one started by the beforeDevCommand. Use `--auto-port` to pick a free port.",
+      port
+    );
+    return Ok(None);
+  }
+
+  let free_port = (port.saturating_add(1)..=port.saturating_add(100))
+    .find(|p| !port_in_use(*p))
+    .with_context(|| {
+      format!(
+        "failed to find a free port for the dev server after {}",
+        port
+      )
+    })?;
+  let mut url = dev_server_url.clone();
+  url
+    .set_port(Some(free_port))
+    .map_err(|_| anyhow::anyhow!("failed to set the port of `{}`", dev_server_url))?;
+  info!(
+    "Port {} is already in use, using {} for the dev server",
+    port, url
+  );
+  Ok(Some(url))
+}
+
+fn port_in_use(port: u16) -> bool {
+  TcpStream::connect_timeout(
+    &SocketAddr::from(([127, 0, 0, 1], port)),
+    Duration::from_millis(200),
+  )
+  .is_ok()
+    || TcpListener::bind(("127.0.0.1", port)).is_err()
+}
+
+/// Polls the dev server until it is ready according to the `build > devServerProbe` configuration.
+fn wait_dev_server(dev_server_url: &url::Url, probe: &DevServerProbeConfig) -> Result<()> {
+  let host = dev_server_url
+    .host()
+    .with_context(|| format!("No host name in the URL `{}`", dev_server_url))?;
+  let port = dev_server_url
+    .port_or_known_default()
+    .with_context(|| format!("No port number in the URL `{}`", dev_server_url))?;
+  let addrs: Vec<SocketAddr> = match host {
+    url::Host::Domain(domain) => (domain, port).to_socket_addrs()?.collect(),
+    url::Host::Ipv4(ip) => vec![(ip, port).into()],
+    url::Host::Ipv6(ip) => vec![(ip, port).into()],
+  };
+
+  let timeout = Duration::from_millis(probe.timeout);
+  let interval = Duration::from_millis(probe.interval);
+  let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+  let attempts = probe.retries.max(1);
+  let mut last_error = String::new();
+
+  for attempt in 1..=attempts {
+    let result = match probe.expected_status {
+      Some(expected_status) => match agent.get(dev_server_url.as_str()).call() {
+        Ok(response) | Err(ureq::Error::Status(_, response))
+          if response.status() == expected_status =>
+        {
+          Ok(())
         }
-        i += 1;
-        if i == max_attempts {
-          error!(
-            "Could not connect to `{}` after {}s. Please make sure that is the URL to your dev server.",
-            dev_server_url, i * sleep_interval.as_secs()
-          );
-          exit(1);
+        Ok(response) | Err(ureq::Error::Status(_, response)) => Err(format!(
+          "expected status {} but got {}",
+          expected_status,
+          response.status()
+        )),
+        Err(e) => Err(e.to_string()),
+      },
+      None => {
+        if addrs
+          .iter()
+          .any(|addr| TcpStream::connect_timeout(addr, timeout).is_ok())
+        {
+          Ok(())
+        } else {
+          Err("connection refused".to_string())
         }
-        std::thread::sleep(sleep_interval);
       }
+    };
+
+    match result {
+      Ok(()) => return Ok(()),
+      Err(e) => last_error = e,
+    }
+
+    if attempt % 3 == 2 {
+      warn!(
+        "Waiting for your frontend dev server to start on {}...",
+        dev_server_url
+      );
+    }
+    if attempt < attempts {
+      std::thread::sleep(interval);
     }
   }
 
-  Ok(interface)
+  bail!(
+    "Could not connect to `{}` after {} attempts ({}). Please make sure that is the URL to your dev server, or use `--no-dev-server-wait` to skip this check.",
+    dev_server_url,
+    attempts,
+    last_error
+  )
 }
 
 pub fn wait_dev_process<
@@ -284,21 +540,24 @@ pub fn wait_dev_process<
 ) {
   std::thread::spawn(move || {
     let status = child.wait().expect("failed to wait on app");
-    on_exit(
-      status,
-      if child.manually_killed_process() {
-        ExitReason::TriggeredKill
-      } else {
-        ExitReason::NormalExit
-      },
-    );
+    let reason = if child.manually_killed_process() {
+      ExitReason::TriggeredKill
+    } else {
+      ExitReason::NormalExit
+    };
+    events::emit(events::Event::AppExited {
+      code: status.code(),
+      reason,
+    });
+    on_exit(status, reason);
   });
 }
 
 pub fn on_app_exit(status: ExitStatus, reason: ExitReason, exit_on_panic: bool, no_watch: bool) {
+  // in watch mode a crashed app is restarted by the watcher unless `--exit-on-panic` is set
   if no_watch
     || (!matches!(reason, ExitReason::TriggeredKill)
-      && (exit_on_panic || matches!(reason, ExitReason::NormalExit)))
+      && (exit_on_panic || (matches!(reason, ExitReason::NormalExit) && status.success())))
   {
     kill_before_dev_process();
     #[cfg(not(debug_assertions))]
@@ -325,6 +584,169 @@ fn check_for_updates() -> Result<()> {
   Ok(())
 }
 
+/// Sets `build > {key}` on the configuration merged with tauri.conf.json.
+fn merge_build_config(options: &mut Options, key: &str, value: serde_json::Value) -> Result<()> {
+  let mut merge_config = match options.config.first() {
+    Some(config) => serde_json::from_str(config)?,
+    None => serde_json::json!({}),
+  };
+  merge_config["build"][key] = value;
+  options.config = vec![merge_config.to_string()];
+  Ok(())
+}
+
+/// Applies the features, environment variables and arguments of a dev profile.
+fn apply_dev_profile(options: &mut Options, profile: DevProfileConfig) {
+  for (key, value) in profile.env {
+    std::env::set_var(key, value);
+  }
+  if let Some(features) = profile.features {
+    options
+      .features
+      .get_or_insert_with(Vec::new)
+      .extend(features);
+  }
+  // keep the runner arguments before the app arguments (after `--`) of both lists
+  let (mut args, app_args) = split_app_args(std::mem::take(&mut options.args));
+  let (profile_args, profile_app_args) = split_app_args(profile.args);
+  args.extend(profile_args);
+  if !(app_args.is_empty() && profile_app_args.is_empty()) {
+    args.push("--".into());
+    args.extend(app_args);
+    args.extend(profile_app_args);
+  }
+  options.args = args;
+}
+
+fn split_app_args(mut args: Vec<String>) -> (Vec<String>, Vec<String>) {
+  match args.iter().position(|a| a == "--") {
+    Some(index) => {
+      let app_args = args.split_off(index + 1);
+      args.pop();
+      (args, app_args)
+    }
+    None => (args, Vec::new()),
+  }
+}
+
+/// Spawns the `beforeDevCommand`, forwarding its output with the `[web]` prefix.
+fn spawn_before_dev(command: &mut Command, before_dev: &str) -> Result<Arc<SharedChild>> {
+  let child =
+    SharedChild::spawn(command).with_context(|| format!("failed to run `{}`", before_dev))?;
+  output::forward(
+    child.take_stdout().unwrap(),
+    Source::Web,
+    Stream::Stdout,
+    |_| (),
+  );
+  output::forward(
+    child.take_stderr().unwrap(),
+    Source::Web,
+    Stream::Stderr,
+    |_| (),
+  );
+  Ok(Arc::new(child))
+}
+
+/// Waits on the `beforeDevCommand` and restarts it whenever it fails, unless the CLI is shutting down.
+/// A command exiting successfully is done, e.g. a one-shot codegen or initial build, and is not restarted.
+///
+/// If the command keeps failing right after it starts, it is not restarted anymore
+/// and `tauri dev` terminates as there is nothing left to serve the app.
+fn supervise_before_dev(mut command: Command, before_dev: String, mut crash_loop: CrashLoop) {
+  loop {
+    let child = BEFORE_DEV.get().unwrap().lock().unwrap().clone();
+    let status = child
+      .wait()
+      .expect("failed to wait on \"beforeDevCommand\"");
+    if KILL_BEFORE_DEV_FLAG.get().unwrap().load(Ordering::Relaxed) {
+      break;
+    }
+    if status.success() {
+      debug!("The \"beforeDevCommand\" finished successfully.");
+      break;
+    }
+
+    if !crash_loop.crashed() {
+      error!("The \"beforeDevCommand\" terminated with a non-zero status code.");
+      kill_before_dev_process();
+      exit(status.code().unwrap_or(1));
+    }
+
+    warn!(
+      "The \"beforeDevCommand\" {}, restarting it...",
+      status
+        .code()
+        .map(|code| format!("exited with status code {}", code))
+        .unwrap_or_else(|| "was terminated by a signal".into())
+    );
+    std::thread::sleep(BEFORE_DEV_RESTART_DELAY);
+
+    let child = match spawn_before_dev(&mut command, &before_dev) {
+      Ok(child) => child,
+      Err(e) => {
+        error!("{:#}", e);
+        kill_before_dev_process();
+        exit(1);
+      }
+    };
+    // the lock is held by `kill_before_dev_process` while it sets the kill flag,
+    // so checking it here guarantees the new child is either registered or killed
+    let mut current = BEFORE_DEV.get().unwrap().lock().unwrap();
+    if KILL_BEFORE_DEV_FLAG.get().unwrap().load(Ordering::Relaxed) {
+      let _ = child.kill();
+      break;
+    }
+    *current = child;
+    crash_loop.started();
+  }
+}
+
+/// Counts the consecutive crashes of a process restarted by `tauri dev`,
+/// so a process that keeps crashing right after starting is not restarted forever.
+pub struct CrashLoop {
+  limit: u32,
+  started_at: Instant,
+  crashes: u32,
+}
+
+impl CrashLoop {
+  /// Starts counting the crashes of a process that was just started.
+  pub fn new(limit: Option<u32>) -> Self {
+    Self {
+      limit: limit.unwrap_or(DEFAULT_CRASH_RESTART_LIMIT),
+      started_at: Instant::now(),
+      crashes: 0,
+    }
+  }
+
+  /// Records a restart of the process after a crash.
+  pub fn started(&mut self) {
+    self.started_at = Instant::now();
+  }
+
+  /// Records a restart that is not caused by a crash, e.g. after a file change.
+  pub fn reset(&mut self) {
+    self.started_at = Instant::now();
+    self.crashes = 0;
+  }
+
+  /// Records a crash, returning whether the process may be restarted.
+  pub fn crashed(&mut self) -> bool {
+    self.crashes = if self.started_at.elapsed() < CRASH_LOOP_WINDOW {
+      self.crashes + 1
+    } else {
+      1
+    };
+    self.crashes <= self.limit
+  }
+
+  /// The number of consecutive crashes.
+  pub fn crashes(&self) -> u32 {
+    self.crashes
+  }
+}
+
 fn kill_before_dev_process() {
   if let Some(child) = BEFORE_DEV.get() {
     let child = child.lock().unwrap();
@@ -366,3 +788,26 @@ fn kill_before_dev_process() {
     let _ = child.kill();
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::CrashLoop;
+
+  #[test]
+  fn crash_loop_honors_the_restart_limit() {
+    let mut crash_loop = CrashLoop::new(Some(2));
+    assert!(crash_loop.crashed());
+    crash_loop.started();
+    assert!(crash_loop.crashed());
+    crash_loop.started();
+    assert!(!crash_loop.crashed());
+    assert_eq!(crash_loop.crashes(), 3);
+
+    crash_loop.reset();
+    assert!(crash_loop.crashed());
+    assert_eq!(crash_loop.crashes(), 1);
+
+    let mut crash_loop = CrashLoop::new(Some(0));
+    assert!(!crash_loop.crashed());
+  }
+}
@@ -0,0 +1,61 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Machine readable events printed to stdout by `tauri dev --message-format json`.
+
+use crate::interface::ExitReason;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use std::{
+  path::PathBuf,
+  sync::atomic::{AtomicBool, Ordering},
+};
+
+static JSON_MESSAGES: AtomicBool = AtomicBool::new(false);
+
+/// The output format of the `tauri dev` messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+  /// Human readable logs.
+  Human,
+  /// One JSON object per line on stdout, the logs and the app output are written to stderr.
+  Json,
+}
+
+/// An event of the dev lifecycle.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum Event {
+  /// The Rust build started.
+  BuildStarted,
+  /// The Rust build finished.
+  BuildFinished { success: bool, code: Option<i32> },
+  /// The app process was spawned.
+  AppSpawned { pid: u32 },
+  /// The app process exited.
+  AppExited {
+    code: Option<i32>,
+    reason: ExitReason,
+  },
+  /// The file watcher detected changes that restart the app.
+  WatcherTriggered { paths: Vec<PathBuf> },
+}
+
+pub fn set_message_format(format: MessageFormat) {
+  JSON_MESSAGES.store(format == MessageFormat::Json, Ordering::Relaxed);
+}
+
+pub fn json_messages() -> bool {
+  JSON_MESSAGES.load(Ordering::Relaxed)
+}
+
+/// Prints the event to stdout when the JSON message format is enabled.
+pub fn emit(event: Event) {
+  if json_messages() {
+    if let Ok(json) = serde_json::to_string(&event) {
+      println!("{}", json);
+    }
+  }
+}
@@ -0,0 +1,110 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Multiplexes the output of the processes spawned by `tauri dev` into a single stream.
+//!
+//! Each line is written at once, prefixed with its source, so the cargo build,
+//! the app and the `beforeDevCommand` output do not interleave mid-line.
+
+use colored::Colorize;
+use once_cell::sync::{Lazy, OnceCell};
+
+use std::{
+  io::{BufReader, Read, Write},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+  },
+  thread::JoinHandle,
+  time::Instant,
+};
+
+static TIMESTAMPS: AtomicBool = AtomicBool::new(false);
+static STARTED_AT: OnceCell<Instant> = OnceCell::new();
+static WRITE_LOCK: Lazy<Mutex<()>> = Lazy::new(Default::default);
+
+/// The process that wrote a line.
+#[derive(Debug, Clone, Copy)]
+pub enum Source {
+  /// The cargo build.
+  Build,
+  /// The application.
+  App,
+  /// The `beforeDevCommand`.
+  Web,
+}
+
+impl Source {
+  fn prefix(self) -> colored::ColoredString {
+    match self {
+      Self::Build => "[build]".cyan(),
+      Self::App => "[app]".green(),
+      Self::Web => "[web]".magenta(),
+    }
+  }
+}
+
+/// The stream a line was written to.
+#[derive(Debug, Clone, Copy)]
+pub enum Stream {
+  Stdout,
+  Stderr,
+}
+
+/// Prefixes each line with the time elapsed since `tauri dev` started.
+pub fn set_timestamps(enabled: bool) {
+  STARTED_AT.get_or_init(Instant::now);
+  TIMESTAMPS.store(enabled, Ordering::Relaxed);
+}
+
+/// Writes a line of a child process output with its source prefix.
+///
+/// Lines ending with `\r` (e.g. progress bars) are written without a trailing new line.
+pub fn write_line(source: Source, stream: Stream, line: &[u8]) {
+  let mut prefix = String::new();
+  if TIMESTAMPS.load(Ordering::Relaxed) {
+    let elapsed = STARTED_AT.get_or_init(Instant::now).elapsed();
+    prefix.push_str(
+      &format!("{:>9.3}s ", elapsed.as_secs_f64())
+        .dimmed()
+        .to_string(),
+    );
+  }
+  prefix.push_str(&source.prefix().to_string());
+  prefix.push(' ');
+
+  let _guard = WRITE_LOCK.lock().unwrap();
+  // in JSON mode stdout is reserved for the dev events
+  let mut writer: Box<dyn Write> = match stream {
+    Stream::Stdout if !super::events::json_messages() => Box::new(std::io::stdout().lock()),
+    _ => Box::new(std::io::stderr().lock()),
+  };
+  let _ = writer.write_all(prefix.as_bytes());
+  let _ = writer.write_all(line);
+  if !line.ends_with(&[b'\r']) {
+    let _ = writer.write_all(b"\n");
+  }
+  let _ = writer.flush();
+}
+
+/// Spawns a thread that writes each line read from `reader` with [`write_line`] and passes it to `on_line`.
+pub fn forward<R, F>(reader: R, source: Source, stream: Stream, mut on_line: F) -> JoinHandle<()>
+where
+  R: Read + Send + 'static,
+  F: FnMut(&[u8]) + Send + 'static,
+{
+  std::thread::spawn(move || {
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+      buf.clear();
+      match tauri_utils::io::read_line(&mut reader, &mut buf) {
+        Ok(0) | Err(_) => break,
+        Ok(_) => (),
+      }
+      write_line(source, stream, &buf);
+      on_line(&buf);
+    }
+  })
+}
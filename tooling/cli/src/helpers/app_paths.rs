@@ -63,6 +63,7 @@ fn get_tauri_dir() -> PathBuf {
 
   if cwd.join("src-tauri/tauri.conf.json").exists()
     || cwd.join("src-tauri/tauri.conf.json5").exists()
+    || cwd.join("src-tauri/Tauri.toml").exists()
   {
     return cwd.join("src-tauri/");
   }
@@ -3,9 +3,14 @@
 // SPDX-License-Identifier: MIT
 
 use anyhow::Context;
+use heck::ToLowerCamelCase;
 use json_patch::merge;
+use log::warn;
 use once_cell::sync::Lazy;
 use serde_json::Value as JsonValue;
+use tauri_utils::config::parse::{
+  apply_profile, interpolate_env, resolve_extends, ENABLED_FORMATS, PROFILE_OVERLAYS,
+};
 
 pub use tauri_utils::config::*;
 
@@ -13,7 +18,7 @@ use std::{
   collections::HashMap,
   env::{set_var, var_os},
   ffi::OsStr,
-  process::exit,
+  path::{Path, PathBuf},
   sync::{Arc, Mutex},
 };
 
@@ -25,6 +30,8 @@ pub struct ConfigMetadata {
   /// The config extensions (platform-specific config files or the config CLI argument).
   /// Maps the extension name to its value.
   extensions: HashMap<String, JsonValue>,
+  /// The base configuration files extended by the configuration files.
+  extended_files: Vec<PathBuf>,
 }
 
 impl std::ops::Deref for ConfigMetadata {
@@ -37,6 +44,11 @@ impl std::ops::Deref for ConfigMetadata {
 }
 
 impl ConfigMetadata {
+  /// The base configuration files extended with the `extends` key.
+  pub fn extended_files(&self) -> &[PathBuf] {
+    &self.extended_files
+  }
+
   /// Checks which config is overwriting the bundle identifier.
   pub fn find_bundle_identifier_overwriter(&self) -> Option<String> {
     for (ext, config) in &self.extensions {
@@ -80,6 +92,7 @@ pub fn wix_settings(config: WixConfig) -> tauri_bundler::WixSettings {
         })
         .collect(),
     }),
+    embed_language_transforms: config.embed_language_transforms,
     template: config.template,
     fragment_paths: config.fragment_paths,
     component_group_refs: config.component_group_refs,
@@ -87,6 +100,7 @@ pub fn wix_settings(config: WixConfig) -> tauri_bundler::WixSettings {
     feature_group_refs: config.feature_group_refs,
     feature_refs: config.feature_refs,
     merge_refs: config.merge_refs,
+    custom_dialogs: config.custom_dialogs,
     skip_webview_install: config.skip_webview_install,
     license: config.license,
     enable_elevated_update_task: config.enable_elevated_update_task,
@@ -96,87 +110,617 @@ pub fn wix_settings(config: WixConfig) -> tauri_bundler::WixSettings {
   }
 }
 
+pub fn nsis_settings(config: NsisConfig) -> tauri_bundler::NsisSettings {
+  tauri_bundler::NsisSettings {
+    template: config.template,
+    license: config.license,
+    header_image: config.header_image,
+    sidebar_image: config.sidebar_image,
+    install_mode: config.install_mode,
+    languages: config.languages,
+    display_language_selector: config.display_language_selector,
+    custom_pages: config.custom_pages,
+  }
+}
+
 fn config_handle() -> &'static ConfigHandle {
   static CONFING_HANDLE: Lazy<ConfigHandle> = Lazy::new(Default::default);
   &CONFING_HANDLE
 }
 
-/// Gets the static parsed config from `tauri.conf.json`.
-fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<ConfigHandle> {
+/// Gets the static parsed config from `tauri.conf.json`, `tauri.conf.json5` or `Tauri.toml`.
+///
+/// The `dev` or `prod` overlay is selected by `release` and merged over the configuration files,
+/// before the `--config` values, which can have overlays of their own.
+fn get_internal(
+  release: bool,
+  merge_config: Option<&str>,
+  reload: bool,
+) -> crate::Result<ConfigHandle> {
   if !reload && config_handle().lock().unwrap().is_some() {
     return Ok(config_handle().clone());
   }
 
+  let schema: JsonValue = serde_json::from_str(include_str!("../../schema.json"))?;
+
   let tauri_dir = super::app_paths::tauri_dir();
   let (mut config, config_path) =
     tauri_utils::config::parse::parse_value(tauri_dir.join("tauri.conf.json"))?;
+  let mut extended_files = resolve_extends(&mut config, &config_path)?;
+  if is_toml(&config_path) || extended_files.iter().any(|p| is_toml(p)) {
+    normalize_toml_config(&mut config, &schema);
+  }
+  interpolate_env(&mut config)?;
   let config_file_name = config_path.file_name().unwrap().to_string_lossy();
   let mut extensions = HashMap::new();
 
-  if let Some((platform_config, config_path)) =
+  if !reload {
+    for format in ENABLED_FORMATS {
+      let path = tauri_dir.join(format.into_file_name());
+      if path != config_path && path.exists() {
+        warn!(
+          "`{}` is ignored because `{}` takes precedence.",
+          format.into_file_name(),
+          config_file_name
+        );
+      }
+    }
+  }
+
+  if let Some((mut platform_config, config_path)) =
     tauri_utils::config::parse::read_platform(tauri_dir)?
   {
+    let platform_extended_files = resolve_extends(&mut platform_config, &config_path)?;
+    if is_toml(&config_path) || platform_extended_files.iter().any(|p| is_toml(p)) {
+      normalize_toml_config(&mut platform_config, &schema);
+    }
+    interpolate_env(&mut platform_config)?;
+    extended_files.extend(platform_extended_files);
     merge(&mut config, &platform_config);
     extensions.insert(
       config_path.file_name().unwrap().to_str().unwrap().into(),
       platform_config,
     );
   }
+  apply_profile(&mut config, release);
 
   if let Some(merge_config) = merge_config {
     set_var("TAURI_CONFIG", merge_config);
-    let merge_config: JsonValue =
+    let mut merge_config: JsonValue =
       serde_json::from_str(merge_config).with_context(|| "failed to parse config to merge")?;
+    interpolate_env(&mut merge_config)?;
+    apply_profile(&mut merge_config, release);
     merge(&mut config, &merge_config);
     extensions.insert(MERGE_CONFIG_EXTENSION_NAME.into(), merge_config);
   };
 
-  if config_path.extension() == Some(OsStr::new("json"))
-    || config_path.extension() == Some(OsStr::new("json5"))
-  {
-    let schema: JsonValue = serde_json::from_str(include_str!("../../schema.json"))?;
-    let mut scope = valico::json_schema::Scope::new();
-    let schema = scope.compile_and_return(schema, false).unwrap();
-    let state = schema.validate(&config);
-    if !state.errors.is_empty() {
-      for error in state.errors {
-        let path = error
-          .get_path()
-          .chars()
-          .skip(1)
-          .collect::<String>()
-          .replace('/', " > ");
-        if path.is_empty() {
-          eprintln!(
-            "`{config_file_name}` error: {}",
-            error.get_detail().unwrap_or_else(|| error.get_title()),
-          );
-        } else {
-          eprintln!(
-            "`{config_file_name}` error on `{}`: {}",
-            path,
-            error.get_detail().unwrap_or_else(|| error.get_title()),
-          );
-        }
-      }
-      exit(1);
-    }
-  }
+  // the TOML keys are normalized, so every format is validated against the same schema
+  validate(&config, &schema, &config_file_name)?;
 
   let config: Config = serde_json::from_value(config)?;
 
   *config_handle().lock().unwrap() = Some(ConfigMetadata {
     inner: config,
     extensions,
+    extended_files,
   });
 
   Ok(config_handle().clone())
 }
 
-pub fn get(merge_config: Option<&str>) -> crate::Result<ConfigHandle> {
-  get_internal(merge_config, false)
+/// An object key that is not a property of the schema.
+struct UnknownKey {
+  /// The JSON pointer of the key, e.g. `/tauri/bundel`.
+  pointer: String,
+  key: String,
+  /// The property of the schema with the closest name.
+  suggestion: Option<String>,
+}
+
+/// Validates the configuration against the schema,
+/// reporting every invalid value with its JSON pointer and the unknown keys with the closest valid key.
+fn validate(config: &JsonValue, schema: &JsonValue, config_file_name: &str) -> crate::Result<()> {
+  let mut unknown_keys = Vec::new();
+  find_unknown_keys(config, &[schema], schema, "", &mut unknown_keys);
+  let mut errors = unknown_keys
+    .iter()
+    .map(|unknown| match &unknown.suggestion {
+      Some(suggestion) => format!(
+        "`{}`: unknown key `{}`, did you mean `{}`?",
+        unknown.pointer, unknown.key, suggestion
+      ),
+      None => format!("`{}`: unknown key `{}`", unknown.pointer, unknown.key),
+    })
+    .collect::<Vec<_>>();
+
+  let mut scope = valico::json_schema::Scope::new();
+  let compiled = scope.compile_and_return(schema.clone(), false).unwrap();
+  for error in compiled.validate(config).errors {
+    let pointer = error.get_path();
+    let detail = error.get_detail().unwrap_or_else(|| error.get_title());
+    // the unknown keys are already reported, skip the additional property errors they cause on their parents
+    let description = format!("{} {}", error.get_title(), detail).to_lowercase();
+    let caused_by_unknown_key = ["additional", "anyof", "oneof"]
+      .iter()
+      .any(|e| description.contains(e))
+      && unknown_keys
+        .iter()
+        .any(|unknown| unknown.pointer.starts_with(&format!("{}/", pointer)));
+    if !caused_by_unknown_key {
+      errors.push(format!(
+        "`{}`: {}",
+        if pointer.is_empty() { "/" } else { pointer },
+        detail
+      ));
+    }
+  }
+
+  if errors.is_empty() {
+    Ok(())
+  } else {
+    Err(anyhow::anyhow!(
+      "`{}` does not match the configuration schema:\n  {}",
+      config_file_name,
+      errors.join("\n  ")
+    ))
+  }
+}
+
+/// Collects the keys of the objects that do not allow additional properties and are not properties of their schema.
+fn find_unknown_keys(
+  value: &JsonValue,
+  schemas: &[&JsonValue],
+  root: &JsonValue,
+  pointer: &str,
+  unknown_keys: &mut Vec<UnknownKey>,
+) {
+  let mut resolved = Vec::new();
+  for schema in schemas {
+    resolve_schema(schema, root, &mut resolved);
+  }
+
+  match value {
+    JsonValue::Object(object) => {
+      // the `null` variant of an optional object is not an object schema
+      let object_schemas = resolved
+        .iter()
+        .filter(|s| s.get("properties").is_some() || s.get("additionalProperties").is_some())
+        .collect::<Vec<_>>();
+      let closed = !object_schemas.is_empty()
+        && object_schemas.iter().all(|s| {
+          s.get("additionalProperties") == Some(&JsonValue::Bool(false))
+            && s.get("patternProperties").is_none()
+        });
+      let properties = object_schemas
+        .iter()
+        .filter_map(|s| s.get("properties").and_then(JsonValue::as_object))
+        .flat_map(|properties| properties.keys())
+        .collect::<Vec<_>>();
+
+      for (key, entry) in object {
+        let entry_pointer = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+        let mut key_schemas = resolved
+          .iter()
+          .filter_map(|s| s.get("properties")?.get(key))
+          .collect::<Vec<_>>();
+        if key_schemas.is_empty() {
+          if closed {
+            let suggestion = properties
+              .iter()
+              .map(|property| (strsim::jaro_winkler(key, property), property))
+              .filter(|(confidence, _)| *confidence > 0.8)
+              .max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
+              .map(|(_, property)| property.to_string());
+            unknown_keys.push(UnknownKey {
+              pointer: entry_pointer,
+              key: key.clone(),
+              suggestion,
+            });
+            continue;
+          }
+          key_schemas = resolved
+            .iter()
+            .filter_map(|s| s.get("additionalProperties"))
+            .filter(|s| s.is_object())
+            .collect();
+        }
+        find_unknown_keys(entry, &key_schemas, root, &entry_pointer, unknown_keys);
+      }
+    }
+    JsonValue::Array(items) => {
+      let item_schemas = resolved
+        .iter()
+        .filter_map(|s| s.get("items"))
+        .filter(|s| s.is_object())
+        .collect::<Vec<_>>();
+      for (i, item) in items.iter().enumerate() {
+        find_unknown_keys(
+          item,
+          &item_schemas,
+          root,
+          &format!("{}/{}", pointer, i),
+          unknown_keys,
+        );
+      }
+    }
+    _ => (),
+  }
+}
+
+fn is_toml(path: &Path) -> bool {
+  path.extension() == Some(OsStr::new("toml"))
+}
+
+/// Normalizes the keys of a TOML configuration and of its `dev` and `prod` overlays, see [`normalize_toml_keys`].
+fn normalize_toml_config(config: &mut JsonValue, schema: &JsonValue) {
+  normalize_toml_keys(config, &[schema], schema);
+  for overlay in PROFILE_OVERLAYS {
+    if let Some(overlay) = config.get_mut(*overlay) {
+      normalize_toml_keys(overlay, &[schema], schema);
+    }
+  }
+}
+
+/// Renames the kebab-case keys of a TOML configuration to the camelCase keys of the schema,
+/// so it is merged with the JSON configurations and validated against the same schema.
+///
+/// Only the properties of the schema are renamed, the keys of maps such as the CSP directives are kept.
+fn normalize_toml_keys(value: &mut JsonValue, schemas: &[&JsonValue], root: &JsonValue) {
+  let mut resolved = Vec::new();
+  for schema in schemas {
+    resolve_schema(schema, root, &mut resolved);
+  }
+  let property_schemas = |name: &str| {
+    resolved
+      .iter()
+      .filter_map(|s| s.get("properties")?.get(name))
+      .collect::<Vec<_>>()
+  };
+
+  match value {
+    JsonValue::Object(object) => {
+      for key in object.keys().cloned().collect::<Vec<_>>() {
+        let mut key = key;
+        let mut key_schemas = property_schemas(&key);
+        let camel_case_key = key.to_lower_camel_case();
+        if key_schemas.is_empty() && camel_case_key != key && !object.contains_key(&camel_case_key)
+        {
+          key_schemas = property_schemas(&camel_case_key);
+          if !key_schemas.is_empty() {
+            let entry = object.remove(&key).unwrap();
+            object.insert(camel_case_key.clone(), entry);
+            key = camel_case_key;
+          }
+        }
+        if key_schemas.is_empty() {
+          key_schemas = resolved
+            .iter()
+            .filter_map(|s| s.get("additionalProperties"))
+            .filter(|s| s.is_object())
+            .collect();
+        }
+        if let Some(entry) = object.get_mut(&key) {
+          normalize_toml_keys(entry, &key_schemas, root);
+        }
+      }
+    }
+    JsonValue::Array(items) => {
+      let item_schemas = resolved
+        .iter()
+        .filter_map(|s| s.get("items"))
+        .filter(|s| s.is_object())
+        .collect::<Vec<_>>();
+      for item in items {
+        normalize_toml_keys(item, &item_schemas, root);
+      }
+    }
+    _ => (),
+  }
 }
 
-pub fn reload(merge_config: Option<&str>) -> crate::Result<ConfigHandle> {
-  get_internal(merge_config, true)
+/// Collects the schema and the schemas it references or combines.
+fn resolve_schema<'a>(
+  schema: &'a JsonValue,
+  root: &'a JsonValue,
+  resolved: &mut Vec<&'a JsonValue>,
+) {
+  if let Some(definition) = schema
+    .get("$ref")
+    .and_then(JsonValue::as_str)
+    .and_then(|r| r.strip_prefix('#'))
+    .and_then(|pointer| root.pointer(pointer))
+  {
+    resolve_schema(definition, root, resolved);
+  }
+  for combinator in ["allOf", "anyOf", "oneOf"] {
+    if let Some(schemas) = schema.get(combinator).and_then(JsonValue::as_array) {
+      for schema in schemas {
+        resolve_schema(schema, root, resolved);
+      }
+    }
+  }
+  resolved.push(schema);
+}
+
+/// Whether the file is read by [`get`]: the configuration file or the platform-specific configuration file, in any enabled format.
+pub fn is_configuration_file(path: &Path) -> bool {
+  path.file_name().map_or(false, |file_name| {
+    ENABLED_FORMATS.iter().any(|format| {
+      file_name == OsStr::new(format.into_file_name())
+        || file_name == OsStr::new(format.into_platform_file_name())
+    })
+  })
+}
+
+/// Reads the `--config` values and merges them in order into a single JSON string, passed to [`get`] and [`reload`].
+///
+/// Each value is an inline JSON object or the path to a JSON, JSON5 or TOML file, detected from its extension.
+/// The values are merged with [JSON Merge Patch (RFC 7396)], like the platform-specific configuration files:
+/// objects are merged key by key, `null` removes a key and arrays replace the previous array entirely.
+///
+/// [JSON Merge Patch (RFC 7396)]: https://datatracker.ietf.org/doc/html/rfc7396
+pub fn merge_config_args(configs: &[String]) -> crate::Result<Option<String>> {
+  let mut merged: Option<JsonValue> = None;
+  for config in configs {
+    let value = if config.trim_start().starts_with('{') {
+      serde_json::from_str(config).with_context(|| "failed to parse config to merge")?
+    } else {
+      read_config_file(Path::new(config))?
+    };
+    match &mut merged {
+      Some(merged) => merge(merged, &value),
+      None => merged = Some(value),
+    }
+  }
+  Ok(merged.map(|merged| merged.to_string()))
+}
+
+fn read_config_file(path: &Path) -> crate::Result<JsonValue> {
+  let raw = std::fs::read_to_string(path)
+    .with_context(|| format!("failed to read custom configuration {}", path.display()))?;
+  let value = match path.extension().and_then(OsStr::to_str) {
+    Some("toml") => {
+      let mut value = toml::from_str(&raw)
+        .with_context(|| format!("failed to parse custom configuration {}", path.display()))?;
+      let schema: JsonValue = serde_json::from_str(include_str!("../../schema.json"))?;
+      normalize_toml_config(&mut value, &schema);
+      value
+    }
+    Some("json5") => tauri_utils::config::parse::parse_json5_value(&raw, path)?,
+    _ => serde_json::from_str(&raw)
+      .with_context(|| format!("failed to parse custom configuration {}", path.display()))?,
+  };
+  Ok(value)
+}
+
+pub fn get(release: bool, merge_config: Option<&str>) -> crate::Result<ConfigHandle> {
+  get_internal(release, merge_config, false)
+}
+
+pub fn reload(release: bool, merge_config: Option<&str>) -> crate::Result<ConfigHandle> {
+  get_internal(release, merge_config, true)
+}
+
+/// Applies the `build > binaries` entry of the binary selected with `--bin`:
+/// its features are added to `features` and its windows replace `tauri > windows` in the merged configuration.
+///
+/// Returns whether the merged configuration changed, in which case the configuration must be reloaded.
+pub fn apply_binary_config(
+  config: &Config,
+  bin: &str,
+  features: &mut Option<Vec<String>>,
+  merge_config: &mut Vec<String>,
+) -> crate::Result<bool> {
+  let binary = match config.build.binaries.as_ref().and_then(|b| b.get(bin)) {
+    Some(binary) => binary.clone(),
+    None => return Ok(false),
+  };
+  if let Some(binary_features) = binary.features {
+    features
+      .get_or_insert_with(Vec::new)
+      .extend(binary_features);
+  }
+  match binary.windows {
+    Some(windows) => {
+      let mut merged = match merge_config.first() {
+        Some(config) => serde_json::from_str(config)?,
+        None => serde_json::json!({}),
+      };
+      merged["tauri"]["windows"] = JsonValue::Array(windows);
+      *merge_config = vec![merged.to_string()];
+      Ok(true)
+    }
+    None => Ok(false),
+  }
+}
+
+/// A configuration value that changed between two versions of the configuration.
+pub struct ConfigChange {
+  /// The path of the value, e.g. `tauri > windows > 0 > title`.
+  pub path: Vec<String>,
+  pub old: Option<JsonValue>,
+  pub new: Option<JsonValue>,
+}
+
+impl std::fmt::Display for ConfigChange {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let value = |v: &Option<JsonValue>| match v {
+      Some(v) => v.to_string(),
+      None => "(unset)".into(),
+    };
+    write!(
+      f,
+      "{}: {} -> {}",
+      self.path.join(" > "),
+      value(&self.old),
+      value(&self.new)
+    )
+  }
+}
+
+/// Lists the values that differ between two configurations.
+pub fn diff(old: &Config, new: &Config) -> crate::Result<Vec<ConfigChange>> {
+  let mut changes = Vec::new();
+  diff_values(
+    &mut Vec::new(),
+    Some(&serde_json::to_value(old)?),
+    Some(&serde_json::to_value(new)?),
+    &mut changes,
+  );
+  Ok(changes)
+}
+
+fn diff_values(
+  path: &mut Vec<String>,
+  old: Option<&JsonValue>,
+  new: Option<&JsonValue>,
+  changes: &mut Vec<ConfigChange>,
+) {
+  match (old, new) {
+    (Some(JsonValue::Object(old)), Some(JsonValue::Object(new))) => {
+      let keys = old
+        .keys()
+        .chain(new.keys())
+        .collect::<std::collections::BTreeSet<_>>();
+      for key in keys {
+        path.push(key.clone());
+        diff_values(path, old.get(key), new.get(key), changes);
+        path.pop();
+      }
+    }
+    (Some(JsonValue::Array(old)), Some(JsonValue::Array(new))) => {
+      for i in 0..old.len().max(new.len()) {
+        path.push(i.to_string());
+        diff_values(path, old.get(i), new.get(i), changes);
+        path.pop();
+      }
+    }
+    (old, new) if old != new => changes.push(ConfigChange {
+      path: path.clone(),
+      old: old.cloned(),
+      new: new.cloned(),
+    }),
+    _ => (),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    apply_binary_config, find_unknown_keys, merge_config_args, normalize_toml_keys, Config,
+  };
+  use serde_json::{json, Value as JsonValue};
+
+  #[test]
+  fn normalizes_toml_keys() {
+    let schema: JsonValue = serde_json::from_str(include_str!("../../schema.json")).unwrap();
+    let mut config = json!({
+      "build": { "dev-path": "http://localhost:1420", "dist-dir": "../dist" },
+      "tauri": {
+        "bundle": { "identifier": "com.tauri.dev", "windows": { "wix": { "language": { "en-US": { "locale-path": "en.wxl" } } } } },
+        "security": { "csp": { "default-src": "'self'" } },
+        "windows": [{ "file-drop-enabled": false, "min-width": 400 }]
+      }
+    });
+    normalize_toml_keys(&mut config, &[&schema], &schema);
+    assert_eq!(
+      config,
+      json!({
+        "build": { "devPath": "http://localhost:1420", "distDir": "../dist" },
+        "tauri": {
+          "bundle": { "identifier": "com.tauri.dev", "windows": { "wix": { "language": { "en-US": { "localePath": "en.wxl" } } } } },
+          "security": { "csp": { "default-src": "'self'" } },
+          "windows": [{ "fileDropEnabled": false, "minWidth": 400 }]
+        }
+      })
+    );
+  }
+
+  #[test]
+  fn finds_unknown_keys() {
+    let schema: JsonValue = serde_json::from_str(include_str!("../../schema.json")).unwrap();
+    let config = json!({
+      "build": { "devPath": "http://localhost:1420", "distDir": "../dist" },
+      "tauri": {
+        "bundel": { "identifier": "com.tauri.dev" },
+        "security": { "csp": { "default-src": "'self'" } },
+        "windows": [{ "title": "Tauri", "resizeable": false }]
+      },
+      "unrelated": true
+    });
+    let mut unknown_keys = Vec::new();
+    find_unknown_keys(&config, &[&schema], &schema, "", &mut unknown_keys);
+    assert_eq!(
+      unknown_keys
+        .iter()
+        .map(|u| (u.pointer.as_str(), u.suggestion.as_deref()))
+        .collect::<Vec<_>>(),
+      [
+        ("/tauri/bundel", Some("bundle")),
+        ("/tauri/windows/0/resizeable", Some("resizable")),
+        ("/unrelated", None),
+      ]
+    );
+  }
+
+  #[test]
+  fn merges_config_args_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml_path = dir.path().join("ci.toml");
+    std::fs::write(
+      &toml_path,
+      "[build]\ndist-dir = \"../dist\"\n\n[tauri.bundle]\ntargets = [\"deb\", \"appimage\"]\n",
+    )
+    .unwrap();
+
+    let merged = merge_config_args(&[
+      r#"{ "build": { "devPath": "http://localhost:1420" }, "tauri": { "bundle": { "targets": ["msi"] } } }"#.into(),
+      toml_path.to_string_lossy().into_owned(),
+      r#"{ "build": { "devPath": null } }"#.into(),
+    ])
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+      serde_json::from_str::<JsonValue>(&merged).unwrap(),
+      json!({
+        "build": { "distDir": "../dist" },
+        "tauri": { "bundle": { "targets": ["deb", "appimage"] } }
+      })
+    );
+    assert!(merge_config_args(&[]).unwrap().is_none());
+  }
+
+  #[test]
+  fn applies_binary_config() {
+    let config: Config = serde_json::from_value(json!({
+      "build": {
+        "features": ["app"],
+        "binaries": {
+          "settings": { "features": ["settings"], "windows": [{ "label": "settings", "title": "Settings" }] },
+          "tray": { "features": ["tray"] }
+        }
+      }
+    }))
+    .unwrap();
+
+    let mut features = Some(vec!["custom".to_string()]);
+    let mut merge_config =
+      vec![r#"{ "build": { "devPath": "http://localhost:1420" } }"#.to_string()];
+    assert!(apply_binary_config(&config, "settings", &mut features, &mut merge_config).unwrap());
+    assert_eq!(features, Some(vec!["custom".into(), "settings".into()]));
+    assert_eq!(
+      serde_json::from_str::<JsonValue>(&merge_config[0]).unwrap(),
+      json!({
+        "build": { "devPath": "http://localhost:1420" },
+        "tauri": { "windows": [{ "label": "settings", "title": "Settings" }] }
+      })
+    );
+
+    let mut features = None;
+    let mut merge_config = Vec::new();
+    assert!(!apply_binary_config(&config, "tray", &mut features, &mut merge_config).unwrap());
+    assert_eq!(features, Some(vec!["tray".into()]));
+    assert!(merge_config.is_empty());
+    assert!(!apply_binary_config(&config, "app", &mut features, &mut merge_config).unwrap());
+  }
 }
@@ -0,0 +1,223 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Detects the missing pieces of the development environment, reported by `tauri info`,
+//! and installs them with `tauri info --fix` or when a build or the mobile setup needs them.
+
+use crate::CommandExt;
+use anyhow::Context;
+use log::info;
+use serde::Serialize;
+
+use std::{
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+/// The Rust targets of the Android project.
+pub const ANDROID_TARGETS: &[&str] = &[
+  "aarch64-linux-android",
+  "armv7-linux-androideabi",
+  "i686-linux-android",
+  "x86_64-linux-android",
+];
+
+/// The Rust targets of the iOS project.
+#[cfg(target_os = "macos")]
+pub const IOS_TARGETS: &[&str] = &[
+  "aarch64-apple-ios",
+  "x86_64-apple-ios",
+  "aarch64-apple-ios-sim",
+];
+
+/// The Android SDK packages used by the Android project.
+const ANDROID_SDK_PACKAGES: &[&str] = &[
+  "platform-tools",
+  "platforms;android-33",
+  "build-tools;33.0.0",
+  "ndk;25.0.8775105",
+];
+
+/// A missing piece of the environment.
+#[derive(Debug, Serialize)]
+pub struct Issue {
+  pub message: String,
+  /// The command installing the missing piece, if it can be installed automatically.
+  pub fix: Option<Fix>,
+}
+
+/// A command fixing an [`Issue`].
+#[derive(Debug, Serialize)]
+pub struct Fix {
+  pub program: String,
+  pub args: Vec<String>,
+}
+
+impl Fix {
+  fn new(program: impl Into<String>, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+    Self {
+      program: program.into(),
+      args: args.into_iter().map(Into::into).collect(),
+    }
+  }
+
+  /// The command line of the fix, e.g. `rustup target add aarch64-apple-ios`.
+  pub fn command_line(&self) -> String {
+    std::iter::once(self.program.as_str())
+      .chain(self.args.iter().map(String::as_str))
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+
+  /// Runs the fix, asking for confirmation unless running in CI mode.
+  ///
+  /// Returns `false` if the user declined or could not be prompted.
+  pub fn confirm_and_run(&self, prompt: &str, ci: bool) -> crate::Result<bool> {
+    let run = ci
+      || dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
+        .with_prompt(format!("{} Run `{}`?", prompt, self.command_line()))
+        .default(true)
+        .interact()
+        .unwrap_or_default();
+    if run {
+      info!(action = "Running"; "{}", self.command_line());
+      let status = Command::new(&self.program)
+        .args(&self.args)
+        .piped()
+        .with_context(|| format!("failed to run `{}`", self.program))?;
+      if !status.success() {
+        anyhow::bail!("`{}` failed", self.command_line());
+      }
+    }
+    Ok(run)
+  }
+}
+
+/// The fix installing Rust targets with rustup.
+pub fn rust_targets_fix(targets: &[&str]) -> Fix {
+  Fix::new("rustup", ["target", "add"].iter().chain(targets).copied())
+}
+
+/// The Rust targets missing from the active toolchain.
+pub fn rust_targets(targets: &[&str]) -> Vec<Issue> {
+  let installed = match Command::new("rustup")
+    .args(["target", "list", "--installed"])
+    .output()
+  {
+    Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).into_owned(),
+    _ => {
+      return vec![Issue {
+        message: "rustup is not installed, see https://rustup.rs".into(),
+        fix: None,
+      }]
+    }
+  };
+  let missing = targets
+    .iter()
+    .copied()
+    .filter(|target| !installed.lines().any(|line| line.trim() == *target))
+    .collect::<Vec<_>>();
+  if missing.is_empty() {
+    Vec::new()
+  } else {
+    vec![Issue {
+      message: format!("Rust targets {} are not installed", missing.join(", ")),
+      fix: Some(rust_targets_fix(&missing)),
+    }]
+  }
+}
+
+/// The WebView2 runtime, given the installed version.
+#[cfg(windows)]
+pub fn webview2(version: Option<&str>) -> Vec<Issue> {
+  if version.map_or(false, |v| !v.trim().is_empty()) {
+    return Vec::new();
+  }
+  vec![Issue {
+    message: "The WebView2 runtime is not installed".into(),
+    fix: Some(Fix::new(
+      "powershell.exe",
+      [
+        "-NoProfile",
+        "-Command",
+        "$setup = \"$env:TEMP\\MicrosoftEdgeWebview2Setup.exe\"; Invoke-WebRequest https://go.microsoft.com/fwlink/p/?LinkId=2124703 -OutFile $setup; Start-Process -Wait $setup -ArgumentList '/install'",
+      ],
+    )),
+  }]
+}
+
+/// The Xcode command line tools.
+#[cfg(target_os = "macos")]
+pub fn xcode_command_line_tools() -> Vec<Issue> {
+  let installed = Command::new("xcode-select")
+    .arg("-p")
+    .output()
+    .map(|output| output.status.success())
+    .unwrap_or_default();
+  if installed {
+    return Vec::new();
+  }
+  vec![Issue {
+    message: "The Xcode command line tools are not installed".into(),
+    fix: Some(Fix::new("xcode-select", ["--install"])),
+  }]
+}
+
+/// The Android SDK from `ANDROID_HOME` or `ANDROID_SDK_ROOT` and its packages used by the Android project.
+pub fn android_sdk() -> Vec<Issue> {
+  let sdk = match std::env::var_os("ANDROID_HOME")
+    .or_else(|| std::env::var_os("ANDROID_SDK_ROOT"))
+    .map(PathBuf::from)
+  {
+    Some(sdk) if sdk.is_dir() => sdk,
+    _ => {
+      return vec![Issue {
+        message: "The Android SDK is not found, install Android Studio and set the `ANDROID_HOME` environment variable".into(),
+        fix: None,
+      }]
+    }
+  };
+
+  let sdkmanager = sdkmanager(&sdk);
+  let missing = ANDROID_SDK_PACKAGES
+    .iter()
+    .copied()
+    .filter(|package| {
+      // an NDK from `NDK_HOME` is used instead of the SDK package
+      let ndk_from_env = package.starts_with("ndk;") && std::env::var_os("NDK_HOME").is_some();
+      !ndk_from_env && !sdk.join(package.replace(';', "/")).is_dir()
+    })
+    .collect::<Vec<_>>();
+  if missing.is_empty() {
+    return Vec::new();
+  }
+  vec![Issue {
+    message: format!(
+      "Android SDK packages {} are not installed",
+      missing.join(", ")
+    ),
+    fix: sdkmanager.map(|sdkmanager| {
+      Fix::new(
+        sdkmanager.to_string_lossy(),
+        std::iter::once("--install").chain(missing),
+      )
+    }),
+  }]
+}
+
+fn sdkmanager(sdk: &Path) -> Option<PathBuf> {
+  let name = if cfg!(windows) {
+    "sdkmanager.bat"
+  } else {
+    "sdkmanager"
+  };
+  Some(
+    sdk
+      .join("cmdline-tools")
+      .join("latest")
+      .join("bin")
+      .join(name),
+  )
+  .filter(|p| p.is_file())
+}
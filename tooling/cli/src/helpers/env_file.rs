@@ -0,0 +1,52 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::Context;
+use log::info;
+
+use super::app_paths::app_dir;
+
+/// Loads the `.env` and `.env.local` files of the app directory and the given env files into the process environment,
+/// so they are available to the hooks, the build script and the app binary.
+///
+/// Variables already set on the process environment are never overridden.
+/// The given env files take precedence over `.env.local`, which takes precedence over `.env`,
+/// and a file passed later takes precedence over the previous ones.
+pub fn load(env_files: &[PathBuf]) -> crate::Result<()> {
+  let mut files = Vec::new();
+  for name in [".env", ".env.local"] {
+    let path = app_dir().join(name);
+    if path.exists() {
+      files.push(path);
+    }
+  }
+  for path in env_files {
+    if !path.exists() {
+      anyhow::bail!("env file {} does not exist", path.display());
+    }
+    files.push(path.clone());
+  }
+
+  let mut vars = HashMap::new();
+  for path in files {
+    info!(action = "Loading"; "environment variables from {}", path.display());
+    for item in dotenvy::from_path_iter(&path)
+      .with_context(|| format!("failed to read env file {}", path.display()))?
+    {
+      let (key, value) =
+        item.with_context(|| format!("failed to parse env file {}", path.display()))?;
+      vars.insert(key, value);
+    }
+  }
+
+  for (key, value) in vars {
+    if std::env::var_os(&key).is_none() {
+      std::env::set_var(key, value);
+    }
+  }
+
+  Ok(())
+}
@@ -2,26 +2,53 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use clap::ValueEnum;
+use serde::Deserialize;
+
 use std::fmt;
 
-#[derive(Debug, Clone)]
+/// A frontend framework, inferred from the package.json or selected with `tauri init --frontend`.
+#[derive(Debug, Clone, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Framework {
+  /// Svelte with Rollup.
   Svelte,
+  /// SvelteKit with the static adapter.
+  #[clap(name = "sveltekit")]
+  #[serde(rename = "sveltekit")]
+  SvelteKit,
+  /// Angular CLI.
   Angular,
+  /// Create React App.
   React,
+  /// Next.js with a static export.
+  #[clap(name = "next")]
+  #[serde(rename = "next")]
   Nextjs,
 
+  /// Gatsby.
   Gatsby,
+  /// Nuxt with a static generation.
   Nuxt,
+  /// Quasar.
   Quasar,
+  /// Vue CLI.
   VueCli,
+  /// Vue.js.
   Vue,
+  /// Vite.
+  Vite,
+  /// A Rust frontend built with Trunk.
+  Trunk,
+  /// Static HTML, CSS and JavaScript without a dev server.
+  Vanilla,
 }
 
 impl Framework {
   pub fn dev_path(&self) -> String {
     match self {
       Self::Svelte => "http://localhost:8080",
+      Self::SvelteKit => "http://localhost:5173",
       Self::Angular => "http://localhost:4200",
       Self::React => "http://localhost:3000",
       Self::Nextjs => "http://localhost:3000",
@@ -30,6 +57,9 @@ impl Framework {
       Self::Quasar => "http://localhost:8080",
       Self::VueCli => "http://localhost:8080",
       Self::Vue => "http://localhost:8080",
+      Self::Vite => "http://localhost:5173",
+      Self::Trunk => "http://localhost:8080",
+      Self::Vanilla => "../src",
     }
     .into()
   }
@@ -37,6 +67,7 @@ impl Framework {
   pub fn dist_dir(&self) -> String {
     match self {
       Self::Svelte => "../public",
+      Self::SvelteKit => "../build",
       Self::Angular => "../dist",
       Self::React => "../build",
       Self::Nextjs => "../out",
@@ -45,6 +76,32 @@ impl Framework {
       Self::Quasar => "../dist/spa",
       Self::VueCli => "../dist",
       Self::Vue => "../dist",
+      Self::Vite => "../dist",
+      Self::Trunk => "../dist",
+      Self::Vanilla => "../src",
+    }
+    .into()
+  }
+
+  pub fn before_dev_command(&self) -> String {
+    match self {
+      Self::Angular | Self::React => "npm run start",
+      Self::Gatsby => "npm run develop",
+      Self::VueCli => "npm run serve",
+      Self::Trunk => "trunk serve",
+      Self::Vanilla => "",
+      _ => "npm run dev",
+    }
+    .into()
+  }
+
+  pub fn before_build_command(&self) -> String {
+    match self {
+      Self::Nextjs => "npm run build && npm run export",
+      Self::Nuxt => "npm run generate",
+      Self::Trunk => "trunk build",
+      Self::Vanilla => "",
+      _ => "npm run build",
     }
     .into()
   }
@@ -54,6 +111,7 @@ impl fmt::Display for Framework {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
       Self::Svelte => write!(f, "Svelte"),
+      Self::SvelteKit => write!(f, "Svelte (SvelteKit)"),
       Self::Angular => write!(f, "Angular"),
       Self::React => write!(f, "React"),
       Self::Nextjs => write!(f, "React (Next.js)"),
@@ -62,6 +120,9 @@ impl fmt::Display for Framework {
       Self::Quasar => write!(f, "Vue.js (Quasar)"),
       Self::VueCli => write!(f, "Vue.js (Vue CLI)"),
       Self::Vue => write!(f, "Vue.js"),
+      Self::Vite => write!(f, "Vite"),
+      Self::Trunk => write!(f, "Rust (Trunk)"),
+      Self::Vanilla => write!(f, "Vanilla"),
     }
   }
 }
@@ -83,6 +144,7 @@ impl fmt::Display for Bundler {
 
 pub fn infer_from_package_json(package_json: &str) -> (Option<Framework>, Option<Bundler>) {
   let framework_map = [
+    ("@sveltejs/kit", Framework::SvelteKit, None),
     ("svelte", Framework::Svelte, None),
     ("@angular", Framework::Angular, Some(Bundler::Webpack)),
     (r#""next""#, Framework::Nextjs, Some(Bundler::Webpack)),
@@ -0,0 +1,108 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::HashMap,
+  path::PathBuf,
+  process::{Command, ExitStatus},
+};
+
+use anyhow::{bail, Context};
+use log::info;
+
+use super::{app_paths::app_dir, command_env, config::HookCommand};
+use crate::{CommandExt, Result};
+
+/// The resolved build context, exposed to the build hooks as environment variables.
+#[derive(Debug, Default)]
+pub struct HookContext {
+  /// `TAURI_TARGET_TRIPLE`
+  pub target_triple: Option<String>,
+  /// `TAURI_PROFILE`, the cargo profile.
+  pub profile: Option<String>,
+  /// `TAURI_OUT_DIR`, the directory the app binary is written to.
+  pub out_dir: Option<PathBuf>,
+  /// `TAURI_APP_VERSION`
+  pub version: Option<String>,
+  /// `TAURI_ARTIFACTS_PATH`, the JSON file listing the artifacts of the build.
+  pub artifacts_path: Option<PathBuf>,
+}
+
+impl HookContext {
+  fn env(&self) -> HashMap<&'static str, String> {
+    let mut env = HashMap::new();
+    if let Some(target_triple) = &self.target_triple {
+      env.insert("TAURI_TARGET_TRIPLE", target_triple.clone());
+    }
+    if let Some(profile) = &self.profile {
+      env.insert("TAURI_PROFILE", profile.clone());
+    }
+    if let Some(out_dir) = &self.out_dir {
+      env.insert("TAURI_OUT_DIR", out_dir.display().to_string());
+    }
+    if let Some(version) = &self.version {
+      env.insert("TAURI_APP_VERSION", version.clone());
+    }
+    if let Some(artifacts_path) = &self.artifacts_path {
+      env.insert("TAURI_ARTIFACTS_PATH", artifacts_path.display().to_string());
+    }
+    env
+  }
+}
+
+pub fn run_hook(name: &str, hook: HookCommand, debug: bool, context: &HookContext) -> Result<()> {
+  if let Some((script, status)) = run_hook_command(name, hook, debug, context)? {
+    if !status.success() {
+      bail!(
+        "{} `{}` failed with exit code {}",
+        name,
+        script,
+        status.code().unwrap_or_default()
+      );
+    }
+  }
+
+  Ok(())
+}
+
+/// Runs the hook script, returning it with its exit status or `None` if the hook is empty.
+pub fn run_hook_command(
+  name: &str,
+  hook: HookCommand,
+  debug: bool,
+  context: &HookContext,
+) -> Result<Option<(String, ExitStatus)>> {
+  let (script, script_cwd) = match hook {
+    HookCommand::Script(s) if s.is_empty() => (None, None),
+    HookCommand::Script(s) => (Some(s), None),
+    HookCommand::ScriptWithOptions { script, cwd } => (Some(script), cwd.map(Into::into)),
+  };
+  let cwd = script_cwd.unwrap_or_else(|| app_dir().clone());
+  if let Some(script) = script {
+    info!(action = "Running"; "{} `{}`", name, script);
+    #[cfg(target_os = "windows")]
+    let status = Command::new("cmd")
+      .arg("/S")
+      .arg("/C")
+      .arg(&script)
+      .current_dir(cwd)
+      .envs(command_env(debug))
+      .envs(context.env())
+      .piped()
+      .with_context(|| format!("failed to run `{}` with `cmd /C`", script))?;
+    #[cfg(not(target_os = "windows"))]
+    let status = Command::new("sh")
+      .arg("-c")
+      .arg(&script)
+      .current_dir(cwd)
+      .envs(command_env(debug))
+      .envs(context.env())
+      .piped()
+      .with_context(|| format!("failed to run `{}` with `sh -c`", script))?;
+
+    Ok(Some((script, status)))
+  } else {
+    Ok(None)
+  }
+}
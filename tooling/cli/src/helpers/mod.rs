@@ -4,8 +4,11 @@
 
 pub mod app_paths;
 pub mod config;
+pub mod doctor;
+pub mod env_file;
 pub mod flock;
 pub mod framework;
+pub mod hook;
 pub mod template;
 pub mod updater_signature;
 
@@ -47,3 +50,51 @@ pub fn resolve_tauri_path<P: AsRef<Path>>(path: P, crate_name: &str) -> PathBuf
     PathBuf::from("..").join(path).join(crate_name)
   }
 }
+
+/// The updater `{{target}}` and `{{arch}}` of the target triple, the platform key of the updater JSON is `<target>-<arch>`.
+pub fn updater_platform(target_triple: &str) -> Option<(&'static str, &'static str)> {
+  let os = if target_triple.contains("linux") && !target_triple.contains("android") {
+    "linux"
+  } else if target_triple.contains("apple-darwin") {
+    "darwin"
+  } else if target_triple.contains("windows") {
+    "windows"
+  } else {
+    return None;
+  };
+  let arch = match target_triple.split('-').next()? {
+    "x86_64" => "x86_64",
+    "i686" | "i586" => "i686",
+    "aarch64" => "aarch64",
+    arch if arch.starts_with("arm") => "armv7",
+    _ => return None,
+  };
+  Some((os, arch))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::updater_platform;
+
+  #[test]
+  fn maps_targets_to_updater_platforms() {
+    assert_eq!(
+      updater_platform("x86_64-unknown-linux-gnu"),
+      Some(("linux", "x86_64"))
+    );
+    assert_eq!(
+      updater_platform("aarch64-apple-darwin"),
+      Some(("darwin", "aarch64"))
+    );
+    assert_eq!(
+      updater_platform("i686-pc-windows-msvc"),
+      Some(("windows", "i686"))
+    );
+    assert_eq!(
+      updater_platform("armv7-unknown-linux-gnueabihf"),
+      Some(("linux", "armv7"))
+    );
+    assert_eq!(updater_platform("aarch64-linux-android"), None);
+    assert_eq!(updater_platform("aarch64-apple-ios"), None);
+  }
+}
@@ -4,15 +4,37 @@
 
 use anyhow::Context;
 use base64::{decode, encode};
-use minisign::{sign, KeyPair as KP, SecretKey, SecretKeyBox, SignatureBox};
+use minisign::{sign, KeyPair as KP, PublicKey, SecretKey, SecretKeyBox, SignatureBox};
 use std::{
   fs::{self, File, OpenOptions},
   io::{BufReader, BufWriter, Write},
   path::{Path, PathBuf},
+  process::Command,
   str,
   time::{SystemTime, UNIX_EPOCH},
 };
 
+/// The signer of the updater archives.
+pub enum Signer {
+  /// A secret key from `TAURI_PRIVATE_KEY`.
+  SecretKey(SecretKey),
+  /// An external minisign-compatible agent from `TAURI_SIGNING_AGENT`, e.g. backed by the OS keychain or a hardware token.
+  ///
+  /// The command line is split on whitespace, `%1` in the arguments is replaced with the path of the file to sign.
+  /// The agent prints the minisign signature of the file, as is or base64 encoded, to stdout.
+  Agent(String),
+}
+
+impl Signer {
+  /// Signs the file, writing the signature to `<file>.sig`.
+  pub fn sign<P: AsRef<Path>>(&self, bin_path: P) -> crate::Result<(PathBuf, SignatureBox)> {
+    match self {
+      Self::SecretKey(secret_key) => sign_file(secret_key, bin_path),
+      Self::Agent(agent) => sign_file_with_agent(agent, bin_path),
+    }
+  }
+}
+
 /// A key pair (`PublicKey` and `SecretKey`).
 #[derive(Clone, Debug)]
 pub struct KeyPair {
@@ -105,13 +127,6 @@ where
   P: AsRef<Path>,
 {
   let bin_path = bin_path.as_ref();
-  // We need to append .sig at the end it's where the signature will be stored
-  let mut extension = bin_path.extension().unwrap().to_os_string();
-  extension.push(".sig");
-  let signature_path = bin_path.with_extension(extension);
-
-  let mut signature_box_writer = create_file(&signature_path)?;
-
   let trusted_comment = format!(
     "timestamp:{}\tfile:{}",
     unix_timestamp(),
@@ -128,6 +143,85 @@ where
     Some("signature from tauri secret key"),
   )?;
 
+  write_signature(bin_path, signature_box)
+}
+
+/// Sign files with an external minisign-compatible agent, see [`Signer::Agent`].
+pub fn sign_file_with_agent<P>(agent: &str, bin_path: P) -> crate::Result<(PathBuf, SignatureBox)>
+where
+  P: AsRef<Path>,
+{
+  let bin_path = bin_path.as_ref();
+  let mut args = agent.split_whitespace();
+  let program = args
+    .next()
+    .ok_or_else(|| anyhow::anyhow!("the signing agent command is empty"))?;
+  let mut cmd = Command::new(program);
+  for arg in args {
+    cmd.arg(arg.replace("%1", &bin_path.to_string_lossy()));
+  }
+  let output = cmd
+    .output()
+    .with_context(|| format!("failed to run the signing agent `{}`", program))?;
+  if !output.status.success() {
+    return Err(anyhow::anyhow!(
+      "the signing agent `{}` failed to sign {}: {}",
+      program,
+      bin_path.display(),
+      String::from_utf8_lossy(&output.stderr).trim()
+    ));
+  }
+
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let signature = stdout.trim();
+  // the agent may print the signature box or its base64 encoding, as in the `.sig` files
+  let signature_box = match SignatureBox::from_string(signature) {
+    Ok(signature_box) => Ok(signature_box),
+    Err(_) => {
+      decode_key(signature.into()).and_then(|decoded| Ok(SignatureBox::from_string(&decoded)?))
+    }
+  }
+  .with_context(|| {
+    format!(
+      "the signing agent `{}` did not print a minisign signature",
+      program
+    )
+  })?;
+
+  write_signature(bin_path, signature_box)
+}
+
+/// Verifies the signature of a file with the public key.
+pub fn verify_file<P>(
+  public_key: &PublicKey,
+  signature_box: &SignatureBox,
+  bin_path: P,
+) -> crate::Result<()>
+where
+  P: AsRef<Path>,
+{
+  minisign::verify(
+    public_key,
+    signature_box,
+    open_data_file(bin_path)?,
+    true,
+    false,
+    false,
+  )?;
+  Ok(())
+}
+
+/// Writes the base64 encoded signature box to `<file>.sig`.
+fn write_signature(
+  bin_path: &Path,
+  signature_box: SignatureBox,
+) -> crate::Result<(PathBuf, SignatureBox)> {
+  // We need to append .sig at the end it's where the signature will be stored
+  let mut extension = bin_path.extension().unwrap().to_os_string();
+  extension.push(".sig");
+  let signature_path = bin_path.with_extension(extension);
+
+  let mut signature_box_writer = create_file(&signature_path)?;
   let encoded_signature = encode(&signature_box.to_string());
   signature_box_writer.write_all(encoded_signature.as_bytes())?;
   signature_box_writer.flush()?;
@@ -6,7 +6,7 @@ use crate::{helpers::app_paths::tauri_dir, Result};
 
 use std::{
   collections::HashMap,
-  fs::{create_dir_all, File},
+  fs::{create_dir_all, read_dir, remove_file, write, File},
   io::{BufWriter, Write},
   path::{Path, PathBuf},
 };
@@ -19,10 +19,11 @@ use image::{
     ico::{IcoEncoder, IcoFrame},
     png::{CompressionType, FilterType as PngFilterType, PngEncoder},
   },
-  imageops::FilterType,
-  open, ColorType, DynamicImage, ImageEncoder,
+  imageops::{self, FilterType},
+  open, ColorType, DynamicImage, ImageEncoder, Rgba, RgbaImage,
 };
 use serde::Deserialize;
+use serde_json::json;
 
 #[derive(Debug, Deserialize)]
 struct IcnsEntry {
@@ -30,17 +31,108 @@ struct IcnsEntry {
   ostype: String,
 }
 
+/// The iOS app icon set: idiom, size in points and scale.
+const IOS_ICONS: &[(&str, f32, u32)] = &[
+  ("iphone", 20.0, 2),
+  ("iphone", 20.0, 3),
+  ("iphone", 29.0, 2),
+  ("iphone", 29.0, 3),
+  ("iphone", 40.0, 2),
+  ("iphone", 40.0, 3),
+  ("iphone", 60.0, 2),
+  ("iphone", 60.0, 3),
+  ("ipad", 20.0, 1),
+  ("ipad", 20.0, 2),
+  ("ipad", 29.0, 1),
+  ("ipad", 29.0, 2),
+  ("ipad", 40.0, 1),
+  ("ipad", 40.0, 2),
+  ("ipad", 76.0, 1),
+  ("ipad", 76.0, 2),
+  ("ipad", 83.5, 2),
+  ("ios-marketing", 1024.0, 1),
+];
+
+/// The Android launcher icon densities and their legacy icon size.
+const ANDROID_DENSITIES: &[(&str, u32)] = &[
+  ("mdpi", 48),
+  ("hdpi", 72),
+  ("xhdpi", 96),
+  ("xxhdpi", 144),
+  ("xxxhdpi", 192),
+];
+
 #[derive(Debug, Parser)]
 #[clap(about = "Generates various icons for all major platforms")]
 pub struct Options {
   // TODO: Confirm 1240px
-  /// Path to the source icon (png, 1240x1240px with transparency).
+  /// Path to the source icon (squared png or svg, 1240x1240px with transparency).
   #[clap(default_value = "./app-icon.png")]
   input: PathBuf,
   /// Output directory.
   /// Default: 'icons' directory next to the tauri.conf.json file.
   #[clap(short, long)]
   output: Option<PathBuf>,
+  /// Background color of the iOS icons, which cannot be transparent.
+  #[clap(long, default_value = "#ffffff")]
+  ios_color: String,
+  /// Background color of the Android adaptive icon.
+  #[clap(long, default_value = "#ffffff")]
+  android_color: String,
+  /// Space around the icon in the Android adaptive icon foreground, in percent of its size.
+  /// The default keeps the icon in the safe zone of the launcher masks.
+  #[clap(long, default_value = "19")]
+  android_padding: u32,
+}
+
+/// The source icon, rendered at each size for the svg input.
+enum Source {
+  Svg(usvg::Tree),
+  DynamicImage(DynamicImage),
+}
+
+impl Source {
+  fn width(&self) -> u32 {
+    match self {
+      Self::Svg(svg) => svg.svg_node().size.width() as u32,
+      Self::DynamicImage(image) => image.width(),
+    }
+  }
+
+  fn height(&self) -> u32 {
+    match self {
+      Self::Svg(svg) => svg.svg_node().size.height() as u32,
+      Self::DynamicImage(image) => image.height(),
+    }
+  }
+
+  fn resize_exact(&self, size: u32) -> Result<DynamicImage> {
+    match self {
+      Self::Svg(svg) => {
+        let mut pixmap = tiny_skia::Pixmap::new(size, size).context("Can't create svg canvas")?;
+        resvg::render(
+          svg,
+          usvg::FitTo::Size(size, size),
+          tiny_skia::Transform::default(),
+          pixmap.as_mut(),
+        )
+        .context("Can't render svg")?;
+        // tiny-skia pixels have premultiplied alpha
+        let data = pixmap
+          .pixels()
+          .iter()
+          .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+          })
+          .collect();
+        Ok(DynamicImage::ImageRgba8(
+          RgbaImage::from_raw(size, size, data).unwrap(),
+        ))
+      }
+      Self::DynamicImage(image) => Ok(image.resize_exact(size, size, FilterType::Lanczos3)),
+    }
+  }
 }
 
 pub fn command(options: Options) -> Result<()> {
@@ -48,13 +140,33 @@ pub fn command(options: Options) -> Result<()> {
   let out_dir = options.output.unwrap_or_else(|| tauri_dir().join("icons"));
   create_dir_all(&out_dir).context("Can't create output directory")?;
 
-  // Try to read the image as a DynamicImage, convert it to rgba8 and turn it into a DynamicImage again.
-  // Both things should be catched by the explicit conversions to rgba8 anyway.
-  let source = open(input)
-    .context("Can't read and decode source image")?
-    .into_rgba8();
+  let ios_color = parse_color(&options.ios_color)?;
+  let android_color = parse_color(&options.android_color)?;
+  if options.android_padding >= 50 {
+    anyhow::bail!("The Android padding must be less than 50 percent");
+  }
 
-  let source = DynamicImage::ImageRgba8(source);
+  let source = if input.extension().map_or(false, |ext| ext == "svg") {
+    let mut opt = usvg::Options {
+      // resolves the images referenced by the svg relatively to its directory
+      resources_dir: input
+        .canonicalize()
+        .ok()
+        .and_then(|p| p.parent().map(Into::into)),
+      ..Default::default()
+    };
+    opt.fontdb.load_system_fonts();
+    let data = std::fs::read(&input).context("Can't read source image")?;
+    Source::Svg(usvg::Tree::from_data(&data, &opt.to_ref()).context("Can't parse source svg")?)
+  } else {
+    // Try to read the image as a DynamicImage, convert it to rgba8 and turn it into a DynamicImage again.
+    // Both things should be catched by the explicit conversions to rgba8 anyway.
+    let source = open(input)
+      .context("Can't read and decode source image")?
+      .into_rgba8();
+
+    Source::DynamicImage(DynamicImage::ImageRgba8(source))
+  };
 
   if source.height() != source.width() {
     panic!("Source image must be square");
@@ -68,10 +180,33 @@ pub fn command(options: Options) -> Result<()> {
 
   png(&source, &out_dir).context("Failed to generate png icons")?;
 
+  // the mobile icons are written in the projects generated by `tauri android init` and `tauri ios init`
+  let tauri_dir = tauri_dir();
+  let android_res_dirs = read_dir(tauri_dir.join("gen/android"))
+    .into_iter()
+    .flatten()
+    .flatten()
+    .map(|entry| entry.path().join("app/src/main/res"))
+    .filter(|dir| dir.is_dir());
+  for res_dir in android_res_dirs {
+    android(&source, &res_dir, android_color, options.android_padding)
+      .context("Failed to generate Android icons")?;
+  }
+
+  let ios_assets_dir = tauri_dir.join("gen/apple/Assets.xcassets");
+  if ios_assets_dir.is_dir() {
+    ios(
+      &source,
+      &ios_assets_dir.join("AppIcon.appiconset"),
+      ios_color,
+    )
+    .context("Failed to generate iOS icons")?;
+  }
+
   Ok(())
 }
 
-fn appx(source: &DynamicImage, out_dir: &Path) -> Result<()> {
+fn appx(source: &Source, out_dir: &Path) -> Result<()> {
   log::info!(action = "Appx"; "Creating StoreLogo.png");
   resize_and_save_png(source, 50, &out_dir.join("StoreLogo.png"))?;
 
@@ -86,7 +221,7 @@ fn appx(source: &DynamicImage, out_dir: &Path) -> Result<()> {
 }
 
 // Main target: macOS
-fn icns(source: &DynamicImage, out_dir: &Path) -> Result<()> {
+fn icns(source: &Source, out_dir: &Path) -> Result<()> {
   log::info!(action = "ICNS"; "Creating icon.icns");
   let entries: HashMap<String, IcnsEntry> =
     serde_json::from_slice(include_bytes!("helpers/icns.json")).unwrap();
@@ -97,9 +232,9 @@ fn icns(source: &DynamicImage, out_dir: &Path) -> Result<()> {
     let size = entry.size;
     let mut buf = Vec::new();
 
-    let image = source.resize_exact(size, size, FilterType::Lanczos3);
+    let image = source.resize_exact(size)?;
 
-    write_png(image.as_bytes(), &mut buf, size)?;
+    write_png(&image, &mut buf)?;
 
     let image = icns::Image::read_png(&buf[..])?;
 
@@ -120,18 +255,18 @@ fn icns(source: &DynamicImage, out_dir: &Path) -> Result<()> {
 
 // Generate .ico file with layers for the most common sizes.
 // Main target: Windows
-fn ico(source: &DynamicImage, out_dir: &Path) -> Result<()> {
+fn ico(source: &Source, out_dir: &Path) -> Result<()> {
   log::info!(action = "ICO"; "Creating icon.ico");
   let mut frames = Vec::new();
 
   for size in [32, 16, 24, 48, 64, 256] {
-    let image = source.resize_exact(size, size, FilterType::Lanczos3);
+    let image = source.resize_exact(size)?;
 
     // Only the 256px layer can be compressed according to the ico specs.
     if size == 256 {
       let mut buf = Vec::new();
 
-      write_png(image.as_bytes(), &mut buf, size)?;
+      write_png(&image, &mut buf)?;
 
       frames.push(IcoFrame::with_encoded(buf, size, size, ColorType::Rgba8)?)
     } else {
@@ -154,7 +289,7 @@ fn ico(source: &DynamicImage, out_dir: &Path) -> Result<()> {
 
 // Generate .png files in 32x32, 128x128, 256x256, 512x512 (icon.png)
 // Main target: Linux
-fn png(source: &DynamicImage, out_dir: &Path) -> Result<()> {
+fn png(source: &Source, out_dir: &Path) -> Result<()> {
   for size in [32, 128, 256, 512] {
     let file_name = match size {
       256 => "128x128@2.png".to_string(),
@@ -169,20 +304,166 @@ fn png(source: &DynamicImage, out_dir: &Path) -> Result<()> {
   Ok(())
 }
 
+// Generate the legacy launcher icons and the adaptive icon layers of the Android project.
+// Main target: Android
+fn android(source: &Source, res_dir: &Path, background: Rgba<u8>, padding: u32) -> Result<()> {
+  for (density, size) in ANDROID_DENSITIES {
+    let dir = res_dir.join(format!("mipmap-{}", density));
+    create_dir_all(&dir)?;
+    // the png icon replaces the webp icon of the project template
+    let _ = remove_file(dir.join("ic_launcher.webp"));
+
+    log::info!(action = "Android"; "Creating mipmap-{}/ic_launcher.png", density);
+    resize_and_save_png(source, *size, &dir.join("ic_launcher.png"))?;
+
+    // the adaptive icon layers are 108dp, the legacy icon is 48dp
+    let layer_size = size * 9 / 4;
+    let icon_size = layer_size * (100 - 2 * padding) / 100;
+    let foreground = center(
+      &source.resize_exact(icon_size)?,
+      layer_size,
+      Rgba([0, 0, 0, 0]),
+    );
+    log::info!(action = "Android"; "Creating mipmap-{}/ic_launcher_foreground.png", density);
+    save_png(&foreground, &dir.join("ic_launcher_foreground.png"))?;
+  }
+
+  let adaptive_dir = res_dir.join("mipmap-anydpi-v26");
+  create_dir_all(&adaptive_dir)?;
+  write(
+    adaptive_dir.join("ic_launcher.xml"),
+    r#"<?xml version="1.0" encoding="utf-8"?>
+<adaptive-icon xmlns:android="http://schemas.android.com/apk/res/android">
+    <background android:drawable="@color/ic_launcher_background" />
+    <foreground android:drawable="@mipmap/ic_launcher_foreground" />
+</adaptive-icon>
+"#,
+  )?;
+  let values_dir = res_dir.join("values");
+  create_dir_all(&values_dir)?;
+  write(
+    values_dir.join("ic_launcher_background.xml"),
+    format!(
+      r#"<?xml version="1.0" encoding="utf-8"?>
+<resources>
+    <color name="ic_launcher_background">#{:02X}{:02X}{:02X}</color>
+</resources>
+"#,
+      background[0], background[1], background[2]
+    ),
+  )?;
+
+  Ok(())
+}
+
+// Generate the app icon set of the Xcode project, flattened on the background color.
+// Main target: iOS
+fn ios(source: &Source, icon_set_dir: &Path, background: Rgba<u8>) -> Result<()> {
+  create_dir_all(icon_set_dir)?;
+
+  let mut images = Vec::new();
+  let mut written = Vec::new();
+  for (idiom, points, scale) in IOS_ICONS {
+    let file_name = format!("AppIcon-{}x{}@{}x.png", points, points, scale);
+    let size = (points * *scale as f32).round() as u32;
+    // the iPhone and iPad icons share the files of the same size
+    if !written.contains(&file_name) {
+      log::info!(action = "iOS"; "Creating {}", file_name);
+      let image = center(&source.resize_exact(size)?, size, background);
+      // the App Store rejects icons with an alpha channel
+      save_png(
+        &DynamicImage::ImageRgb8(image.to_rgb8()),
+        &icon_set_dir.join(&file_name),
+      )?;
+      written.push(file_name.clone());
+    }
+
+    images.push(json!({
+      "size": format!("{}x{}", points, points),
+      "idiom": idiom,
+      "filename": file_name,
+      "scale": format!("{}x", scale),
+    }));
+  }
+
+  write(
+    icon_set_dir.join("Contents.json"),
+    serde_json::to_string_pretty(&json!({
+      "images": images,
+      "info": {
+        "author": "xcode",
+        "version": 1
+      }
+    }))?,
+  )?;
+
+  Ok(())
+}
+
+// Center the image on a square canvas of the given size and color.
+fn center(image: &DynamicImage, size: u32, color: Rgba<u8>) -> DynamicImage {
+  let mut canvas = RgbaImage::from_pixel(size, size, color);
+  let offset = i64::from((size - image.width()) / 2);
+  imageops::overlay(&mut canvas, image, offset, offset);
+  DynamicImage::ImageRgba8(canvas)
+}
+
+// Parse a `#rrggbb` or `#rgb` color.
+fn parse_color(color: &str) -> Result<Rgba<u8>> {
+  let hex = color.trim_start_matches('#');
+  let hex = if hex.len() == 3 {
+    hex.chars().flat_map(|c| [c, c]).collect()
+  } else {
+    hex.to_string()
+  };
+  if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+    anyhow::bail!(
+      "Invalid color `{}`, expected a hex color like `#ffffff`",
+      color
+    );
+  }
+  let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+  Ok(Rgba([channel(0), channel(2), channel(4), 255]))
+}
+
 // Resize image and save it to disk.
-fn resize_and_save_png(source: &DynamicImage, size: u32, file_path: &Path) -> Result<()> {
-  let image = source.resize_exact(size, size, FilterType::Lanczos3);
+fn resize_and_save_png(source: &Source, size: u32, file_path: &Path) -> Result<()> {
+  let image = source.resize_exact(size)?;
+  save_png(&image, file_path)
+}
 
+// Save image to disk.
+fn save_png(image: &DynamicImage, file_path: &Path) -> Result<()> {
   let mut out_file = BufWriter::new(File::create(file_path)?);
 
-  write_png(image.as_bytes(), &mut out_file, size)?;
+  write_png(image, &mut out_file)?;
 
   Ok(out_file.flush()?)
 }
 
 // Encode image data as png with compression.
-fn write_png<W: Write>(image_data: &[u8], w: W, size: u32) -> Result<()> {
+fn write_png<W: Write>(image: &DynamicImage, w: W) -> Result<()> {
   let encoder = PngEncoder::new_with_quality(w, CompressionType::Best, PngFilterType::Adaptive);
-  encoder.write_image(image_data, size, size, ColorType::Rgba8)?;
+  encoder.write_image(
+    image.as_bytes(),
+    image.width(),
+    image.height(),
+    image.color(),
+  )?;
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::parse_color;
+  use image::Rgba;
+
+  #[test]
+  fn parses_hex_colors() {
+    assert_eq!(parse_color("#ffffff").unwrap(), Rgba([255, 255, 255, 255]));
+    assert_eq!(parse_color("1e90ff").unwrap(), Rgba([30, 144, 255, 255]));
+    assert_eq!(parse_color("#f00").unwrap(), Rgba([255, 0, 0, 255]));
+    assert!(parse_color("#ff").is_err());
+    assert!(parse_color("white").is_err());
+  }
+}
@@ -3,13 +3,19 @@
 // SPDX-License-Identifier: MIT
 
 use crate::{
-  helpers::{config::get as get_config, framework::infer_from_package_json as infer_framework},
+  helpers::{
+    config::get as get_config,
+    doctor::{self, Issue},
+    framework::infer_from_package_json as infer_framework,
+  },
   interface::rust::get_workspace_dir,
   Result,
 };
 use clap::Parser;
 use colored::Colorize;
-use serde::Deserialize;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use std::{
   collections::HashMap,
@@ -87,7 +93,52 @@ enum PackageManager {
 
 #[derive(Debug, Parser)]
 #[clap(about = "Shows information about Tauri dependencies and project configuration")]
-pub struct Options;
+pub struct Options {
+  /// Print the information as JSON
+  #[clap(long)]
+  json: bool,
+  /// Offer to install the missing pieces of the environment: Rust targets, WebView2, the Xcode command line tools and Android SDK packages
+  #[clap(long, conflicts_with = "json")]
+  fix: bool,
+  /// Install the missing pieces without prompting
+  #[clap(long, requires = "fix")]
+  ci: bool,
+}
+
+/// The information of the command, printed as it is collected or as JSON at the end.
+#[derive(Default, Serialize)]
+struct Report {
+  #[serde(skip)]
+  json: bool,
+  sections: Vec<ReportSection>,
+  issues: Vec<Issue>,
+}
+
+#[derive(Serialize)]
+struct ReportSection {
+  name: &'static str,
+  items: Vec<ReportItem>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportItem {
+  name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  version: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  latest_version: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  value: Option<Value>,
+}
+
+impl Report {
+  fn push(&mut self, item: ReportItem) {
+    if let Some(section) = self.sections.last_mut() {
+      section.items.push(item);
+    }
+  }
+}
 
 fn version_metadata() -> Result<VersionMetadata> {
   serde_json::from_str::<VersionMetadata>(include_str!("../metadata.json")).map_err(Into::into)
@@ -526,9 +577,16 @@ fn indent(spaces: usize) {
 
 struct Section(&'static str);
 impl Section {
-  fn display(&self) {
-    println!();
-    println!("{}", self.0.yellow().bold());
+  fn display(&self, report: &mut Report) {
+    if report.json {
+      report.sections.push(ReportSection {
+        name: self.0,
+        items: Vec::new(),
+      });
+    } else {
+      println!();
+      println!("{}", self.0.yellow().bold());
+    }
   }
 }
 
@@ -554,7 +612,16 @@ impl VersionBlock {
     self
   }
 
-  fn display(&self) {
+  fn display(&self, report: &mut Report) {
+    if report.json {
+      report.push(ReportItem {
+        name: self.name.clone(),
+        version: Some(self.version.clone()).filter(|v| !v.is_empty()),
+        latest_version: Some(self.target_version.clone()).filter(|v| !v.is_empty()),
+        value: None,
+      });
+      return;
+    }
     indent(self.indentation);
     print!("{} ", "›".cyan());
     print!("{}", self.name.bold());
@@ -585,6 +652,7 @@ impl VersionBlock {
 struct InfoBlock {
   key: String,
   value: String,
+  list: Vec<String>,
   indentation: usize,
 }
 
@@ -593,22 +661,54 @@ impl InfoBlock {
     Self {
       key: key.into(),
       value: val.into(),
+      list: Vec::new(),
       indentation: 2,
     }
   }
 
-  fn display(&self) {
+  #[cfg(windows)]
+  fn list(mut self, list: Vec<String>) -> Self {
+    self.list = list;
+    self
+  }
+
+  fn display(&self, report: &mut Report) {
+    if report.json {
+      report.push(ReportItem {
+        name: self.key.clone(),
+        version: None,
+        latest_version: None,
+        value: Some(if self.list.is_empty() {
+          Value::String(self.value.clone())
+        } else {
+          self.list.clone().into()
+        }),
+      });
+      return;
+    }
     indent(self.indentation);
     print!("{} ", "›".cyan());
     print!("{}", self.key.bold());
     print!(": ");
     print!("{}", self.value.clone());
     println!();
+    for item in &self.list {
+      indent(self.indentation + 4);
+      println!("{} {}", "-".cyan(), item);
+    }
   }
 }
 
-pub fn command(_options: Options) -> Result<()> {
-  Section("Environment").display();
+pub fn command(options: Options) -> Result<()> {
+  let mut report = Report {
+    json: options.json,
+    ..Default::default()
+  };
+  if options.json {
+    colored::control::set_override(false);
+  }
+
+  Section("Environment").display(&mut report);
 
   let os_info = os_info::get();
   VersionBlock::new(
@@ -620,31 +720,21 @@ pub fn command(_options: Options) -> Result<()> {
       os_info.bitness()
     ),
   )
-  .display();
+  .display(&mut report);
 
   #[cfg(windows)]
-  VersionBlock::new(
-    "Webview2",
-    webview2_version().unwrap_or_default().unwrap_or_default(),
-  )
-  .display();
-
+  let webview2_version = webview2_version().unwrap_or_default();
   #[cfg(windows)]
-  {
-    let build_tools = build_tools_version()
-      .unwrap_or_default()
-      .unwrap_or_default();
+  VersionBlock::new("Webview2", webview2_version.clone().unwrap_or_default()).display(&mut report);
 
-    if build_tools.is_empty() {
-      InfoBlock::new("MSVC", "").display();
-    } else {
-      InfoBlock::new("MSVC", "").display();
-      for i in build_tools {
-        indent(6);
-        println!("{} {}", "-".cyan(), i);
-      }
-    }
-  }
+  #[cfg(windows)]
+  InfoBlock::new("MSVC", "")
+    .list(
+      build_tools_version()
+        .unwrap_or_default()
+        .unwrap_or_default(),
+    )
+    .display(&mut report);
 
   let hook = panic::take_hook();
   panic::set_hook(Box::new(|_info| {
@@ -670,7 +760,7 @@ pub fn command(_options: Options) -> Result<()> {
       .collect::<String>(),
   )
   .target_version(metadata.js_cli.node.replace(">= ", ""))
-  .display();
+  .display(&mut report);
 
   VersionBlock::new(
     "npm",
@@ -678,15 +768,15 @@ pub fn command(_options: Options) -> Result<()> {
       .unwrap_or_default()
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   VersionBlock::new(
     "pnpm",
     get_version("pnpm", &[])
       .unwrap_or_default()
       .unwrap_or_default(),
   )
-  .display();
-  VersionBlock::new("yarn", &yarn_version).display();
+  .display(&mut report);
+  VersionBlock::new("yarn", &yarn_version).display(&mut report);
   VersionBlock::new(
     "rustup",
     get_version("rustup", &[])
@@ -698,7 +788,7 @@ pub fn command(_options: Options) -> Result<()> {
       })
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   VersionBlock::new(
     "rustc",
     get_version("rustc", &[])
@@ -710,7 +800,7 @@ pub fn command(_options: Options) -> Result<()> {
       })
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   VersionBlock::new(
     "cargo",
     get_version("cargo", &[])
@@ -722,16 +812,16 @@ pub fn command(_options: Options) -> Result<()> {
       })
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   InfoBlock::new(
     "Rust toolchain",
     active_rust_toolchain()
       .unwrap_or_default()
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
 
-  Section("Packages").display();
+  Section("Packages").display(&mut report);
 
   let mut package_manager = PackageManager::Npm;
   if let Some(app_dir) = &app_dir {
@@ -761,7 +851,7 @@ pub fn command(_options: Options) -> Result<()> {
       .unwrap_or_default()
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   if let Some(app_dir) = &app_dir {
     VersionBlock::new(
       format!("{} {}", "@tauri-apps/api", "[NPM]".dimmed()),
@@ -774,7 +864,7 @@ pub fn command(_options: Options) -> Result<()> {
         .unwrap_or_default()
         .unwrap_or_default(),
     )
-    .display();
+    .display(&mut report);
   }
 
   let hook = panic::take_hook();
@@ -818,15 +908,15 @@ pub fn command(_options: Options) -> Result<()> {
             version_suffix.unwrap_or_else(|| "".into())
           ),
         )
-        .display();
+        .display(&mut report);
       }
     }
   }
 
   if tauri_dir.is_some() || app_dir.is_some() {
-    Section("App").display();
+    Section("App").display(&mut report);
     if tauri_dir.is_some() {
-      if let Ok(config) = get_config(None) {
+      if let Ok(config) = get_config(true, None) {
         let config_guard = config.lock().unwrap();
         let config = config_guard.as_ref().unwrap();
         InfoBlock::new(
@@ -837,7 +927,7 @@ pub fn command(_options: Options) -> Result<()> {
             "build".to_string()
           },
         )
-        .display();
+        .display(&mut report);
         InfoBlock::new(
           "CSP",
           config
@@ -848,9 +938,9 @@ pub fn command(_options: Options) -> Result<()> {
             .map(|c| c.to_string())
             .unwrap_or_else(|| "unset".to_string()),
         )
-        .display();
-        InfoBlock::new("distDir", config.build.dist_dir.to_string()).display();
-        InfoBlock::new("devPath", config.build.dev_path.to_string()).display();
+        .display(&mut report);
+        InfoBlock::new("distDir", config.build.dist_dir.to_string()).display(&mut report);
+        InfoBlock::new("devPath", config.build.dev_path.to_string()).display(&mut report);
       }
     }
 
@@ -858,35 +948,91 @@ pub fn command(_options: Options) -> Result<()> {
       if let Ok(package_json) = read_to_string(app_dir.join("package.json")) {
         let (framework, bundler) = infer_framework(&package_json);
         if let Some(framework) = framework {
-          InfoBlock::new("framework", framework.to_string()).display();
+          InfoBlock::new("framework", framework.to_string()).display(&mut report);
         }
         if let Some(bundler) = bundler {
-          InfoBlock::new("bundler", bundler.to_string()).display();
+          InfoBlock::new("bundler", bundler.to_string()).display(&mut report);
         }
-      } else {
+      } else if !report.json {
         println!("package.json not found");
       }
     }
   }
 
-  if let Some(app_dir) = app_dir {
-    Section("App directory structure").display();
+  if let Some(app_dir) = &app_dir {
+    Section("App directory structure").display(&mut report);
     let dirs = read_dir(app_dir)?
       .filter(|p| p.is_ok() && p.as_ref().unwrap().path().is_dir())
       .collect::<Vec<Result<std::fs::DirEntry, _>>>();
     let dirs_len = dirs.len();
     for (i, entry) in dirs.into_iter().enumerate() {
       let entry = entry?;
+      let name = entry
+        .path()
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .into_owned();
+      if report.json {
+        report.push(ReportItem {
+          name,
+          version: None,
+          latest_version: None,
+          value: None,
+        });
+        continue;
+      }
       let prefix = if i + 1 == dirs_len {
         "└─".cyan()
       } else {
         "├─".cyan()
       };
-      println!(
-        "  {} {}",
-        prefix,
-        entry.path().file_name().unwrap().to_string_lossy()
-      );
+      println!("  {} {}", prefix, name);
+    }
+  }
+
+  let mut issues = Vec::new();
+  #[cfg(windows)]
+  issues.extend(doctor::webview2(webview2_version.as_deref()));
+  #[cfg(target_os = "macos")]
+  issues.extend(doctor::xcode_command_line_tools());
+  if let Some(tauri_dir) = &tauri_dir {
+    if tauri_dir.join("gen/android").exists() {
+      issues.extend(doctor::rust_targets(doctor::ANDROID_TARGETS));
+      issues.extend(doctor::android_sdk());
+    }
+    #[cfg(target_os = "macos")]
+    if tauri_dir.join("gen/apple").exists() {
+      issues.extend(doctor::rust_targets(doctor::IOS_TARGETS));
+    }
+  }
+
+  if report.json {
+    report.issues = issues;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    return Ok(());
+  }
+
+  if !issues.is_empty() {
+    Section("Issues").display(&mut report);
+    for issue in &issues {
+      indent(2);
+      println!("{} {}", "›".red(), issue.message);
+      if let Some(fix) = &issue.fix {
+        indent(4);
+        println!("{} {}", "fix:".dimmed(), fix.command_line());
+      }
+    }
+    if options.fix {
+      println!();
+      for issue in &issues {
+        if let Some(fix) = &issue.fix {
+          fix.confirm_and_run(&format!("{}.", issue.message), options.ci)?;
+        }
+      }
+    } else if issues.iter().any(|issue| issue.fix.is_some()) {
+      println!();
+      println!("Run `tauri info --fix` to install the missing pieces.");
     }
   }
 
@@ -909,7 +1055,7 @@ fn get_package_manager<T: AsRef<str>>(app_dir_entries: &[T]) -> crate::Result<Pa
   }
 
   if !use_npm && !use_pnpm && !use_yarn {
-    println!("WARNING: no lock files found, defaulting to npm");
+    warn!("no lock files found, defaulting to npm");
     return Ok(PackageManager::Npm);
   }
 
@@ -14,7 +14,7 @@ use std::{
   env::current_dir,
   fmt::Display,
   fs::{read_to_string, remove_dir_all},
-  path::PathBuf,
+  path::{Path, PathBuf},
   str::FromStr,
 };
 
@@ -25,8 +25,9 @@ use dialoguer::Input;
 use handlebars::{to_json, Handlebars};
 use include_dir::{include_dir, Dir};
 use log::warn;
+use serde::Deserialize;
 
-const TEMPLATE_DIR: Dir<'_> = include_dir!("templates/app");
+pub(crate) const TEMPLATE_DIR: Dir<'_> = include_dir!("templates/app");
 const TAURI_CONF_TEMPLATE: &str = include_str!("../templates/tauri.conf.json");
 
 #[derive(Debug, Parser)]
@@ -66,6 +67,35 @@ pub struct Options {
   /// A shell command to run before `tauri build` kicks in.
   #[clap(long)]
   before_build_command: Option<String>,
+  /// Frontend framework preset, setting the defaults of the dev server url, web assets location and frontend commands.
+  #[clap(long, value_enum)]
+  frontend: Option<Framework>,
+  /// Path to a JSON file answering the prompts, e.g. `{ "appName": "My App", "frontend": "vite" }`.
+  /// The command line flags take precedence over its values.
+  #[clap(long)]
+  preset: Option<PathBuf>,
+}
+
+/// The answers of `--preset <file>`, keyed by the camelCase name of the flags.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct Preset {
+  app_name: Option<String>,
+  window_title: Option<String>,
+  dist_dir: Option<String>,
+  dev_path: Option<String>,
+  before_dev_command: Option<String>,
+  before_build_command: Option<String>,
+  frontend: Option<Framework>,
+}
+
+impl Preset {
+  fn load(path: &Path) -> Result<Self> {
+    let preset =
+      read_to_string(path).with_context(|| format!("failed to read preset {}", path.display()))?;
+    serde_json::from_str(&preset)
+      .with_context(|| format!("failed to parse preset {}", path.display()))
+  }
 }
 
 #[derive(Default)]
@@ -77,9 +107,21 @@ struct InitDefaults {
 impl Options {
   fn load(mut self) -> Result<Self> {
     self.ci = self.ci || std::env::var("CI").is_ok();
+
+    if let Some(path) = &self.preset {
+      let preset = Preset::load(path)?;
+      self.app_name = self.app_name.or(preset.app_name);
+      self.window_title = self.window_title.or(preset.window_title);
+      self.dist_dir = self.dist_dir.or(preset.dist_dir);
+      self.dev_path = self.dev_path.or(preset.dev_path);
+      self.before_dev_command = self.before_dev_command.or(preset.before_dev_command);
+      self.before_build_command = self.before_build_command.or(preset.before_build_command);
+      self.frontend = self.frontend.or(preset.frontend);
+    }
+
     let package_json_path = PathBuf::from(&self.directory).join("package.json");
 
-    let init_defaults = if package_json_path.exists() {
+    let mut init_defaults = if package_json_path.exists() {
       let package_json_text = read_to_string(package_json_path)?;
       let package_json: crate::PackageJson = serde_json::from_str(&package_json_text)?;
       let (framework, _) = infer_framework(&package_json_text);
@@ -90,6 +132,9 @@ impl Options {
     } else {
       Default::default()
     };
+    if let Some(frontend) = &self.frontend {
+      init_defaults.framework.replace(frontend.clone());
+    }
 
     self.app_name = self.app_name.map(|s| Ok(Some(s))).unwrap_or_else(|| {
       request_input(
@@ -119,7 +164,7 @@ impl Options {
     self.dev_path = self.dev_path.map(|s| Ok(Some(s))).unwrap_or_else(|| {
       request_input(
         "What is the url of your dev server?",
-        init_defaults.framework.map(|f| f.dev_path()),
+        init_defaults.framework.as_ref().map(|f| f.dev_path()),
         self.ci,
         false,
       )
@@ -131,7 +176,12 @@ impl Options {
       .unwrap_or_else(|| {
         request_input(
           "What is your frontend dev command?",
-          Some("npm run dev".to_string()),
+          Some(
+            init_defaults
+              .framework
+              .as_ref()
+              .map_or_else(|| "npm run dev".to_string(), |f| f.before_dev_command()),
+          ),
           self.ci,
           true,
         )
@@ -142,7 +192,12 @@ impl Options {
       .unwrap_or_else(|| {
         request_input(
           "What is your frontend build command?",
-          Some("npm run build".to_string()),
+          Some(
+            init_defaults
+              .framework
+              .as_ref()
+              .map_or_else(|| "npm run build".to_string(), |f| f.before_build_command()),
+          ),
           self.ci,
           true,
         )
@@ -186,7 +241,12 @@ pub fn command(mut options: Options) -> Result<()> {
     };
 
     let _ = remove_dir_all(&template_target_path);
-    let handlebars = Handlebars::new();
+    let mut handlebars = Handlebars::new();
+    // the values are rendered in the JSON strings of the tauri.conf.json template
+    handlebars.register_escape_fn(|value| {
+      let value = serde_json::to_string(value).unwrap();
+      value[1..value.len() - 1].to_string()
+    });
 
     let mut data = BTreeMap::new();
     data.insert("tauri_dep", to_json(tauri_dep));
@@ -307,3 +367,21 @@ where
     builder.interact_text().map(Some).map_err(Into::into)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::Preset;
+  use crate::helpers::framework::Framework;
+
+  #[test]
+  fn parses_preset() {
+    let preset: Preset = serde_json::from_str(
+      r#"{ "appName": "My App", "frontend": "next", "beforeDevCommand": "" }"#,
+    )
+    .unwrap();
+    assert_eq!(preset.app_name.as_deref(), Some("My App"));
+    assert!(matches!(preset.frontend, Some(Framework::Nextjs)));
+    assert_eq!(preset.before_dev_command.as_deref(), Some(""));
+    assert!(serde_json::from_str::<Preset>(r#"{ "name": "app" }"#).is_err());
+  }
+}
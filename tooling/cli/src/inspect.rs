@@ -0,0 +1,33 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::Result;
+use clap::{Parser, Subcommand};
+
+mod bundle_config;
+
+#[derive(Parser)]
+#[clap(
+  author,
+  version,
+  about = "Print the settings resolved by the CLI, to debug the configuration",
+  subcommand_required(true),
+  arg_required_else_help(true)
+)]
+pub struct Cli {
+  #[clap(subcommand)]
+  command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  BundleConfig(bundle_config::Options),
+}
+
+pub fn command(cli: Cli) -> Result<()> {
+  match cli.command {
+    Commands::BundleConfig(options) => bundle_config::command(options)?,
+  }
+  Ok(())
+}
@@ -0,0 +1,149 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  build::{package_types, read_merge_config},
+  helpers::{app_paths::tauri_dir, config::get as get_config, env_file},
+  interface::{AppInterface, Interface},
+  Result,
+};
+use anyhow::Context;
+use clap::Parser;
+use serde::Serialize;
+use tauri_bundler::{BundleBinary, BundleSettings, PackageSettings};
+
+use std::{env::set_current_dir, path::PathBuf};
+
+#[derive(Debug, Parser)]
+#[clap(
+  about = "Print the bundler settings, cargo features and directories a `tauri build` would use, as JSON"
+)]
+pub struct Options {
+  /// Binary to use to build the application, defaults to `cargo`
+  #[clap(short, long)]
+  runner: Option<String>,
+  /// Resolve the settings of a debug build
+  #[clap(short, long)]
+  debug: bool,
+  /// Resolve the settings of a build with the given cargo profile instead of `release`
+  #[clap(long, value_name = "PROFILE", conflicts_with = "debug")]
+  profile: Option<String>,
+  /// Target triple of the build. Defaults to the host
+  #[clap(short, long)]
+  target: Option<String>,
+  /// Space or comma separated list of features to activate
+  #[clap(short, long, multiple_occurrences(true), multiple_values(true))]
+  features: Option<Vec<String>>,
+  /// Space or comma separated list of bundles to package, as in `tauri build --bundles`
+  #[clap(short, long, multiple_occurrences(true), multiple_values(true))]
+  bundles: Option<Vec<String>>,
+  /// JSON string or path to a JSON, JSON5 or TOML file to merge with tauri.conf.json. Can be used multiple times, the later values take precedence
+  #[clap(short, long, multiple_occurrences(true))]
+  config: Vec<String>,
+  /// Load environment variables from the given file, as in `tauri build --env-file`
+  #[clap(long = "env-file", multiple_occurrences(true))]
+  env_files: Vec<PathBuf>,
+}
+
+impl From<Options> for crate::build::Options {
+  fn from(options: Options) -> Self {
+    Self {
+      runner: options.runner,
+      debug: options.debug,
+      profile: options.profile,
+      target: options.target.into_iter().collect(),
+      features: options.features,
+      bin: None,
+      example: None,
+      bundles: options.bundles,
+      no_bundle: false,
+      config: options.config,
+      skip_checks: Vec::new(),
+      env_files: options.env_files,
+      ci: false,
+      use_sccache: false,
+      reproducible: false,
+      feature_matrix: false,
+      matrix_jobs: None,
+      checksum: None,
+      sbom: None,
+      delta_from: None,
+      timings: None,
+      dry_run: false,
+      frozen_manifest: false,
+      args: Vec::new(),
+    }
+  }
+}
+
+/// The resolved settings of the build.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleConfig<'a> {
+  target: &'a str,
+  profile: String,
+  /// The features passed to cargo, `default` excluded.
+  features: Vec<String>,
+  target_dir: PathBuf,
+  out_dir: PathBuf,
+  package_types: Vec<&'static str>,
+  binaries: &'a [BundleBinary],
+  package: &'a PackageSettings,
+  bundle: &'a BundleSettings,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let mut options: crate::build::Options = options.into();
+  env_file::load(&options.env_files)?;
+  options.config = read_merge_config(&options.config)?.0.into_iter().collect();
+
+  set_current_dir(tauri_dir()).with_context(|| "failed to change current working directory")?;
+  let config = get_config(!options.debug, options.config.first().map(String::as_str))?;
+  let config_guard = config.lock().unwrap();
+  let config_ = config_guard.as_ref().unwrap();
+
+  let interface = AppInterface::new(config_)?;
+  if options.runner.is_none() {
+    options.runner = config_.build.runner.clone();
+  }
+  let package_types = package_types(&options, config_)?;
+
+  // the build enables the configured features and `custom-protocol` in addition to the given ones
+  let mut features = config_.build.features.clone().unwrap_or_default();
+  features.extend(options.features.take().unwrap_or_default());
+  features.push("custom-protocol".into());
+  let mut unique_features = Vec::new();
+  for feature in features {
+    if !unique_features.contains(&feature) {
+      unique_features.push(feature);
+    }
+  }
+  options.features = Some(unique_features.clone());
+
+  let interface_options: crate::interface::Options = options.into();
+  let app_settings = interface.app_settings();
+  let target_dir = app_settings.target_dir(&interface_options)?;
+  let out_dir = app_settings.out_dir(&interface_options)?;
+  let settings = app_settings
+    .get_bundler_settings(&interface_options, config_, &out_dir, package_types)
+    .with_context(|| "failed to build bundler settings")?;
+
+  let bundle_config = BundleConfig {
+    target: settings.target(),
+    profile: interface_options.cargo_profile().into(),
+    features: unique_features,
+    target_dir,
+    out_dir: settings.project_out_directory().to_path_buf(),
+    package_types: settings
+      .package_types()?
+      .iter()
+      .map(|t| t.short_name())
+      .collect(),
+    binaries: settings.binaries(),
+    package: settings.package_settings(),
+    bundle: settings.bundle_settings(),
+  };
+  println!("{}", serde_json::to_string_pretty(&bundle_config)?);
+  Ok(())
+}
@@ -0,0 +1,504 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::HashMap,
+  path::{Component, Path, PathBuf},
+  process::{Command, ExitStatus},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc::{channel, sync_channel},
+    Arc,
+  },
+  time::Duration,
+};
+
+use anyhow::Context;
+use log::{info, warn};
+use notify::RecursiveMode;
+use notify_debouncer_mini::new_debouncer;
+use shared_child::SharedChild;
+use tauri_bundler::{BundleBinary, BundleSettings, PackageSettings};
+
+use super::{
+  manifest::Manifest,
+  rust::{tauri_config_to_bundle_settings, write_resolved_config, DEFAULT_WATCHER_DEBOUNCE_MS},
+  AppSettings, DevProcess, ExitReason, Interface, MobileOptions, Options,
+};
+use crate::{
+  dev::events::{self, Event},
+  helpers::{
+    app_paths::tauri_dir,
+    command_env,
+    config::{Config, ExternalInterfaceConfig},
+  },
+  CommandExt,
+};
+
+/// Builds the app with the external build system of `build > externalInterface`, e.g. Bazel or Nix.
+pub struct External {
+  app_settings: ExternalAppSettings,
+  config_features: Vec<String>,
+}
+
+pub struct ExternalAppSettings {
+  config: ExternalInterfaceConfig,
+  package_settings: PackageSettings,
+  /// Always empty, the Cargo manifest is managed by the external build system.
+  manifest: Manifest,
+}
+
+struct ExternalDevChild {
+  manually_killed_app: Arc<AtomicBool>,
+  app_child: Arc<SharedChild>,
+}
+
+impl DevProcess for ExternalDevChild {
+  fn kill(&self) -> std::io::Result<()> {
+    self.manually_killed_app.store(true, Ordering::Relaxed);
+    self.app_child.kill()
+  }
+
+  fn try_wait(&self) -> std::io::Result<Option<ExitStatus>> {
+    self.app_child.try_wait()
+  }
+
+  fn wait(&self) -> std::io::Result<ExitStatus> {
+    self.app_child.wait()
+  }
+
+  fn manually_killed_process(&self) -> bool {
+    self.manually_killed_app.load(Ordering::Relaxed)
+  }
+}
+
+impl Interface for External {
+  type AppSettings = ExternalAppSettings;
+
+  fn new(config: &Config) -> crate::Result<Self> {
+    let external_config = config
+      .build
+      .external_interface
+      .clone()
+      .expect("the external interface requires `build > externalInterface`");
+
+    if let Some(minimum_system_version) = &config.tauri.bundle.macos.minimum_system_version {
+      std::env::set_var("MACOSX_DEPLOYMENT_TARGET", minimum_system_version);
+    }
+
+    let version = match &config.package.version {
+      Some(version) => version.clone(),
+      None => anyhow::bail!(
+        "`package > version` must be set in the configuration when the app is built with `build > externalInterface`"
+      ),
+    };
+    let product_name = match &config.package.product_name {
+      Some(product_name) => product_name.clone(),
+      None => Path::new(&external_config.binary_path)
+        .file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default(),
+    };
+
+    Ok(Self {
+      app_settings: ExternalAppSettings {
+        config: external_config,
+        package_settings: PackageSettings {
+          product_name,
+          version,
+          description: String::new(),
+          homepage: None,
+          authors: None,
+          default_run: None,
+        },
+        manifest: Manifest::default(),
+      },
+      config_features: config.build.features.clone().unwrap_or_default(),
+    })
+  }
+
+  fn app_settings(&self) -> &Self::AppSettings {
+    &self.app_settings
+  }
+
+  fn build(&mut self, options: Options) -> crate::Result<PathBuf> {
+    let build_command = self.app_settings.config.build_command.clone();
+    let status = self.run_command(&build_command, &options)?;
+    if !status.success() {
+      anyhow::bail!(
+        "`{}` failed with exit code {}",
+        build_command,
+        status.code().unwrap_or_default()
+      );
+    }
+    self.app_settings.built_binary(&options)
+  }
+
+  fn dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(
+    &mut self,
+    mut options: Options,
+    on_exit: F,
+  ) -> crate::Result<()> {
+    let on_exit = Arc::new(on_exit);
+    // the arguments after `--` are passed to the app
+    let run_args = match options.args.iter().position(|arg| arg == "--") {
+      Some(index) => {
+        let run_args = options.args.split_off(index + 1);
+        options.args.pop();
+        run_args
+      }
+      None => Vec::new(),
+    };
+
+    if options.no_watch {
+      let (tx, rx) = sync_channel(1);
+      let on_exit_ = on_exit.clone();
+      self.run_dev(&options, run_args, move |status, reason| {
+        let _ = tx.send(());
+        on_exit_(status, reason)
+      })?;
+      rx.recv().unwrap();
+      return Ok(());
+    }
+
+    let tauri_path = tauri_dir();
+    let patterns = self
+      .app_settings
+      .config
+      .watch
+      .iter()
+      .map(|pattern| {
+        glob::Pattern::new(&tauri_path.join(pattern).to_string_lossy()).with_context(|| {
+          format!(
+            "invalid `build > externalInterface > watch` glob `{}`",
+            pattern
+          )
+        })
+      })
+      .collect::<crate::Result<Vec<_>>>()?;
+    if patterns.is_empty() {
+      warn!("`build > externalInterface > watch` is empty, the app won't be rebuilt on changes.");
+    }
+
+    let (tx, rx) = channel();
+    let delay = Duration::from_millis(
+      options
+        .watch_debounce
+        .unwrap_or(DEFAULT_WATCHER_DEBOUNCE_MS),
+    );
+    let mut debouncer = new_debouncer(delay, None, tx)?;
+    for pattern in &self.app_settings.config.watch {
+      let (base, recursive_mode) = glob_base(pattern);
+      let path = tauri_path.join(base);
+      if let Err(e) = debouncer.watcher().watch(&path, recursive_mode) {
+        warn!("Cannot watch {}: {}", path.display(), e);
+      } else {
+        info!("Watching {} for changes...", path.display());
+      }
+    }
+
+    let mut process = {
+      let on_exit = on_exit.clone();
+      self.run_dev(&options, run_args.clone(), move |status, reason| {
+        on_exit(status, reason)
+      })?
+    };
+    loop {
+      match rx.recv() {
+        Ok(Ok(events)) => {
+          let changed = events
+            .iter()
+            .find(|event| patterns.iter().any(|p| p.matches_path(&event.path)));
+          if let Some(event) = changed {
+            info!(
+              "File {} changed. Rebuilding application...",
+              event
+                .path
+                .strip_prefix(&tauri_path)
+                .unwrap_or(&event.path)
+                .display()
+            );
+            if let Some(process) = &process {
+              process.kill()?;
+              process.wait()?;
+            }
+            let on_exit = on_exit.clone();
+            process = self.run_dev(&options, run_args.clone(), move |status, reason| {
+              on_exit(status, reason)
+            })?;
+          }
+        }
+        Ok(Err(errors)) => {
+          for error in errors {
+            warn!("File watcher error: {}", error);
+          }
+        }
+        Err(_) => return Ok(()),
+      }
+    }
+  }
+
+  fn mobile_dev<R: Fn(MobileOptions) -> crate::Result<Box<dyn DevProcess>>>(
+    &mut self,
+    _options: MobileOptions,
+    _runner: R,
+  ) -> crate::Result<()> {
+    anyhow::bail!("mobile development is not supported with `build > externalInterface`")
+  }
+}
+
+impl External {
+  /// Runs a command of the external build system from the `src-tauri` folder.
+  ///
+  /// The arguments given to the CLI are appended to the command.
+  fn run_command(&self, command: &str, options: &Options) -> crate::Result<ExitStatus> {
+    write_resolved_config(options, &self.app_settings.target_dir(options)?)?;
+
+    let mut features = self.config_features.clone();
+    features.extend(options.features.clone().unwrap_or_default());
+    let mut script = command.to_string();
+    for arg in &options.args {
+      script.push(' ');
+      script.push_str(arg);
+    }
+
+    info!(action = "Running"; "`{}`", script);
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+      let mut cmd = Command::new("cmd");
+      cmd.arg("/S").arg("/C");
+      cmd
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut cmd = {
+      let mut cmd = Command::new("sh");
+      cmd.arg("-c");
+      cmd
+    };
+    cmd
+      .arg(&script)
+      .current_dir(tauri_dir())
+      .envs(command_env(options.debug))
+      .envs(self.app_settings.command_env(options)?)
+      .env("TAURI_FEATURES", features.join(","))
+      .piped()
+      .with_context(|| format!("failed to run `{}`", script))
+  }
+
+  /// Builds the app with the dev command and starts it, returning `None` when the build failed.
+  fn run_dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(
+    &mut self,
+    options: &Options,
+    run_args: Vec<String>,
+    on_exit: F,
+  ) -> crate::Result<Option<Box<dyn DevProcess>>> {
+    let dev_command = self
+      .app_settings
+      .config
+      .dev_command
+      .clone()
+      .unwrap_or_else(|| self.app_settings.config.build_command.clone());
+    events::emit(Event::BuildStarted);
+    let status = self.run_command(&dev_command, options)?;
+    if !status.success() {
+      on_exit(status, ExitReason::CompilationFailed);
+      return Ok(None);
+    }
+
+    let bin_path = self.app_settings.built_binary(options)?;
+    let mut app = Command::new(&bin_path);
+    app.args(run_args);
+    if options.open_devtools {
+      app.env("TAURI_OPEN_DEVTOOLS", "true");
+    }
+    let app_child = Arc::new(
+      SharedChild::spawn(&mut app)
+        .with_context(|| format!("failed to run {}", bin_path.display()))?,
+    );
+    events::emit(Event::AppSpawned {
+      pid: app_child.id(),
+    });
+
+    let manually_killed_app = Arc::new(AtomicBool::default());
+    crate::dev::wait_dev_process(
+      ExternalDevChild {
+        manually_killed_app: manually_killed_app.clone(),
+        app_child: app_child.clone(),
+      },
+      on_exit,
+    );
+    Ok(Some(Box::new(ExternalDevChild {
+      manually_killed_app,
+      app_child,
+    })))
+  }
+}
+
+impl ExternalAppSettings {
+  /// The target triple of the build, the host when no target is given.
+  fn target_triple(&self, options: &Options) -> crate::Result<String> {
+    match &options.target {
+      Some(target) => Ok(target.clone()),
+      None => Ok(tauri_utils::platform::target_triple()?),
+    }
+  }
+
+  /// The environment variables describing the build to the external build system.
+  fn command_env(&self, options: &Options) -> crate::Result<HashMap<&'static str, String>> {
+    let mut env = HashMap::new();
+    env.insert("TAURI_PROFILE", options.cargo_profile().to_string());
+    env.insert("TAURI_TARGET_TRIPLE", self.target_triple(options)?);
+    Ok(env)
+  }
+
+  /// The app binary of a finished build, failing when the build system didn't produce it.
+  fn built_binary(&self, options: &Options) -> crate::Result<PathBuf> {
+    let bin_path = self.app_binary_path(options)?;
+    if !bin_path.is_file() {
+      anyhow::bail!(
+        "the app binary {} was not found, check `build > externalInterface > binaryPath`",
+        bin_path.display()
+      );
+    }
+    Ok(bin_path)
+  }
+}
+
+impl AppSettings for ExternalAppSettings {
+  fn get_package_settings(&self) -> PackageSettings {
+    self.package_settings.clone()
+  }
+
+  fn get_bundle_settings(
+    &self,
+    config: &Config,
+    features: &[String],
+    target: &str,
+  ) -> crate::Result<BundleSettings> {
+    tauri_config_to_bundle_settings(
+      &self.manifest,
+      features,
+      target,
+      config.tauri.bundle.clone(),
+      config.tauri.system_tray.clone(),
+      config.tauri.updater.clone(),
+    )
+  }
+
+  fn app_binary_path(&self, options: &Options) -> crate::Result<PathBuf> {
+    let binary_path = resolve_binary_path(
+      &self.config.binary_path,
+      options.cargo_profile(),
+      &self.target_triple(options)?,
+    );
+    Ok(tauri_dir().join(binary_path))
+  }
+
+  fn out_dir(&self, options: &Options) -> crate::Result<PathBuf> {
+    let bin_path = self.app_binary_path(options)?;
+    Ok(bin_path.parent().unwrap_or(&bin_path).to_path_buf())
+  }
+
+  fn target_dir(&self, options: &Options) -> crate::Result<PathBuf> {
+    self.out_dir(options)
+  }
+
+  fn manifest(&self) -> &Manifest {
+    &self.manifest
+  }
+
+  fn get_binaries(
+    &self,
+    _config: &Config,
+    _target: &str,
+    options: &Options,
+  ) -> crate::Result<Vec<BundleBinary>> {
+    let bin_path = self.app_binary_path(options)?;
+    let name = bin_path
+      .file_name()
+      .map(|name| name.to_string_lossy().into_owned())
+      .unwrap_or_default();
+    Ok(vec![BundleBinary::new(name, true)])
+  }
+}
+
+/// Replaces the `{profile}` and `{target}` placeholders of `build > externalInterface > binaryPath`.
+fn resolve_binary_path(binary_path: &str, profile: &str, target: &str) -> String {
+  binary_path
+    .replace("{profile}", profile)
+    .replace("{target}", target)
+}
+
+/// The folder watched for a glob pattern: its leading components without wildcards.
+///
+/// The folder is only watched recursively when the rest of the pattern spans several components.
+fn glob_base(pattern: &str) -> (PathBuf, RecursiveMode) {
+  let mut base = PathBuf::new();
+  let mut components = Path::new(pattern).components().peekable();
+  while let Some(component) = components.peek() {
+    if let Component::Normal(name) = component {
+      if name
+        .to_string_lossy()
+        .contains(|c| c == '*' || c == '?' || c == '[')
+      {
+        break;
+      }
+    }
+    base.push(component);
+    components.next();
+  }
+  let rest = components.collect::<Vec<_>>();
+  match rest.as_slice() {
+    // a pattern without wildcards matches a single file
+    [] => (
+      base.parent().map(Path::to_path_buf).unwrap_or_default(),
+      RecursiveMode::NonRecursive,
+    ),
+    [name] if name.as_os_str() != "**" => (base, RecursiveMode::NonRecursive),
+    _ => (base, RecursiveMode::Recursive),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{glob_base, resolve_binary_path};
+  use notify::RecursiveMode;
+  use std::path::PathBuf;
+
+  #[test]
+  fn resolves_binary_path() {
+    assert_eq!(
+      resolve_binary_path(
+        "bazel-out/{target}/{profile}/app",
+        "release",
+        "x86_64-unknown-linux-gnu"
+      ),
+      "bazel-out/x86_64-unknown-linux-gnu/release/app"
+    );
+    assert_eq!(
+      resolve_binary_path("result/bin/app", "debug", "x"),
+      "result/bin/app"
+    );
+  }
+
+  #[test]
+  fn watches_glob_base() {
+    assert_eq!(
+      glob_base("src/**/*.rs"),
+      (PathBuf::from("src"), RecursiveMode::Recursive)
+    );
+    assert_eq!(
+      glob_base("crates/*/BUILD.bazel"),
+      (PathBuf::from("crates"), RecursiveMode::Recursive)
+    );
+    assert_eq!(
+      glob_base("nix/*.nix"),
+      (PathBuf::from("nix"), RecursiveMode::NonRecursive)
+    );
+    assert_eq!(
+      glob_base("BUILD.bazel"),
+      (PathBuf::new(), RecursiveMode::NonRecursive)
+    );
+  }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+pub mod external;
 pub mod rust;
 
 use std::{
@@ -10,15 +11,25 @@ use std::{
 };
 
 use crate::helpers::config::Config;
+use serde::Serialize;
 use tauri_bundler::bundle::{PackageType, Settings, SettingsBuilder};
 
-pub use rust::{manifest, MobileOptions, Options, Rust as AppInterface};
+pub use external::External;
+pub use rust::{manifest, MobileOptions, Options, Rust};
 
 pub trait DevProcess {
   fn kill(&self) -> std::io::Result<()>;
+  /// Asks the process to exit, giving it a chance to clean up. Defaults to [`DevProcess::kill`].
+  fn terminate(&self) -> std::io::Result<()> {
+    self.kill()
+  }
   fn try_wait(&self) -> std::io::Result<Option<ExitStatus>>;
   fn wait(&self) -> std::io::Result<ExitStatus>;
   fn manually_killed_process(&self) -> bool;
+  /// The last lines the process wrote to stderr, if they were captured.
+  fn captured_stderr(&self) -> Vec<String> {
+    Vec::new()
+  }
 }
 
 pub trait AppSettings {
@@ -27,12 +38,21 @@ pub trait AppSettings {
     &self,
     config: &Config,
     features: &[String],
+    target: &str,
   ) -> crate::Result<tauri_bundler::BundleSettings>;
   fn app_binary_path(&self, options: &Options) -> crate::Result<PathBuf>;
+  /// The directory the app binary is written to with the given options.
+  fn out_dir(&self, options: &Options) -> crate::Result<PathBuf>;
+  /// The root of the build output directory with the given options.
+  fn target_dir(&self, options: &Options) -> crate::Result<PathBuf>;
+  /// The Cargo manifest of the app, empty when it isn't built with cargo.
+  fn manifest(&self) -> &manifest::Manifest;
+  /// The binaries of the app, with the `[[bin]]` target or the example selected in the options as the main binary.
   fn get_binaries(
     &self,
     config: &Config,
     target: &str,
+    options: &Options,
   ) -> crate::Result<Vec<tauri_bundler::BundleBinary>>;
 
   fn get_bundler_settings(
@@ -48,16 +68,16 @@ pub trait AppSettings {
       enabled_features.push("default".into());
     }
 
-    let target: String = if let Some(target) = options.target.clone() {
-      target
+    let target: String = if let Some(target) = &options.target {
+      rust::target_dir_name(target).into()
     } else {
       tauri_utils::platform::target_triple()?
     };
 
     let mut settings_builder = SettingsBuilder::new()
       .package_settings(self.get_package_settings())
-      .bundle_settings(self.get_bundle_settings(config, &enabled_features)?)
-      .binaries(self.get_binaries(config, &target)?)
+      .bundle_settings(self.get_bundle_settings(config, &enabled_features, &target)?)
+      .binaries(self.get_binaries(config, &target, options)?)
       .project_out_directory(out_dir)
       .target(target);
 
@@ -69,7 +89,8 @@ pub trait AppSettings {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum ExitReason {
   /// Killed manually.
   TriggeredKill,
@@ -80,11 +101,12 @@ pub enum ExitReason {
 }
 
 pub trait Interface: Sized {
-  type AppSettings: AppSettings;
+  type AppSettings: AppSettings + ?Sized;
 
   fn new(config: &Config) -> crate::Result<Self>;
   fn app_settings(&self) -> &Self::AppSettings;
-  fn build(&mut self, options: Options) -> crate::Result<()>;
+  /// Builds the app, returning the path of the app binary.
+  fn build(&mut self, options: Options) -> crate::Result<PathBuf>;
   fn dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(
     &mut self,
     options: Options,
@@ -96,3 +118,57 @@ pub trait Interface: Sized {
     runner: R,
   ) -> crate::Result<()>;
 }
+
+/// The interface of the app: cargo, or the external build system of `build > externalInterface`.
+pub enum AppInterface {
+  Rust(Box<Rust>),
+  External(External),
+}
+
+impl Interface for AppInterface {
+  type AppSettings = dyn AppSettings;
+
+  fn new(config: &Config) -> crate::Result<Self> {
+    if config.build.external_interface.is_some() {
+      External::new(config).map(Self::External)
+    } else {
+      Rust::new(config).map(|rust| Self::Rust(Box::new(rust)))
+    }
+  }
+
+  fn app_settings(&self) -> &Self::AppSettings {
+    match self {
+      Self::Rust(rust) => rust.app_settings(),
+      Self::External(external) => external.app_settings(),
+    }
+  }
+
+  fn build(&mut self, options: Options) -> crate::Result<PathBuf> {
+    match self {
+      Self::Rust(rust) => rust.build(options),
+      Self::External(external) => external.build(options),
+    }
+  }
+
+  fn dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(
+    &mut self,
+    options: Options,
+    on_exit: F,
+  ) -> crate::Result<()> {
+    match self {
+      Self::Rust(rust) => rust.dev(options, on_exit),
+      Self::External(external) => external.dev(options, on_exit),
+    }
+  }
+
+  fn mobile_dev<R: Fn(MobileOptions) -> crate::Result<Box<dyn DevProcess>>>(
+    &mut self,
+    options: MobileOptions,
+    runner: R,
+  ) -> crate::Result<()> {
+    match self {
+      Self::Rust(rust) => rust.mobile_dev(options, runner),
+      Self::External(external) => external.mobile_dev(options, runner),
+    }
+  }
+}
@@ -3,38 +3,51 @@
 // SPDX-License-Identifier: MIT
 
 use std::{
-  collections::HashMap,
-  ffi::OsStr,
+  collections::{HashMap, HashSet, VecDeque},
   fs::{File, FileType},
-  io::{Read, Write},
+  io::{BufRead, Read, Write},
   path::{Path, PathBuf},
   process::{Command, ExitStatus},
   str::FromStr,
   sync::{
-    mpsc::{channel, sync_channel},
+    mpsc::{channel, sync_channel, Receiver, Sender},
     Arc, Mutex,
   },
-  time::{Duration, Instant},
+  time::{Duration, Instant, SystemTime},
 };
 
 use anyhow::Context;
 #[cfg(target_os = "linux")]
-use heck::ToKebabCase;
-use log::{debug, info};
-use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher};
+use ignore::{gitignore::GitignoreBuilder, Match};
+use log::{debug, error, info, warn};
+use notify::{PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use notify_debouncer_mini::{new_debouncer, new_debouncer_opt, DebounceEventResult, Debouncer};
 use serde::Deserialize;
 use tauri_bundler::{
-  AppCategory, BundleBinary, BundleSettings, DebianSettings, MacOsSettings, PackageSettings,
-  UpdaterSettings, WindowsSettings,
+  AppCategory, AppImageSettings, BundleBinary, BundleSettings, DebianSettings, DesktopAction,
+  DesktopEntrySettings, DmgSettings, FlatpakSettings, MacOsSettings, MsixSettings,
+  NotarizationSettings, PackageSettings, RpmSettings, SignCommand, UpdaterSettings,
+  WindowsSettings,
 };
+use tauri_utils::{config::parse::RESOLVED_CONFIG_ENV, resources::resource_map_files};
 
 use super::{AppSettings, DevProcess, ExitReason, Interface};
+use crate::dev::{
+  events::{self, Event},
+  CrashLoop,
+};
+#[cfg(target_os = "linux")]
+use crate::helpers::config::{DebConfig, DebTrayDependency};
 use crate::helpers::{
   app_paths::tauri_dir,
-  config::{reload as reload_config, wix_settings, Config},
+  config::{
+    diff as diff_config, get as get_config, is_configuration_file, nsis_settings,
+    reload as reload_config, wix_settings, AppUrl, BundleResources, Config, HookCommand, WindowUrl,
+  },
 };
 
 mod cargo_config;
+mod cargo_messages;
 mod desktop;
 pub mod manifest;
 use cargo_config::Config as CargoConfig;
@@ -44,11 +57,33 @@ use manifest::{rewrite_manifest, Manifest};
 pub struct Options {
   pub runner: Option<String>,
   pub debug: bool,
+  /// The cargo profile to build with, taking precedence over `debug`.
+  pub profile: Option<String>,
   pub target: Option<String>,
   pub features: Option<Vec<String>>,
+  /// The `[[bin]]` target used as the main app binary instead of the default one.
+  pub bin: Option<String>,
+  /// The cargo example built as the app instead of the package binaries.
+  pub example: Option<String>,
   pub args: Vec<String>,
   pub config: Option<String>,
   pub no_watch: bool,
+  pub watch_debounce: Option<u64>,
+  pub watch_poll: bool,
+  pub restart_grace_period: Option<u64>,
+  pub open_devtools: bool,
+  pub crash_restart_limit: Option<u32>,
+  pub after_build_command: Option<HookCommand>,
+  pub remote_debugging_port: Option<u16>,
+  pub remote_debugging_address: Option<String>,
+  /// Whether missing rustup targets are installed without prompting.
+  pub ci: bool,
+  /// Whether rustc is wrapped with sccache.
+  pub use_sccache: bool,
+  /// Whether the local paths are remapped so the binary does not depend on the build machine.
+  pub reproducible: bool,
+  /// Whether the dev watcher fails instead of rewriting Cargo.toml when the configuration changes.
+  pub frozen_manifest: bool,
 }
 
 impl From<crate::build::Options> for Options {
@@ -56,11 +91,38 @@ impl From<crate::build::Options> for Options {
     Self {
       runner: options.runner,
       debug: options.debug,
-      target: options.target,
+      profile: options.profile,
+      // `tauri build` converts the options once per target
+      target: options.target.into_iter().next(),
       features: options.features,
+      bin: options.bin,
+      example: options.example,
       args: options.args,
-      config: options.config,
+      config: options.config.into_iter().next(),
       no_watch: true,
+      watch_debounce: None,
+      watch_poll: false,
+      restart_grace_period: None,
+      open_devtools: false,
+      crash_restart_limit: None,
+      after_build_command: None,
+      remote_debugging_port: None,
+      remote_debugging_address: None,
+      ci: options.ci,
+      use_sccache: options.use_sccache,
+      reproducible: options.reproducible,
+      frozen_manifest: options.frozen_manifest,
+    }
+  }
+}
+
+impl Options {
+  /// The name of the cargo profile used to build the app.
+  pub fn cargo_profile(&self) -> &str {
+    match &self.profile {
+      Some(profile) => profile,
+      None if self.debug => "dev",
+      None => "release",
     }
   }
 }
@@ -70,11 +132,26 @@ impl From<crate::dev::Options> for Options {
     Self {
       runner: options.runner,
       debug: !options.release_mode,
+      profile: options.cargo_profile,
       target: options.target,
       features: options.features,
+      bin: options.bin,
+      example: options.example,
       args: options.args,
-      config: options.config,
+      config: options.config.into_iter().next(),
       no_watch: options.no_watch,
+      watch_debounce: options.watch_debounce,
+      watch_poll: options.watch_poll,
+      restart_grace_period: options.restart_grace_period,
+      open_devtools: options.open_devtools,
+      crash_restart_limit: options.crash_restart_limit,
+      after_build_command: options.after_build_command.map(HookCommand::Script),
+      remote_debugging_port: options.remote_debugging_port,
+      remote_debugging_address: options.remote_debugging_address,
+      ci: false,
+      use_sccache: options.use_sccache,
+      reproducible: false,
+      frozen_manifest: options.frozen_manifest,
     }
   }
 }
@@ -86,6 +163,10 @@ pub struct MobileOptions {
   pub args: Vec<String>,
   pub config: Option<String>,
   pub no_watch: bool,
+  pub watch_debounce: Option<u64>,
+  pub watch_poll: bool,
+  pub restart_grace_period: Option<u64>,
+  pub crash_restart_limit: Option<u32>,
 }
 
 #[derive(Debug)]
@@ -105,23 +186,7 @@ impl Interface for Rust {
   type AppSettings = RustAppSettings;
 
   fn new(config: &Config) -> crate::Result<Self> {
-    let manifest = {
-      let (tx, rx) = channel();
-      let mut watcher = watcher(tx, Duration::from_secs(1)).unwrap();
-      watcher.watch(tauri_dir().join("Cargo.toml"), RecursiveMode::Recursive)?;
-      let manifest = rewrite_manifest(config)?;
-      let now = Instant::now();
-      let timeout = Duration::from_secs(2);
-      loop {
-        if now.elapsed() >= timeout {
-          break;
-        }
-        if let Ok(DebouncedEvent::NoticeWrite(_)) = rx.try_recv() {
-          break;
-        }
-      }
-      manifest
-    };
+    let (manifest, _) = rewrite_manifest(config, false)?;
 
     if let Some(minimum_system_version) = &config.tauri.bundle.macos.minimum_system_version {
       std::env::set_var("MACOSX_DEPLOYMENT_TARGET", minimum_system_version);
@@ -141,19 +206,19 @@ impl Interface for Rust {
     &self.app_settings
   }
 
-  fn build(&mut self, mut options: Options) -> crate::Result<()> {
+  fn build(&mut self, mut options: Options) -> crate::Result<PathBuf> {
     options
       .features
       .get_or_insert(Vec::new())
       .push("custom-protocol".into());
+    self.write_resolved_config(&options)?;
     desktop::build(
       options,
       &self.app_settings,
       self.product_name.clone(),
       &mut self.available_targets,
       self.config_features.clone(),
-    )?;
-    Ok(())
+    )
   }
 
   fn dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(
@@ -166,7 +231,7 @@ impl Interface for Rust {
     let run_args = dev_options(
       &mut options.args,
       &mut options.features,
-      self.app_settings.manifest.features(),
+      &self.app_settings.manifest,
     );
 
     if options.no_watch {
@@ -180,13 +245,26 @@ impl Interface for Rust {
       Ok(())
     } else {
       let config = options.config.clone();
+      let watcher_options = WatcherOptions {
+        debounce: options.watch_debounce,
+        poll: options.watch_poll,
+        restart_grace_period: options.restart_grace_period,
+        crash_restart_limit: options.crash_restart_limit,
+        frozen_manifest: options.frozen_manifest,
+      };
+      let (tx, rx) = channel();
+      let exit_tx = tx.clone();
       let run = Arc::new(|rust: &mut Rust| {
         let on_exit = on_exit.clone();
+        let exit_tx = exit_tx.clone();
         rust.run_dev(options.clone(), run_args.clone(), move |status, reason| {
+          if matches!(reason, ExitReason::NormalExit) && !status.success() {
+            let _ = exit_tx.send(WatcherMessage::AppCrashed(status));
+          }
           on_exit(status, reason)
         })
       });
-      self.run_dev_watcher(config, run)
+      self.run_dev_watcher(!options.debug, config, watcher_options, (tx, rx), run)
     }
   }
 
@@ -198,21 +276,295 @@ impl Interface for Rust {
     dev_options(
       &mut options.args,
       &mut options.features,
-      self.app_settings.manifest.features(),
+      &self.app_settings.manifest,
     );
+    extend_cargo_features(&mut options.features, self.app_settings.manifest());
 
     if options.no_watch {
       runner(options)?;
       Ok(())
     } else {
       let config = options.config.clone();
+      let watcher_options = WatcherOptions {
+        debounce: options.watch_debounce,
+        poll: options.watch_poll,
+        restart_grace_period: options.restart_grace_period,
+        crash_restart_limit: options.crash_restart_limit,
+        frozen_manifest: false,
+      };
       let run = Arc::new(|_rust: &mut Rust| runner(options.clone()));
-      self.run_dev_watcher(config, run)
+      self.run_dev_watcher(!options.debug, config, watcher_options, channel(), run)
     }
   }
 }
 
-fn lookup<F: FnMut(FileType, PathBuf)>(dir: &Path, mut f: F) {
+/// Default delay used to debounce file system events in `tauri dev`.
+pub(super) const DEFAULT_WATCHER_DEBOUNCE_MS: u64 = 1000;
+/// Default time the app has to exit gracefully when `tauri dev` restarts it.
+const DEFAULT_RESTART_GRACE_PERIOD_MS: u64 = 3000;
+/// Time the watcher keeps collecting file changes after the first one before restarting the app.
+const WATCHER_BATCH_WINDOW: Duration = Duration::from_millis(500);
+
+/// File watcher configuration requested on the command line.
+#[derive(Debug, Clone, Copy)]
+struct WatcherOptions {
+  /// Debounce delay in milliseconds, takes precedence over `build > devWatcherDebounce`.
+  debounce: Option<u64>,
+  /// Use the polling backend instead of the native OS notifications.
+  poll: bool,
+  /// Time in milliseconds the app has to exit after being asked to before it is killed on restart.
+  restart_grace_period: Option<u64>,
+  /// Number of consecutive crashes after which the app is no longer restarted until a file changes.
+  crash_restart_limit: Option<u32>,
+  /// Fail instead of rewriting Cargo.toml when the reloaded configuration requires other features.
+  frozen_manifest: bool,
+}
+
+/// The file watcher backend used by `tauri dev`.
+enum FileWatcher {
+  Native(Debouncer<RecommendedWatcher>),
+  /// Scans the watched paths periodically, for file systems where native events aren't delivered (Docker, WSL, NFS).
+  Poll(Debouncer<PollWatcher>),
+}
+
+impl FileWatcher {
+  fn new(tx: Sender<WatcherMessage>, delay: Duration, poll: bool) -> notify::Result<Self> {
+    let tx = move |result: DebounceEventResult| {
+      let _ = tx.send(WatcherMessage::Events(result));
+    };
+    if poll {
+      // poll at the same rate the events are debounced
+      new_debouncer_opt::<_, PollWatcher>(
+        delay,
+        None,
+        tx,
+        notify::Config::default().with_poll_interval(delay),
+      )
+      .map(Self::Poll)
+    } else {
+      new_debouncer(delay, None, tx).map(Self::Native)
+    }
+  }
+
+  fn watcher(&mut self) -> &mut dyn Watcher {
+    match self {
+      Self::Native(d) => d.watcher(),
+      Self::Poll(d) => d.watcher(),
+    }
+  }
+}
+
+/// A message handled by the `tauri dev` watcher loop.
+enum WatcherMessage {
+  Events(DebounceEventResult),
+  Command(DevCommand),
+  /// The app exited with a failure status.
+  AppCrashed(ExitStatus),
+}
+
+/// A command typed on stdin while `tauri dev` is running.
+#[derive(Debug, Clone, Copy)]
+enum DevCommand {
+  Restart,
+  ClearScreen,
+  Quit,
+}
+
+/// Reads the dev commands from stdin, one per line.
+fn spawn_stdin_reader(tx: Sender<WatcherMessage>) {
+  std::thread::spawn(move || {
+    let stdin = std::io::stdin();
+    for line in stdin.lock().lines() {
+      let command = match line.as_deref().map(str::trim) {
+        Ok("r") => DevCommand::Restart,
+        Ok("c") => DevCommand::ClearScreen,
+        Ok("q") => DevCommand::Quit,
+        Ok("") => continue,
+        Ok(command) => {
+          warn!(
+            "Unknown command `{}`. Use `r` to restart the app, `c` to clear the screen or `q` to quit.",
+            command
+          );
+          continue;
+        }
+        Err(_) => break,
+      };
+      if tx.send(WatcherMessage::Command(command)).is_err() {
+        break;
+      }
+    }
+  });
+}
+
+/// The folders watched by `tauri dev` and the entries registered for each one of them.
+///
+/// Each folder is watched non recursively so removed and recreated entries can be registered again,
+/// since the native backends silently stop watching a path once it is deleted.
+struct WatchedPaths {
+  folders: Vec<PathBuf>,
+  entries: HashSet<PathBuf>,
+}
+
+impl WatchedPaths {
+  fn new(watcher: &mut dyn Watcher, paths: Vec<PathBuf>) -> Self {
+    let mut watched = Self {
+      folders: Vec::new(),
+      entries: HashSet::new(),
+    };
+
+    for path in paths {
+      if !path.exists() {
+        warn!("Cannot watch {}: path does not exist", path.display());
+        continue;
+      }
+      info!("Watching {} for changes...", path.display());
+      if path.is_file() {
+        watch_entry(watcher, &path, false);
+        watched.entries.insert(path);
+        continue;
+      }
+      let _ = watcher.watch(&path, RecursiveMode::NonRecursive);
+      lookup(&path, |file_type, p| {
+        if p != path {
+          watch_entry(watcher, &p, file_type.is_dir());
+          watched.entries.insert(p);
+        }
+      });
+      watched.folders.push(path);
+    }
+
+    watched
+  }
+
+  /// Updates the watched entries after a change to `path`,
+  /// returning whether the change is relevant to the app or comes from an ignored entry.
+  fn on_change(&mut self, watcher: &mut dyn Watcher, path: &Path) -> bool {
+    let folder = match self
+      .folders
+      .iter()
+      .find(|f| path.parent() == Some(f.as_path()))
+    {
+      Some(folder) => folder,
+      // changes inside a watched entry
+      None => {
+        return match self.folders.iter().find(|f| path.starts_with(f)) {
+          Some(folder) => !is_ignored(folder, path, path.is_dir()),
+          None => true,
+        }
+      }
+    };
+
+    let mut is_dir = None;
+    lookup(folder, |file_type, p| {
+      if p == path {
+        is_dir.replace(file_type.is_dir());
+      }
+    });
+
+    if let Some(is_dir) = is_dir {
+      // the entry might have been removed and recreated, so it must be registered again
+      let _ = watcher.unwatch(path);
+      watch_entry(watcher, path, is_dir);
+      self.entries.insert(path.to_path_buf());
+      true
+    } else {
+      // either the entry was removed or it is ignored
+      self.entries.remove(path)
+    }
+  }
+}
+
+fn watch_entry(watcher: &mut dyn Watcher, path: &Path, is_dir: bool) {
+  debug!("Watching {} for changes...", path.display());
+  let _ = watcher.watch(
+    path,
+    if is_dir {
+      RecursiveMode::Recursive
+    } else {
+      RecursiveMode::NonRecursive
+    },
+  );
+}
+
+/// The `distDir` folders whose changes don't require a rebuild in development.
+///
+/// The dev app only loads its frontend from `devPath`: a dev server reloads the webview itself,
+/// and a local `devPath` folder is embedded in the app binary at compile time, so changes there still rebuild the app.
+/// The `distDir` folders that are not part of `devPath` are never used by the dev app.
+fn frontend_dist_dirs(config: &Config, tauri_path: &Path) -> Vec<PathBuf> {
+  // notify reports canonical paths on some platforms
+  let with_canonical = |dirs: Vec<PathBuf>| -> Vec<PathBuf> {
+    dirs
+      .into_iter()
+      .flat_map(|dir| {
+        let canonical = dir.canonicalize().ok();
+        std::iter::once(dir).chain(canonical)
+      })
+      .collect()
+  };
+
+  let dev_dirs = match &config.build.dev_path {
+    AppUrl::Url(WindowUrl::External(_)) => Vec::new(),
+    AppUrl::Url(WindowUrl::App(path)) => with_canonical(vec![tauri_path.join(path)]),
+    // the embedded files could be anywhere
+    _ => return Vec::new(),
+  };
+  let dist_dirs = match &config.build.dist_dir {
+    AppUrl::Url(WindowUrl::App(path)) => vec![tauri_path.join(path)],
+    AppUrl::Files(files) => files.iter().map(|f| tauri_path.join(f)).collect(),
+    _ => Vec::new(),
+  };
+
+  with_canonical(dist_dirs)
+    .into_iter()
+    .filter(|dir| {
+      !dev_dirs
+        .iter()
+        .any(|dev_dir| dir.starts_with(dev_dir) || dev_dir.starts_with(dir))
+    })
+    .collect()
+}
+
+/// Stops the dev process, asking it to exit first and killing it if it is still running after the grace period.
+fn stop_dev_process(process: &dyn DevProcess, grace_period: Duration) -> crate::Result<()> {
+  if grace_period.is_zero() {
+    process
+      .kill()
+      .with_context(|| "failed to kill app process")?;
+  } else {
+    process
+      .terminate()
+      .with_context(|| "failed to terminate app process")?;
+    let start = Instant::now();
+    loop {
+      if let Ok(Some(_)) = process.try_wait() {
+        return Ok(());
+      }
+      if start.elapsed() >= grace_period {
+        warn!(
+          "App did not exit within {}ms, killing it",
+          grace_period.as_millis()
+        );
+        process
+          .kill()
+          .with_context(|| "failed to kill app process")?;
+        break;
+      }
+      std::thread::sleep(Duration::from_millis(50));
+    }
+  }
+
+  // wait for the process to exit
+  loop {
+    if let Ok(Some(_)) = process.try_wait() {
+      break;
+    }
+  }
+  Ok(())
+}
+
+/// The ignore files applied to every watched folder.
+fn default_ignore_files() -> Vec<PathBuf> {
   let mut default_gitignore = std::env::temp_dir();
   default_gitignore.push(".tauri-dev");
   let _ = std::fs::create_dir_all(&default_gitignore);
@@ -223,11 +575,18 @@ fn lookup<F: FnMut(FileType, PathBuf)>(dir: &Path, mut f: F) {
     }
   }
 
+  let mut files = vec![default_gitignore];
+  if let Ok(ignore_file) = std::env::var("TAURI_DEV_WATCHER_IGNORE_FILE") {
+    files.push(ignore_file.into());
+  }
+  files
+}
+
+fn lookup<F: FnMut(FileType, PathBuf)>(dir: &Path, mut f: F) {
   let mut builder = ignore::WalkBuilder::new(dir);
   builder.add_custom_ignore_filename(".taurignore");
-  let _ = builder.add_ignore(default_gitignore);
-  if let Ok(ignore_file) = std::env::var("TAURI_DEV_WATCHER_IGNORE_FILE") {
-    builder.add_ignore(ignore_file);
+  for ignore_file in default_ignore_files() {
+    let _ = builder.add_ignore(ignore_file);
   }
   builder.require_git(false).ignore(false).max_depth(Some(1));
 
@@ -236,12 +595,85 @@ fn lookup<F: FnMut(FileType, PathBuf)>(dir: &Path, mut f: F) {
   }
 }
 
+/// Whether `path` is ignored by the default ignore files or any `.gitignore` and `.taurignore`
+/// found between the watched `folder` and the path, with the same precedence used by [`lookup`].
+fn is_ignored(folder: &Path, path: &Path, is_dir: bool) -> bool {
+  let relative = match path.strip_prefix(folder) {
+    Ok(relative) => relative,
+    Err(_) => return false,
+  };
+
+  let mut ignored = false;
+  let mut apply = |root: &Path, file: &Path| {
+    let mut builder = GitignoreBuilder::new(root);
+    builder.add(file);
+    if let Ok(gitignore) = builder.build() {
+      match gitignore.matched_path_or_any_parents(path, is_dir) {
+        Match::Ignore(_) => ignored = true,
+        Match::Whitelist(_) => ignored = false,
+        Match::None => {}
+      }
+    }
+  };
+
+  for ignore_file in default_ignore_files() {
+    apply(folder, &ignore_file);
+  }
+
+  // ignore files in deeper directories take precedence
+  let mut dirs = vec![folder.to_path_buf()];
+  if let Some(parent) = relative.parent() {
+    for component in parent.components() {
+      let dir = dirs.last().unwrap().join(component);
+      dirs.push(dir);
+    }
+  }
+  for dir in dirs {
+    for name in [".gitignore", ".taurignore"] {
+      let file = dir.join(name);
+      if file.exists() {
+        apply(&dir, &file);
+      }
+    }
+  }
+
+  ignored
+}
+
+/// Writes the merged configuration to the target directory and exposes its path to the Tauri build script.
+pub(super) fn write_resolved_config(options: &Options, target_dir: &Path) -> crate::Result<()> {
+  let config = {
+    let config = get_config(!options.debug, options.config.as_deref())?;
+    let config = config.lock().unwrap();
+    let config: &Config = config.as_ref().unwrap();
+    serde_json::to_string_pretty(config)?
+  };
+  let path = target_dir.join(".tauri").join("config.json");
+  if std::fs::read_to_string(&path).ok().as_deref() != Some(config.as_str()) {
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(&path, config).with_context(|| format!("failed to write {}", path.display()))?;
+  }
+  std::env::set_var(RESOLVED_CONFIG_ENV, &path);
+  Ok(())
+}
+
+/// Adds the allowlist features that aren't written to Cargo.toml with `build > skipManifestRewrite`.
+pub fn extend_cargo_features(features: &mut Option<Vec<String>>, manifest: &Manifest) {
+  if !manifest.cargo_features.is_empty() {
+    features
+      .get_or_insert(Vec::new())
+      .extend(manifest.cargo_features.iter().cloned());
+  }
+}
+
 fn dev_options(
   args: &mut Vec<String>,
   features: &mut Option<Vec<String>>,
-  manifest_features: HashMap<String, Vec<String>>,
+  manifest: &Manifest,
 ) -> Vec<String> {
   if !args.contains(&"--no-default-features".into()) {
+    let manifest_features = manifest.features();
+    let custom_protocol = format!("{}/custom-protocol", manifest.tauri_dependency_name());
     let enable_features: Vec<String> = manifest_features
       .get("default")
       .cloned()
@@ -249,9 +681,9 @@ fn dev_options(
       .into_iter()
       .filter(|feature| {
         if let Some(manifest_feature) = manifest_features.get(feature) {
-          !manifest_feature.contains(&"tauri/custom-protocol".into())
+          !manifest_feature.contains(&custom_protocol)
         } else {
-          feature != "tauri/custom-protocol"
+          feature != &custom_protocol
         }
       })
       .collect();
@@ -278,12 +710,33 @@ fn dev_options(
 }
 
 impl Rust {
+  /// Writes the resolved configuration to `.tauri/config.json` in the target directory and sets
+  /// [`RESOLVED_CONFIG_ENV`] to its path, so `tauri-build`, `tauri::generate_context!` and the build scripts of the app
+  /// use the configuration merged and interpolated by the CLI.
+  ///
+  /// The file is only written when the configuration changes, since cargo reruns the build scripts on file changes.
+  fn write_resolved_config(&self, options: &Options) -> crate::Result<()> {
+    write_resolved_config(options, &self.app_settings.target_dir(options)?)
+  }
+
   fn run_dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(
     &mut self,
-    options: Options,
+    mut options: Options,
     run_args: Vec<String>,
     on_exit: F,
   ) -> crate::Result<Box<dyn DevProcess>> {
+    if options.after_build_command.is_none() {
+      let config = get_config(!options.debug, options.config.as_deref())?;
+      options.after_build_command = config
+        .lock()
+        .unwrap()
+        .as_ref()
+        .unwrap()
+        .build
+        .after_dev_build_command
+        .clone();
+    }
+    self.write_resolved_config(&options)?;
     desktop::run_dev(
       options,
       run_args,
@@ -298,18 +751,42 @@ impl Rust {
 
   fn run_dev_watcher<F: Fn(&mut Rust) -> crate::Result<Box<dyn DevProcess>>>(
     &mut self,
+    release: bool,
     config: Option<String>,
+    watcher_options: WatcherOptions,
+    (tx, rx): (Sender<WatcherMessage>, Receiver<WatcherMessage>),
     run: Arc<F>,
   ) -> crate::Result<()> {
+    let tauri_path = tauri_dir();
+    let (config_debounce, extra_watch_paths, mut frontend_dirs, mut extended_files) = {
+      let config = get_config(release, config.as_deref())?;
+      let config = config.lock().unwrap();
+      let config = config.as_ref().unwrap();
+      (
+        config.build.dev_watcher_debounce,
+        config.build.watch_paths.clone().unwrap_or_default(),
+        frontend_dist_dirs(config, &tauri_path),
+        config.extended_files().to_vec(),
+      )
+    };
+    let debounce = watcher_options
+      .debounce
+      .or(config_debounce)
+      .unwrap_or(DEFAULT_WATCHER_DEBOUNCE_MS);
+    let restart_grace_period = Duration::from_millis(
+      watcher_options
+        .restart_grace_period
+        .unwrap_or(DEFAULT_RESTART_GRACE_PERIOD_MS),
+    );
+
     let child = run(self)?;
 
     let process = Arc::new(Mutex::new(child));
-    let (tx, rx) = channel();
-    let tauri_path = tauri_dir();
-    let workspace_path = get_workspace_dir()?;
+    let mut crash_loop = CrashLoop::new(watcher_options.crash_restart_limit);
+    let workspace_path = self.app_settings.workspace_dir()?;
 
-    let watch_folders = if tauri_path == workspace_path {
-      vec![tauri_path]
+    let mut watch_folders = if tauri_path == workspace_path {
+      vec![tauri_path.clone()]
     } else {
       let cargo_settings = CargoSettings::load(&workspace_path)?;
       cargo_settings
@@ -323,63 +800,221 @@ impl Rust {
             .map(|p| workspace_path.join(p))
             .collect()
         })
-        .unwrap_or_else(|| vec![tauri_path])
+        .unwrap_or_else(|| vec![tauri_path.clone()])
     };
-
-    let mut watcher = watcher(tx, Duration::from_secs(1)).unwrap();
-    for path in watch_folders {
-      info!("Watching {} for changes...", path.display());
-      lookup(&path, |file_type, p| {
-        if p != path {
-          debug!("Watching {} for changes...", p.display());
-          let _ = watcher.watch(
-            p,
-            if file_type.is_dir() {
-              RecursiveMode::Recursive
-            } else {
-              RecursiveMode::NonRecursive
-            },
-          );
-        }
-      });
+    watch_folders.extend(extra_watch_paths.into_iter().map(|p| tauri_path.join(p)));
+    // the base configuration files may live outside of the watched folders, e.g. at the root of a monorepo
+    let extended_files_outside = extended_files
+      .iter()
+      .filter(|f| !watch_folders.iter().any(|folder| f.starts_with(folder)))
+      .cloned()
+      .collect::<Vec<_>>();
+    watch_folders.extend(extended_files_outside);
+
+    let tx_ = tx.clone();
+    let mut watcher = FileWatcher::new(tx, Duration::from_millis(debounce), watcher_options.poll)
+      .with_context(|| "failed to start the file watcher")?;
+    if watcher_options.poll {
+      info!(
+        "Using the polling file watcher with a {}ms interval",
+        debounce
+      );
     }
+    let mut watched = WatchedPaths::new(watcher.watcher(), watch_folders);
+
+    spawn_stdin_reader(tx_);
+    info!("Type `r` and press Enter to restart the app, `c` to clear the screen or `q` to quit.");
 
+    let mut pending = VecDeque::new();
     loop {
       let run = run.clone();
-      if let Ok(event) = rx.recv() {
-        let event_path = match event {
-          DebouncedEvent::Create(path) => Some(path),
-          DebouncedEvent::Remove(path) => Some(path),
-          DebouncedEvent::Rename(_, dest) => Some(dest),
-          DebouncedEvent::Write(path) => Some(path),
-          _ => None,
+      if let Some(message) = pending.pop_front().or_else(|| rx.recv().ok()) {
+        let result = match message {
+          WatcherMessage::Events(result) => result,
+          WatcherMessage::Command(DevCommand::Restart) => {
+            info!(action = "Restarting"; "app");
+            let mut p = process.lock().unwrap();
+            stop_dev_process(p.as_ref(), restart_grace_period)?;
+            *p = run(self)?;
+            crash_loop.reset();
+            continue;
+          }
+          WatcherMessage::AppCrashed(status) => {
+            let restart = crash_loop.crashed();
+            let mut p = process.lock().unwrap();
+            if !restart {
+              error!(
+                "The app crashed {} times in a row, waiting for changes before restarting it.",
+                crash_loop.crashes()
+              );
+              let stderr = p.captured_stderr();
+              if !stderr.is_empty() {
+                error!("Last output of the app:\n{}", stderr.join("\n"));
+              }
+            } else {
+              warn!(
+                "The app exited with status code {}, restarting it...",
+                status.code().unwrap_or(-1)
+              );
+              *p = run(self)?;
+              crash_loop.started();
+            }
+            continue;
+          }
+          WatcherMessage::Command(DevCommand::ClearScreen) => {
+            eprint!("\x1B[2J\x1B[1;1H");
+            continue;
+          }
+          WatcherMessage::Command(DevCommand::Quit) => {
+            let p = process.lock().unwrap();
+            stop_dev_process(p.as_ref(), restart_grace_period)?;
+            return Ok(());
+          }
         };
 
-        if let Some(event_path) = event_path {
-          if event_path.file_name() == Some(OsStr::new("tauri.conf.json")) {
-            let config = reload_config(config.as_deref())?;
-            self.app_settings.manifest =
-              rewrite_manifest(config.lock().unwrap().as_ref().unwrap())?;
+        let (events, errors) = coalesce_events(result, &rx, &mut pending);
+        for error in errors {
+          warn!("File watcher error: {}", error);
+        }
+
+        let mut config_path = None;
+        let mut changed_paths = Vec::new();
+        for event_path in events {
+          if !watched.on_change(watcher.watcher(), &event_path) {
+            continue;
+          }
+
+          if is_configuration_file(&event_path) || extended_files.contains(&event_path) {
+            config_path.replace(event_path);
+          } else if frontend_dirs.iter().any(|d| event_path.starts_with(d)) {
+            // the dev app doesn't use the `distDir` assets, so it doesn't need to be rebuilt
+            debug!("Ignoring change to frontend asset {}", event_path.display());
           } else {
-            // When tauri.conf.json is changed, rewrite_manifest will be called
-            // which will trigger the watcher again
-            // So the app should only be started when a file other than tauri.conf.json is changed
-            let mut p = process.lock().unwrap();
-            p.kill().with_context(|| "failed to kill app process")?;
-            // wait for the process to exit
-            loop {
-              if let Ok(Some(_)) = p.try_wait() {
-                break;
+            changed_paths.push(event_path);
+          }
+        }
+
+        // an invalid configuration is reported and the app keeps running with the previous one
+        let reloaded = match config_path {
+          Some(config_path) => {
+            let previous_config = {
+              let config = get_config(release, config.as_deref())?;
+              let config = config.lock().unwrap();
+              Config::clone(config.as_ref().unwrap())
+            };
+            match reload_config(release, config.as_deref()) {
+              Ok(config) => Some((config_path, previous_config, config)),
+              Err(e) => {
+                error!("{:#}", e);
+                None
               }
             }
-            *p = run(self)?;
+          }
+          None => None,
+        };
+
+        if let Some((config_path, previous_config, config)) = reloaded {
+          let config = config.lock().unwrap();
+          let config = config.as_ref().unwrap();
+          extended_files = config.extended_files().to_vec();
+          // a rewritten Cargo.toml triggers the watcher again, which restarts the app
+          let (manifest, manifest_rewritten) =
+            rewrite_manifest(config, watcher_options.frozen_manifest)?;
+          self.app_settings.manifest = manifest;
+          frontend_dirs = frontend_dist_dirs(config, &tauri_path);
+
+          let changes = diff_config(&previous_config, config)?;
+          if !changes.is_empty() {
+            info!(
+              "{} changed:\n{}",
+              config_path.file_name().unwrap().to_string_lossy(),
+              changes
+                .iter()
+                .map(|c| format!("  {}", c))
+                .collect::<Vec<_>>()
+                .join("\n")
+            );
+            let (app_changes, cli_changes): (Vec<_>, Vec<_>) =
+              changes.iter().partition(|c| is_app_config(&c.path));
+            if !cli_changes.is_empty() {
+              warn!(
+                "Changes to {} are applied the next time `tauri dev` starts.",
+                cli_changes
+                  .iter()
+                  .map(|c| format!("`{}`", c.path.join(" > ")))
+                  .collect::<Vec<_>>()
+                  .join(", ")
+              );
+            }
+            if !(app_changes.is_empty() || manifest_rewritten) {
+              changed_paths.push(config_path);
+            }
           }
         }
+
+        if !changed_paths.is_empty() {
+          events::emit(Event::WatcherTriggered {
+            paths: changed_paths,
+          });
+          let mut p = process.lock().unwrap();
+          stop_dev_process(p.as_ref(), restart_grace_period)?;
+          *p = run(self)?;
+          crash_loop.reset();
+        }
       }
     }
   }
 }
 
+/// Collects the file changes received within [`WATCHER_BATCH_WINDOW`] of the first one,
+/// so editors writing several files at once only restart the app once.
+///
+/// Other messages received in the meantime are queued in `pending`.
+fn coalesce_events(
+  result: DebounceEventResult,
+  rx: &Receiver<WatcherMessage>,
+  pending: &mut VecDeque<WatcherMessage>,
+) -> (Vec<PathBuf>, Vec<notify::Error>) {
+  let mut paths = Vec::new();
+  let mut seen = HashSet::new();
+  let mut errors = Vec::new();
+  let mut add = |result: DebounceEventResult| match result {
+    Ok(events) => {
+      for event in events {
+        if seen.insert(event.path.clone()) {
+          paths.push(event.path);
+        }
+      }
+    }
+    Err(e) => errors.extend(e),
+  };
+  add(result);
+
+  let deadline = Instant::now() + WATCHER_BATCH_WINDOW;
+  loop {
+    let timeout = deadline.saturating_duration_since(Instant::now());
+    match rx.recv_timeout(timeout) {
+      Ok(WatcherMessage::Events(result)) => add(result),
+      Ok(message) => pending.push_back(message),
+      Err(_) => break,
+    }
+  }
+
+  (paths, errors)
+}
+
+/// Whether the configuration value is embedded in the app, so changing it requires a restart.
+/// The other `build` values are only read by the CLI when `tauri dev` starts.
+fn is_app_config(path: &[String]) -> bool {
+  match path.first().map(|p| p.as_str()) {
+    Some("build") => matches!(
+      path.get(1).map(|p| p.as_str()),
+      Some("devPath" | "distDir" | "withGlobalTauri")
+    ),
+    _ => true,
+  }
+}
+
 /// The `workspace` section of the app configuration (read from Cargo.toml).
 #[derive(Clone, Debug, Deserialize)]
 struct WorkspaceSettings {
@@ -410,9 +1045,36 @@ pub struct CargoPackageSettings {
   pub default_run: Option<String>,
 }
 
+/// A dependency of the Cargo manifest.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum CargoDependency {
+  /// the version requirement, e.g. `tauri = "1.0"`.
+  Version(String),
+  /// the dependency table, e.g. `tauri = { version = "1.0", features = [] }`.
+  Detailed(CargoDependencyDetails),
+}
+
+impl CargoDependency {
+  /// The version requirement, `None` for path, git and workspace dependencies without a version.
+  pub fn version(&self) -> Option<&str> {
+    match self {
+      Self::Version(version) => Some(version),
+      Self::Detailed(details) => details.version.as_deref(),
+    }
+  }
+}
+
+/// The dependency table.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CargoDependencyDetails {
+  /// the version requirement.
+  pub version: Option<String>,
+}
+
 /// The Cargo settings (Cargo.toml root descriptor).
 #[derive(Clone, Debug, Deserialize)]
-struct CargoSettings {
+pub struct CargoSettings {
   /// the package settings.
   ///
   /// it's optional because ancestor workspace Cargo.toml files may not have package info.
@@ -423,11 +1085,16 @@ struct CargoSettings {
   workspace: Option<WorkspaceSettings>,
   /// the binary targets configuration.
   bin: Option<Vec<BinarySettings>>,
+  /// the dependencies.
+  pub dependencies: Option<HashMap<String, CargoDependency>>,
+  /// the build dependencies.
+  #[serde(rename = "build-dependencies")]
+  pub build_dependencies: Option<HashMap<String, CargoDependency>>,
 }
 
 impl CargoSettings {
   /// Try to load a set of CargoSettings from a "Cargo.toml" file in the specified directory.
-  fn load(dir: &Path) -> crate::Result<Self> {
+  pub fn load(dir: &Path) -> crate::Result<Self> {
     let toml_path = dir.join("Cargo.toml");
     let mut toml_str = String::new();
     let mut toml_file = File::open(toml_path).with_context(|| "failed to open Cargo.toml")?;
@@ -446,6 +1113,8 @@ pub struct RustAppSettings {
   cargo_package_settings: CargoPackageSettings,
   package_settings: PackageSettings,
   cargo_config: CargoConfig,
+  cargo_metadata: Mutex<Option<CachedCargoMetadata>>,
+  target_env: HashMap<String, HashMap<String, String>>,
 }
 
 impl AppSettings for RustAppSettings {
@@ -457,10 +1126,12 @@ impl AppSettings for RustAppSettings {
     &self,
     config: &Config,
     features: &[String],
+    target: &str,
   ) -> crate::Result<BundleSettings> {
     tauri_config_to_bundle_settings(
       &self.manifest,
       features,
+      target,
       config.tauri.bundle.clone(),
       config.tauri.system_tray.clone(),
       config.tauri.updater.clone(),
@@ -468,15 +1139,17 @@ impl AppSettings for RustAppSettings {
   }
 
   fn app_binary_path(&self, options: &Options) -> crate::Result<PathBuf> {
-    let bin_name = self
-      .cargo_package_settings()
-      .name
-      .clone()
-      .expect("Cargo manifest must have the `package.name` field");
-
-    let out_dir = self
-      .out_dir(options.target.clone(), options.debug)
+    let bin_name = match options.example.as_ref().or(options.bin.as_ref()) {
+      Some(bin) => bin.clone(),
+      None => self.main_binary_name()?,
+    };
+
+    let mut out_dir = self
+      .out_dir(options)
       .with_context(|| "failed to get project out directory")?;
+    if options.example.is_some() {
+      out_dir.push("examples");
+    }
     let target: String = if let Some(target) = options.target.clone() {
       target
     } else {
@@ -493,9 +1166,64 @@ impl AppSettings for RustAppSettings {
     Ok(out_dir.join(bin_name).with_extension(&binary_extension))
   }
 
-  fn get_binaries(&self, config: &Config, target: &str) -> crate::Result<Vec<BundleBinary>> {
-    let mut binaries: Vec<BundleBinary> = vec![];
+  fn out_dir(&self, options: &Options) -> crate::Result<PathBuf> {
+    Ok(get_target_dir(
+      self.target_dir(options)?,
+      options
+        .target
+        .as_deref()
+        .or_else(|| self.cargo_config.build().target()),
+      options.cargo_profile(),
+    ))
+  }
 
+  fn target_dir(&self, options: &Options) -> crate::Result<PathBuf> {
+    // the runner may set the target directory for the cargo process only
+    let runner = desktop::Runner::parse(options.runner.as_deref().unwrap_or("cargo"));
+    let args = runner
+      .args
+      .iter()
+      .chain(&options.args)
+      .cloned()
+      .collect::<Vec<_>>();
+    let env = runner
+      .env
+      .iter()
+      .rev()
+      .find(|(key, _)| key == "CARGO_TARGET_DIR")
+      .map(|(_, value)| value.into())
+      .or_else(|| {
+        self
+          .target_env(options.target.as_deref())
+          .ok()?
+          .remove("CARGO_TARGET_DIR")
+          .map(Into::into)
+      })
+      .or_else(|| std::env::var_os("CARGO_TARGET_DIR"))
+      .filter(|value| !value.is_empty())
+      .map(PathBuf::from);
+
+    match target_dir_override(
+      &args,
+      env,
+      self.cargo_config.build().target_dir(),
+      &tauri_dir(),
+    ) {
+      Some(target_dir) => Ok(target_dir),
+      None => Ok(self.cargo_metadata()?.target_directory),
+    }
+  }
+
+  fn manifest(&self) -> &Manifest {
+    &self.manifest
+  }
+
+  fn get_binaries(
+    &self,
+    config: &Config,
+    target: &str,
+    options: &Options,
+  ) -> crate::Result<Vec<BundleBinary>> {
     let binary_extension: String = if target.contains("windows") {
       ".exe"
     } else {
@@ -503,99 +1231,104 @@ impl AppSettings for RustAppSettings {
     }
     .into();
 
-    if let Some(bin) = &self.cargo_settings.bin {
-      let default_run = self
-        .package_settings
-        .default_run
-        .clone()
-        .unwrap_or_else(|| "".to_string());
-      for binary in bin {
-        binaries.push(
-          if Some(&binary.name) == self.cargo_package_settings.name.as_ref()
-            || binary.name.as_str() == default_run
-          {
-            BundleBinary::new(
-              format!(
-                "{}{}",
-                config
-                  .package
-                  .binary_name()
-                  .unwrap_or_else(|| binary.name.clone()),
-                &binary_extension
-              ),
-              true,
-            )
-          } else {
-            BundleBinary::new(
-              format!("{}{}", binary.name.clone(), &binary_extension),
-              false,
-            )
-          }
-          .set_src_path(binary.path.clone()),
-        )
-      }
+    // an example is bundled on its own, the package binaries aren't built
+    if let Some(example) = &options.example {
+      return Ok(vec![BundleBinary::new(
+        format!(
+          "{}{}",
+          config
+            .package
+            .binary_name()
+            .unwrap_or_else(|| example.clone()),
+          &binary_extension
+        ),
+        true,
+      )]);
     }
 
-    let mut bins_path = tauri_dir();
-    bins_path.push("src/bin");
-    if let Ok(fs_bins) = std::fs::read_dir(bins_path) {
-      for entry in fs_bins {
-        let path = entry?.path();
-        if let Some(name) = path.file_stem() {
-          let bin_exists = binaries.iter().any(|bin| {
-            bin.name() == name || path.ends_with(bin.src_path().unwrap_or(&"".to_string()))
-          });
-          if !bin_exists {
-            binaries.push(BundleBinary::new(
-              format!("{}{}", name.to_string_lossy(), &binary_extension),
-              false,
-            ))
-          }
-        }
+    let main_binary = match &options.bin {
+      Some(bin) => bin.clone(),
+      None => self.main_binary_name()?,
+    };
+    let src_bins = match std::fs::read_dir(tauri_dir().join("src/bin")) {
+      Ok(entries) => entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?,
+      Err(_) => Vec::new(),
+    };
+    package_binaries(
+      self.cargo_settings.bin.as_deref().unwrap_or_default(),
+      &src_bins,
+      &main_binary,
+      self.cargo_package_settings.name.as_deref(),
+      config.package.binary_name(),
+      &binary_extension,
+    )
+  }
+}
+
+/// The bundled binaries of the package: its `[[bin]]` targets and the `src/bin` files,
+/// with the main binary named after the app.
+fn package_binaries(
+  bins: &[BinarySettings],
+  src_bins: &[PathBuf],
+  main_binary: &str,
+  package_name: Option<&str>,
+  binary_name: Option<String>,
+  binary_extension: &str,
+) -> crate::Result<Vec<BundleBinary>> {
+  let main_binary_name = format!(
+    "{}{}",
+    binary_name.unwrap_or_else(|| main_binary.to_string()),
+    binary_extension
+  );
+  let mut binaries = Vec::new();
+
+  for binary in bins {
+    binaries.push(
+      if binary.name == main_binary {
+        BundleBinary::new(main_binary_name.clone(), true)
+      } else {
+        BundleBinary::new(format!("{}{}", binary.name, binary_extension), false)
       }
-    }
+      .set_src_path(binary.path.clone()),
+    )
+  }
 
-    if let Some(default_run) = self.package_settings.default_run.as_ref() {
-      match binaries.iter_mut().find(|bin| bin.name() == default_run) {
-        Some(bin) => {
-          if let Some(bin_name) = config.package.binary_name() {
-            bin.set_name(bin_name);
-          }
-        }
-        None => {
-          binaries.push(BundleBinary::new(
-            format!(
-              "{}{}",
-              config
-                .package
-                .binary_name()
-                .unwrap_or_else(|| default_run.to_string()),
-              &binary_extension
-            ),
-            true,
-          ));
-        }
+  for path in src_bins {
+    if let Some(name) = path.file_stem() {
+      let bin_exists = binaries
+        .iter()
+        .any(|bin| bin.name() == name || bin.src_path().map_or(false, |src| path.ends_with(src)));
+      if !bin_exists {
+        binaries.push(BundleBinary::new(
+          format!("{}{}", name.to_string_lossy(), binary_extension),
+          false,
+        ))
       }
     }
+  }
 
-    match binaries.len() {
-      0 => binaries.push(BundleBinary::new(
-        #[cfg(target_os = "linux")]
-        self.package_settings.product_name.to_kebab_case(),
-        #[cfg(not(target_os = "linux"))]
-        format!(
-          "{}{}",
-          self.package_settings.product_name.clone(),
-          &binary_extension
-        ),
-        true,
-      )),
-      1 => binaries.get_mut(0).unwrap().set_main(true),
-      _ => {}
+  // the `[[bin]]` targets are already marked as main
+  if !binaries.iter().any(|bin| bin.main()) {
+    let file_name = format!("{}{}", main_binary, binary_extension);
+    match binaries.iter_mut().find(|bin| bin.name() == file_name) {
+      Some(bin) => {
+        bin.set_name(main_binary_name);
+        bin.set_main(true);
+      }
+      // the `src/main.rs` binary, named after the package
+      None if Some(main_binary) == package_name => {
+        binaries.push(BundleBinary::new(main_binary_name, true));
+      }
+      None => anyhow::bail!(
+        "no `{}` binary found in the `[[bin]]` targets of Cargo.toml or in src/bin",
+        main_binary
+      ),
     }
-
-    Ok(binaries)
   }
+
+  Ok(binaries)
 }
 
 impl RustAppSettings {
@@ -641,27 +1374,209 @@ impl RustAppSettings {
       cargo_package_settings,
       package_settings,
       cargo_config,
+      cargo_metadata: Default::default(),
+      target_env: config.build.target_env.clone().unwrap_or_default(),
     })
   }
 
-  pub fn cargo_package_settings(&self) -> &CargoPackageSettings {
-    &self.cargo_package_settings
+  /// The `build > targetEnv` variables of the target, or of the host when no target is given.
+  pub fn target_env(&self, target: Option<&str>) -> crate::Result<HashMap<String, String>> {
+    if self.target_env.is_empty() {
+      return Ok(HashMap::new());
+    }
+    let target = self.target_triple(target)?;
+    Ok(
+      self
+        .target_env
+        .get(target_dir_name(&target))
+        .cloned()
+        .unwrap_or_default(),
+    )
+  }
+
+  /// The `[env]` variables of the cargo configuration, applied to the cargo and app processes.
+  pub fn cargo_env(&self) -> Vec<(String, String)> {
+    self.cargo_config.env()
   }
 
-  pub fn out_dir(&self, target: Option<String>, debug: bool) -> crate::Result<PathBuf> {
-    get_target_dir(
-      target
-        .as_deref()
-        .or_else(|| self.cargo_config.build().target()),
-      !debug,
-    )
+  /// The runner of the app binary, the program followed by its arguments.
+  ///
+  /// Read from the `CARGO_TARGET_<TRIPLE>_RUNNER` variable (including `build > targetEnv`)
+  /// or the `target.<triple>.runner` cargo config of the target, or of the host when no target is given.
+  pub fn target_runner(&self, target: Option<&str>) -> crate::Result<Option<Vec<String>>> {
+    let target = self.target_triple(target)?;
+    let target = target_dir_name(&target);
+    let var = format!(
+      "CARGO_TARGET_{}_RUNNER",
+      target.to_uppercase().replace(|c| c == '-' || c == '.', "_")
+    );
+    let runner = self
+      .target_env(Some(target))?
+      .remove(&var)
+      .or_else(|| std::env::var(&var).ok())
+      .map(|runner| {
+        runner
+          .split_whitespace()
+          .map(Into::into)
+          .collect::<Vec<_>>()
+      })
+      .or_else(|| self.cargo_config.target_runner(target).map(|r| r.to_vec()));
+    Ok(runner.filter(|runner| !runner.is_empty()))
+  }
+
+  /// The target triple, defaulting to the `build.target` cargo config and the host.
+  fn target_triple(&self, target: Option<&str>) -> crate::Result<String> {
+    match target.or_else(|| self.cargo_config.build().target()) {
+      Some(target) => Ok(target.to_string()),
+      None => Ok(tauri_utils::platform::target_triple()?),
+    }
+  }
+
+  /// The name of the binary built as the app when no `--bin` or `--example` is given,
+  /// read from the targets of the package reported by `cargo metadata`.
+  fn main_binary_name(&self) -> crate::Result<String> {
+    let package_name = || {
+      self
+        .cargo_package_settings
+        .name
+        .clone()
+        .expect("Cargo manifest must have the `package.name` field")
+    };
+    let metadata = self.cargo_metadata()?;
+    let package = match metadata.package(&tauri_dir().join("Cargo.toml")) {
+      Some(package) => package,
+      None => return Ok(package_name()),
+    };
+    if let Some(name) = package.main_binary() {
+      return Ok(name.into());
+    }
+    let binaries = package.binaries().collect::<Vec<_>>();
+    match &package.default_run {
+      Some(default_run) => anyhow::bail!(
+        "the `default-run` binary `{}` of the `{}` package does not exist",
+        default_run,
+        package.name
+      ),
+      // a library crate, e.g. a mobile app
+      None if binaries.is_empty() => Ok(package_name()),
+      None => anyhow::bail!(
+        "could not determine the app binary of the `{}` package, set `default-run` in Cargo.toml or use `--bin` to select one of its binaries: {}",
+        package.name,
+        binaries.join(", ")
+      ),
+    }
+  }
+
+  /// The workspace root of the app.
+  pub fn workspace_dir(&self) -> crate::Result<PathBuf> {
+    Ok(self.cargo_metadata()?.workspace_root)
+  }
+
+  /// Runs `cargo metadata`, reusing the previous result unless a manifest or the lock file changed.
+  fn cargo_metadata(&self) -> crate::Result<CargoMetadata> {
+    let mut cache = self.cargo_metadata.lock().unwrap();
+    if let Some(cached) = cache.as_ref().filter(|c| !c.is_stale()) {
+      return Ok(cached.metadata.clone());
+    }
+    let metadata = get_cargo_metadata().with_context(|| "failed to get cargo metadata")?;
+    *cache = Some(CachedCargoMetadata::new(metadata.clone()));
+    Ok(metadata)
   }
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 struct CargoMetadata {
   target_directory: PathBuf,
   workspace_root: PathBuf,
+  #[serde(default)]
+  packages: Vec<MetadataPackage>,
+}
+
+/// A workspace member listed by `cargo metadata`.
+#[derive(Clone, Deserialize)]
+struct MetadataPackage {
+  name: String,
+  manifest_path: PathBuf,
+  default_run: Option<String>,
+  #[serde(default)]
+  targets: Vec<MetadataTarget>,
+}
+
+#[derive(Clone, Deserialize)]
+struct MetadataTarget {
+  name: String,
+  kind: Vec<String>,
+}
+
+impl CargoMetadata {
+  /// The workspace member defined by the given manifest.
+  fn package(&self, manifest_path: &Path) -> Option<&MetadataPackage> {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let manifest_path = canonical(manifest_path);
+    self
+      .packages
+      .iter()
+      .find(|package| canonical(&package.manifest_path) == manifest_path)
+  }
+}
+
+impl MetadataPackage {
+  /// The `[[bin]]` targets of the package.
+  fn binaries(&self) -> impl Iterator<Item = &str> {
+    self
+      .targets
+      .iter()
+      .filter(|target| target.kind.iter().any(|kind| kind == "bin"))
+      .map(|target| target.name.as_str())
+  }
+
+  /// The binary `cargo run` executes: the `default-run` binary, the binary named after the package or the only binary.
+  fn main_binary(&self) -> Option<&str> {
+    if let Some(default_run) = &self.default_run {
+      return self.binaries().find(|name| name == default_run);
+    }
+    let mut binaries = self.binaries();
+    match (binaries.next(), binaries.next()) {
+      (Some(name), None) => Some(name),
+      _ => self.binaries().find(|name| *name == self.name),
+    }
+  }
+}
+
+/// A `cargo metadata` result along with the modification time of the files it depends on.
+struct CachedCargoMetadata {
+  metadata: CargoMetadata,
+  files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl CachedCargoMetadata {
+  fn new(metadata: CargoMetadata) -> Self {
+    let mut paths = vec![
+      tauri_dir().join("Cargo.toml"),
+      metadata.workspace_root.join("Cargo.toml"),
+      metadata.workspace_root.join("Cargo.lock"),
+    ];
+    paths.dedup();
+    let files = paths
+      .into_iter()
+      .map(|path| {
+        let modified = modified_time(&path);
+        (path, modified)
+      })
+      .collect();
+    Self { metadata, files }
+  }
+
+  fn is_stale(&self) -> bool {
+    self
+      .files
+      .iter()
+      .any(|(path, modified)| modified_time(path) != *modified)
+  }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+  std::fs::metadata(path).and_then(|m| m.modified()).ok()
 }
 
 fn get_cargo_metadata() -> crate::Result<CargoMetadata> {
@@ -680,20 +1595,70 @@ fn get_cargo_metadata() -> crate::Result<CargoMetadata> {
   Ok(serde_json::from_slice(&output.stdout)?)
 }
 
-/// This function determines the 'target' directory and suffixes it with 'release' or 'debug'
+/// Suffixes the 'target' directory with the target triple and profile folders
 /// to determine where the compiled binary will be located.
-fn get_target_dir(target: Option<&str>, is_release: bool) -> crate::Result<PathBuf> {
-  let mut path = get_cargo_metadata()
-    .with_context(|| "failed to get cargo metadata")?
-    .target_directory;
-
+fn get_target_dir(mut target_dir: PathBuf, target: Option<&str>, profile: &str) -> PathBuf {
   if let Some(triple) = target {
-    path.push(triple);
+    target_dir.push(target_dir_name(triple));
   }
 
-  path.push(if is_release { "release" } else { "debug" });
+  target_dir.push(profile_dir_name(profile));
+
+  target_dir
+}
+
+/// The target directory set by the `--target-dir` argument, the `CARGO_TARGET_DIR` environment variable
+/// or the `build.target-dir` cargo configuration, in this order of precedence.
+///
+/// Relative argument and environment values are resolved from `cwd`, where cargo is executed.
+fn target_dir_override(
+  args: &[String],
+  env: Option<PathBuf>,
+  config: Option<&Path>,
+  cwd: &Path,
+) -> Option<PathBuf> {
+  let mut args = args.iter().take_while(|arg| *arg != "--");
+  let mut arg = None;
+  while let Some(a) = args.next() {
+    if a == "--target-dir" {
+      arg = args.next().map(PathBuf::from);
+      break;
+    } else if let Some(dir) = a.strip_prefix("--target-dir=") {
+      arg = Some(PathBuf::from(dir));
+      break;
+    }
+  }
+
+  arg
+    .or(env)
+    .map(|dir| cwd.join(dir))
+    .or_else(|| config.map(Path::to_path_buf))
+}
+
+/// The name of the folder cargo uses for the artifacts of the given profile.
+///
+/// The built-in `dev` and `test` profiles output to `debug` and `bench` to `release`.
+fn profile_dir_name(profile: &str) -> &str {
+  match profile {
+    "dev" | "test" => "debug",
+    "bench" => "release",
+    profile => profile,
+  }
+}
 
-  Ok(path)
+/// The name of the folder cargo uses for the given `--target` value.
+///
+/// Strips the glibc version suffix supported by `cargo zigbuild` (e.g. `x86_64-unknown-linux-gnu.2.17`)
+/// and resolves custom target specification files to their file name.
+pub fn target_dir_name(target: &str) -> &str {
+  if target.ends_with(".json") {
+    Path::new(target)
+      .file_stem()
+      .and_then(|s| s.to_str())
+      .unwrap_or(target)
+  } else {
+    target.split('.').next().unwrap()
+  }
 }
 
 /// Executes `cargo metadata` to get the workspace directory.
@@ -705,41 +1670,135 @@ pub fn get_workspace_dir() -> crate::Result<PathBuf> {
   )
 }
 
+/// The Flatpak sandbox permissions needed by the enabled APIs, followed by the configured ones.
+fn flatpak_finish_args(
+  enabled_features: &[String],
+  system_tray: bool,
+  updater: bool,
+  configured: Vec<String>,
+) -> Vec<String> {
+  let api_enabled = |api: &str| {
+    enabled_features
+      .iter()
+      .any(|f| f == "tauri/api-all" || f.starts_with(&format!("tauri/{}-", api)))
+  };
+
+  let mut args = vec![
+    "--share=ipc".to_string(),
+    "--socket=wayland".to_string(),
+    "--socket=fallback-x11".to_string(),
+    "--device=dri".to_string(),
+  ];
+  if updater || api_enabled("http") {
+    args.push("--share=network".into());
+  }
+  if api_enabled("fs") {
+    args.push("--filesystem=home".into());
+  }
+  if api_enabled("notification") {
+    args.push("--talk-name=org.freedesktop.Notifications".into());
+  }
+  if system_tray {
+    args.push("--talk-name=org.kde.StatusNotifierWatcher".into());
+  }
+  for arg in configured {
+    if !args.contains(&arg) {
+      args.push(arg);
+    }
+  }
+  args
+}
+
+/// The MSIX capabilities needed by the enabled APIs, followed by the configured ones.
+fn msix_capabilities(
+  enabled_features: &[String],
+  updater: bool,
+  configured: Vec<String>,
+) -> Vec<String> {
+  let http_enabled = enabled_features
+    .iter()
+    .any(|f| f == "tauri/api-all" || f.starts_with("tauri/http-"));
+
+  // desktop apps run with full trust
+  let mut capabilities = vec!["runFullTrust".to_string()];
+  if updater || http_enabled {
+    capabilities.push("internetClient".into());
+  }
+  for capability in configured {
+    if !capabilities.contains(&capability) {
+      capabilities.push(capability);
+    }
+  }
+  capabilities
+}
+
+/// The tray library of the package dependencies: `tauri > bundle > deb > trayDependency`,
+/// then the `TAURI_TRAY` environment variable, then either library.
+#[cfg(target_os = "linux")]
+pub fn deb_tray_dependency(config: &DebConfig) -> DebTrayDependency {
+  config
+    .tray_dependency
+    .unwrap_or_else(|| match std::env::var("TAURI_TRAY").as_deref() {
+      Ok("ayatana") => DebTrayDependency::Ayatana,
+      Ok(_) => DebTrayDependency::Appindicator,
+      Err(_) => DebTrayDependency::Either,
+    })
+}
+
 #[allow(unused_variables)]
-fn tauri_config_to_bundle_settings(
+pub(super) fn tauri_config_to_bundle_settings(
   manifest: &Manifest,
   features: &[String],
+  target: &str,
   config: crate::helpers::config::BundleConfig,
   system_tray_config: Option<crate::helpers::config::SystemTrayConfig>,
   updater_config: crate::helpers::config::UpdaterConfig,
 ) -> crate::Result<BundleSettings> {
   let enabled_features = manifest.all_enabled_features(features);
 
-  #[cfg(windows)]
-  let windows_icon_path = PathBuf::from(
-    config
-      .icon
-      .iter()
-      .find(|i| i.ends_with(".ico"))
-      .cloned()
-      .expect("the bundle config must have a `.ico` icon"),
-  );
-  #[cfg(not(windows))]
-  let windows_icon_path = PathBuf::from("");
-
+  // Windows installers can be bundled on any host, the bundler fails if they need the missing icon
+  let windows_icon_path = config
+    .icon
+    .iter()
+    .find(|i| i.ends_with(".ico"))
+    .map(PathBuf::from)
+    .unwrap_or_default();
+
+  // the map sources are expanded now, so a missing one is reported before compiling the app
+  let mut resources = Vec::new();
+  let mut resources_map = None;
+  for target_resources in config.resources_for_target(target) {
+    match target_resources {
+      BundleResources::List(list) => resources.extend(list.iter().cloned()),
+      BundleResources::Map(map) => resources_map
+        .get_or_insert_with(HashMap::new)
+        .extend(resource_map_files(map).with_context(|| "failed to expand the bundle resources")?),
+    }
+  }
+  let external_bin = config.external_bin_for_target(target);
   #[allow(unused_mut)]
-  let mut resources = config.resources.unwrap_or_default();
+  let mut depends = config.deb.depends.clone().unwrap_or_default();
   #[allow(unused_mut)]
-  let mut depends = config.deb.depends.unwrap_or_default();
+  let mut rpm_depends = config.rpm.depends.unwrap_or_default();
 
   #[cfg(target_os = "linux")]
   {
+    // rpmbuild detects the linked libraries such as webkit2gtk,
+    // but not the tray library which is loaded at runtime
     if let Some(system_tray_config) = &system_tray_config {
-      let tray = std::env::var("TAURI_TRAY").unwrap_or_else(|_| "ayatana".to_string());
-      if tray == "ayatana" {
-        depends.push("libayatana-appindicator3-1".into());
-      } else {
-        depends.push("libappindicator3-1".into());
+      match deb_tray_dependency(&config.deb) {
+        DebTrayDependency::Ayatana => {
+          depends.push("libayatana-appindicator3-1".into());
+          rpm_depends.push("libayatana-appindicator-gtk3".into());
+        }
+        DebTrayDependency::Appindicator => {
+          depends.push("libappindicator3-1".into());
+          rpm_depends.push("libappindicator-gtk3".into());
+        }
+        DebTrayDependency::Either => {
+          depends.push("libayatana-appindicator3-1 | libappindicator3-1".into());
+          rpm_depends.push("(libayatana-appindicator-gtk3 or libappindicator-gtk3)".into());
+        }
       }
     }
 
@@ -787,6 +1846,7 @@ fn tauri_config_to_bundle_settings(
     } else {
       Some(resources)
     },
+    resources_map,
     copyright: config.copyright,
     category: match config.category {
       Some(category) => Some(AppCategory::from_str(&category).map_err(|e| match e {
@@ -797,14 +1857,82 @@ fn tauri_config_to_bundle_settings(
     },
     short_description: config.short_description,
     long_description: config.long_description,
-    external_bin: config.external_bin,
+    artifact_name: config.artifact_name,
+    external_bin: if external_bin.is_empty() {
+      None
+    } else {
+      Some(external_bin)
+    },
     deb: DebianSettings {
       depends: if depends.is_empty() {
         None
       } else {
         Some(depends)
       },
+      recommends: config.deb.recommends,
+      suggests: config.deb.suggests,
+      provides: config.deb.provides,
+      conflicts: config.deb.conflicts,
+      replaces: config.deb.replaces,
+      section: config.deb.section,
+      priority: config.deb.priority,
       files: config.deb.files,
+      conffiles: config.deb.conffiles,
+      systemd_user_unit: config.deb.systemd_user_unit,
+      pre_install_script: config.deb.pre_install_script,
+      post_install_script: config.deb.post_install_script,
+      pre_remove_script: config.deb.pre_remove_script,
+      post_remove_script: config.deb.post_remove_script,
+    },
+    appimage: AppImageSettings {
+      update_information: config.appimage.update_information,
+      desktop_entry: config.appimage.desktop_entry,
+      exclude_libraries: config.appimage.exclude_libraries,
+      app_run: config.appimage.app_run,
+    },
+    flatpak: FlatpakSettings {
+      finish_args: flatpak_finish_args(
+        &enabled_features,
+        system_tray_config.is_some(),
+        updater_config.active,
+        config.flatpak.finish_args,
+      ),
+      runtime: config.flatpak.runtime,
+      runtime_version: config.flatpak.runtime_version,
+      sdk: config.flatpak.sdk,
+    },
+    desktop: DesktopEntrySettings {
+      categories: config.linux.desktop.categories,
+      keywords: config.linux.desktop.keywords,
+      startup_wm_class: config.linux.desktop.startup_wm_class,
+      exec_args: config.linux.desktop.exec_args,
+      actions: config
+        .linux
+        .desktop
+        .actions
+        .into_iter()
+        .map(|action| DesktopAction {
+          id: action.id,
+          name: action.name,
+          exec_args: action.exec_args,
+        })
+        .collect(),
+    },
+    rpm: RpmSettings {
+      depends: if rpm_depends.is_empty() {
+        None
+      } else {
+        Some(rpm_depends)
+      },
+      release: config.rpm.release,
+      epoch: config.rpm.epoch,
+      license: config.rpm.license,
+      desktop_template: config.rpm.desktop_template,
+      files: config.rpm.files,
+      pre_install_script: config.rpm.pre_install_script,
+      post_install_script: config.rpm.post_install_script,
+      pre_remove_script: config.rpm.pre_remove_script,
+      post_remove_script: config.rpm.post_remove_script,
     },
     macos: MacOsSettings {
       frameworks: config.macos.frameworks,
@@ -822,17 +1950,49 @@ fn tauri_config_to_bundle_settings(
           None
         }
       },
+      info_plist: config.macos.info_plist,
+      dmg: DmgSettings {
+        background: config.macos.dmg.background,
+        window_position: config.macos.dmg.window_position,
+        window_size: config.macos.dmg.window_size,
+        icon_size: config.macos.dmg.icon_size,
+        app_position: config.macos.dmg.app_position,
+        application_folder_position: config.macos.dmg.application_folder_position,
+      },
+      notarization: NotarizationSettings {
+        apple_id: config.macos.notarization.apple_id,
+        team_id: config.macos.notarization.team_id,
+        api_key: config.macos.notarization.api_key,
+        api_issuer: config.macos.notarization.api_issuer,
+        api_key_path: config.macos.notarization.api_key_path,
+      },
     },
     windows: WindowsSettings {
       timestamp_url: config.windows.timestamp_url,
       tsp: config.windows.tsp,
       digest_algorithm: config.windows.digest_algorithm,
       certificate_thumbprint: config.windows.certificate_thumbprint,
+      sign: config.windows.sign,
       wix: config.windows.wix.map(|w| {
         let mut wix = wix_settings(w);
         wix.license = wix.license.map(|l| tauri_dir().join(l));
         wix
       }),
+      nsis: config.windows.nsis.map(nsis_settings),
+      msix: {
+        let msix = config.windows.msix.unwrap_or_default();
+        MsixSettings {
+          capabilities: msix_capabilities(
+            &enabled_features,
+            updater_config.active,
+            msix.capabilities,
+          ),
+          identity_name: msix.identity_name,
+          publisher: msix.publisher,
+          publisher_display_name: msix.publisher_display_name,
+          min_version: msix.min_version,
+        }
+      },
       icon_path: windows_icon_path,
       webview_install_mode: config.windows.webview_install_mode,
       webview_fixed_runtime_path: config.windows.webview_fixed_runtime_path,
@@ -849,6 +2009,255 @@ fn tauri_config_to_bundle_settings(
         .map(|endpoints| endpoints.iter().map(|e| e.to_string()).collect()),
       msiexec_args: Some(updater_config.windows.install_mode.msiexec_args()),
     }),
+    sign_command: config.sign_command.map(|sign_command| SignCommand {
+      command: sign_command.command().into(),
+      args: sign_command.args(),
+    }),
+    file_associations: config.file_associations,
+    deep_link_schemes: Some(config.deep_link.schemes),
     ..Default::default()
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    flatpak_finish_args, frontend_dist_dirs, msix_capabilities, package_binaries,
+    target_dir_override, BinarySettings, CargoMetadata, Config,
+  };
+  use std::path::{Path, PathBuf};
+
+  fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+  }
+
+  #[test]
+  fn target_dir_precedence() {
+    let cwd = Path::new("/app/src-tauri");
+    let env = Some(PathBuf::from("env-target"));
+    let config = Some(Path::new("/workspace/config-target"));
+
+    assert_eq!(target_dir_override(&[], None, None, cwd), None);
+    assert_eq!(
+      target_dir_override(&[], None, config, cwd),
+      Some(PathBuf::from("/workspace/config-target"))
+    );
+    assert_eq!(
+      target_dir_override(&[], env.clone(), config, cwd),
+      Some(PathBuf::from("/app/src-tauri/env-target"))
+    );
+    assert_eq!(
+      target_dir_override(
+        &args(&["--target-dir", "arg-target"]),
+        env.clone(),
+        config,
+        cwd
+      ),
+      Some(PathBuf::from("/app/src-tauri/arg-target"))
+    );
+    assert_eq!(
+      target_dir_override(
+        &args(&["--locked", "--target-dir=/tmp/target"]),
+        env,
+        config,
+        cwd
+      ),
+      Some(PathBuf::from("/tmp/target"))
+    );
+  }
+
+  #[test]
+  fn flatpak_permissions_follow_allowlist() {
+    let base = args(&[
+      "--share=ipc",
+      "--socket=wayland",
+      "--socket=fallback-x11",
+      "--device=dri",
+    ]);
+    assert_eq!(flatpak_finish_args(&[], false, false, Vec::new()), base);
+
+    let mut expected = base.clone();
+    expected.extend(args(&[
+      "--share=network",
+      "--filesystem=home",
+      "--filesystem=xdg-download",
+    ]));
+    assert_eq!(
+      flatpak_finish_args(
+        &args(&["tauri/http-request", "tauri/fs-read-file"]),
+        false,
+        false,
+        args(&["--filesystem=xdg-download", "--share=ipc"])
+      ),
+      expected
+    );
+
+    let all = flatpak_finish_args(&args(&["tauri/api-all"]), true, false, Vec::new());
+    assert!(all.contains(&"--talk-name=org.freedesktop.Notifications".to_string()));
+    assert!(all.contains(&"--talk-name=org.kde.StatusNotifierWatcher".to_string()));
+  }
+
+  #[test]
+  fn msix_capabilities_follow_allowlist() {
+    assert_eq!(
+      msix_capabilities(&[], false, Vec::new()),
+      args(&["runFullTrust"])
+    );
+    assert_eq!(
+      msix_capabilities(
+        &args(&["tauri/http-request"]),
+        false,
+        args(&["webcam", "runFullTrust"])
+      ),
+      args(&["runFullTrust", "internetClient", "webcam"])
+    );
+    assert_eq!(
+      msix_capabilities(&[], true, Vec::new()),
+      args(&["runFullTrust", "internetClient"])
+    );
+  }
+
+  #[test]
+  fn skips_dist_dir_unused_by_the_dev_app() {
+    let tauri_path = Path::new("/app/src-tauri");
+    let config = |dev_path: &str| -> Config {
+      serde_json::from_value(serde_json::json!({
+        "build": { "devPath": dev_path, "distDir": "../dist" }
+      }))
+      .unwrap()
+    };
+
+    assert_eq!(
+      frontend_dist_dirs(&config("http://localhost:1420"), tauri_path),
+      vec![tauri_path.join("../dist")]
+    );
+    assert_eq!(
+      frontend_dist_dirs(&config("../src"), tauri_path),
+      vec![tauri_path.join("../dist")]
+    );
+    // the local devPath is embedded in the dev app
+    assert!(frontend_dist_dirs(&config("../dist"), tauri_path).is_empty());
+  }
+
+  #[test]
+  fn target_dir_ignores_app_args() {
+    let cwd = Path::new("/app/src-tauri");
+    assert_eq!(
+      target_dir_override(&args(&["--", "--target-dir", "app-arg"]), None, None, cwd),
+      None
+    );
+  }
+
+  fn metadata(packages: serde_json::Value) -> CargoMetadata {
+    serde_json::from_value(serde_json::json!({
+      "target_directory": "/ws/target",
+      "workspace_root": "/ws",
+      "packages": packages,
+    }))
+    .unwrap()
+  }
+
+  fn package(name: &str, default_run: Option<&str>, bins: &[&str]) -> serde_json::Value {
+    let mut targets = vec![serde_json::json!({ "name": name, "kind": ["lib"] })];
+    targets.extend(
+      bins
+        .iter()
+        .map(|bin| serde_json::json!({ "name": bin, "kind": ["bin"] })),
+    );
+    serde_json::json!({
+      "name": name,
+      "manifest_path": format!("/ws/{}/Cargo.toml", name),
+      "default_run": default_run,
+      "targets": targets,
+    })
+  }
+
+  fn main_binary<'a>(metadata: &'a CargoMetadata, member: &str) -> Option<&'a str> {
+    metadata
+      .package(&Path::new("/ws").join(member).join("Cargo.toml"))
+      .and_then(|package| package.main_binary())
+  }
+
+  #[test]
+  fn resolves_renamed_main_binary() {
+    let metadata = metadata(serde_json::json!([package("app", None, &["my-app"])]));
+    assert_eq!(main_binary(&metadata, "app"), Some("my-app"));
+  }
+
+  #[test]
+  fn renames_main_binary_to_app_name() {
+    let bins = [BinarySettings {
+      name: "my-app".into(),
+      path: Some("src/main.rs".into()),
+    }];
+    let binaries = package_binaries(
+      &bins,
+      &[],
+      "my-app",
+      Some("app"),
+      Some("awesome-app".into()),
+      "",
+    )
+    .unwrap();
+    assert_eq!(binaries.len(), 1);
+    assert_eq!(binaries[0].name(), "awesome-app");
+    assert!(binaries[0].main());
+
+    let binaries = package_binaries(
+      &[],
+      &[
+        PathBuf::from("/app/src-tauri/src/bin/helper.rs"),
+        PathBuf::from("/app/src-tauri/src/bin/desktop.rs"),
+      ],
+      "desktop",
+      Some("app"),
+      Some("awesome-app".into()),
+      ".exe",
+    )
+    .unwrap();
+    let names = binaries
+      .iter()
+      .map(|bin| (bin.name(), bin.main()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      names,
+      vec![("helper.exe", false), ("awesome-app.exe", true)]
+    );
+
+    assert!(package_binaries(&[], &[], "missing", Some("app"), None, "").is_err());
+  }
+
+  #[test]
+  fn resolves_default_run_binary() {
+    let metadata = metadata(serde_json::json!([
+      package("app", Some("desktop"), &["app", "desktop", "helper"]),
+      package("broken", Some("missing"), &["broken"]),
+    ]));
+    assert_eq!(main_binary(&metadata, "app"), Some("desktop"));
+    assert_eq!(main_binary(&metadata, "broken"), None);
+  }
+
+  #[test]
+  fn resolves_binary_of_several() {
+    let metadata = metadata(serde_json::json!([
+      package("app", None, &["helper", "app"]),
+      package("tools", None, &["a", "b"]),
+      package("mobile", None, &[]),
+    ]));
+    assert_eq!(main_binary(&metadata, "app"), Some("app"));
+    // `cargo run` can't pick one either
+    assert_eq!(main_binary(&metadata, "tools"), None);
+    assert_eq!(main_binary(&metadata, "mobile"), None);
+  }
+
+  #[test]
+  fn resolves_workspace_member_binary() {
+    let metadata = metadata(serde_json::json!([
+      package("core", None, &["core-cli"]),
+      package("src-tauri", None, &["desktop-app"]),
+    ]));
+    assert_eq!(main_binary(&metadata, "src-tauri"), Some("desktop-app"));
+    assert_eq!(main_binary(&metadata, "core"), Some("core-cli"));
+    assert_eq!(main_binary(&metadata, "missing"), None);
+  }
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::{
+  collections::HashMap,
   fs,
   path::{Path, PathBuf},
 };
@@ -32,18 +33,81 @@ impl<'a> Iterator for PathAncestors<'a> {
 }
 
 #[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct BuildConfig {
   target: Option<String>,
+  target_dir: Option<PathBuf>,
+  #[serde(default)]
+  rustflags: Flags,
+}
+
+/// A list of flags, either as an array or a space separated string.
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+enum Flags {
+  List(Vec<String>),
+  String(String),
+}
+
+impl Default for Flags {
+  fn default() -> Self {
+    Self::List(Vec::new())
+  }
+}
+
+impl Flags {
+  fn into_vec(self) -> Vec<String> {
+    match self {
+      Self::List(flags) => flags,
+      Self::String(flags) => flags.split_whitespace().map(Into::into).collect(),
+    }
+  }
+}
+
+#[derive(Deserialize)]
+pub struct TargetConfig {
+  linker: Option<String>,
+  runner: Option<Flags>,
+  rustflags: Option<Flags>,
+}
+
+/// A value of the `[env]` table, either a string or a table with the `value`, `force` and `relative` keys.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EnvValue {
+  Value(String),
+  Table {
+    value: String,
+    #[serde(default)]
+    force: bool,
+    #[serde(default)]
+    relative: bool,
+  },
 }
 
 #[derive(Deserialize)]
 pub struct ConfigSchema {
   build: Option<BuildConfig>,
+  #[serde(default)]
+  target: HashMap<String, TargetConfig>,
+  #[serde(default)]
+  env: HashMap<String, EnvValue>,
+}
+
+/// A resolved `[env]` variable.
+struct EnvVar {
+  value: String,
+  force: bool,
 }
 
 #[derive(Default)]
 pub struct Config {
   build: BuildConfig,
+  target_linkers: HashMap<String, String>,
+  target_runners: HashMap<String, Vec<String>>,
+  /// The `target.<triple>` and `target.'cfg(..)'` tables that set `rustflags`.
+  target_rustflags: Vec<String>,
+  env: HashMap<String, EnvVar>,
 }
 
 impl Config {
@@ -56,26 +120,117 @@ impl Config {
           .with_context(|| format!("failed to read configuration file `{}`", path.display()))?;
         let toml: ConfigSchema = toml::from_str(&contents)
           .with_context(|| format!("could not parse TOML configuration in `{}`", path.display()))?;
-
-        if let Some(target) = toml.build.and_then(|b| b.target) {
-          config.build.target = Some(target);
-          break;
-        }
+        config.merge(current, toml);
       }
     }
 
     Ok(config)
   }
 
+  /// Merges the configuration file of the `.cargo` directory in `dir`,
+  /// which is further from the project than the files merged before.
+  fn merge(&mut self, dir: &Path, toml: ConfigSchema) {
+    // the closest configuration file wins for each key
+    if let Some(build) = toml.build {
+      if self.build.target.is_none() {
+        self.build.target = build.target;
+      }
+      if self.build.target_dir.is_none() {
+        // relative paths are resolved from the directory containing `.cargo`
+        self.build.target_dir = build.target_dir.map(|target_dir| dir.join(target_dir));
+      }
+      // arrays are joined, with the flags of the closest configuration file last
+      let mut rustflags = build.rustflags.into_vec();
+      rustflags.extend(std::mem::take(&mut self.build.rustflags).into_vec());
+      self.build.rustflags = Flags::List(rustflags);
+    }
+    for (target, target_config) in toml.target {
+      if target_config.rustflags.is_some() && !self.target_rustflags.contains(&target) {
+        self.target_rustflags.push(target.clone());
+      }
+      if let Some(linker) = target_config.linker {
+        self.target_linkers.entry(target.clone()).or_insert(linker);
+      }
+      if let Some(runner) = target_config.runner {
+        self
+          .target_runners
+          .entry(target)
+          .or_insert_with(|| runner.into_vec());
+      }
+    }
+    for (key, value) in toml.env {
+      self.env.entry(key).or_insert_with(|| match value {
+        EnvValue::Value(value) => EnvVar {
+          value,
+          force: false,
+        },
+        EnvValue::Table {
+          value,
+          force,
+          relative,
+        } => EnvVar {
+          value: if relative {
+            dir.join(value).to_string_lossy().into_owned()
+          } else {
+            value
+          },
+          force,
+        },
+      });
+    }
+  }
+
   pub fn build(&self) -> &BuildConfig {
     &self.build
   }
+
+  /// The `target.<triple>.linker` value.
+  pub fn target_linker(&self, target: &str) -> Option<&str> {
+    self.target_linkers.get(target).map(|l| l.as_str())
+  }
+
+  /// The `target.<triple>.runner` value, the program followed by its arguments.
+  pub fn target_runner(&self, target: &str) -> Option<&[String]> {
+    self
+      .target_runners
+      .get(target)
+      .filter(|runner| !runner.is_empty())
+      .map(|runner| runner.as_slice())
+  }
+
+  /// Whether `target.<triple>.rustflags` or a `target.'cfg(..)'.rustflags` table is set,
+  /// in which case cargo ignores `build.rustflags`. The `cfg` expressions are assumed to match.
+  pub fn has_target_rustflags(&self, target: &str) -> bool {
+    self
+      .target_rustflags
+      .iter()
+      .any(|key| key == target || key.starts_with("cfg("))
+  }
+
+  /// The `[env]` variables, except the ones already set in the environment unless `force` is set.
+  pub fn env(&self) -> Vec<(String, String)> {
+    self
+      .env
+      .iter()
+      .filter(|(key, var)| var.force || std::env::var_os(key).is_none())
+      .map(|(key, var)| (key.clone(), var.value.clone()))
+      .collect()
+  }
 }
 
 impl BuildConfig {
   pub fn target(&self) -> Option<&str> {
     self.target.as_deref()
   }
+
+  pub fn target_dir(&self) -> Option<&Path> {
+    self.target_dir.as_deref()
+  }
+
+  /// The `build.rustflags` value.
+  pub fn rustflags(&self) -> Vec<String> {
+    self.rustflags.clone().into_vec()
+  }
 }
 
 /// The purpose of this function is to aid in the transition to using
@@ -121,3 +276,101 @@ fn get_file_path(
     Ok(None)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{Config, ConfigSchema};
+  use std::path::Path;
+
+  fn merge(config: &mut Config, dir: &str, toml: &str) {
+    let toml: ConfigSchema = toml::from_str(toml).unwrap();
+    config.merge(Path::new(dir), toml);
+  }
+
+  #[test]
+  fn merges_env_and_runners() {
+    let mut config = Config::default();
+    merge(
+      &mut config,
+      "/project",
+      r#"
+        [env]
+        TAURI_CONFIG_ENV_TEST_A = "project"
+        TAURI_CONFIG_ENV_TEST_B = { value = "assets", relative = true }
+
+        [target.aarch64-unknown-linux-gnu]
+        runner = "ssh pi@raspberrypi"
+      "#,
+    );
+    merge(
+      &mut config,
+      "/",
+      r#"
+        [env]
+        TAURI_CONFIG_ENV_TEST_A = "home"
+        TAURI_CONFIG_ENV_TEST_C = { value = "forced", force = true }
+
+        [target.aarch64-unknown-linux-gnu]
+        runner = ["qemu-aarch64"]
+
+        [target.x86_64-pc-windows-gnu]
+        runner = ["wine"]
+      "#,
+    );
+
+    let mut env = config.env();
+    env.sort();
+    assert_eq!(
+      env,
+      vec![
+        ("TAURI_CONFIG_ENV_TEST_A".into(), "project".into()),
+        (
+          "TAURI_CONFIG_ENV_TEST_B".into(),
+          Path::new("/project")
+            .join("assets")
+            .to_string_lossy()
+            .into_owned()
+        ),
+        ("TAURI_CONFIG_ENV_TEST_C".into(), "forced".into()),
+      ]
+    );
+    assert_eq!(
+      config.target_runner("aarch64-unknown-linux-gnu"),
+      Some(&["ssh".to_string(), "pi@raspberrypi".to_string()][..])
+    );
+    assert_eq!(
+      config.target_runner("x86_64-pc-windows-gnu"),
+      Some(&["wine".to_string()][..])
+    );
+    assert_eq!(config.target_runner("x86_64-unknown-linux-gnu"), None);
+    assert!(!config.has_target_rustflags("aarch64-unknown-linux-gnu"));
+  }
+
+  #[test]
+  fn tracks_target_rustflags() {
+    let mut config = Config::default();
+    merge(
+      &mut config,
+      "/project",
+      r#"
+        [build]
+        rustflags = ["--cfg", "app"]
+
+        [target.x86_64-pc-windows-msvc]
+        rustflags = ["-C", "target-feature=+crt-static"]
+      "#,
+    );
+    assert!(config.has_target_rustflags("x86_64-pc-windows-msvc"));
+    assert!(!config.has_target_rustflags("x86_64-unknown-linux-gnu"));
+
+    merge(
+      &mut config,
+      "/",
+      r#"
+        [target.'cfg(target_os = "linux")']
+        rustflags = ["-C", "link-arg=-s"]
+      "#,
+    );
+    assert!(config.has_target_rustflags("x86_64-unknown-linux-gnu"));
+  }
+}
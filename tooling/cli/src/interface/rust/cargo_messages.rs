@@ -0,0 +1,148 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Parses the JSON messages cargo writes to stdout with `--message-format json-diagnostic-rendered-ansi`.
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+pub const MESSAGE_FORMAT: &str = "json-diagnostic-rendered-ansi";
+
+#[derive(Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum Message {
+  CompilerArtifact {
+    target: ArtifactTarget,
+    executable: Option<PathBuf>,
+  },
+  CompilerMessage {
+    message: Diagnostic,
+  },
+  #[serde(other)]
+  Other,
+}
+
+#[derive(Deserialize)]
+struct ArtifactTarget {
+  name: String,
+  kind: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+  message: String,
+  level: String,
+  rendered: Option<String>,
+  #[serde(default)]
+  spans: Vec<DiagnosticSpan>,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticSpan {
+  file_name: String,
+  line_start: usize,
+  is_primary: bool,
+}
+
+/// What cargo reported while building the app.
+#[derive(Debug, Default)]
+pub struct BuildReport {
+  /// The executables that were built, with their binary name.
+  executables: Vec<(String, PathBuf)>,
+  /// A one line summary of each compiler error.
+  pub errors: Vec<String>,
+}
+
+impl BuildReport {
+  /// Handles a line cargo wrote to stdout, calling `print` with the text that should be displayed.
+  pub fn handle_line<F: FnMut(&str)>(&mut self, line: &[u8], mut print: F) {
+    match serde_json::from_slice::<Message>(line) {
+      Ok(Message::CompilerArtifact {
+        target,
+        executable: Some(executable),
+      }) if target.kind.iter().any(|k| k == "bin" || k == "example") => {
+        self.executables.push((target.name, executable));
+      }
+      Ok(Message::CompilerMessage { message }) => {
+        if message.level == "error" {
+          // skip the summaries such as "aborting due to previous error"
+          if let Some(span) = message.spans.iter().find(|s| s.is_primary) {
+            self.errors.push(format!(
+              "{} ({}:{})",
+              message.message, span.file_name, span.line_start
+            ));
+          }
+        }
+        if let Some(rendered) = &message.rendered {
+          print(rendered.trim_end());
+        }
+      }
+      Ok(_) => (),
+      // not a cargo message, e.g. the output of a custom runner
+      Err(_) => print(&String::from_utf8_lossy(line)),
+    }
+  }
+
+  /// The path of the executable built for the given binary.
+  pub fn executable(&self, name: &str) -> Option<&Path> {
+    self
+      .executables
+      .iter()
+      .rev()
+      .find(|(n, _)| n == name)
+      .map(|(_, path)| path.as_path())
+  }
+
+  /// The error returned when the build fails.
+  pub fn build_error(&self) -> anyhow::Error {
+    if self.errors.is_empty() {
+      anyhow::anyhow!("failed to build app")
+    } else {
+      anyhow::anyhow!(
+        "failed to build app due to {} compiler {}:\n{}",
+        self.errors.len(),
+        if self.errors.len() == 1 {
+          "error"
+        } else {
+          "errors"
+        },
+        self
+          .errors
+          .iter()
+          .map(|e| format!("  - {}", e))
+          .collect::<Vec<_>>()
+          .join("\n")
+      )
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::BuildReport;
+  use std::path::Path;
+
+  #[test]
+  fn records_executables() {
+    let mut report = BuildReport::default();
+    for line in [
+      r#"{"reason":"compiler-artifact","target":{"name":"app_lib","kind":["lib"]},"executable":null}"#,
+      r#"{"reason":"compiler-artifact","target":{"name":"build-script-build","kind":["custom-build"]},"executable":null}"#,
+      r#"{"reason":"compiler-artifact","target":{"name":"my-app","kind":["bin"]},"executable":"/ws/target/debug/my-app"}"#,
+      r#"{"reason":"compiler-artifact","target":{"name":"demo","kind":["example"]},"executable":"/ws/target/debug/examples/demo"}"#,
+      r#"{"reason":"build-finished","success":true}"#,
+    ] {
+      report.handle_line(line.as_bytes(), |_| panic!("unexpected output"));
+    }
+    assert_eq!(
+      report.executable("my-app"),
+      Some(Path::new("/ws/target/debug/my-app"))
+    );
+    assert_eq!(
+      report.executable("demo"),
+      Some(Path::new("/ws/target/debug/examples/demo"))
+    );
+    assert_eq!(report.executable("app_lib"), None);
+  }
+}
@@ -1,15 +1,28 @@
-use super::{AppSettings, DevProcess, ExitReason, Options, RustAppSettings, Target};
-use crate::CommandExt;
+use super::{
+  cargo_config::Config as CargoConfig,
+  cargo_messages::{self, BuildReport},
+  target_dir_name, AppSettings, DevProcess, ExitReason, Options, RustAppSettings, Target,
+};
+use crate::{
+  dev::{
+    events::{self, Event},
+    output::{self, Source, Stream},
+  },
+  helpers::{app_paths::tauri_dir, doctor::rust_targets_fix},
+  CommandExt,
+};
 
 use anyhow::Context;
 #[cfg(target_os = "linux")]
 use heck::ToKebabCase;
+use log::{error, info, warn};
 use shared_child::SharedChild;
 use std::{
+  collections::{HashMap, VecDeque},
   fs::rename,
-  io::{BufReader, ErrorKind, Write},
+  io::{BufReader, ErrorKind, Read, Write},
   path::{Path, PathBuf},
-  process::{Command, ExitStatus, Stdio},
+  process::{ChildStderr, Command, ExitStatus, Stdio},
   sync::{
     atomic::{AtomicBool, Ordering},
     Arc, Mutex,
@@ -20,8 +33,18 @@ pub struct DevChild {
   manually_killed_app: Arc<AtomicBool>,
   build_child: Option<Arc<SharedChild>>,
   app_child: Arc<Mutex<Option<Arc<SharedChild>>>>,
+  app_stderr: Arc<Mutex<VecDeque<String>>>,
 }
 
+/// The target triple of an universal macOS app, built by merging the binaries of [`UNIVERSAL_MACOS_TRIPLES`].
+const UNIVERSAL_MACOS_TARGET: &str = "universal-apple-darwin";
+const UNIVERSAL_MACOS_TRIPLES: [&str; 2] = ["aarch64-apple-darwin", "x86_64-apple-darwin"];
+/// The compiler wrapper set as `RUSTC_WRAPPER` with `--use-sccache`.
+const SCCACHE: &str = "sccache";
+
+/// Number of lines of the app stderr kept to be displayed when the app keeps crashing.
+const CAPTURED_STDERR_LINES: usize = 50;
+
 impl DevProcess for DevChild {
   fn kill(&self) -> std::io::Result<()> {
     if let Some(child) = &*self.app_child.lock().unwrap() {
@@ -33,6 +56,20 @@ impl DevProcess for DevChild {
     Ok(())
   }
 
+  #[cfg(unix)]
+  fn terminate(&self) -> std::io::Result<()> {
+    use shared_child::unix::SharedChildExt;
+    // flag it first so the exit handler doesn't treat the app exit as a regular close
+    self.manually_killed_app.store(true, Ordering::Relaxed);
+    if let Some(child) = &*self.app_child.lock().unwrap() {
+      child.send_signal(libc::SIGTERM)
+    } else if let Some(child) = &self.build_child {
+      child.send_signal(libc::SIGTERM)
+    } else {
+      Ok(())
+    }
+  }
+
   fn try_wait(&self) -> std::io::Result<Option<ExitStatus>> {
     if let Some(child) = &*self.app_child.lock().unwrap() {
       child.try_wait()
@@ -56,6 +93,10 @@ impl DevProcess for DevChild {
   fn manually_killed_process(&self) -> bool {
     self.manually_killed_app.load(Ordering::Relaxed)
   }
+
+  fn captured_stderr(&self) -> Vec<String> {
+    self.app_stderr.lock().unwrap().iter().cloned().collect()
+  }
 }
 
 pub fn run_dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(
@@ -67,31 +108,109 @@ pub fn run_dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(
   product_name: Option<String>,
   on_exit: F,
 ) -> crate::Result<impl DevProcess> {
+  if options.target.as_deref() == Some(UNIVERSAL_MACOS_TARGET) {
+    anyhow::bail!(
+      "The `{}` target is not supported by `tauri dev`, use `{}` instead.",
+      UNIVERSAL_MACOS_TARGET,
+      UNIVERSAL_MACOS_TRIPLES.join("` or `")
+    );
+  }
   let bin_path = app_settings.app_binary_path(&options)?;
+  // like `cargo run`, the binary is executed with the runner of the target
+  let app_runner = app_settings.target_runner(options.target.as_deref())?;
+  let cargo_env = app_settings.cargo_env();
+  let open_devtools = options.open_devtools;
+  let debug = options.debug;
+  let after_build_command = options.after_build_command.clone();
+  let remote_debugging_env = options.remote_debugging_port.map(|port| {
+    remote_debugging_env(
+      options
+        .remote_debugging_address
+        .as_deref()
+        .unwrap_or("127.0.0.1"),
+      port,
+    )
+  });
 
   let manually_killed_app = Arc::new(AtomicBool::default());
   let manually_killed_app_ = manually_killed_app.clone();
   let app_child = Arc::new(Mutex::new(None));
   let app_child_ = app_child.clone();
+  let app_stderr = Arc::new(Mutex::new(VecDeque::new()));
+  let app_stderr_ = app_stderr.clone();
+  let report = Arc::new(Mutex::new(BuildReport::default()));
+  let report_ = report.clone();
 
   let build_child = build_dev_app(
     options,
+    report,
     available_targets,
     config_features,
+    app_settings,
     move |status, reason| {
       if status.success() {
+        if let Some(hook) = after_build_command {
+          match crate::helpers::hook::run_hook_command(
+            "afterDevBuildCommand",
+            hook,
+            debug,
+            &Default::default(),
+          ) {
+            Ok(Some((script, hook_status))) if !hook_status.success() => {
+              error!(
+                "afterDevBuildCommand `{}` failed with exit code {}",
+                script,
+                hook_status.code().unwrap_or_default()
+              );
+              on_exit(hook_status, ExitReason::CompilationFailed);
+              return;
+            }
+            Err(e) => {
+              error!("{:#}", e);
+              on_exit(failed_status(), ExitReason::CompilationFailed);
+              return;
+            }
+            _ => (),
+          }
+        }
+        let bin_path = built_executable(&report_.lock().unwrap(), bin_path);
         let bin_path =
           rename_app(&bin_path, product_name.as_deref()).expect("failed to rename app");
-        let mut app = Command::new(bin_path);
-        app.stdout(os_pipe::dup_stdout().unwrap());
-        app.stderr(os_pipe::dup_stderr().unwrap());
+        let mut app = match &app_runner {
+          Some(runner) => {
+            let mut app = Command::new(&runner[0]);
+            app.args(&runner[1..]).arg(bin_path);
+            app
+          }
+          None => Command::new(bin_path),
+        };
+        app.envs(cargo_env);
+        app.stdout(Stdio::piped());
+        app.stderr(Stdio::piped());
         app.args(run_args);
+        if open_devtools {
+          app.env("TAURI_OPEN_DEVTOOLS", "true");
+        }
+        if let Some(env) = remote_debugging_env {
+          app.envs(env);
+        }
         let app_child = Arc::new(SharedChild::spawn(&mut app).unwrap());
+        output::forward(
+          app_child.take_stdout().unwrap(),
+          Source::App,
+          Stream::Stdout,
+          |_| (),
+        );
+        forward_app_stderr(app_child.take_stderr().unwrap(), app_stderr_.clone());
+        events::emit(Event::AppSpawned {
+          pid: app_child.id(),
+        });
         crate::dev::wait_dev_process(
           DevChild {
             manually_killed_app: manually_killed_app_,
             build_child: None,
             app_child: Arc::new(Mutex::new(Some(app_child.clone()))),
+            app_stderr: app_stderr_,
           },
           on_exit,
         );
@@ -114,26 +233,91 @@ pub fn run_dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(
     manually_killed_app,
     build_child: Some(build_child),
     app_child,
+    app_stderr,
   })
 }
 
+/// The environment variables that expose the webview remote debugging server.
+/// The exit status reported when a step of the dev build could not run.
+fn failed_status() -> ExitStatus {
+  #[cfg(unix)]
+  {
+    use std::os::unix::process::ExitStatusExt;
+    // the raw wait status of an exit with code 1
+    ExitStatus::from_raw(1 << 8)
+  }
+  #[cfg(windows)]
+  {
+    use std::os::windows::process::ExitStatusExt;
+    ExitStatus::from_raw(1)
+  }
+}
+
+fn remote_debugging_env(address: &str, port: u16) -> Vec<(&'static str, String)> {
+  if cfg!(windows) {
+    let mut args = std::env::var("WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS").unwrap_or_default();
+    if !args.is_empty() {
+      args.push(' ');
+    }
+    args.push_str(&format!("--remote-debugging-port={}", port));
+    vec![("WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS", args)]
+  } else if cfg!(target_os = "macos") {
+    Vec::new()
+  } else {
+    vec![("WEBKIT_INSPECTOR_SERVER", format!("{}:{}", address, port))]
+  }
+}
+
+/// Writes the app stderr to the CLI stderr, keeping the last lines to show them if the app keeps crashing.
+fn forward_app_stderr(stderr: ChildStderr, captured: Arc<Mutex<VecDeque<String>>>) {
+  output::forward(stderr, Source::App, Stream::Stderr, move |line| {
+    let mut captured = captured.lock().unwrap();
+    if captured.len() == CAPTURED_STDERR_LINES {
+      captured.pop_front();
+    }
+    captured.push_back(String::from_utf8_lossy(line).into_owned());
+  });
+}
+
 pub fn build(
   options: Options,
   app_settings: &RustAppSettings,
   product_name: Option<String>,
   available_targets: &mut Option<Vec<Target>>,
   config_features: Vec<String>,
-) -> crate::Result<()> {
+) -> crate::Result<PathBuf> {
   let bin_path = app_settings.app_binary_path(&options)?;
   let out_dir = bin_path.parent().unwrap();
 
   let bin_name = bin_path.file_stem().unwrap();
+  let is_example = options.example.is_some();
 
   if !std::env::var("STATIC_VCRUNTIME").map_or(false, |v| v == "false") {
     std::env::set_var("STATIC_VCRUNTIME", "true");
   }
 
-  if options.target == Some("universal-apple-darwin".into()) {
+  let use_sccache = options.use_sccache;
+  if use_sccache {
+    ensure_sccache()?;
+    // only report the statistics of this build
+    let _ = Command::new(SCCACHE).arg("--zero-stats").output_ok();
+  }
+
+  let bin_path = if options.target.as_deref() == Some(UNIVERSAL_MACOS_TARGET) {
+    if !cfg!(target_os = "macos") {
+      anyhow::bail!(
+        "The `{}` target can only be built on macOS since it requires `lipo`.",
+        UNIVERSAL_MACOS_TARGET
+      );
+    }
+    // fail before the first build if one of the targets is missing
+    if available_targets.is_none() {
+      *available_targets = fetch_available_targets();
+    }
+    for triple in UNIVERSAL_MACOS_TRIPLES {
+      validate_target(available_targets, triple, options.ci)?;
+    }
+
     std::fs::create_dir_all(&out_dir).with_context(|| "failed to create project out directory")?;
 
     let mut lipo_cmd = Command::new("lipo");
@@ -141,44 +325,101 @@ pub fn build(
       .arg("-create")
       .arg("-output")
       .arg(out_dir.join(&bin_name));
-    for triple in ["aarch64-apple-darwin", "x86_64-apple-darwin"] {
+    for triple in UNIVERSAL_MACOS_TRIPLES {
       let mut options = options.clone();
       options.target.replace(triple.into());
 
       let triple_out_dir = app_settings
-        .out_dir(Some(triple.into()), options.debug)
+        .out_dir(&options)
         .with_context(|| format!("failed to get {} out dir", triple))?;
 
-      build_production_app(options, available_targets, config_features.clone())
-        .with_context(|| format!("failed to build {} binary", triple))?;
+      let report = build_production_app(
+        options,
+        available_targets,
+        config_features.clone(),
+        app_settings,
+      )
+      .with_context(|| format!("failed to build {} binary", triple))?;
 
-      lipo_cmd.arg(triple_out_dir.join(&bin_name));
+      lipo_cmd.arg(built_executable(&report, triple_out_dir.join(&bin_name)));
     }
 
-    let lipo_status = lipo_cmd.output_ok()?.status;
-    if !lipo_status.success() {
-      return Err(anyhow::anyhow!(format!(
-        "Result of `lipo` command was unsuccessful: {}. (Is `lipo` installed?)",
-        lipo_status
-      )));
+    info!(action = "Merging"; "{} binaries with lipo", UNIVERSAL_MACOS_TRIPLES.join(" and "));
+    let lipo_output = lipo_cmd.output().with_context(|| {
+      "failed to run `lipo`, make sure the Xcode command line tools are installed"
+    })?;
+    if !lipo_output.status.success() {
+      anyhow::bail!(
+        "`lipo` failed to create the universal binary ({}): {}",
+        lipo_output.status,
+        String::from_utf8_lossy(&lipo_output.stderr).trim()
+      );
     }
+    bin_path
   } else {
-    build_production_app(options, available_targets, config_features)
-      .with_context(|| "failed to build app")?;
+    let report = build_production_app(options, available_targets, config_features, app_settings)?;
+    built_executable(&report, bin_path)
+  };
+
+  if use_sccache {
+    info!(action = "Sccache"; "statistics of this build:");
+    let _ = Command::new(SCCACHE).arg("--show-stats").piped();
   }
 
-  rename_app(&bin_path, product_name.as_deref())?;
+  // the bundler looks for the app binary next to the package binaries, not in the `examples` folder
+  app_binary(bin_path, is_example, product_name.as_deref())
+}
 
-  Ok(())
+/// Moves the built binary to the path the bundler expects: the example binaries next to the other ones,
+/// renamed after the product name.
+fn app_binary(
+  bin_path: PathBuf,
+  is_example: bool,
+  product_name: Option<&str>,
+) -> crate::Result<PathBuf> {
+  let bin_path = if is_example {
+    let examples_dir = bin_path.parent().unwrap();
+    let app_path = examples_dir
+      .parent()
+      .unwrap()
+      .join(bin_path.file_name().unwrap());
+    std::fs::copy(&bin_path, &app_path).with_context(|| {
+      format!(
+        "failed to copy the example binary to `{}`",
+        app_path.display()
+      )
+    })?;
+    app_path
+  } else {
+    bin_path
+  };
+
+  rename_app(&bin_path, product_name)
+}
+
+/// The executable cargo reported for the app binary, falling back to its expected location.
+fn built_executable(report: &BuildReport, bin_path: PathBuf) -> PathBuf {
+  bin_path
+    .file_stem()
+    .and_then(|name| report.executable(&name.to_string_lossy()))
+    .map(Path::to_path_buf)
+    .unwrap_or(bin_path)
 }
 
 fn build_dev_app<F: FnOnce(ExitStatus, ExitReason) + Send + 'static>(
   options: Options,
+  report: Arc<Mutex<BuildReport>>,
   available_targets: &mut Option<Vec<Target>>,
   config_features: Vec<String>,
+  app_settings: &RustAppSettings,
   on_exit: F,
 ) -> crate::Result<Arc<SharedChild>> {
-  let mut build_cmd = build_command(options, available_targets, config_features)?;
+  let bin = options.bin.clone();
+  let mut build_cmd = build_command(options, available_targets, config_features, app_settings)?;
+  // only the app binary is compiled in development
+  if let Some(bin) = bin {
+    build_cmd.arg("--bin").arg(bin);
+  }
   let runner = build_cmd.get_program().to_string_lossy().into_owned();
   build_cmd
     .env(
@@ -198,9 +439,11 @@ fn build_dev_app<F: FnOnce(ExitStatus, ExitReason) + Send + 'static>(
   build_cmd.arg("--color");
   build_cmd.arg("always");
 
-  build_cmd.stdout(os_pipe::dup_stdout()?);
+  build_cmd.stdout(Stdio::piped());
   build_cmd.stderr(Stdio::piped());
 
+  events::emit(Event::BuildStarted);
+
   let build_child = match SharedChild::spawn(&mut build_cmd) {
     Ok(c) => Ok(c),
     Err(e) if e.kind() == ErrorKind::NotFound => Err(anyhow::anyhow!(
@@ -215,31 +458,36 @@ fn build_dev_app<F: FnOnce(ExitStatus, ExitReason) + Send + 'static>(
     Err(e) => Err(e.into()),
   }?;
   let build_child = Arc::new(build_child);
-  let build_child_stderr = build_child.take_stderr().unwrap();
-  let mut stderr = BufReader::new(build_child_stderr);
+  let stdout_reader =
+    read_cargo_messages(build_child.take_stdout().unwrap(), report.clone(), |text| {
+      for line in text.lines() {
+        output::write_line(Source::Build, Stream::Stderr, line.as_bytes());
+      }
+    });
   let stderr_lines = Arc::new(Mutex::new(Vec::new()));
   let stderr_lines_ = stderr_lines.clone();
-  std::thread::spawn(move || {
-    let mut buf = Vec::new();
-    let mut lines = stderr_lines_.lock().unwrap();
-    let mut io_stderr = std::io::stderr();
-    loop {
-      buf.clear();
-      match tauri_utils::io::read_line(&mut stderr, &mut buf) {
-        Ok(s) if s == 0 => break,
-        _ => (),
-      }
-      let _ = io_stderr.write_all(&buf);
-      if !buf.ends_with(&[b'\r']) {
-        let _ = io_stderr.write_all(b"\n");
-      }
-      lines.push(String::from_utf8_lossy(&buf).into_owned());
-    }
-  });
+  let stderr_forwarder = output::forward(
+    build_child.take_stderr().unwrap(),
+    Source::Build,
+    Stream::Stderr,
+    move |line| {
+      stderr_lines_
+        .lock()
+        .unwrap()
+        .push(String::from_utf8_lossy(line).into_owned())
+    },
+  );
 
   let build_child_ = build_child.clone();
   std::thread::spawn(move || {
     let status = build_child_.wait().expect("failed to wait on build");
+    // make sure all the build output was read before looking for compile errors
+    let _ = stdout_reader.join();
+    let _ = stderr_forwarder.join();
+    events::emit(Event::BuildFinished {
+      success: status.success(),
+      code: status.code(),
+    });
 
     if status.success() {
       on_exit(status, ExitReason::NormalExit);
@@ -249,7 +497,8 @@ fn build_dev_app<F: FnOnce(ExitStatus, ExitReason) + Send + 'static>(
         .unwrap()
         .last()
         .map(|l| l.contains("could not compile"))
-        .unwrap_or_default();
+        .unwrap_or_default()
+        || !report.lock().unwrap().errors.is_empty();
       stderr_lines.lock().unwrap().clear();
 
       on_exit(
@@ -270,13 +519,15 @@ fn build_production_app(
   options: Options,
   available_targets: &mut Option<Vec<Target>>,
   config_features: Vec<String>,
-) -> crate::Result<()> {
-  let mut build_cmd = build_command(options, available_targets, config_features)?;
+  app_settings: &RustAppSettings,
+) -> crate::Result<BuildReport> {
+  let mut build_cmd = build_command(options, available_targets, config_features, app_settings)?;
   let runner = build_cmd.get_program().to_string_lossy().into_owned();
-  match build_cmd.piped() {
-    Ok(status) if status.success() => Ok(()),
-    Ok(_) => Err(anyhow::anyhow!("failed to build app")),
-    Err(e) if e.kind() == ErrorKind::NotFound => Err(anyhow::anyhow!(
+  build_cmd.stdout(Stdio::piped());
+  build_cmd.stderr(os_pipe::dup_stderr()?);
+  let mut build_child = match build_cmd.spawn() {
+    Ok(c) => c,
+    Err(e) if e.kind() == ErrorKind::NotFound => anyhow::bail!(
       "`{}` command not found.{}",
       runner,
       if runner == "cargo" {
@@ -284,23 +535,98 @@ fn build_production_app(
       } else {
         ""
       }
-    )),
-    Err(e) => Err(e.into()),
+    ),
+    Err(e) => return Err(e.into()),
+  };
+
+  let report = Arc::new(Mutex::new(BuildReport::default()));
+  let stdout_reader =
+    read_cargo_messages(build_child.stdout.take().unwrap(), report.clone(), |text| {
+      eprintln!("{}", text)
+    });
+  let status = build_child.wait()?;
+  let _ = stdout_reader.join();
+
+  let report = std::mem::take(&mut *report.lock().unwrap());
+  if status.success() {
+    Ok(report)
+  } else {
+    Err(report.build_error())
   }
 }
 
+/// Reads the cargo messages on a separate thread, calling `print` with the diagnostics to display.
+fn read_cargo_messages<R, F>(
+  stdout: R,
+  report: Arc<Mutex<BuildReport>>,
+  mut print: F,
+) -> std::thread::JoinHandle<()>
+where
+  R: Read + Send + 'static,
+  F: FnMut(&str) + Send + 'static,
+{
+  std::thread::spawn(move || {
+    let mut stdout = BufReader::new(stdout);
+    let mut buf = Vec::new();
+    loop {
+      buf.clear();
+      match tauri_utils::io::read_line(&mut stdout, &mut buf) {
+        Ok(0) | Err(_) => break,
+        Ok(_) => (),
+      }
+      report.lock().unwrap().handle_line(&buf, &mut print);
+    }
+  })
+}
+
 fn build_command(
   options: Options,
   available_targets: &mut Option<Vec<Target>>,
   config_features: Vec<String>,
+  app_settings: &RustAppSettings,
 ) -> crate::Result<Command> {
-  let runner = options.runner.unwrap_or_else(|| "cargo".into());
+  let runner = Runner::parse(options.runner.as_deref().unwrap_or("cargo"));
+  let target_env = app_settings.target_env(options.target.as_deref())?;
+  let reproducible_env = if options.reproducible {
+    let target = app_settings.target_triple(options.target.as_deref())?;
+    let target = if target.ends_with(".json") {
+      target_dir_name(&target)
+    } else {
+      &target
+    };
+    Some(reproducible_rustflags(app_settings, &target_env, target)?)
+  } else {
+    None
+  };
 
-  if let Some(target) = &options.target {
+  // custom target specification files are not managed by rustup
+  if let Some(target) = options.target.as_deref().filter(|t| !t.ends_with(".json")) {
     if available_targets.is_none() {
       *available_targets = fetch_available_targets();
     }
-    validate_target(available_targets, target)?;
+    validate_target(available_targets, target_dir_name(target), options.ci)?;
+  }
+
+  // other runners such as `cross` or `cargo zigbuild` provide their own toolchain
+  let default_runner = runner.program == "cargo" && runner.args.is_empty();
+  let mut linker_env = None;
+  if let Some(target) = options.target.as_deref() {
+    if default_runner && !cfg!(windows) && target.contains("windows") {
+      if target.contains("windows-gnu") {
+        linker_env = windows_gnu_linker(target, &target_env)?;
+        if let Some((_, linker)) = &linker_env {
+          info!(
+            "Using the `{}` linker to cross compile to {}",
+            linker, target
+          );
+        }
+      } else {
+        warn!(
+          "Cross compiling to {} requires the MSVC libraries, consider using `--runner cargo-xwin` or a `windows-gnu` target",
+          target
+        );
+      }
+    }
   }
 
   let mut args = Vec::new();
@@ -309,6 +635,8 @@ fn build_command(
   }
 
   let mut features = config_features;
+  // the allowlist features aren't written to Cargo.toml with `build > skipManifestRewrite`
+  features.extend(app_settings.manifest().cargo_features.iter().cloned());
   if let Some(f) = options.features {
     features.extend(f);
   }
@@ -317,7 +645,10 @@ fn build_command(
     args.push(features.join(","));
   }
 
-  if !options.debug {
+  if let Some(profile) = options.profile {
+    args.push("--profile".into());
+    args.push(profile);
+  } else if !options.debug {
     args.push("--release".into());
   }
 
@@ -326,13 +657,200 @@ fn build_command(
     args.push(target);
   }
 
-  let mut build_cmd = Command::new(&runner);
-  build_cmd.arg("build");
+  if let Some(example) = options.example {
+    args.push("--example".into());
+    args.push(example);
+  }
+
+  // the messages are parsed to locate the binary and report the compiler errors
+  if !args.iter().any(|a| a.starts_with("--message-format")) {
+    args.push("--message-format".into());
+    args.push(cargo_messages::MESSAGE_FORMAT.into());
+  }
+
+  let mut build_cmd = Command::new(&runner.program);
+  build_cmd.envs(linker_env);
+  build_cmd.envs(app_settings.cargo_env());
+  build_cmd.envs(&target_env);
+  if options.use_sccache {
+    ensure_sccache()?;
+    build_cmd.env("RUSTC_WRAPPER", SCCACHE);
+  }
+  if let Some((key, rustflags)) = reproducible_env {
+    build_cmd.env(key, rustflags);
+  }
+  build_cmd.envs(runner.env);
+  if runner.args.is_empty() {
+    build_cmd.arg("build");
+  } else {
+    build_cmd.args(runner.args);
+  }
   build_cmd.args(args);
 
   Ok(build_cmd)
 }
 
+/// The environment variable and the MinGW linker used to cross compile to a `windows-gnu` target,
+/// unless a linker is already configured for the target.
+///
+/// llvm-mingw (`<arch>-w64-mingw32-clang`) is preferred over the MinGW-w64 GCC toolchain.
+fn windows_gnu_linker(
+  target: &str,
+  target_env: &HashMap<String, String>,
+) -> crate::Result<Option<(String, String)>> {
+  let target = target_dir_name(target);
+  let var = format!(
+    "CARGO_TARGET_{}_LINKER",
+    target.to_uppercase().replace('-', "_")
+  );
+  if target_env.contains_key(&var)
+    || std::env::var_os(&var).is_some()
+    || CargoConfig::load(&tauri_dir())?
+      .target_linker(target)
+      .is_some()
+  {
+    return Ok(None);
+  }
+
+  let arch = target.split('-').next().unwrap();
+  for linker in [
+    format!("{}-w64-mingw32-clang", arch),
+    format!("{}-w64-mingw32-gcc", arch),
+  ] {
+    if Command::new(&linker)
+      .arg("--version")
+      .output()
+      .map_or(false, |o| o.status.success())
+    {
+      return Ok(Some((var, linker)));
+    }
+  }
+
+  anyhow::bail!(
+    "Cross compiling to {} requires a MinGW toolchain. Install llvm-mingw (https://github.com/mstorsjo/llvm-mingw) or MinGW-w64 and make sure `{}-w64-mingw32-clang` or `{}-w64-mingw32-gcc` is on the PATH.",
+    target,
+    arch,
+    arch
+  )
+}
+
+/// The rustflags environment variable adding the `--remap-path-prefix` flags of `--reproducible` to the build,
+/// so the paths of the build machine are not embedded in the binary (e.g. in panic messages).
+///
+/// `CARGO_ENCODED_RUSTFLAGS` and `RUSTFLAGS` override every other rustflags source, so the flags are appended to them when set.
+/// Otherwise they are passed with `CARGO_TARGET_<TRIPLE>_RUSTFLAGS`, which cargo merges with the `target.<triple>.rustflags`
+/// and `target.'cfg(..)'.rustflags` config arrays. Target rustflags replace `build.rustflags`, so those are included
+/// when the cargo config has no target rustflags.
+fn reproducible_rustflags(
+  app_settings: &RustAppSettings,
+  target_env: &HashMap<String, String>,
+  target: &str,
+) -> crate::Result<(String, String)> {
+  let env_var = |key: &str| {
+    target_env
+      .get(key)
+      .cloned()
+      .or_else(|| std::env::var_os(key).map(|v| v.to_string_lossy().into_owned()))
+  };
+  let encoded = |flags: Vec<String>| ("CARGO_ENCODED_RUSTFLAGS".to_string(), flags.join("\x1f"));
+
+  let mut remap = Vec::new();
+  let cargo_home = std::env::var_os("CARGO_HOME")
+    .map(PathBuf::from)
+    .or_else(|| {
+      std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })
+        .map(|home| PathBuf::from(home).join(".cargo"))
+    });
+  if let Some(cargo_home) = cargo_home {
+    remap.push(format!(
+      "--remap-path-prefix={}=/cargo",
+      cargo_home.display()
+    ));
+  }
+  remap.push(format!(
+    "--remap-path-prefix={}=.",
+    super::get_workspace_dir()?.display()
+  ));
+
+  if let Some(flags) = env_var("CARGO_ENCODED_RUSTFLAGS") {
+    let mut flags: Vec<String> = flags
+      .split('\x1f')
+      .filter(|f| !f.is_empty())
+      .map(Into::into)
+      .collect();
+    flags.extend(remap);
+    return Ok(encoded(flags));
+  }
+  if let Some(flags) = env_var("RUSTFLAGS") {
+    let mut flags: Vec<String> = flags.split_whitespace().map(Into::into).collect();
+    flags.extend(remap);
+    return Ok(encoded(flags));
+  }
+
+  let cargo_config = &app_settings.cargo_config;
+  let has_target_rustflags = cargo_config.has_target_rustflags(target);
+  // cargo splits the config environment variables on whitespace
+  if remap.iter().any(|flag| flag.contains(char::is_whitespace)) {
+    if has_target_rustflags {
+      warn!("The workspace or cargo home path contains whitespace, so `--reproducible` passes its rustflags with CARGO_ENCODED_RUSTFLAGS and the `target.{}.rustflags` cargo config is not applied", target);
+    }
+    let mut flags = cargo_config.build().rustflags();
+    flags.extend(remap);
+    return Ok(encoded(flags));
+  }
+
+  let key = format!(
+    "CARGO_TARGET_{}_RUSTFLAGS",
+    target.to_uppercase().replace(|c| c == '-' || c == '.', "_")
+  );
+  let mut flags = Vec::new();
+  match env_var(&key) {
+    Some(existing) => flags.extend(existing.split_whitespace().map(String::from)),
+    None if !has_target_rustflags => flags.extend(cargo_config.build().rustflags()),
+    None => {}
+  }
+  flags.extend(remap);
+  Ok((key, flags.join(" ")))
+}
+
+/// Fails with an install hint when sccache is not available on the `PATH`.
+fn ensure_sccache() -> crate::Result<()> {
+  match Command::new(SCCACHE).arg("--version").output_ok() {
+    Ok(_) => Ok(()),
+    Err(e) => Err(anyhow::anyhow!(
+      "`--use-sccache` requires sccache to be installed ({}). Install it with `cargo install sccache` or see https://github.com/mozilla/sccache#installation",
+      e
+    )),
+  }
+}
+
+/// The command used to build the app, parsed from the runner option.
+///
+/// A single program (e.g. `cross`) is invoked with the `build` subcommand,
+/// while a full command (e.g. `cargo zigbuild`) is used as is.
+/// Leading `KEY=VALUE` words are set as environment variables.
+pub(super) struct Runner {
+  pub(super) env: Vec<(String, String)>,
+  program: String,
+  pub(super) args: Vec<String>,
+}
+
+impl Runner {
+  pub(super) fn parse(runner: &str) -> Self {
+    let mut env = Vec::new();
+    let mut words = runner.split_whitespace().peekable();
+    while let Some((key, value)) = words.peek().and_then(|w| w.split_once('=')) {
+      env.push((key.to_string(), value.to_string()));
+      words.next();
+    }
+    Self {
+      env,
+      program: words.next().unwrap_or("cargo").to_string(),
+      args: words.map(Into::into).collect(),
+    }
+  }
+}
+
 fn fetch_available_targets() -> Option<Vec<Target>> {
   if let Ok(output) = Command::new("rustup").args(["target", "list"]).output() {
     let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
@@ -353,24 +871,38 @@ fn fetch_available_targets() -> Option<Vec<Target>> {
   }
 }
 
-fn validate_target(available_targets: &Option<Vec<Target>>, target: &str) -> crate::Result<()> {
+fn validate_target(
+  available_targets: &mut Option<Vec<Target>>,
+  target: &str,
+  ci: bool,
+) -> crate::Result<()> {
   if let Some(available_targets) = available_targets {
-    if let Some(target) = available_targets.iter().find(|t| t.name == target) {
-      if !target.installed {
-        anyhow::bail!(
+    if let Some(index) = available_targets.iter().position(|t| t.name == target) {
+      if !available_targets[index].installed {
+        if install_target(target, ci)? {
+          available_targets[index].installed = true;
+        } else {
+          anyhow::bail!(
             "Target {target} is not installed (installed targets: {installed}). Please run `rustup target add {target}`.",
-            target = target.name,
+            target = target,
             installed = available_targets.iter().filter(|t| t.installed).map(|t| t.name.as_str()).collect::<Vec<&str>>().join(", ")
           );
+        }
       }
-    }
-    if !available_targets.iter().any(|t| t.name == target) {
+    } else {
       anyhow::bail!("Target {target} does not exist. Please run `rustup target list` to see the available targets.", target = target);
     }
   }
   Ok(())
 }
 
+/// Installs a missing target with rustup, asking for confirmation unless running in CI mode.
+///
+/// Returns `false` if the user declined or could not be prompted.
+fn install_target(target: &str, ci: bool) -> crate::Result<bool> {
+  rust_targets_fix(&[target]).confirm_and_run(&format!("Target {} is not installed.", target), ci)
+}
+
 fn rename_app(bin_path: &Path, product_name: Option<&str>) -> crate::Result<PathBuf> {
   if let Some(product_name) = product_name {
     #[cfg(target_os = "linux")]
@@ -473,3 +1005,47 @@ mod terminal {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{app_binary, failed_status};
+
+  #[test]
+  fn failed_status_exits_with_an_error() {
+    let status = failed_status();
+    assert!(!status.success());
+    assert_eq!(status.code(), Some(1));
+  }
+
+  #[test]
+  fn returns_the_renamed_app_binary() {
+    let dir = tempfile::tempdir().unwrap();
+    let bin_path = dir
+      .path()
+      .join("app")
+      .with_extension(std::env::consts::EXE_EXTENSION);
+    std::fs::write(&bin_path, "").unwrap();
+
+    let app_path = app_binary(bin_path.clone(), false, Some("MyProduct")).unwrap();
+
+    assert_ne!(app_path, bin_path);
+    assert!(app_path.exists());
+    assert!(!bin_path.exists());
+  }
+
+  #[test]
+  fn returns_the_example_app_binary() {
+    let dir = tempfile::tempdir().unwrap();
+    let examples_dir = dir.path().join("examples");
+    std::fs::create_dir(&examples_dir).unwrap();
+    let bin_path = examples_dir
+      .join("demo")
+      .with_extension(std::env::consts::EXE_EXTENSION);
+    std::fs::write(&bin_path, "").unwrap();
+
+    let app_path = app_binary(bin_path, true, Some("Demo App")).unwrap();
+
+    assert_eq!(app_path.parent(), Some(dir.path()));
+    assert!(app_path.exists());
+  }
+}
@@ -8,20 +8,27 @@ use crate::helpers::{
 };
 
 use anyhow::Context;
+use log::{info, warn};
 use toml_edit::{Array, Document, InlineTable, Item, Table, Value};
 
 use std::{
   collections::{HashMap, HashSet},
-  fs::File,
-  io::{Read, Write},
+  fs::{read_to_string, write, File},
+  io::Read,
   iter::FromIterator,
-  path::Path,
+  path::{Path, PathBuf},
 };
 
 #[derive(Default)]
 pub struct Manifest {
   pub inner: Document,
   pub tauri_features: HashSet<String>,
+  /// The name the `tauri` crate is declared with in Cargo.toml when it is renamed,
+  /// e.g. `tauri-nightly = { git = "...", package = "tauri" }`.
+  pub tauri_dependency: Option<String>,
+  /// The `tauri` and `tauri-build` features of the allowlist passed to cargo with `--features`
+  /// when Cargo.toml isn't rewritten, see `build > skipManifestRewrite`.
+  pub cargo_features: Vec<String>,
 }
 
 impl Manifest {
@@ -50,6 +57,14 @@ impl Manifest {
     f
   }
 
+  /// The name the `tauri` crate is declared with in the dependencies.
+  pub fn tauri_dependency_name(&self) -> &str {
+    self.tauri_dependency.as_deref().unwrap_or("tauri")
+  }
+
+  /// The features enabled by the `tauri` dependency and the given app features.
+  ///
+  /// The features of a renamed `tauri` dependency are reported as `tauri/<feature>`.
   pub fn all_enabled_features(&self, enabled_features: &[String]) -> Vec<String> {
     let mut all_enabled_features: Vec<String> = self
       .tauri_features
@@ -57,9 +72,21 @@ impl Manifest {
       .map(|f| format!("tauri/{}", f))
       .collect();
 
+    let tauri = self.tauri_dependency_name();
     let manifest_features = self.features();
     for f in enabled_features {
-      all_enabled_features.extend(get_enabled_features(&manifest_features, f));
+      all_enabled_features.extend(get_enabled_features(&manifest_features, f).into_iter().map(
+        |feature| {
+          // `tauri-nightly/devtools` and the weak `tauri?/devtools` syntax
+          match feature
+            .strip_prefix(tauri)
+            .and_then(|f| f.strip_prefix('/').or_else(|| f.strip_prefix("?/")))
+          {
+            Some(tauri_feature) => format!("tauri/{}", tauri_feature),
+            None => feature,
+          }
+        },
+      ));
     }
 
     all_enabled_features
@@ -96,6 +123,36 @@ pub fn read_manifest(manifest_path: &Path) -> crate::Result<Document> {
   Ok(manifest)
 }
 
+/// Sets the version requirement of a dependency of the `table` (e.g. `dependencies`), keeping its other keys.
+///
+/// Returns the previous requirement, or `None` if the dependency has no version (e.g. a path dependency).
+pub fn set_dependency_version(
+  manifest: &mut Document,
+  table: &str,
+  name: &str,
+  version: &str,
+) -> Option<String> {
+  let dependency = manifest.as_table_mut().get_mut(table)?.get_mut(name)?;
+  let current = match dependency {
+    Item::Value(Value::String(current)) => current,
+    Item::Value(Value::InlineTable(dependency)) => match dependency.get_mut("version") {
+      Some(Value::String(current)) => current,
+      _ => return None,
+    },
+    Item::Table(dependency) => match dependency.get_mut("version") {
+      Some(Item::Value(Value::String(current))) => current,
+      _ => return None,
+    },
+    _ => return None,
+  };
+  let previous = current.value().clone();
+  // keeps the formatting around the value
+  let decor = current.decor().clone();
+  *current = toml_edit::Formatted::new(version.to_string());
+  *current.decor_mut() = decor;
+  Some(previous)
+}
+
 fn toml_array(features: &HashSet<String>) -> Array {
   let mut f = Array::default();
   let mut features: Vec<String> = features.iter().map(|f| f.to_string()).collect();
@@ -106,148 +163,758 @@ fn toml_array(features: &HashSet<String>) -> Array {
   f
 }
 
+/// Adds the features of the dependency that are not managed by the CLI to `features`.
+fn extend_user_features(
+  manifest_features: Option<&Array>,
+  all_features: &[&str],
+  features: &mut HashSet<String>,
+) {
+  for feature in manifest_features.into_iter().flatten() {
+    if let Some(feature) = feature.as_str() {
+      if !all_features.contains(&feature) {
+        features.insert(feature.to_string());
+      }
+    }
+  }
+}
+
+/// Removes the features that aren't in `features` from the array and appends the missing ones,
+/// keeping the formatting of the array (e.g. one feature per line).
+fn sync_features(manifest_features: &mut Array, features: &HashSet<String>) {
+  let mut i = 0;
+  while i < manifest_features.len() {
+    match manifest_features.get(i).and_then(Value::as_str) {
+      Some(feature) if !features.contains(feature) => {
+        let decor = manifest_features.get(i).unwrap().decor().clone();
+        manifest_features.remove(i);
+        // the next feature takes the place of the first one
+        if i == 0 {
+          if let Some(first) = manifest_features.get_mut(0) {
+            *first.decor_mut() = decor;
+          }
+        }
+      }
+      _ => i += 1,
+    }
+  }
+
+  let mut missing: Vec<&String> = features
+    .iter()
+    .filter(|feature| {
+      !manifest_features
+        .iter()
+        .any(|f| f.as_str() == Some(feature.as_str()))
+    })
+    .collect();
+  missing.sort();
+  for feature in missing {
+    // multiline arrays keep one feature per line
+    let decor = manifest_features
+      .iter()
+      .last()
+      .map(|f| f.decor().clone())
+      .filter(|decor| decor.prefix().map_or(false, |p| p.contains('\n')));
+    manifest_features.push(feature.as_str());
+    if let Some(decor) = decor {
+      let last = manifest_features.len() - 1;
+      *manifest_features.get_mut(last).unwrap().decor_mut() = decor;
+    }
+  }
+}
+
 fn write_features(
   dependencies: &mut Table,
   dependency_name: &str,
   all_features: Vec<&str>,
   features: &mut HashSet<String>,
-) -> crate::Result<bool> {
-  let item = dependencies.entry(dependency_name).or_insert(Item::None);
-
-  if let Some(dep) = item.as_table_mut() {
-    let manifest_features = dep.entry("features").or_insert(Item::None);
-    if let Item::Value(Value::Array(f)) = &manifest_features {
-      for feat in f.iter() {
-        if let Value::String(feature) = feat {
-          if !all_features.contains(&feature.value().as_str()) {
-            features.insert(feature.value().to_string());
-          }
+) -> crate::Result<()> {
+  let dependency = match dependencies.get_mut(dependency_name) {
+    Some(dependency) => dependency,
+    None => return Ok(()),
+  };
+
+  match dependency {
+    Item::Table(dep) => {
+      extend_user_features(
+        dep.get("features").and_then(Item::as_array),
+        &all_features,
+        features,
+      );
+      match dep.get_mut("features").and_then(Item::as_array_mut) {
+        Some(manifest_features) => sync_features(manifest_features, features),
+        None if !features.is_empty() => {
+          dep.insert("features", toml_edit::value(toml_array(features)));
         }
+        None => (),
       }
     }
-    if let Some(features_array) = manifest_features.as_array_mut() {
-      // add features that aren't in the manifest
-      for feature in features.iter() {
-        if !features_array.iter().any(|f| f.as_str() == Some(feature)) {
-          features_array.insert(0, feature.as_str());
+    Item::Value(Value::InlineTable(dep)) => {
+      extend_user_features(
+        dep.get("features").and_then(Value::as_array),
+        &all_features,
+        features,
+      );
+      match dep.get_mut("features").and_then(Value::as_array_mut) {
+        Some(manifest_features) => sync_features(manifest_features, features),
+        None if !features.is_empty() => {
+          dep.insert("features", Value::Array(toml_array(features)));
+          dep.fmt();
         }
+        None => (),
       }
-
-      // remove features that shouldn't be in the manifest anymore
-      let mut i = 0;
-      while i < features_array.len() {
-        if let Some(f) = features_array.get(i).and_then(|f| f.as_str()) {
-          if !features.contains(f) {
-            features_array.remove(i);
-          }
-        }
-        i += 1;
+    }
+    Item::Value(Value::String(version)) => {
+      if !features.is_empty() {
+        let mut def = InlineTable::default();
+        def.insert("version", version.value().as_str().into());
+        def.insert("features", Value::Array(toml_array(features)));
+        def.fmt();
+        // keeps the comment after the version requirement
+        *def.decor_mut() = version.decor().clone();
+        *dependency = Item::Value(Value::InlineTable(def));
       }
-    } else {
-      *manifest_features = Item::Value(Value::Array(toml_array(features)));
-    }
-    Ok(true)
-  } else if let Some(dep) = item.as_value_mut() {
-    match dep {
-      Value::InlineTable(table) => {
-        let manifest_features = table.get_or_insert("features", Value::Array(Default::default()));
-        if let Value::Array(f) = &manifest_features {
-          for feat in f.iter() {
-            if let Value::String(feature) = feat {
-              if !all_features.contains(&feature.value().as_str()) {
-                features.insert(feature.value().to_string());
-              }
-            }
-          }
+    }
+    _ => {
+      return Err(anyhow::anyhow!(
+        "Unsupported {} dependency format on Cargo.toml",
+        dependency_name
+      ))
+    }
+  }
+
+  Ok(())
+}
+
+/// The `[workspace.dependencies]` entry of a dependency,
+/// read from the manifest of the closest workspace root containing `manifest_dir`.
+fn workspace_dependency(manifest_dir: &Path, dependency_name: &str) -> Option<Item> {
+  manifest_dir
+    .ancestors()
+    .map(|dir| dir.join("Cargo.toml"))
+    .filter(|path| path.exists())
+    .filter_map(|path| read_manifest(&path).ok())
+    .find(|manifest| manifest.as_table().contains_key("workspace"))?
+    .get("workspace")?
+    .get("dependencies")?
+    .get(dependency_name)
+    .cloned()
+}
+
+/// Adds the features of a dependency inherited with `workspace = true` to `features`,
+/// since Cargo enables the features of the `[workspace.dependencies]` entry in addition to the ones of the app manifest.
+///
+/// The features managed by the CLI can't be removed from the workspace manifest, so they are only reported.
+fn inherit_workspace_features(
+  dependencies: &Table,
+  dependency_name: &str,
+  all_features: &[&str],
+  features: &mut HashSet<String>,
+) {
+  let inherited = dependencies
+    .get(dependency_name)
+    .and_then(|dependency| dependency.get("workspace"))
+    .and_then(Item::as_bool)
+    .unwrap_or_default();
+  if !inherited {
+    return;
+  }
+  let workspace_dependency = match workspace_dependency(&tauri_dir(), dependency_name) {
+    Some(dependency) => dependency,
+    None => return,
+  };
+  let workspace_features = workspace_dependency
+    .get("features")
+    .and_then(Item::as_array)
+    .into_iter()
+    .flatten()
+    .filter_map(Value::as_str);
+  for feature in workspace_features {
+    if all_features.contains(&feature) && !features.contains(feature) {
+      warn!(
+        "The `{}` feature of the `{}` workspace dependency isn't required by the configuration, remove it from the `[workspace.dependencies]` of the workspace Cargo.toml.",
+        feature, dependency_name
+      );
+    }
+    features.insert(feature.to_string());
+  }
+}
+
+/// The `tauri-build` features required by the configuration.
+fn config_tauri_build_features(config: &Config) -> HashSet<String> {
+  let mut features = HashSet::new();
+  if let PatternKind::Isolation { .. } = config.tauri.pattern {
+    features.insert("isolation".to_string());
+  }
+  features
+}
+
+/// The `tauri` features required by the configuration.
+fn config_tauri_features(config: &Config) -> HashSet<String> {
+  HashSet::from_iter(config.tauri.features().into_iter().map(|f| f.to_string()))
+}
+
+/// The dependency tables of the given kind (`dependencies` or `build-dependencies`),
+/// including the platform specific ones, e.g. `[target.'cfg(windows)'.dependencies]`.
+fn dependency_tables_mut<'a>(manifest: &'a mut Document, kind: &str) -> Vec<&'a mut Table> {
+  let mut tables = Vec::new();
+  for (key, item) in manifest.as_table_mut().iter_mut() {
+    if key.get() == kind {
+      tables.extend(item.as_table_mut());
+    } else if key.get() == "target" {
+      if let Some(targets) = item.as_table_mut() {
+        for (_, target) in targets.iter_mut() {
+          tables.extend(target.get_mut(kind).and_then(Item::as_table_mut));
         }
-        *manifest_features = Value::Array(toml_array(features));
-      }
-      Value::String(version) => {
-        let mut def = InlineTable::default();
-        def.get_or_insert(
-          "version",
-          version.to_string().replace('\"', "").replace(' ', ""),
-        );
-        def.get_or_insert("features", Value::Array(toml_array(features)));
-        *dep = Value::InlineTable(def);
       }
-      _ => {
-        return Err(anyhow::anyhow!(
-          "Unsupported {} dependency format on Cargo.toml",
-          dependency_name
-        ))
+    }
+  }
+  tables
+}
+
+/// The name of the dependency on the `package` crate in the table: the crate name itself,
+/// or the key of a renamed dependency (`tauri-nightly = { git = "...", package = "tauri" }`).
+///
+/// Git, path and registry dependencies are all declared with the same keys.
+fn dependency_name(dependencies: &Table, package: &str) -> Option<String> {
+  dependencies
+    .iter()
+    .find(
+      |(name, dependency)| match dependency.get("package").and_then(Item::as_str) {
+        Some(dependency_package) => dependency_package == package,
+        None => *name == package,
+      },
+    )
+    .map(|(name, _)| name.to_string())
+}
+
+/// Warns about the features set on the `[patch]` entries of the `package` crate that are managed by the CLI.
+///
+/// Cargo only uses the source of the patch (e.g. a git branch or a local checkout of Tauri),
+/// the features must be enabled on the dependency itself.
+fn warn_patch_features(manifest: &Document, package: &str, all_features: &[&str]) {
+  let sources = manifest
+    .get("patch")
+    .and_then(Item::as_table)
+    .into_iter()
+    .flat_map(|patch| patch.iter());
+  for (source, patches) in sources {
+    let patch = match patches.as_table().and_then(|p| {
+      dependency_name(p, package).and_then(|name| p.get(&name).and_then(|d| d.get("features")))
+    }) {
+      Some(features) => features,
+      None => continue,
+    };
+    let managed_features: Vec<&str> = patch
+      .as_array()
+      .into_iter()
+      .flatten()
+      .filter_map(Value::as_str)
+      .filter(|f| all_features.contains(f))
+      .collect();
+    if !managed_features.is_empty() {
+      warn!(
+        "Cargo ignores the features of `[patch.{}]` entries, remove `{}` from the `{}` patch: the CLI enables the features required by the configuration on the dependency itself.",
+        source,
+        managed_features.join(", "),
+        package
+      );
+    }
+  }
+}
+
+/// The features of the `tauri` dependency and the names the `tauri` and `tauri-build` crates are declared with.
+struct InjectedFeatures {
+  tauri_features: HashSet<String>,
+  tauri_dependency: Option<String>,
+  tauri_build_dependency: Option<String>,
+}
+
+/// Injects the `tauri-build` and `tauri` features required by the configuration in the manifest,
+/// in every dependency table declaring the crates, renamed or not.
+///
+/// Returns the `tauri` features, including the ones enabled by the workspace manifest
+/// when the dependency is inherited with `workspace = true`.
+fn inject_features(manifest: &mut Document, config: &Config) -> crate::Result<InjectedFeatures> {
+  let config_tauri_build_features = config_tauri_build_features(config);
+  let mut tauri_build_dependency = None;
+  for build_dependencies in dependency_tables_mut(manifest, "build-dependencies") {
+    if let Some(name) = dependency_name(build_dependencies, "tauri-build") {
+      let mut features = config_tauri_build_features.clone();
+      write_features(build_dependencies, &name, vec!["isolation"], &mut features)?;
+      inherit_workspace_features(build_dependencies, &name, &["isolation"], &mut features);
+      tauri_build_dependency.get_or_insert(name);
+    }
+  }
+
+  let config_tauri_features = config_tauri_features(config);
+  let mut tauri_features = config_tauri_features.clone();
+  let mut tauri_dependency = None;
+  let cli_managed_tauri_features = crate::helpers::config::TauriConfig::all_features();
+  for dependencies in dependency_tables_mut(manifest, "dependencies") {
+    if let Some(name) = dependency_name(dependencies, "tauri") {
+      let mut features = config_tauri_features.clone();
+      write_features(
+        dependencies,
+        &name,
+        cli_managed_tauri_features.clone(),
+        &mut features,
+      )?;
+      inherit_workspace_features(
+        dependencies,
+        &name,
+        &cli_managed_tauri_features,
+        &mut features,
+      );
+      tauri_features.extend(features);
+      tauri_dependency.get_or_insert(name);
+    }
+  }
+  warn_patch_features(manifest, "tauri", &cli_managed_tauri_features);
+
+  Ok(InjectedFeatures {
+    tauri_features,
+    tauri_dependency,
+    tauri_build_dependency,
+  })
+}
+
+/// The app Cargo.toml with the features required by the configuration injected.
+struct InjectedManifest {
+  path: PathBuf,
+  original: String,
+  manifest: Document,
+  features: InjectedFeatures,
+}
+
+fn inject_manifest_features(config: &Config) -> crate::Result<InjectedManifest> {
+  let path = tauri_dir().join("Cargo.toml");
+  let original =
+    read_to_string(&path).with_context(|| format!("failed to read `{:?}` file", path))?;
+  let mut manifest: Document = original
+    .parse()
+    .with_context(|| "failed to parse Cargo.toml")?;
+  let features = inject_features(&mut manifest, config)?;
+  Ok(InjectedManifest {
+    path,
+    original,
+    manifest,
+    features,
+  })
+}
+
+/// Rewrites the features of the `tauri` and `tauri-build` dependencies of the app Cargo.toml
+/// to match the configuration, keeping the formatting and the comments of the manifest.
+///
+/// The manifest is only written when its content changes, and never with `build > skipManifestRewrite`,
+/// in which case the features are returned in [`Manifest::cargo_features`]. Returns whether the manifest was written.
+///
+/// With `frozen`, an error showing the required changes is returned instead of writing the manifest.
+pub fn rewrite_manifest(config: &Config, frozen: bool) -> crate::Result<(Manifest, bool)> {
+  let InjectedManifest {
+    path,
+    original,
+    manifest,
+    features,
+  } = inject_manifest_features(config)?;
+
+  let mut cargo_features = Vec::new();
+  let written = if config.build.skip_manifest_rewrite {
+    cargo_features = skipped_rewrite_features(config, &features);
+    false
+  } else {
+    let rewritten = manifest.to_string();
+    let written = rewritten != original;
+    if written {
+      if frozen {
+        return Err(frozen_manifest_error(&unified_diff(
+          "Cargo.toml",
+          &original,
+          &rewritten,
+        )));
       }
+      write(&path, rewritten).with_context(|| "failed to rewrite Cargo.toml")?;
     }
-    Ok(true)
+    written
+  };
+
+  Ok((
+    Manifest {
+      inner: manifest,
+      tauri_features: features.tauri_features,
+      tauri_dependency: features.tauri_dependency,
+      cargo_features,
+    },
+    written,
+  ))
+}
+
+/// The changes [`rewrite_manifest`] would make to the app Cargo.toml, as a unified diff.
+///
+/// Returns `None` when the manifest is up to date or is not rewritten because of `build > skipManifestRewrite`.
+pub fn manifest_diff(config: &Config) -> crate::Result<Option<String>> {
+  if config.build.skip_manifest_rewrite {
+    return Ok(None);
+  }
+  let injected = inject_manifest_features(config)?;
+  let rewritten = injected.manifest.to_string();
+  Ok(if rewritten == injected.original {
+    None
   } else {
-    Ok(false)
+    Some(unified_diff("Cargo.toml", &injected.original, &rewritten))
+  })
+}
+
+/// Handles the `--dry-run` and `--frozen-manifest` flags of `tauri dev` and `tauri build`.
+///
+/// With `dry_run`, prints the changes the manifest rewrite would make to Cargo.toml.
+/// With `frozen`, fails if Cargo.toml does not match the configuration.
+pub fn check_manifest(config: &Config, dry_run: bool, frozen: bool) -> crate::Result<()> {
+  if !(dry_run || frozen) {
+    return Ok(());
   }
+  if config.build.external_interface.is_some() {
+    if dry_run {
+      info!("Cargo.toml is not rewritten when the app is built with `build > externalInterface`.");
+    }
+    return Ok(());
+  }
+
+  match manifest_diff(config)? {
+    Some(diff) if frozen => return Err(frozen_manifest_error(&diff)),
+    Some(diff) => print!("{}", diff),
+    None if dry_run => info!("Cargo.toml is up to date, no changes needed."),
+    None => (),
+  }
+  if dry_run && config.build.skip_manifest_rewrite {
+    let features = skipped_rewrite_features(config, &inject_manifest_features(config)?.features);
+    if !features.is_empty() {
+      info!("Features passed to cargo: {}", features.join(", "));
+    }
+  }
+
+  Ok(())
+}
+
+fn frozen_manifest_error(diff: &str) -> anyhow::Error {
+  anyhow::anyhow!(
+    "Cargo.toml does not match the configuration and `--frozen-manifest` is set, the following changes are needed:\n{}",
+    diff
+  )
 }
 
-pub fn rewrite_manifest(config: &Config) -> crate::Result<Manifest> {
-  let manifest_path = tauri_dir().join("Cargo.toml");
-  let mut manifest = read_manifest(&manifest_path)?;
+/// The `tauri` and `tauri-build` features passed to cargo with `build > skipManifestRewrite`,
+/// prefixed with the names the crates are declared with.
+fn skipped_rewrite_features(config: &Config, injected: &InjectedFeatures) -> Vec<String> {
+  let tauri = injected.tauri_dependency.as_deref().unwrap_or("tauri");
+  let tauri_build = injected
+    .tauri_build_dependency
+    .as_deref()
+    .unwrap_or("tauri-build");
+  let mut features: Vec<String> = config_tauri_features(config)
+    .into_iter()
+    .map(|f| format!("{}/{}", tauri, f))
+    .chain(
+      config_tauri_build_features(config)
+        .into_iter()
+        .map(|f| format!("{}/{}", tauri_build, f)),
+    )
+    .collect();
+  features.sort();
+  features
+}
 
-  let mut tauri_build_features = HashSet::new();
-  if let PatternKind::Isolation { .. } = config.tauri.pattern {
-    tauri_build_features.insert("isolation".to_string());
-  }
-  let resp = write_features(
-    manifest
-      .as_table_mut()
-      .entry("build-dependencies")
-      .or_insert(Item::Table(Table::new()))
-      .as_table_mut()
-      .expect("manifest build-dependencies isn't a table"),
-    "tauri-build",
-    vec!["isolation"],
-    &mut tauri_build_features,
-  )?;
-
-  let mut tauri_features =
-    HashSet::from_iter(config.tauri.features().into_iter().map(|f| f.to_string()));
-  let cli_managed_tauri_features = crate::helpers::config::TauriConfig::all_features();
-  let res = match write_features(
-    manifest
-      .as_table_mut()
-      .entry("dependencies")
-      .or_insert(Item::Table(Table::new()))
-      .as_table_mut()
-      .expect("manifest dependencies isn't a table"),
-    "tauri",
-    cli_managed_tauri_features,
-    &mut tauri_features,
-  ) {
-    Err(e) => Err(e),
-    Ok(t) if !resp => Ok(t),
-    _ => Ok(true),
+enum DiffLine<'a> {
+  Context(&'a str),
+  Removed(&'a str),
+  Added(&'a str),
+}
+
+/// A line based unified diff of `old` and `new` with three lines of context around the changes.
+fn unified_diff(name: &str, old: &str, new: &str) -> String {
+  const CONTEXT: usize = 3;
+
+  let old_lines: Vec<&str> = old.lines().collect();
+  let new_lines: Vec<&str> = new.lines().collect();
+
+  // length of the longest common subsequence of the remaining lines
+  let mut lcs = vec![vec![0usize; new_lines.len() + 1]; old_lines.len() + 1];
+  for i in (0..old_lines.len()).rev() {
+    for j in (0..new_lines.len()).rev() {
+      lcs[i][j] = if old_lines[i] == new_lines[j] {
+        lcs[i + 1][j + 1] + 1
+      } else {
+        lcs[i + 1][j].max(lcs[i][j + 1])
+      };
+    }
+  }
+
+  // each line with the number of old and new lines preceding it
+  let mut lines = Vec::new();
+  let (mut i, mut j) = (0, 0);
+  while i < old_lines.len() || j < new_lines.len() {
+    if i < old_lines.len() && j < new_lines.len() && old_lines[i] == new_lines[j] {
+      lines.push((DiffLine::Context(old_lines[i]), i, j));
+      i += 1;
+      j += 1;
+    } else if j == new_lines.len() || (i < old_lines.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+      lines.push((DiffLine::Removed(old_lines[i]), i, j));
+      i += 1;
+    } else {
+      lines.push((DiffLine::Added(new_lines[j]), i, j));
+      j += 1;
+    }
+  }
+
+  let changes: Vec<usize> = lines
+    .iter()
+    .enumerate()
+    .filter(|(_, (line, _, _))| !matches!(line, DiffLine::Context(_)))
+    .map(|(index, _)| index)
+    .collect();
+
+  let mut diff = format!("--- a/{}\n+++ b/{}\n", name, name);
+  let mut change = 0;
+  while change < changes.len() {
+    let start = changes[change].saturating_sub(CONTEXT);
+    let mut end = changes[change];
+    while change + 1 < changes.len() && changes[change + 1] - end <= 2 * CONTEXT + 1 {
+      change += 1;
+      end = changes[change];
+    }
+    change += 1;
+    let end = (end + CONTEXT).min(lines.len() - 1);
+    let hunk = &lines[start..=end];
+
+    let old_len = hunk
+      .iter()
+      .filter(|(line, _, _)| !matches!(line, DiffLine::Added(_)))
+      .count();
+    let new_len = hunk
+      .iter()
+      .filter(|(line, _, _)| !matches!(line, DiffLine::Removed(_)))
+      .count();
+    let (_, old_start, new_start) = hunk[0];
+    diff.push_str(&format!(
+      "@@ -{},{} +{},{} @@\n",
+      if old_len == 0 {
+        old_start
+      } else {
+        old_start + 1
+      },
+      old_len,
+      if new_len == 0 {
+        new_start
+      } else {
+        new_start + 1
+      },
+      new_len
+    ));
+    for (line, _, _) in hunk {
+      let (prefix, line) = match line {
+        DiffLine::Context(line) => (' ', line),
+        DiffLine::Removed(line) => ('-', line),
+        DiffLine::Added(line) => ('+', line),
+      };
+      diff.push(prefix);
+      diff.push_str(line);
+      diff.push('\n');
+    }
+  }
+
+  diff
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    dependency_name, dependency_tables_mut, unified_diff, workspace_dependency, write_features,
+    Manifest,
   };
+  use std::collections::HashSet;
+  use toml_edit::Document;
 
-  match res {
-    Ok(true) => {
-      let mut manifest_file =
-        File::create(&manifest_path).with_context(|| "failed to open Cargo.toml for rewrite")?;
-      manifest_file.write_all(
-        manifest
-          .to_string()
-          // apply some formatting fixes
-          .replace(r#"" ,features =["#, r#"", features = ["#)
-          .replace(r#"" , features"#, r#"", features"#)
-          .replace("]}", "] }")
-          .replace("={", "= {")
-          .replace("=[", "= [")
-          .as_bytes(),
-      )?;
-      manifest_file.flush()?;
-      Ok(Manifest {
-        inner: manifest,
-        tauri_features,
+  fn rewrite(manifest: &str, features: &[&str]) -> String {
+    let mut manifest: Document = manifest.parse().unwrap();
+    let mut features: HashSet<String> = features.iter().map(ToString::to_string).collect();
+    write_features(
+      manifest["dependencies"].as_table_mut().unwrap(),
+      "tauri",
+      vec!["dialog-open", "shell-open"],
+      &mut features,
+    )
+    .unwrap();
+    manifest.to_string()
+  }
+
+  #[test]
+  fn rewrites_features_keeping_formatting() {
+    let manifest = r#"[dependencies]
+# the Tauri runtime
+tauri = { version = "1.2", features = ["shell-open", "devtools"] } # pinned
+serde = "1"
+"#;
+    assert_eq!(rewrite(manifest, &["shell-open"]), manifest);
+    assert_eq!(
+      rewrite(manifest, &["dialog-open"]),
+      r#"[dependencies]
+# the Tauri runtime
+tauri = { version = "1.2", features = ["devtools", "dialog-open"] } # pinned
+serde = "1"
+"#
+    );
+
+    let manifest = r#"[dependencies.tauri]
+version = "1.2"
+features = [
+  "shell-open",
+]
+"#;
+    assert_eq!(
+      rewrite(manifest, &["shell-open", "dialog-open"]),
+      r#"[dependencies.tauri]
+version = "1.2"
+features = [
+  "shell-open",
+  "dialog-open",
+]
+"#
+    );
+
+    assert_eq!(
+      rewrite("[dependencies]\ntauri = \"1.2\" # pinned\n", &[]),
+      "[dependencies]\ntauri = \"1.2\" # pinned\n"
+    );
+    assert_eq!(
+      rewrite(
+        "[dependencies]\ntauri = \"1.2\" # pinned\n",
+        &["shell-open"]
+      ),
+      "[dependencies]\ntauri = { version = \"1.2\", features = [\"shell-open\"] } # pinned\n"
+    );
+  }
+
+  #[test]
+  fn reads_workspace_dependency() {
+    let workspace = tempfile::tempdir().unwrap();
+    let app_dir = workspace.path().join("app").join("src-tauri");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(
+      workspace.path().join("Cargo.toml"),
+      r#"[workspace]
+members = ["app/src-tauri"]
+
+[workspace.dependencies]
+tauri = { version = "1.2", features = ["devtools"] }
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+      app_dir.join("Cargo.toml"),
+      "[package]\nname = \"app\"\n\n[dependencies]\ntauri = { workspace = true }\n",
+    )
+    .unwrap();
+
+    let tauri = workspace_dependency(&app_dir, "tauri").unwrap();
+    assert_eq!(tauri.get("version").and_then(|v| v.as_str()), Some("1.2"));
+    assert_eq!(
+      tauri
+        .get("features")
+        .and_then(|f| f.as_array())
+        .map(|f| f.iter().filter_map(|f| f.as_str()).collect::<Vec<_>>()),
+      Some(vec!["devtools"])
+    );
+    assert!(workspace_dependency(&app_dir, "serde").is_none());
+  }
+
+  #[test]
+  fn diffs_manifest_changes() {
+    let old = "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[build-dependencies]\ntauri-build = \"1.2\"\n\n[dependencies]\nserde = \"1\"\ntauri = { version = \"1.2\", features = [] }\n";
+    let new = old.replace("features = []", "features = [\"shell-open\"]");
+    assert_eq!(
+      unified_diff("Cargo.toml", old, &new),
+      r#"--- a/Cargo.toml
++++ b/Cargo.toml
+@@ -8,4 +8,4 @@
+ 
+ [dependencies]
+ serde = "1"
+-tauri = { version = "1.2", features = [] }
++tauri = { version = "1.2", features = ["shell-open"] }
+"#
+    );
+
+    let new = format!("# managed by the Tauri CLI\n{}", new);
+    assert_eq!(
+      unified_diff("Cargo.toml", old, &new),
+      r#"--- a/Cargo.toml
++++ b/Cargo.toml
+@@ -1,3 +1,4 @@
++# managed by the Tauri CLI
+ [package]
+ name = "app"
+ version = "0.1.0"
+@@ -8,4 +9,4 @@
+ 
+ [dependencies]
+ serde = "1"
+-tauri = { version = "1.2", features = [] }
++tauri = { version = "1.2", features = ["shell-open"] }
+"#
+    );
+  }
+
+  #[test]
+  fn rewrites_renamed_and_target_dependencies() {
+    let mut manifest: Document = r#"[dependencies]
+tauri-nightly = { git = "https://github.com/tauri-apps/tauri", branch = "dev", package = "tauri" }
+serde = "1"
+
+[target.'cfg(windows)'.dependencies]
+tauri = { path = "../../tauri/core/tauri" }
+
+[patch.crates-io]
+tauri = { git = "https://github.com/tauri-apps/tauri", branch = "dev" }
+"#
+    .parse()
+    .unwrap();
+
+    let names: Vec<String> = dependency_tables_mut(&mut manifest, "dependencies")
+      .into_iter()
+      .map(|dependencies| {
+        let name = dependency_name(dependencies, "tauri").unwrap();
+        let mut features: HashSet<String> = vec!["shell-open".to_string()].into_iter().collect();
+        write_features(dependencies, &name, vec!["shell-open"], &mut features).unwrap();
+        name
       })
-    }
-    Ok(false) => Ok(Manifest {
-      inner: manifest,
-      tauri_features,
-    }),
-    Err(e) => Err(e),
+      .collect();
+    assert_eq!(names, vec!["tauri-nightly", "tauri"]);
+    assert_eq!(
+      manifest.to_string(),
+      r#"[dependencies]
+tauri-nightly = { git = "https://github.com/tauri-apps/tauri", branch = "dev", package = "tauri", features = ["shell-open"] }
+serde = "1"
+
+[target.'cfg(windows)'.dependencies]
+tauri = { path = "../../tauri/core/tauri", features = ["shell-open"] }
+
+[patch.crates-io]
+tauri = { git = "https://github.com/tauri-apps/tauri", branch = "dev" }
+"#
+    );
+  }
+
+  #[test]
+  fn reports_renamed_dependency_features() {
+    let manifest = Manifest {
+      inner: "[features]\ndefault = [\"inspector\"]\ninspector = [\"tauri-nightly?/devtools\"]\n"
+        .parse()
+        .unwrap(),
+      tauri_features: vec!["shell-open".to_string()].into_iter().collect(),
+      tauri_dependency: Some("tauri-nightly".into()),
+      ..Default::default()
+    };
+    let mut features = manifest.all_enabled_features(&["default".into()]);
+    features.sort();
+    assert_eq!(features, vec!["tauri/devtools", "tauri/shell-open"]);
   }
 }
@@ -5,15 +5,21 @@
 pub use anyhow::Result;
 
 mod build;
+mod clean;
+mod completions;
+mod deps;
 mod dev;
 mod helpers;
 mod icon;
 mod info;
 mod init;
+mod inspect;
 mod interface;
+mod migrate;
 mod mobile;
 mod plugin;
 mod signer;
+mod updater;
 
 use clap::{FromArgMatches, IntoApp, Parser, Subcommand, ValueEnum};
 use env_logger::fmt::Color;
@@ -87,12 +93,18 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
   Build(build::Options),
+  Clean(clean::Options),
+  Completions(completions::Options),
+  Deps(deps::Options),
   Dev(dev::Options),
   Icon(icon::Options),
   Info(info::Options),
   Init(init::Options),
+  Inspect(inspect::Cli),
+  Migrate(migrate::Options),
   Plugin(plugin::Cli),
   Signer(signer::Cli),
+  Updater(updater::Cli),
   Android(mobile::android::Cli),
   #[cfg(target_os = "macos")]
   Ios(mobile::ios::Cli),
@@ -134,7 +146,7 @@ where
   I: IntoIterator<Item = A>,
   A: Into<OsString> + Clone,
 {
-  let matches = match bin_name {
+  let matches = match &bin_name {
     Some(bin_name) => Cli::command().bin_name(bin_name),
     None => Cli::command(),
   }
@@ -189,12 +201,18 @@ where
 
   match cli.command {
     Commands::Build(options) => build::command(options)?,
+    Commands::Clean(options) => clean::command(options)?,
+    Commands::Completions(options) => completions::command(options, bin_name.as_deref())?,
+    Commands::Deps(options) => deps::command(options)?,
     Commands::Dev(options) => dev::command(options)?,
     Commands::Icon(options) => icon::command(options)?,
     Commands::Info(options) => info::command(options)?,
     Commands::Init(options) => init::command(options)?,
+    Commands::Inspect(cli) => inspect::command(cli)?,
+    Commands::Migrate(options) => migrate::command(options)?,
     Commands::Plugin(cli) => plugin::command(cli)?,
     Commands::Signer(cli) => signer::command(cli)?,
+    Commands::Updater(cli) => updater::command(cli)?,
     Commands::Android(c) => mobile::android::command(c, cli.verbose)?,
     #[cfg(target_os = "macos")]
     Commands::Ios(c) => mobile::ios::command(c, cli.verbose)?,
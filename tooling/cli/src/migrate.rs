@@ -0,0 +1,100 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! `tauri migrate`, upgrading a project from the Tauri 1.0 pre-releases to the current version.
+
+use crate::{
+  helpers::app_paths::{app_dir, tauri_dir},
+  Result,
+};
+use clap::Parser;
+use log::info;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+
+use std::path::{Path, PathBuf};
+
+mod config;
+mod frontend;
+mod manifest;
+
+#[derive(Debug, Parser)]
+#[clap(
+  about = "Migrates the configuration and the Tauri dependencies of a project created with a Tauri 1.0 pre-release"
+)]
+pub struct Options {
+  /// Print the changes without writing them
+  #[clap(long)]
+  dry_run: bool,
+}
+
+#[derive(Deserialize)]
+struct JsCliVersionMetadata {
+  version: String,
+}
+
+#[derive(Deserialize)]
+struct VersionMetadata {
+  tauri: String,
+  #[serde(rename = "tauri-build")]
+  tauri_build: String,
+  #[serde(rename = "cli.js")]
+  js_cli: JsCliVersionMetadata,
+}
+
+/// A change made by the migration.
+pub struct Change {
+  path: PathBuf,
+  description: String,
+}
+
+impl Change {
+  fn new(path: &Path, description: impl Into<String>) -> Self {
+    Self {
+      path: path.to_path_buf(),
+      description: description.into(),
+    }
+  }
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let metadata = serde_json::from_str::<VersionMetadata>(include_str!("../metadata.json"))?;
+  let tauri_dir = tauri_dir();
+
+  let mut changes = config::migrate(&tauri_dir, options.dry_run)?;
+  changes.extend(manifest::migrate(&tauri_dir, &metadata, options.dry_run)?);
+  changes.extend(frontend::migrate(app_dir(), &metadata, options.dry_run)?);
+
+  if changes.is_empty() {
+    info!("Nothing to migrate, the project is up to date.");
+    return Ok(());
+  }
+
+  let cwd = std::env::current_dir()?;
+  for change in &changes {
+    info!(
+      action = if options.dry_run { "Would change" } else { "Changed" };
+      "{}: {}",
+      change.path.strip_prefix(&cwd).unwrap_or(&change.path).display(),
+      change.description
+    );
+  }
+  if !options.dry_run {
+    info!("Run `cargo update` and install the frontend dependencies to apply the new versions.");
+  }
+
+  Ok(())
+}
+
+/// Whether the version requirement must be upgraded to `version`: it does not match it or targets a pre-release.
+///
+/// Requirements which are not semver ranges, e.g. git or path dependencies, are kept.
+fn needs_upgrade(requirement: &str, version: &Version) -> bool {
+  match VersionReq::parse(requirement) {
+    Ok(requirement) => {
+      !requirement.matches(version) || requirement.comparators.iter().any(|c| !c.pre.is_empty())
+    }
+    Err(_) => false,
+  }
+}
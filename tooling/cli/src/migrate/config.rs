@@ -0,0 +1,180 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::Change;
+use crate::Result;
+use anyhow::Context;
+use serde_json::{json, Map, Value};
+
+use std::path::Path;
+
+/// The configuration files of the project.
+const CONFIG_FILES: &[&str] = &[
+  "tauri.conf.json",
+  "tauri.linux.conf.json",
+  "tauri.windows.conf.json",
+  "tauri.macos.conf.json",
+];
+
+/// The renamed keys, from the old to the new path. Merged allowlist flags are enabled if any of the old flags was.
+const RENAMED_KEYS: &[(&[&str], &[&str])] = &[
+  (&["tauri", "bundle", "osx"], &["tauri", "bundle", "macOS"]),
+  (
+    &["tauri", "allowlist", "fs", "readBinaryFile"],
+    &["tauri", "allowlist", "fs", "readFile"],
+  ),
+  (
+    &["tauri", "allowlist", "fs", "readTextFile"],
+    &["tauri", "allowlist", "fs", "readFile"],
+  ),
+  (
+    &["tauri", "allowlist", "fs", "writeBinaryFile"],
+    &["tauri", "allowlist", "fs", "writeFile"],
+  ),
+];
+
+/// The removed keys.
+const REMOVED_KEYS: &[&[&str]] = &[
+  &["tauri", "bundle", "deb", "useBootstrapper"],
+  &["tauri", "bundle", "macOS", "useBootstrapper"],
+];
+
+/// Migrates the configuration files of the Tauri directory.
+pub fn migrate(tauri_dir: &Path, dry_run: bool) -> Result<Vec<Change>> {
+  let mut changes = Vec::new();
+  for file_name in CONFIG_FILES {
+    let path = tauri_dir.join(file_name);
+    if !path.exists() {
+      continue;
+    }
+    let mut config: Value = serde_json::from_str(
+      &std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?,
+    )
+    .with_context(|| format!("failed to parse {}", path.display()))?;
+
+    let descriptions = migrate_config(&mut config);
+    if !descriptions.is_empty() && !dry_run {
+      std::fs::write(&path, serde_json::to_string_pretty(&config)? + "\n")
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    changes.extend(descriptions.into_iter().map(|d| Change::new(&path, d)));
+  }
+  Ok(changes)
+}
+
+/// Rewrites the renamed and removed keys of the configuration, returning the description of the changes.
+fn migrate_config(config: &mut Value) -> Vec<String> {
+  let mut changes = Vec::new();
+
+  for (from, to) in RENAMED_KEYS {
+    if let Some(value) = take(config, from) {
+      let (parent, key) = to.split_at(to.len() - 1);
+      if let Some(object) = object_mut(config, parent) {
+        match (object.get_mut(key[0]), &value) {
+          (Some(Value::Bool(current)), Value::Bool(value)) => *current |= value,
+          (Some(_), _) => (),
+          (None, _) => {
+            object.insert(key[0].to_string(), value);
+          }
+        }
+      }
+      changes.push(format!(
+        "renamed `{}` to `{}`",
+        from.join(" > "),
+        to.join(" > ")
+      ));
+    }
+  }
+
+  for path in REMOVED_KEYS {
+    if take(config, path).is_some() {
+      changes.push(format!("removed `{}`", path.join(" > ")));
+    }
+  }
+
+  // `skipWebviewInstall` is replaced by the `webviewInstallMode` option
+  let skip_webview_install = ["tauri", "bundle", "windows", "wix", "skipWebviewInstall"];
+  if let Some(skip) = take(config, &skip_webview_install) {
+    if skip == Value::Bool(true) {
+      if let Some(windows) = object_mut(config, &["tauri", "bundle", "windows"]) {
+        windows.insert(
+          "webviewInstallMode".into(),
+          json!({
+            "type": "skip"
+          }),
+        );
+      }
+      changes.push(format!(
+        "replaced `{}` with `tauri > bundle > windows > webviewInstallMode`",
+        skip_webview_install.join(" > ")
+      ));
+    } else {
+      changes.push(format!("removed `{}`", skip_webview_install.join(" > ")));
+    }
+  }
+
+  changes
+}
+
+/// Removes the value at the path.
+fn take(config: &mut Value, path: &[&str]) -> Option<Value> {
+  let (parent, key) = path.split_at(path.len() - 1);
+  object_mut(config, parent)?.remove(key[0])
+}
+
+/// The object at the path, `None` if the path does not exist.
+fn object_mut<'a>(config: &'a mut Value, path: &[&str]) -> Option<&'a mut Map<String, Value>> {
+  path
+    .iter()
+    .try_fold(config, |value, key| value.get_mut(key))?
+    .as_object_mut()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::migrate_config;
+  use serde_json::json;
+
+  #[test]
+  fn migrates_renamed_and_removed_keys() {
+    let mut config = json!({
+      "tauri": {
+        "allowlist": {
+          "fs": {
+            "readTextFile": false,
+            "readBinaryFile": true,
+            "writeBinaryFile": true,
+            "writeFile": false
+          }
+        },
+        "bundle": {
+          "osx": { "useBootstrapper": true, "minimumSystemVersion": "10.13" },
+          "windows": { "wix": { "skipWebviewInstall": true } }
+        }
+      }
+    });
+    let changes = migrate_config(&mut config);
+    assert_eq!(changes.len(), 6);
+    assert_eq!(
+      config,
+      json!({
+        "tauri": {
+          "allowlist": {
+            "fs": {
+              "readFile": true,
+              "writeFile": true
+            }
+          },
+          "bundle": {
+            "macOS": { "minimumSystemVersion": "10.13" },
+            "windows": { "wix": {}, "webviewInstallMode": { "type": "skip" } }
+          }
+        }
+      })
+    );
+
+    assert!(migrate_config(&mut config).is_empty());
+  }
+}
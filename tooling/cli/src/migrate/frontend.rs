@@ -0,0 +1,53 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{needs_upgrade, Change, VersionMetadata};
+use crate::Result;
+use anyhow::Context;
+use semver::Version;
+use serde_json::Value;
+
+use std::path::Path;
+
+/// The version of the `@tauri-apps/api` package compatible with the current Tauri version.
+const API_VERSION: &str = "1.0.0";
+
+/// Upgrades the `@tauri-apps/api` and `@tauri-apps/cli` dependencies of the package.json.
+pub fn migrate(app_dir: &Path, metadata: &VersionMetadata, dry_run: bool) -> Result<Vec<Change>> {
+  let path = app_dir.join("package.json");
+  if !path.exists() {
+    return Ok(Vec::new());
+  }
+  let mut package_json: Value = serde_json::from_str(
+    &std::fs::read_to_string(&path)
+      .with_context(|| format!("failed to read {}", path.display()))?,
+  )
+  .with_context(|| format!("failed to parse {}", path.display()))?;
+
+  let mut changes = Vec::new();
+  for (name, version) in [
+    ("@tauri-apps/api", API_VERSION),
+    ("@tauri-apps/cli", metadata.js_cli.version.as_str()),
+  ] {
+    let target = Version::parse(version)?;
+    for table in ["dependencies", "devDependencies"] {
+      if let Some(dependency) = package_json.get_mut(table).and_then(|t| t.get_mut(name)) {
+        let current = dependency.as_str().unwrap_or_default().to_string();
+        if needs_upgrade(&current, &target) {
+          *dependency = Value::String(format!("^{}", version));
+          changes.push(Change::new(
+            &path,
+            format!("upgraded `{}` from {} to ^{}", name, current, version),
+          ));
+        }
+      }
+    }
+  }
+
+  if !changes.is_empty() && !dry_run {
+    std::fs::write(&path, serde_json::to_string_pretty(&package_json)? + "\n")
+      .with_context(|| format!("failed to write {}", path.display()))?;
+  }
+  Ok(changes)
+}
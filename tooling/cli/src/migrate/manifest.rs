@@ -0,0 +1,48 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{needs_upgrade, Change, VersionMetadata};
+use crate::{
+  interface::rust::manifest::{read_manifest, set_dependency_version},
+  Result,
+};
+use anyhow::Context;
+use semver::Version;
+
+use std::path::Path;
+
+/// Upgrades the `tauri` and `tauri-build` dependencies of the Cargo manifest.
+pub fn migrate(tauri_dir: &Path, metadata: &VersionMetadata, dry_run: bool) -> Result<Vec<Change>> {
+  let path = tauri_dir.join("Cargo.toml");
+  let mut manifest = read_manifest(&path)?;
+
+  let mut changes = Vec::new();
+  for (table, name, version) in [
+    ("dependencies", "tauri", &metadata.tauri),
+    ("build-dependencies", "tauri-build", &metadata.tauri_build),
+  ] {
+    let target = Version::parse(version)?;
+    let current = manifest
+      .get(table)
+      .and_then(|t| t.get(name))
+      .and_then(|d| {
+        d.as_str()
+          .or_else(|| d.get("version").and_then(|v| v.as_str()))
+      })
+      .map(ToString::to_string);
+    if let Some(current) = current.filter(|current| needs_upgrade(current, &target)) {
+      set_dependency_version(&mut manifest, table, name, version);
+      changes.push(Change::new(
+        &path,
+        format!("upgraded `{}` from {} to {}", name, current, version),
+      ));
+    }
+  }
+
+  if !changes.is_empty() && !dry_run {
+    std::fs::write(&path, manifest.to_string())
+      .with_context(|| format!("failed to write {}", path.display()))?;
+  }
+  Ok(changes)
+}
@@ -19,8 +19,9 @@ use cargo_mobile::{
 use clap::{Parser, Subcommand};
 use std::{
   env::set_var,
+  process::Command,
   thread::{sleep, spawn},
-  time::Duration,
+  time::{Duration, Instant},
 };
 use sublime_fuzzy::best_match;
 
@@ -37,6 +38,8 @@ use crate::{
 mod android_studio_script;
 mod build;
 mod dev;
+mod devices;
+mod logcat;
 mod open;
 pub(crate) mod project;
 
@@ -68,6 +71,8 @@ enum Commands {
   Open,
   Dev(dev::Options),
   Build(build::Options),
+  /// List the connected Android devices and the available emulators
+  Devices,
   #[clap(hide(true))]
   AndroidStudioScript(android_studio_script::Options),
 }
@@ -79,6 +84,7 @@ pub fn command(cli: Cli, verbosity: usize) -> Result<()> {
     Commands::Open => open::command()?,
     Commands::Dev(options) => dev::command(options, noise_level)?,
     Commands::Build(options) => build::command(options, noise_level)?,
+    Commands::Devices => devices::command()?,
     Commands::AndroidStudioScript(options) => android_studio_script::command(options)?,
   }
 
@@ -95,6 +101,7 @@ pub fn get_config(
 
   let raw = RawAndroidConfig {
     features: android_options.features.clone(),
+    min_sdk_version: config.tauri.bundle.android.min_sdk_version,
     ..Default::default()
   };
   let config = AndroidConfig::from_raw(app.clone(), Some(raw)).unwrap();
@@ -125,11 +132,12 @@ pub fn get_config(
 }
 
 fn with_config<T>(
+  release: bool,
   cli_options: Option<CliOptions>,
   f: impl FnOnce(&App, &AndroidConfig, &AndroidMetadata, CliOptions) -> Result<T>,
 ) -> Result<T> {
   let (app, config, metadata, cli_options) = {
-    let tauri_config = get_tauri_config(None)?;
+    let tauri_config = get_tauri_config(release, None)?;
     let tauri_config_guard = tauri_config.lock().unwrap();
     let tauri_config_ = tauri_config_guard.as_ref().unwrap();
     let cli_options =
@@ -153,25 +161,32 @@ fn delete_codegen_vars() {
   }
 }
 
+/// How long to wait for a started emulator to boot.
+const EMULATOR_BOOT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
 fn adb_device_prompt<'a>(env: &'_ Env, target: Option<&str>) -> Result<Device<'a>> {
   let device_list = adb::device_list(env)
     .map_err(|cause| anyhow::anyhow!("Failed to detect connected Android devices: {cause}"))?;
   if !device_list.is_empty() {
     let device = if let Some(t) = target {
-      let (device, score) = device_list
-        .into_iter()
-        .rev()
-        .map(|d| {
-          let score = best_match(t, d.name()).map_or(0, |m| m.score());
-          (d, score)
-        })
-        .max_by_key(|(_, score)| *score)
-        // we already checked the list is not empty
-        .unwrap();
-      if score > MIN_DEVICE_MATCH_SCORE {
-        device
+      if let Some(index) = device_list.iter().position(|d| d.serial_no() == t) {
+        device_list.into_iter().nth(index).unwrap()
       } else {
-        anyhow::bail!("Could not find an Android device matching {t}")
+        let (device, score) = device_list
+          .into_iter()
+          .rev()
+          .map(|d| {
+            let score = best_match(t, d.name()).map_or(0, |m| m.score());
+            (d, score)
+          })
+          .max_by_key(|(_, score)| *score)
+          // we already checked the list is not empty
+          .unwrap();
+        if score > MIN_DEVICE_MATCH_SCORE {
+          device
+        } else {
+          anyhow::bail!("Could not find an Android device matching {t}")
+        }
       }
     } else if device_list.len() > 1 {
       let index = prompt::list(
@@ -187,8 +202,9 @@ fn adb_device_prompt<'a>(env: &'_ Env, target: Option<&str>) -> Result<Device<'a
       device_list.into_iter().next().unwrap()
     };
     println!(
-      "Detected connected device: {} with target {:?}",
+      "Detected connected device: {} ({}) with target {:?}",
       device,
+      device.serial_no(),
       device.target().triple,
     );
     Ok(device)
@@ -201,20 +217,24 @@ fn emulator_prompt(env: &'_ Env, target: Option<&str>) -> Result<emulator::Emula
   let emulator_list = emulator::avd_list(env).unwrap_or_default();
   if !emulator_list.is_empty() {
     let emulator = if let Some(t) = target {
-      let (device, score) = emulator_list
-        .into_iter()
-        .rev()
-        .map(|d| {
-          let score = best_match(t, d.name()).map_or(0, |m| m.score());
-          (d, score)
-        })
-        .max_by_key(|(_, score)| *score)
-        // we already checked the list is not empty
-        .unwrap();
-      if score > MIN_DEVICE_MATCH_SCORE {
-        device
+      if let Some(index) = emulator_list.iter().position(|e| e.name() == t) {
+        emulator_list.into_iter().nth(index).unwrap()
       } else {
-        anyhow::bail!("Could not find an Android Emulator matching {t}")
+        let (device, score) = emulator_list
+          .into_iter()
+          .rev()
+          .map(|d| {
+            let score = best_match(t, d.name()).map_or(0, |m| m.score());
+            (d, score)
+          })
+          .max_by_key(|(_, score)| *score)
+          // we already checked the list is not empty
+          .unwrap();
+        if score > MIN_DEVICE_MATCH_SCORE {
+          device
+        } else {
+          anyhow::bail!("Could not find an Android Emulator matching {t}")
+        }
       }
     } else if emulator_list.len() > 1 {
       let index = prompt::list(
@@ -230,37 +250,100 @@ fn emulator_prompt(env: &'_ Env, target: Option<&str>) -> Result<emulator::Emula
       emulator_list.into_iter().next().unwrap()
     };
 
-    let handle = emulator.start(env)?;
-    spawn(move || {
-      let _ = handle.wait();
-    });
-
     Ok(emulator)
   } else {
     Err(anyhow::anyhow!("No available Android Emulator detected"))
   }
 }
 
-fn device_prompt<'a>(env: &'_ Env, target: Option<&str>) -> Result<Device<'a>> {
+/// The connected device of a running emulator, named after its AVD.
+fn running_emulator<'a>(env: &'_ Env, emulator: &emulator::Emulator) -> Option<Device<'a>> {
+  adb::device_list(env)
+    .unwrap_or_default()
+    .into_iter()
+    .find(|device| device.name() == emulator.name())
+}
+
+/// Whether the device finished booting, so apps can be installed on it.
+fn boot_completed(env: &Env, device: &Device<'_>) -> bool {
+  Command::new(env.platform_tools_path().join("adb"))
+    .args([
+      "-s",
+      device.serial_no(),
+      "shell",
+      "getprop",
+      "sys.boot_completed",
+    ])
+    .output()
+    .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+    .unwrap_or(false)
+}
+
+/// Boots the emulator unless it is already running and waits for it.
+fn emulator_device<'a>(env: &'_ Env, emulator: &emulator::Emulator) -> Result<Device<'a>> {
+  if let Some(device) = running_emulator(env, emulator) {
+    println!(
+      "Detected running emulator: {} ({})",
+      device,
+      device.serial_no()
+    );
+    return Ok(device);
+  }
+
+  println!("Starting emulator {}", emulator.name());
+  let handle = emulator.start(env)?;
+  spawn(move || {
+    let _ = handle.wait();
+  });
+
+  println!("Waiting for emulator {} to boot...", emulator.name());
+  let started = Instant::now();
+  loop {
+    sleep(Duration::from_secs(2));
+    if let Some(device) = running_emulator(env, emulator) {
+      if boot_completed(env, &device) {
+        println!(
+          "Emulator {} booted: {} ({}) with target {:?}",
+          emulator.name(),
+          device,
+          device.serial_no(),
+          device.target().triple,
+        );
+        return Ok(device);
+      }
+    }
+    if started.elapsed() > EMULATOR_BOOT_TIMEOUT {
+      anyhow::bail!(
+        "Emulator {} did not boot within {} seconds",
+        emulator.name(),
+        EMULATOR_BOOT_TIMEOUT.as_secs()
+      );
+    }
+  }
+}
+
+/// Picks the device to run on: the given emulator, booted if needed, the connected device matching the given
+/// serial number or name, or a prompt among the connected devices, falling back to the emulators.
+fn device_prompt<'a>(
+  env: &'_ Env,
+  target: Option<&str>,
+  emulator: Option<&str>,
+) -> Result<Device<'a>> {
+  if let Some(emulator) = emulator {
+    return emulator_device(env, &emulator_prompt(env, Some(emulator))?);
+  }
   if let Ok(device) = adb_device_prompt(env, target) {
     Ok(device)
   } else {
     let emulator = emulator_prompt(env, target)?;
-    let handle = emulator.start(env)?;
-    spawn(move || {
-      let _ = handle.wait();
-    });
-    loop {
-      sleep(Duration::from_secs(2));
-      if let Ok(device) = adb_device_prompt(env, Some(emulator.name())) {
-        return Ok(device);
-      }
-    }
+    emulator_device(env, &emulator)
   }
 }
 
 fn detect_target_ok<'a>(env: &Env) -> Option<&'a Target<'a>> {
-  device_prompt(env, None).map(|device| device.target()).ok()
+  device_prompt(env, None, None)
+    .map(|device| device.target())
+    .ok()
 }
 
 fn open_and_wait(config: &AndroidConfig, env: &Env) -> ! {
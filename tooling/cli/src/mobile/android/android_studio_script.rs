@@ -32,28 +32,32 @@ pub fn command(options: Options) -> Result<()> {
     Profile::Debug
   };
 
-  with_config(None, |_app, config, metadata, cli_options| {
-    ensure_init(config.project_dir(), MobileTarget::Android)?;
+  with_config(
+    options.release,
+    None,
+    |_app, config, metadata, cli_options| {
+      ensure_init(config.project_dir(), MobileTarget::Android)?;
 
-    let env = env()?;
+      let env = env()?;
 
-    call_for_targets_with_fallback(
-      options.targets.unwrap_or_default().iter(),
-      &detect_target_ok,
-      &env,
-      |target: &Target| {
-        target
-          .build(
-            config,
-            metadata,
-            &env,
-            cli_options.noise_level,
-            true,
-            profile,
-          )
-          .map_err(Into::into)
-      },
-    )
-    .map_err(|e| anyhow::anyhow!(e.to_string()))?
-  })
+      call_for_targets_with_fallback(
+        options.targets.unwrap_or_default().iter(),
+        &detect_target_ok,
+        &env,
+        |target: &Target| {
+          target
+            .build(
+              config,
+              metadata,
+              &env,
+              cli_options.noise_level,
+              true,
+              profile,
+            )
+            .map_err(Into::into)
+        },
+      )
+      .map_err(|e| anyhow::anyhow!(e.to_string()))?
+    },
+  )
 }
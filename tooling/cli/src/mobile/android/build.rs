@@ -3,12 +3,14 @@ use super::{
   MobileTarget,
 };
 use crate::{
-  helpers::{config::get as get_tauri_config, flock},
-  interface::{AppSettings, Interface, Options as InterfaceOptions},
+  helpers::{app_paths::tauri_dir, config::get as get_tauri_config, flock},
+  interface::{Interface, Options as InterfaceOptions},
   mobile::{write_options, CliOptions},
   Result,
 };
+use anyhow::Context;
 use clap::Parser;
+use tauri_utils::config::AndroidSigningConfig;
 
 use cargo_mobile::{
   android::{aab, apk, config::Config as AndroidConfig, env::Env, target::Target},
@@ -16,7 +18,12 @@ use cargo_mobile::{
   target::TargetTrait,
 };
 
-use std::env::set_var;
+use std::{
+  env::{set_var, var, var_os},
+  fmt::Write,
+  fs::{remove_file, write},
+  path::{Path, PathBuf},
+};
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Android build")]
@@ -24,7 +31,7 @@ pub struct Options {
   /// Builds with the debug flag
   #[clap(short, long)]
   pub debug: bool,
-  /// Which targets to build (all by default).
+  /// Which targets to build (the `tauri > bundle > android > abiFilters` ones or all by default).
   #[clap(
     short,
     long = "target",
@@ -36,9 +43,9 @@ pub struct Options {
   /// List of cargo features to activate
   #[clap(short, long, multiple_occurrences(true), multiple_values(true))]
   pub features: Option<Vec<String>>,
-  /// JSON string or path to JSON file to merge with tauri.conf.json
-  #[clap(short, long)]
-  pub config: Option<String>,
+  /// JSON string or path to a JSON, JSON5 or TOML file to merge with tauri.conf.json. Can be used multiple times, the later values take precedence
+  #[clap(short, long, multiple_occurrences(true))]
+  pub config: Vec<String>,
   /// Whether to split the APKs and AABs per ABIs.
   #[clap(long)]
   pub split_per_abi: bool,
@@ -58,10 +65,27 @@ impl From<Options> for crate::build::Options {
     Self {
       runner: None,
       debug: options.debug,
-      target: None,
+      profile: None,
+      target: Vec::new(),
       features: options.features,
+      bin: None,
+      example: None,
       bundles: None,
+      no_bundle: false,
       config: options.config,
+      skip_checks: Vec::new(),
+      env_files: Vec::new(),
+      ci: false,
+      use_sccache: false,
+      reproducible: false,
+      feature_matrix: false,
+      matrix_jobs: None,
+      checksum: None,
+      sbom: None,
+      delta_from: None,
+      timings: None,
+      dry_run: false,
+      frozen_manifest: false,
       args: Vec::new(),
     }
   }
@@ -70,6 +94,7 @@ impl From<Options> for crate::build::Options {
 pub fn command(options: Options, noise_level: NoiseLevel) -> Result<()> {
   delete_codegen_vars();
   with_config(
+    !options.debug,
     Some(Default::default()),
     |app, config, _metadata, _cli_options| {
       set_var("WRY_RUSTWEBVIEWCLIENT_CLASS_EXTENSION", "");
@@ -111,17 +136,45 @@ fn run_build(
     options.aab = true;
   }
 
-  let bundle_identifier = {
-    let tauri_config = get_tauri_config(None)?;
+  let (bundle_identifier, android_config) = {
+    let tauri_config = get_tauri_config(!options.debug, None)?;
     let tauri_config_guard = tauri_config.lock().unwrap();
     let tauri_config_ = tauri_config_guard.as_ref().unwrap();
-    tauri_config_.tauri.bundle.identifier.clone()
+    (
+      tauri_config_.tauri.bundle.identifier.clone(),
+      tauri_config_.tauri.bundle.android.clone(),
+    )
+  };
+
+  // the targets of the configured ABI filters unless the targets were given on the command line
+  let targets = options.targets.clone().unwrap_or_else(|| {
+    android_config
+      .abi_filters
+      .iter()
+      .flatten()
+      .filter_map(|abi| {
+        Target::all()
+          .iter()
+          .find(|(_, target)| target.abi == abi.as_str())
+          .map(|(name, _)| name.to_string())
+      })
+      .collect()
+  });
+
+  let signing = if options.debug {
+    None
+  } else {
+    signing_config(&android_config.signing)?
   };
 
   let mut build_options = options.clone().into();
-  let interface = crate::build::setup(&mut build_options)?;
+  let interface = crate::build::setup(&mut build_options, true)?;
 
   let app_settings = interface.app_settings();
+  crate::interface::rust::extend_cargo_features(
+    &mut build_options.features,
+    app_settings.manifest(),
+  );
   let bin_path = app_settings.app_binary_path(&InterfaceOptions {
     debug: build_options.debug,
     ..Default::default()
@@ -142,13 +195,23 @@ fn run_build(
     .get_or_insert(Vec::new())
     .push("custom-protocol".into());
 
-  let apk_outputs = if options.apk {
+  let _keystore_properties = match &signing {
+    Some(signing) => Some(KeystoreProperties::write(&config.project_dir(), signing)?),
+    None => {
+      if !options.debug {
+        log::warn!("No Android signing keystore configured, the release APKs and AABs are unsigned. Set `tauri > bundle > android > signing` or the `TAURI_ANDROID_KEYSTORE_PATH` environment variable to sign them.");
+      }
+      None
+    }
+  };
+
+  let mut apk_outputs = if options.apk {
     apk::build(
       config,
       env,
       noise_level,
       profile,
-      get_targets_or_all(Vec::new())?,
+      get_targets_or_all(targets.clone())?,
       options.split_per_abi,
     )?
   } else {
@@ -161,15 +224,22 @@ fn run_build(
       env,
       noise_level,
       profile,
-      get_targets_or_all(Vec::new())?,
+      get_targets_or_all(targets)?,
       options.split_per_abi,
     )?
   } else {
     Vec::new()
   };
 
-  log_finished(apk_outputs, "APK");
-  log_finished(aab_outputs, "AAB");
+  if signing.is_some() {
+    // signed release APKs are not suffixed with `-unsigned`
+    apk_outputs = apk_outputs.into_iter().map(signed_output_path).collect();
+    log_finished(apk_outputs, "signed APK");
+    log_finished(aab_outputs, "signed AAB");
+  } else {
+    log_finished(apk_outputs, "APK");
+    log_finished(aab_outputs, "AAB");
+  }
 
   Ok(())
 }
@@ -199,3 +269,138 @@ fn get_targets_or_all<'a>(targets: Vec<String>) -> Result<Vec<&'a Target<'a>>> {
     Ok(outs)
   }
 }
+
+/// The keystore used to sign the release builds.
+struct Signing {
+  keystore_path: PathBuf,
+  key_alias: String,
+  keystore_password: String,
+  key_password: String,
+}
+
+/// Reads the signing keystore from the environment variables, falling back to the configuration.
+fn signing_config(config: &AndroidSigningConfig) -> Result<Option<Signing>> {
+  let keystore_path = match var_os("TAURI_ANDROID_KEYSTORE_PATH")
+    .map(PathBuf::from)
+    .or_else(|| config.keystore_path.clone())
+  {
+    Some(path) => tauri_dir().join(path),
+    None => return Ok(None),
+  };
+  if !keystore_path.exists() {
+    anyhow::bail!(
+      "The Android keystore {} doesn't exist.",
+      keystore_path.display()
+    );
+  }
+
+  let key_alias = var("TAURI_ANDROID_KEY_ALIAS")
+    .ok()
+    .or_else(|| config.key_alias.clone())
+    .ok_or_else(|| anyhow::anyhow!("The Android keystore is configured but its key alias is missing. Set `tauri > bundle > android > signing > keyAlias` or the `TAURI_ANDROID_KEY_ALIAS` environment variable."))?;
+  let keystore_password = var("TAURI_ANDROID_KEYSTORE_PASSWORD")
+    .ok()
+    .or_else(|| config.keystore_password.clone())
+    .ok_or_else(|| anyhow::anyhow!("The Android keystore is configured but its password is missing. Set the `TAURI_ANDROID_KEYSTORE_PASSWORD` environment variable."))?;
+  let key_password = var("TAURI_ANDROID_KEY_PASSWORD")
+    .ok()
+    .or_else(|| config.key_password.clone())
+    .unwrap_or_else(|| keystore_password.clone());
+
+  Ok(Some(Signing {
+    keystore_path,
+    key_alias,
+    keystore_password,
+    key_password,
+  }))
+}
+
+/// The `keystore.properties` file read by the app `build.gradle.kts`, removed once the build is done.
+struct KeystoreProperties(PathBuf);
+
+impl KeystoreProperties {
+  fn write(project_dir: &Path, signing: &Signing) -> Result<Self> {
+    if !std::fs::read_to_string(project_dir.join("app/build.gradle.kts"))
+      .unwrap_or_default()
+      .contains("keystore.properties")
+    {
+      log::warn!("The Android project was generated without signing support, the release builds won't be signed. Run `tauri android init` to regenerate it.");
+    }
+
+    let mut properties = String::new();
+    for (key, value) in [
+      (
+        "storeFile",
+        signing.keystore_path.to_string_lossy().into_owned(),
+      ),
+      ("storePassword", signing.keystore_password.clone()),
+      ("keyAlias", signing.key_alias.clone()),
+      ("keyPassword", signing.key_password.clone()),
+    ] {
+      writeln!(properties, "{}={}", key, escape_property_value(&value)).unwrap();
+    }
+
+    let path = project_dir.join("keystore.properties");
+    write(&path, properties).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(Self(path))
+  }
+}
+
+impl Drop for KeystoreProperties {
+  fn drop(&mut self) {
+    let _ = remove_file(&self.0);
+  }
+}
+
+/// Escapes a `.properties` value, which Gradle reads as ISO 8859-1.
+fn escape_property_value(value: &str) -> String {
+  let mut escaped = String::new();
+  for (i, c) in value.chars().enumerate() {
+    match c {
+      '\\' => escaped.push_str("\\\\"),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      '\t' => escaped.push_str("\\t"),
+      // leading whitespace is skipped
+      ' ' if i == 0 => escaped.push_str("\\ "),
+      c if c.is_ascii() => escaped.push(c),
+      c => {
+        for unit in c.encode_utf16(&mut [0; 2]) {
+          write!(escaped, "\\u{:04x}", unit).unwrap();
+        }
+      }
+    }
+  }
+  escaped
+}
+
+/// The path of a signed APK, which the build output paths assume to be unsigned.
+fn signed_output_path(path: PathBuf) -> PathBuf {
+  if path.exists() {
+    return path;
+  }
+  let signed = path
+    .file_name()
+    .and_then(|name| name.to_str())
+    .map(|name| path.with_file_name(name.replace("-unsigned", "")));
+  match signed {
+    Some(signed) if signed.exists() => signed,
+    _ => path,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::escape_property_value;
+
+  #[test]
+  fn escapes_property_values() {
+    assert_eq!(
+      escape_property_value(r"C:\keys\release.jks"),
+      r"C:\\keys\\release.jks"
+    );
+    assert_eq!(escape_property_value(" pass=word"), r"\ pass=word");
+    assert_eq!(escape_property_value("pässwörd"), r"p\u00e4ssw\u00f6rd");
+    assert_eq!(escape_property_value("🔑"), r"\ud83d\udd11");
+  }
+}
@@ -1,10 +1,10 @@
 use super::{
-  delete_codegen_vars, device_prompt, ensure_init, env, init_dot_cargo, open_and_wait, with_config,
-  MobileTarget,
+  delete_codegen_vars, device_prompt, ensure_init, env, init_dot_cargo, logcat::spawn_logcat,
+  open_and_wait, with_config, MobileTarget,
 };
 use crate::{
   helpers::{config::get as get_tauri_config, flock},
-  interface::{AppSettings, Interface, MobileOptions, Options as InterfaceOptions},
+  interface::{Interface, MobileOptions, Options as InterfaceOptions},
   mobile::{write_options, CliOptions, DevChild, DevProcess},
   Result,
 };
@@ -39,17 +39,21 @@ pub struct Options {
   /// Exit on panic
   #[clap(short, long)]
   exit_on_panic: bool,
-  /// JSON string or path to JSON file to merge with tauri.conf.json
-  #[clap(short, long)]
-  pub config: Option<String>,
+  /// JSON string or path to a JSON, JSON5 or TOML file to merge with tauri.conf.json. Can be used multiple times, the later values take precedence
+  #[clap(short, long, multiple_occurrences(true))]
+  pub config: Vec<String>,
   /// Disable the file watcher
   #[clap(long)]
   pub no_watch: bool,
   /// Open Android Studio instead of trying to run on a connected device
   #[clap(short, long)]
   pub open: bool,
-  /// Runs on the given device name
+  /// Runs on the connected device with the given serial number or name
+  #[clap(long, conflicts_with = "emulator")]
   pub device: Option<String>,
+  /// Runs on the given Android Virtual Device, booting the emulator if needed
+  #[clap(long)]
+  pub emulator: Option<String>,
 }
 
 impl From<Options> for crate::dev::Options {
@@ -58,11 +62,31 @@ impl From<Options> for crate::dev::Options {
       runner: None,
       target: None,
       features: options.features,
+      bin: None,
+      example: None,
       exit_on_panic: options.exit_on_panic,
       config: options.config,
       release_mode: false,
+      cargo_profile: None,
       args: Vec::new(),
       no_watch: options.no_watch,
+      watch_debounce: None,
+      watch_poll: false,
+      restart_grace_period: None,
+      no_dev_server_wait: false,
+      open_devtools: false,
+      message_format: crate::dev::events::MessageFormat::Human,
+      auto_port: false,
+      crash_restart_limit: None,
+      env_files: Vec::new(),
+      profile: None,
+      log_timestamps: false,
+      after_build_command: None,
+      remote_debugging_port: None,
+      remote_debugging_address: None,
+      use_sccache: false,
+      dry_run: false,
+      frozen_manifest: false,
     }
   }
 }
@@ -70,6 +94,7 @@ impl From<Options> for crate::dev::Options {
 pub fn command(options: Options, noise_level: NoiseLevel) -> Result<()> {
   delete_codegen_vars();
   with_config(
+    false,
     Some(Default::default()),
     |app, config, metadata, _cli_options| {
       set_var(
@@ -95,7 +120,7 @@ fn run_dev(
   let mut interface = crate::dev::setup(&mut dev_options)?;
 
   let bundle_identifier = {
-    let tauri_config = get_tauri_config(None)?;
+    let tauri_config = get_tauri_config(false, None)?;
     let tauri_config_guard = tauri_config.lock().unwrap();
     let tauri_config_ = tauri_config_guard.as_ref().unwrap();
     tauri_config_.tauri.bundle.identifier.clone()
@@ -116,13 +141,18 @@ fn run_dev(
   let exit_on_panic = options.exit_on_panic;
   let no_watch = options.no_watch;
   let device = options.device;
+  let emulator = options.emulator;
   interface.mobile_dev(
     MobileOptions {
       debug: true,
       features: options.features,
       args: Vec::new(),
-      config: options.config,
+      config: dev_options.config.first().cloned(),
       no_watch: options.no_watch,
+      watch_debounce: None,
+      watch_poll: false,
+      restart_grace_period: None,
+      crash_restart_limit: None,
     },
     |options| {
       let cli_options = CliOptions {
@@ -138,6 +168,7 @@ fn run_dev(
       } else {
         match run(
           device.as_deref(),
+          emulator.as_deref(),
           options,
           config,
           &env,
@@ -171,6 +202,7 @@ enum RunError {
 
 fn run(
   device: Option<&str>,
+  emulator: Option<&str>,
   options: MobileOptions,
   config: &AndroidConfig,
   env: &Env,
@@ -185,8 +217,9 @@ fn run(
 
   let build_app_bundle = metadata.asset_packs().is_some();
 
-  device_prompt(env, device)
-    .map_err(|e| RunError::FailedToPromptForDevice(e.to_string()))?
+  let device = device_prompt(env, device, emulator)
+    .map_err(|e| RunError::FailedToPromptForDevice(e.to_string()))?;
+  let handle = device
     .run(
       config,
       env,
@@ -197,6 +230,16 @@ fn run(
       false,
       ".MainActivity".into(),
     )
+    .map_err(|e| RunError::RunFailed(e.to_string()))?;
+
+  // the `adb logcat` started by the run streams the log of the whole device,
+  // replaced with one filtered to the app process
+  let mut device_logcat: std::process::Child = handle.into();
+  let _ = device_logcat.kill();
+  let _ = device_logcat.wait();
+
+  let package = format!("{}.{}", config.app().reverse_domain(), config.app().name());
+  spawn_logcat(env, &device, &package)
     .map(DevChild::new)
     .map_err(|e| RunError::RunFailed(e.to_string()))
 }
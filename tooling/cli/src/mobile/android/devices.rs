@@ -0,0 +1,39 @@
+use super::{env, running_emulator};
+use crate::Result;
+use cargo_mobile::android::{adb, emulator};
+
+pub fn command() -> Result<()> {
+  let env = env()?;
+
+  let device_list = adb::device_list(&env)
+    .map_err(|cause| anyhow::anyhow!("Failed to detect connected Android devices: {cause}"))?;
+  if device_list.is_empty() {
+    println!("No connected Android devices detected");
+  } else {
+    println!("Connected devices:");
+    for device in &device_list {
+      println!(
+        "  {}  {} with target {}",
+        device.serial_no(),
+        device,
+        device.target().triple
+      );
+    }
+  }
+
+  let emulator_list = emulator::avd_list(&env).unwrap_or_default();
+  if emulator_list.is_empty() {
+    println!("No available Android Emulator detected");
+  } else {
+    println!("Emulators:");
+    for emulator in &emulator_list {
+      if running_emulator(&env, emulator).is_some() {
+        println!("  {} (running)", emulator.name());
+      } else {
+        println!("  {}", emulator.name());
+      }
+    }
+  }
+
+  Ok(())
+}
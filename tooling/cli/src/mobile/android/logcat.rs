@@ -0,0 +1,142 @@
+use crate::Result;
+use cargo_mobile::android::{device::Device, env::Env};
+use log::Level;
+
+use std::{
+  io::BufReader,
+  path::Path,
+  process::{Child, Command, Stdio},
+  thread::{sleep, spawn},
+  time::Duration,
+};
+
+/// The tag of the app stdout and stderr lines.
+const STDOUT_STDERR_TAG: &str = "RustStdoutStderr";
+/// The tag of the webview log, including the console messages.
+const WEBVIEW_TAG: &str = "chromium";
+/// How many times to look for the app process after it was started, once per second.
+const PID_ATTEMPTS: u32 = 10;
+
+/// Streams the log of the app process on the device into the CLI output.
+///
+/// The `adb logcat` child never exits on its own, it is killed when the app is rebuilt or the dev command stops.
+pub fn spawn_logcat(env: &Env, device: &Device<'_>, package: &str) -> Result<Child> {
+  let adb = env.platform_tools_path().join("adb");
+  let pid = app_pid(&adb, device.serial_no(), package)?;
+
+  let mut child = Command::new(&adb)
+    .args([
+      "-s",
+      device.serial_no(),
+      "logcat",
+      "-v",
+      "tag",
+      &format!("--pid={}", pid),
+    ])
+    .stdout(Stdio::piped())
+    .spawn()?;
+
+  let mut stdout = child.stdout.take().map(BufReader::new).unwrap();
+  spawn(move || {
+    let mut buf = Vec::new();
+    loop {
+      buf.clear();
+      match tauri_utils::io::read_line(&mut stdout, &mut buf) {
+        Ok(0) | Err(_) => break,
+        Ok(_) => (),
+      }
+      if let Some((level, message)) = parse_line(&String::from_utf8_lossy(&buf)) {
+        log::log!(level, "{}", message);
+      }
+    }
+  });
+
+  Ok(child)
+}
+
+fn app_pid(adb: &Path, serial_no: &str, package: &str) -> Result<String> {
+  for _ in 0..PID_ATTEMPTS {
+    let output = Command::new(adb)
+      .args(["-s", serial_no, "shell", "pidof", "-s", package])
+      .output()?;
+    let pid = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !pid.is_empty() {
+      return Ok(pid);
+    }
+    sleep(Duration::from_secs(1));
+  }
+  anyhow::bail!("Could not find the process of {} on the device", package)
+}
+
+/// Parses a `logcat -v tag` line, e.g. `I/RustStdoutStderr: message`.
+fn parse_line(line: &str) -> Option<(Level, String)> {
+  let line = line.trim_end();
+  let mut chars = line.chars();
+  let level = match chars.next()? {
+    'V' => Level::Trace,
+    'D' => Level::Debug,
+    'I' => Level::Info,
+    'W' => Level::Warn,
+    'E' | 'F' | 'A' => Level::Error,
+    _ => return None,
+  };
+  // the tag is padded to a minimum width
+  let (tag, message) = chars.as_str().strip_prefix('/')?.split_once(": ")?;
+  let tag = tag.trim_end();
+
+  if tag == STDOUT_STDERR_TAG {
+    return Some((level, message.to_string()));
+  }
+  if tag == WEBVIEW_TAG {
+    if let Some(console) = parse_console_message(message) {
+      return Some(console);
+    }
+  }
+  Some((level, format!("[{}] {}", tag, message)))
+}
+
+/// Parses a webview console message, e.g. `[WARNING:CONSOLE(12)] "message", source: http://localhost:1430/main.js (12)`.
+fn parse_console_message(message: &str) -> Option<(Level, String)> {
+  let (level, message) = message.strip_prefix('[')?.split_once(":CONSOLE(")?;
+  let level = match level {
+    "VERBOSE" => Level::Debug,
+    "INFO" => Level::Info,
+    "WARNING" => Level::Warn,
+    "ERROR" | "FATAL" => Level::Error,
+    _ => return None,
+  };
+  let (_, message) = message.split_once(")] ")?;
+  Some((level, format!("[console] {}", message)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::parse_line;
+  use log::Level;
+
+  #[test]
+  fn parses_logcat_lines() {
+    assert_eq!(
+      parse_line("I/RustStdoutStderr: hello from Rust\n"),
+      Some((Level::Info, "hello from Rust".into()))
+    );
+    assert_eq!(
+      parse_line("W/my_app::cmd: slow command\n"),
+      Some((Level::Warn, "[my_app::cmd] slow command".into()))
+    );
+    assert_eq!(
+      parse_line("D/Tauri   : initialized\n"),
+      Some((Level::Debug, "[Tauri] initialized".into()))
+    );
+    assert_eq!(
+      parse_line(
+        "I/chromium: [ERROR:CONSOLE(3)] \"Uncaught TypeError\", source: http://localhost:1430/main.js (3)\n"
+      ),
+      Some((
+        Level::Error,
+        "[console] \"Uncaught TypeError\", source: http://localhost:1430/main.js (3)".into()
+      ))
+    );
+    assert_eq!(parse_line("--------- beginning of main\n"), None);
+  }
+}
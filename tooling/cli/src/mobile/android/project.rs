@@ -20,17 +20,23 @@ use cargo_mobile::{
 };
 use handlebars::Handlebars;
 use include_dir::{include_dir, Dir};
+use tauri_utils::config::{AndroidConfig, AndroidLanguage};
 
 use std::{ffi::OsStr, fs, path::Path};
 
 const TEMPLATE_DIR: Dir<'_> = include_dir!("templates/mobile/android");
+const DEFAULT_TARGET_SDK_VERSION: u32 = 33;
+const DEFAULT_GRADLE_VERSION: &str = "7.3.3";
 
 pub fn gen(
   config: &Config,
   metadata: &Metadata,
+  android_config: &AndroidConfig,
   (handlebars, mut map): (Handlebars, template::JsonMap),
   wrapper: &TextWrapper,
 ) -> Result<()> {
+  let targets = targets(android_config.abi_filters.as_deref())?;
+
   println!("Installing Android toolchains...");
   Target::install_all().with_context(|| "failed to run rustup")?;
   println!("Generating Android Studio project...");
@@ -48,15 +54,42 @@ pub fn gen(
     )),
   );
   map.insert("root-dir", config.app().root_dir());
-  map.insert("targets", Target::all().values().collect::<Vec<_>>());
-  map.insert("target-names", Target::all().keys().collect::<Vec<_>>());
+  map.insert(
+    "targets",
+    targets.iter().map(|(_, target)| target).collect::<Vec<_>>(),
+  );
+  map.insert(
+    "target-names",
+    targets.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+  );
   map.insert(
     "arches",
-    Target::all()
-      .values()
-      .map(|target| target.arch)
+    targets
+      .iter()
+      .map(|(_, target)| target.arch)
       .collect::<Vec<_>>(),
   );
+  let target_sdk_version = android_config
+    .target_sdk_version
+    .unwrap_or(DEFAULT_TARGET_SDK_VERSION);
+  map.insert("android-target-sdk-version", target_sdk_version);
+  map.insert(
+    "android-compile-sdk-version",
+    android_config
+      .compile_sdk_version
+      .unwrap_or(target_sdk_version),
+  );
+  map.insert(
+    "android-gradle-version",
+    android_config
+      .gradle_version
+      .as_deref()
+      .unwrap_or(DEFAULT_GRADLE_VERSION),
+  );
+  map.insert(
+    "android-app-extra-dependencies",
+    &android_config.dependencies,
+  );
   map.insert("android-app-plugins", metadata.app_plugins());
   map.insert(
     "android-project-dependencies",
@@ -71,7 +104,8 @@ pub fn gen(
     "has-code",
     metadata.project_dependencies().is_some()
       || metadata.app_dependencies().is_some()
-      || metadata.app_dependencies_platform().is_some(),
+      || metadata.app_dependencies_platform().is_some()
+      || !android_config.dependencies.is_empty(),
   );
   map.insert(
     "asset-packs",
@@ -87,6 +121,11 @@ pub fn gen(
 
   map.insert("package-path", &package_path);
 
+  let activity_extension = match android_config.language {
+    AndroidLanguage::Kotlin => "kt",
+    AndroidLanguage::Java => "java",
+  };
+
   let mut created_dirs = Vec::new();
   template::render_with_generator(
     &handlebars,
@@ -94,6 +133,13 @@ pub fn gen(
     &TEMPLATE_DIR,
     &dest,
     &mut |path| {
+      // only one of the `MainActivity` templates is generated
+      if path.file_stem() == Some(OsStr::new("MainActivity"))
+        && path.extension() != Some(OsStr::new(activity_extension))
+      {
+        return Ok(None);
+      }
+
       let mut iter = path.iter();
       let root = iter.next().unwrap().to_str().unwrap();
       let path_without_root: std::path::PathBuf = iter.collect();
@@ -102,7 +148,7 @@ pub fn gen(
         path.extension().and_then(|o| o.to_str()),
         path_without_root.strip_prefix("src/main"),
       ) {
-        ("app" | "buildSrc", Some("kt"), Ok(path)) => {
+        ("app" | "buildSrc", Some("kt" | "java"), Ok(path)) => {
           let parent = path.parent().unwrap();
           let file_name = path.file_name().unwrap();
           let out_dir = dest
@@ -121,6 +167,11 @@ pub fn gen(
         created_dirs.push(parent);
       }
 
+      // keeps the launcher icons generated by `tauri icon`
+      if path.extension() == Some(OsStr::new("webp")) && path.with_extension("png").exists() {
+        return Ok(None);
+      }
+
       let mut options = fs::OpenOptions::new();
       options.write(true);
 
@@ -183,3 +234,38 @@ pub fn gen(
 
   Ok(())
 }
+
+/// The Rust targets building the ABIs of the filter, or all of them.
+fn targets(
+  abi_filters: Option<&[String]>,
+) -> Result<Vec<(&'static str, &'static Target<'static>)>> {
+  if let Some(abi_filters) = abi_filters {
+    for abi in abi_filters {
+      if !Target::all()
+        .values()
+        .any(|target| target.abi == abi.as_str())
+      {
+        anyhow::bail!(
+          "unknown Android ABI `{}` in `tauri > bundle > android > abiFilters`, expected one of {}",
+          abi,
+          Target::all()
+            .values()
+            .map(|target| target.abi)
+            .collect::<Vec<_>>()
+            .join(", ")
+        );
+      }
+    }
+  }
+  Ok(
+    Target::all()
+      .iter()
+      .filter(|(_, target)| {
+        abi_filters.map_or(true, |abis| {
+          abis.iter().any(|abi| abi.as_str() == target.abi)
+        })
+      })
+      .map(|(name, target)| (*name, target))
+      .collect(),
+  )
+}
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 use super::{get_app, Target};
-use crate::helpers::{config::get as get_tauri_config, template::JsonMap};
+use crate::helpers::{config::get as get_tauri_config, doctor, template::JsonMap};
 use crate::Result;
 use cargo_mobile::{
   android::{
@@ -62,10 +62,10 @@ pub fn init_dot_cargo(app: &App, android: Option<(&AndroidEnv, &AndroidConfig)>)
 pub fn exec(
   target: Target,
   wrapper: &TextWrapper,
-  #[allow(unused_variables)] non_interactive: bool,
+  non_interactive: bool,
   #[allow(unused_variables)] reinstall_deps: bool,
 ) -> Result<App> {
-  let tauri_config = get_tauri_config(None)?;
+  let tauri_config = get_tauri_config(false, None)?;
   let tauri_config_guard = tauri_config.lock().unwrap();
   let tauri_config_ = tauri_config_guard.as_ref().unwrap();
 
@@ -114,7 +114,13 @@ pub fn exec(
         let (app, config, metadata) =
           super::android::get_config(Some(app), tauri_config_, &Default::default());
         map.insert("android", &config);
-        super::android::project::gen(&config, &metadata, (handlebars, map), wrapper)?;
+        super::android::project::gen(
+          &config,
+          &metadata,
+          &tauri_config_.tauri.bundle.android,
+          (handlebars, map),
+          wrapper,
+        )?;
         init_dot_cargo(&app, Some((&env, &config)))?;
         app
       }
@@ -125,6 +131,14 @@ pub fn exec(
             err,
           )
           .print(wrapper);
+          // offers to install the missing SDK packages, shared with `tauri info --fix`
+          if !non_interactive {
+            for issue in doctor::android_sdk() {
+              if let Some(fix) = issue.fix {
+                fix.confirm_and_run(&format!("{}.", issue.message), false)?;
+              }
+            }
+          }
           init_dot_cargo(&app, None)?;
           app
         } else {
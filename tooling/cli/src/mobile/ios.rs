@@ -123,11 +123,12 @@ pub fn get_config(
 }
 
 fn with_config<T>(
+  release: bool,
   cli_options: Option<CliOptions>,
   f: impl FnOnce(&App, &AppleConfig, &AppleMetadata, CliOptions) -> Result<T>,
 ) -> Result<T> {
   let (app, config, metadata, cli_options) = {
-    let tauri_config = get_tauri_config(None)?;
+    let tauri_config = get_tauri_config(release, None)?;
     let tauri_config_guard = tauri_config.lock().unwrap();
     let tauri_config_ = tauri_config_guard.as_ref().unwrap();
     let cli_options = cli_options.unwrap_or_else(|| read_options(tauri_config_, MobileTarget::Ios));
@@ -4,7 +4,7 @@ use super::{
 };
 use crate::{
   helpers::{config::get as get_tauri_config, flock},
-  interface::{AppSettings, Interface, Options as InterfaceOptions},
+  interface::{Interface, Options as InterfaceOptions},
   mobile::{write_options, CliOptions},
   Result,
 };
@@ -38,9 +38,9 @@ pub struct Options {
   /// List of cargo features to activate
   #[clap(short, long, multiple_occurrences(true), multiple_values(true))]
   pub features: Option<Vec<String>>,
-  /// JSON string or path to JSON file to merge with tauri.conf.json
-  #[clap(short, long)]
-  pub config: Option<String>,
+  /// JSON string or path to a JSON, JSON5 or TOML file to merge with tauri.conf.json. Can be used multiple times, the later values take precedence
+  #[clap(short, long, multiple_occurrences(true))]
+  pub config: Vec<String>,
   /// Build number to append to the app version.
   #[clap(long)]
   pub build_number: Option<u32>,
@@ -54,10 +54,27 @@ impl From<Options> for crate::build::Options {
     Self {
       runner: None,
       debug: options.debug,
-      target: None,
+      profile: None,
+      target: Vec::new(),
       features: options.features,
+      bin: None,
+      example: None,
       bundles: None,
+      no_bundle: false,
       config: options.config,
+      skip_checks: Vec::new(),
+      env_files: Vec::new(),
+      ci: false,
+      use_sccache: false,
+      reproducible: false,
+      feature_matrix: false,
+      matrix_jobs: None,
+      checksum: None,
+      sbom: None,
+      delta_from: None,
+      timings: None,
+      dry_run: false,
+      frozen_manifest: false,
       args: Vec::new(),
     }
   }
@@ -65,6 +82,7 @@ impl From<Options> for crate::build::Options {
 
 pub fn command(options: Options, noise_level: NoiseLevel) -> Result<()> {
   with_config(
+    !options.debug,
     Some(Default::default()),
     |app, config, _metadata, _cli_options| {
       ensure_init(config.project_dir(), MobileTarget::Ios)?;
@@ -98,16 +116,20 @@ fn run_build(
   };
 
   let bundle_identifier = {
-    let tauri_config = get_tauri_config(None)?;
+    let tauri_config = get_tauri_config(!options.debug, None)?;
     let tauri_config_guard = tauri_config.lock().unwrap();
     let tauri_config_ = tauri_config_guard.as_ref().unwrap();
     tauri_config_.tauri.bundle.identifier.clone()
   };
 
   let mut build_options = options.clone().into();
-  let interface = crate::build::setup(&mut build_options)?;
+  let interface = crate::build::setup(&mut build_options, true)?;
 
   let app_settings = interface.app_settings();
+  crate::interface::rust::extend_cargo_features(
+    &mut build_options.features,
+    app_settings.manifest(),
+  );
   let bin_path = app_settings.app_binary_path(&InterfaceOptions {
     debug: build_options.debug,
     ..Default::default()
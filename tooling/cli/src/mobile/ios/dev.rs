@@ -3,7 +3,7 @@ use super::{
 };
 use crate::{
   helpers::{config::get as get_tauri_config, flock},
-  interface::{AppSettings, Interface, MobileOptions, Options as InterfaceOptions},
+  interface::{Interface, MobileOptions, Options as InterfaceOptions},
   mobile::{write_options, CliOptions, DevChild, DevProcess},
   Result,
 };
@@ -25,9 +25,9 @@ pub struct Options {
   /// Exit on panic
   #[clap(short, long)]
   exit_on_panic: bool,
-  /// JSON string or path to JSON file to merge with tauri.conf.json
-  #[clap(short, long)]
-  pub config: Option<String>,
+  /// JSON string or path to a JSON, JSON5 or TOML file to merge with tauri.conf.json. Can be used multiple times, the later values take precedence
+  #[clap(short, long, multiple_occurrences(true))]
+  pub config: Vec<String>,
   /// Run the code in release mode
   #[clap(long = "release")]
   pub release_mode: bool,
@@ -47,17 +47,38 @@ impl From<Options> for crate::dev::Options {
       runner: None,
       target: None,
       features: options.features,
+      bin: None,
+      example: None,
       exit_on_panic: options.exit_on_panic,
       config: options.config,
       release_mode: options.release_mode,
+      cargo_profile: None,
       args: Vec::new(),
       no_watch: options.no_watch,
+      watch_debounce: None,
+      watch_poll: false,
+      restart_grace_period: None,
+      no_dev_server_wait: false,
+      open_devtools: false,
+      message_format: crate::dev::events::MessageFormat::Human,
+      auto_port: false,
+      crash_restart_limit: None,
+      env_files: Vec::new(),
+      profile: None,
+      log_timestamps: false,
+      after_build_command: None,
+      remote_debugging_port: None,
+      remote_debugging_address: None,
+      use_sccache: false,
+      dry_run: false,
+      frozen_manifest: false,
     }
   }
 }
 
 pub fn command(options: Options, noise_level: NoiseLevel) -> Result<()> {
   with_config(
+    options.release_mode,
     Some(Default::default()),
     |app, config, _metadata, _cli_options| {
       ensure_init(config.project_dir(), MobileTarget::Ios)?;
@@ -76,7 +97,7 @@ fn run_dev(
   let mut interface = crate::dev::setup(&mut dev_options)?;
 
   let bundle_identifier = {
-    let tauri_config = get_tauri_config(None)?;
+    let tauri_config = get_tauri_config(dev_options.release_mode, None)?;
     let tauri_config_guard = tauri_config.lock().unwrap();
     let tauri_config_ = tauri_config_guard.as_ref().unwrap();
     tauri_config_.tauri.bundle.identifier.clone()
@@ -102,8 +123,12 @@ fn run_dev(
       debug: true,
       features: options.features,
       args: Vec::new(),
-      config: options.config,
+      config: dev_options.config.first().cloned(),
       no_watch: options.no_watch,
+      watch_debounce: None,
+      watch_poll: false,
+      restart_grace_period: None,
+      crash_restart_limit: None,
     },
     |options| {
       let cli_options = CliOptions {
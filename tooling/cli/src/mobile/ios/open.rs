@@ -4,6 +4,7 @@ use cargo_mobile::os;
 
 pub fn command() -> Result<()> {
   with_config(
+    false,
     Some(Default::default()),
     |_root_conf, config, _metadata, _cli_options| {
       ensure_init(config.project_dir(), MobileTarget::Ios)?;
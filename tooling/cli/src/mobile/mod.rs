@@ -49,9 +49,9 @@ pub struct DevChild {
 }
 
 impl DevChild {
-  fn new(handle: bossy::Handle) -> Self {
+  fn new(child: impl Into<std::process::Child>) -> Self {
     Self {
-      child: Arc::new(SharedChild::new(handle.into()).unwrap()),
+      child: Arc::new(SharedChild::new(child.into()).unwrap()),
       manually_killed_process: Default::default(),
     }
   }
@@ -131,7 +131,11 @@ fn env_vars() -> HashMap<String, OsString> {
   let mut vars = HashMap::new();
   for (k, v) in std::env::vars_os() {
     let k = k.to_string_lossy();
-    if (k.starts_with("TAURI") && k != "TAURI_PRIVATE_KEY" && k != "TAURI_KEY_PASSWORD")
+    if (k.starts_with("TAURI")
+      && k != "TAURI_PRIVATE_KEY"
+      && k != "TAURI_KEY_PASSWORD"
+      && k != "TAURI_ANDROID_KEYSTORE_PASSWORD"
+      && k != "TAURI_ANDROID_KEY_PASSWORD")
       || k.starts_with("WRY")
       || k == "TMPDIR"
     {
@@ -7,6 +7,7 @@ use clap::{Parser, Subcommand};
 use crate::Result;
 
 mod init;
+mod new;
 
 #[derive(Parser)]
 #[clap(
@@ -24,11 +25,13 @@ pub struct Cli {
 #[derive(Subcommand)]
 enum Commands {
   Init(init::Options),
+  New(new::Options),
 }
 
 pub fn command(cli: Cli) -> Result<()> {
   match cli.command {
     Commands::Init(options) => init::command(options)?,
+    Commands::New(options) => new::command(options)?,
   }
 
   Ok(())
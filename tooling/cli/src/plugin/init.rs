@@ -13,7 +13,13 @@ use handlebars::{to_json, Handlebars};
 use heck::{AsKebabCase, ToKebabCase, ToSnakeCase};
 use include_dir::{include_dir, Dir};
 use log::warn;
-use std::{collections::BTreeMap, env::current_dir, fs::remove_dir_all, path::PathBuf};
+use serde_json::Value as JsonValue;
+use std::{
+  collections::BTreeMap,
+  env::current_dir,
+  fs::remove_dir_all,
+  path::{Path, PathBuf},
+};
 
 const BACKEND_PLUGIN_DIR: Dir<'_> = include_dir!("templates/plugin/backend");
 const API_PLUGIN_DIR: Dir<'_> = include_dir!("templates/plugin/with-api");
@@ -42,83 +48,21 @@ pub struct Options {
   author: Option<String>,
 }
 
-impl Options {
-  fn load(&mut self) {
-    if self.author.is_none() {
-      self.author.replace(if self.tauri {
-        "Tauri Programme within The Commons Conservancy".into()
-      } else {
-        "You".into()
-      });
-    }
-  }
-}
-
-pub fn command(mut options: Options) -> Result<()> {
-  options.load();
-  let template_target_path = PathBuf::from(options.directory).join(&format!(
-    "tauri-plugin-{}",
-    AsKebabCase(&options.plugin_name)
-  ));
-  let metadata = serde_json::from_str::<VersionMetadata>(include_str!("../../metadata.json"))?;
+pub fn command(options: Options) -> Result<()> {
+  let template_target_path = plugin_dir(&options.directory, &options.plugin_name);
   if template_target_path.exists() {
     warn!("Plugin dir ({:?}) not empty.", template_target_path);
   } else {
-    let (tauri_dep, tauri_example_dep, tauri_build_dep) =
-      if let Some(tauri_path) = options.tauri_path {
-        (
-          format!(
-            r#"{{  path = {:?} }}"#,
-            resolve_tauri_path(&tauri_path, "core/tauri")
-          ),
-          format!(
-            r#"{{  path = {:?}, features = [ "api-all" ] }}"#,
-            resolve_tauri_path(&tauri_path, "core/tauri")
-          ),
-          format!(
-            "{{  path = {:?} }}",
-            resolve_tauri_path(&tauri_path, "core/tauri-build")
-          ),
-        )
-      } else {
-        (
-          format!(r#"{{ version = "{}" }}"#, metadata.tauri),
-          format!(
-            r#"{{ version = "{}", features = [ "api-all" ] }}"#,
-            metadata.tauri
-          ),
-          format!(r#"{{ version = "{}" }}"#, metadata.tauri_build),
-        )
-      };
+    let data = template_data(
+      &options.plugin_name,
+      options.author,
+      options.tauri,
+      options.tauri_path.as_deref(),
+    )?;
 
     let _ = remove_dir_all(&template_target_path);
     let handlebars = Handlebars::new();
 
-    let mut data = BTreeMap::new();
-    data.insert("plugin_name_original", to_json(&options.plugin_name));
-    data.insert("plugin_name", to_json(options.plugin_name.to_kebab_case()));
-    data.insert(
-      "plugin_name_snake_case",
-      to_json(options.plugin_name.to_snake_case()),
-    );
-    data.insert("tauri_dep", to_json(tauri_dep));
-    data.insert("tauri_example_dep", to_json(tauri_example_dep));
-    data.insert("tauri_build_dep", to_json(tauri_build_dep));
-    data.insert("author", to_json(options.author));
-
-    if options.tauri {
-      data.insert(
-        "license_header",
-        to_json(
-          "// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
-             // SPDX-License-Identifier: Apache-2.0
-             // SPDX-License-Identifier: MIT\n\n"
-            .replace("  ", "")
-            .replace(" //", "//"),
-        ),
-      );
-    }
-
     template::render(
       &handlebars,
       &data,
@@ -133,3 +77,77 @@ pub fn command(mut options: Options) -> Result<()> {
   }
   Ok(())
 }
+
+/// The directory of the plugin project, `<directory>/tauri-plugin-<name>`.
+pub(super) fn plugin_dir(directory: &str, plugin_name: &str) -> PathBuf {
+  PathBuf::from(directory).join(format!("tauri-plugin-{}", AsKebabCase(plugin_name)))
+}
+
+/// The data of the plugin templates, shared by `tauri plugin init` and `tauri plugin new`.
+pub(super) fn template_data(
+  plugin_name: &str,
+  author: Option<String>,
+  tauri: bool,
+  tauri_path: Option<&Path>,
+) -> Result<BTreeMap<&'static str, JsonValue>> {
+  let metadata = serde_json::from_str::<VersionMetadata>(include_str!("../../metadata.json"))?;
+  let (tauri_dep, tauri_example_dep, tauri_build_dep) = if let Some(tauri_path) = tauri_path {
+    (
+      format!(
+        r#"{{  path = {:?} }}"#,
+        resolve_tauri_path(tauri_path, "core/tauri")
+      ),
+      format!(
+        r#"{{  path = {:?}, features = [ "api-all" ] }}"#,
+        resolve_tauri_path(tauri_path, "core/tauri")
+      ),
+      format!(
+        "{{  path = {:?} }}",
+        resolve_tauri_path(tauri_path, "core/tauri-build")
+      ),
+    )
+  } else {
+    (
+      format!(r#"{{ version = "{}" }}"#, metadata.tauri),
+      format!(
+        r#"{{ version = "{}", features = [ "api-all" ] }}"#,
+        metadata.tauri
+      ),
+      format!(r#"{{ version = "{}" }}"#, metadata.tauri_build),
+    )
+  };
+  let author = author.unwrap_or_else(|| {
+    if tauri {
+      "Tauri Programme within The Commons Conservancy".into()
+    } else {
+      "You".into()
+    }
+  });
+
+  let mut data = BTreeMap::new();
+  data.insert("plugin_name_original", to_json(plugin_name));
+  data.insert("plugin_name", to_json(plugin_name.to_kebab_case()));
+  data.insert(
+    "plugin_name_snake_case",
+    to_json(plugin_name.to_snake_case()),
+  );
+  data.insert("tauri_dep", to_json(tauri_dep));
+  data.insert("tauri_example_dep", to_json(tauri_example_dep));
+  data.insert("tauri_build_dep", to_json(tauri_build_dep));
+  data.insert("author", to_json(author));
+
+  if tauri {
+    data.insert(
+      "license_header",
+      to_json(
+        "// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+           // SPDX-License-Identifier: Apache-2.0
+           // SPDX-License-Identifier: MIT\n\n"
+          .replace("  ", "")
+          .replace(" //", "//"),
+      ),
+    );
+  }
+
+  Ok(data)
+}
@@ -0,0 +1,142 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::init::{plugin_dir, template_data};
+use crate::{helpers::template, Result};
+use anyhow::Context;
+use clap::Parser;
+use handlebars::{to_json, Handlebars};
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use include_dir::{include_dir, Dir};
+use std::{
+  env::current_dir,
+  ffi::OsString,
+  fs::{create_dir_all, File},
+  path::{Component, PathBuf},
+};
+
+const TEMPLATE_DIR: Dir<'_> = include_dir!("templates/plugin/new");
+
+#[derive(Debug, Parser)]
+#[clap(about = "Creates a new Tauri plugin project")]
+pub struct Options {
+  /// Name of your Tauri plugin
+  plugin_name: String,
+  /// Do not create the TypeScript API package
+  #[clap(long)]
+  no_api: bool,
+  /// Do not create the example app
+  #[clap(long)]
+  no_example: bool,
+  /// Create an Android library for the Kotlin side of the plugin
+  #[clap(long)]
+  android: bool,
+  /// Create a Swift package for the iOS side of the plugin
+  #[clap(long)]
+  ios: bool,
+  /// Initializes a Tauri core plugin (internal usage)
+  #[clap(long, hide(true))]
+  tauri: bool,
+  /// Set target directory for the plugin project
+  #[clap(short, long)]
+  #[clap(default_value_t = current_dir().expect("failed to read cwd").display().to_string())]
+  directory: String,
+  /// Path of the Tauri project to use (relative to the cwd)
+  #[clap(short, long)]
+  tauri_path: Option<PathBuf>,
+  /// Author name
+  #[clap(short, long)]
+  author: Option<String>,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let template_target_path = plugin_dir(&options.directory, &options.plugin_name);
+  if template_target_path.exists() {
+    anyhow::bail!(
+      "Plugin dir ({:?}) already exists, use `tauri plugin init` to initialize an existing directory.",
+      template_target_path
+    );
+  }
+
+  let plugin_class = format!("{}Plugin", options.plugin_name.to_upper_camel_case());
+  let android_package = format!("app.tauri.{}", options.plugin_name.to_snake_case());
+
+  let mut data = template_data(
+    &options.plugin_name,
+    options.author,
+    options.tauri,
+    options.tauri_path.as_deref(),
+  )?;
+  data.insert(
+    "plugin_name_pascal_case",
+    to_json(options.plugin_name.to_upper_camel_case()),
+  );
+  data.insert("android_package", to_json(&android_package));
+  data.insert("api", to_json(!options.no_api));
+  data.insert("example", to_json(!options.no_example));
+  data.insert("android", to_json(options.android));
+  data.insert("ios", to_json(options.ios));
+
+  let handlebars = Handlebars::new();
+  let mut created_dirs = Vec::new();
+  template::render_with_generator(
+    &handlebars,
+    &data,
+    &TEMPLATE_DIR,
+    &template_target_path,
+    &mut |path| {
+      let root = path
+        .components()
+        .next()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .unwrap_or_default();
+      let skip = match root.as_str() {
+        "webview-src" | "package.json" => options.no_api,
+        "examples" => options.no_example,
+        "android" => !options.android,
+        "ios" => !options.ios,
+        _ => false,
+      };
+      if skip {
+        return Ok(None);
+      }
+
+      // names the native sources after the plugin and moves the Kotlin sources to their package directory
+      let mut components: Vec<OsString> = Vec::new();
+      for component in path.components() {
+        if let Component::Normal(c) = component {
+          let c = c.to_string_lossy();
+          components.push(c.replace("ExamplePlugin", &plugin_class).into());
+          if root == "android" && c == "java" {
+            components.extend(android_package.split('.').map(OsString::from));
+          }
+        }
+      }
+      let path = template_target_path.join(components.iter().collect::<PathBuf>());
+
+      let parent = path.parent().unwrap().to_path_buf();
+      if !created_dirs.contains(&parent) {
+        create_dir_all(&parent)?;
+        created_dirs.push(parent);
+      }
+      File::create(path).map(Some)
+    },
+  )
+  .with_context(|| "failed to render Tauri plugin template")?;
+
+  if !options.no_example {
+    // the example app uses the icons of the app template
+    template::render(
+      &handlebars,
+      &data,
+      crate::init::TEMPLATE_DIR
+        .get_dir("src-tauri/icons")
+        .expect("missing app template icons"),
+      template_target_path.join("examples").join("tauri-app"),
+    )
+    .with_context(|| "failed to copy the example app icons")?;
+  }
+
+  Ok(())
+}
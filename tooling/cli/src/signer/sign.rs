@@ -5,7 +5,7 @@
 use std::path::{Path, PathBuf};
 
 use crate::{
-  helpers::updater_signature::{read_key_from_file, secret_key, sign_file},
+  helpers::updater_signature::{read_key_from_file, secret_key, sign_file, sign_file_with_agent},
   Result,
 };
 use anyhow::Context;
@@ -23,11 +23,22 @@ pub struct Options {
   /// Set private key password when signing
   #[clap(short, long)]
   password: Option<String>,
+  /// Sign with an external minisign-compatible agent instead of a private key, e.g. backed by the OS keychain or a hardware token.
+  /// `%1` in the command line is replaced with the path of the file; the agent must print the signature to stdout.
+  #[clap(long, conflicts_with_all(&["private-key", "private-key-path"]))]
+  agent: Option<String>,
   /// Sign the specified file
   file: PathBuf,
 }
 
 pub fn command(mut options: Options) -> Result<()> {
+  if let Some(agent) = &options.agent {
+    let (signature_path, signature) =
+      sign_file_with_agent(agent, &options.file).with_context(|| "failed to sign file")?;
+    print_signature(&signature_path, &signature);
+    return Ok(());
+  }
+
   options.private_key = if let Some(private_key) = options.private_key_path {
     Some(read_key_from_file(Path::new(&private_key)).expect("Unable to extract private key"))
   } else {
@@ -49,11 +60,15 @@ pub fn command(mut options: Options) -> Result<()> {
     sign_file(&secret_key(private_key, options.password)?, options.file)
       .with_context(|| "failed to sign file")?;
 
+  print_signature(&manifest_dir, &signature);
+
+  Ok(())
+}
+
+fn print_signature(signature_path: &Path, signature: &minisign::SignatureBox) {
   println!(
            "\nYour file was signed successfully, You can find the signature here:\n{}\n\nPublic signature:\n{}\n\nMake sure to include this into the signature field of your update server.",
-           manifest_dir.display(),
+           signature_path.display(),
            base64::encode(signature.to_string())
          );
-
-  Ok(())
 }
@@ -0,0 +1,304 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  helpers::{
+    config::{get as get_config, merge_config_args},
+    updater_platform,
+  },
+  Result,
+};
+use anyhow::{bail, Context};
+use clap::Parser;
+use log::{info, warn};
+use semver::Version;
+use serde_json::Value;
+
+use std::io::{Cursor, Read};
+
+#[derive(Debug, Parser)]
+#[clap(
+  about = "Request the updater endpoints as the app would and report whether an update is offered"
+)]
+pub struct Options {
+  /// Target triple of the app checking for updates. Defaults to the host
+  #[clap(short, long)]
+  target: Option<String>,
+  /// Version of the app checking for updates. Defaults to the app version
+  #[clap(long)]
+  current_version: Option<String>,
+  /// Do not download the update to verify its signature
+  #[clap(long)]
+  no_download: bool,
+  /// JSON string or path to a JSON, JSON5 or TOML file to merge with tauri.conf.json. Can be used multiple times, the later values take precedence
+  #[clap(short, long, multiple_occurrences(true))]
+  config: Vec<String>,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let merge_config = merge_config_args(&options.config)?;
+  let config = get_config(true, merge_config.as_deref())?;
+  let config_guard = config.lock().unwrap();
+  let config_ = config_guard.as_ref().unwrap();
+  let updater = &config_.tauri.updater;
+
+  if !updater.active {
+    warn!("The updater is not active in `tauri.conf.json > tauri > updater > active`, so the app does not check for updates.");
+  }
+  let endpoints = updater.endpoints.as_deref().unwrap_or_default();
+  if endpoints.is_empty() {
+    bail!("`tauri.conf.json > tauri > updater > endpoints` is empty, so the app has no update server to check.");
+  }
+
+  let current_version = match options.current_version.or_else(|| config_.package.version.clone()) {
+    Some(version) => Version::parse(version.trim_start_matches('v'))
+      .with_context(|| format!("invalid current version `{}`", version))?,
+    None => bail!("`tauri.conf.json > package > version` is not set, pass the version of the app with `--current-version`."),
+  };
+  let target_triple = match options.target {
+    Some(target) => target,
+    None => tauri_utils::platform::target_triple()?,
+  };
+  let (target, arch) = updater_platform(&target_triple).ok_or_else(|| {
+    anyhow::anyhow!(
+      "the updater does not support the `{}` target",
+      target_triple
+    )
+  })?;
+  let json_target = format!("{}-{}", target, arch);
+
+  // the endpoints are tried in order until a response can be read, as in the runtime updater
+  let mut response = None;
+  for endpoint in endpoints {
+    // the runtime percent-decodes the endpoint before replacing the variables
+    let url = endpoint
+      .to_string()
+      .replace("%7B%7B", "{{")
+      .replace("%7D%7D", "}}")
+      .replace("{{current_version}}", &current_version.to_string())
+      .replace("{{target}}", target)
+      .replace("{{arch}}", arch);
+    info!(action = "Requesting"; "{}", url);
+
+    let res = match ureq::get(&url).set("Accept", "application/json").call() {
+      Ok(res) => res,
+      Err(ureq::Error::Status(status, _)) => {
+        warn!(
+          "The endpoint answered with the {} status, trying the next endpoint.",
+          status
+        );
+        continue;
+      }
+      Err(ureq::Error::Transport(transport)) => {
+        warn!(
+          "The endpoint cannot be reached ({}), trying the next endpoint.",
+          transport
+        );
+        continue;
+      }
+    };
+    if res.status() == 204 {
+      info!(
+        "No update offered: the endpoint answered with `204 No Content`, so the app is up to date."
+      );
+      return Ok(());
+    }
+
+    let body = res.into_string()?;
+    let value = match serde_json::from_str::<Value>(&body) {
+      Ok(value) => value,
+      Err(e) => {
+        warn!(
+          "The response is not JSON ({}), trying the next endpoint.",
+          e
+        );
+        continue;
+      }
+    };
+    match read_version(&value) {
+      Ok(version) => {
+        response = Some((version, value));
+        break;
+      }
+      Err(e) => warn!(
+        "The response is invalid: {:#}, trying the next endpoint.",
+        e
+      ),
+    }
+  }
+
+  let (version, value) = match response {
+    Some(response) => response,
+    None => bail!("No update offered: none of the endpoints returned a valid response."),
+  };
+  // the runtime reads the platform before comparing the versions
+  let (url, signature) = read_platform(&value, &json_target)?;
+  if version <= current_version {
+    info!(
+      "No update offered: the version {} of the response is not greater than the current version {}.",
+      version,
+      current_version
+    );
+    return Ok(());
+  }
+  info!("Update offered: version {} from {}.", version, url);
+
+  if options.no_download {
+    return Ok(());
+  }
+  info!(action = "Downloading"; "{}", url);
+  let mut archive = Vec::new();
+  ureq::get(&url)
+    .call()
+    .with_context(|| format!("failed to download {}", url))?
+    .into_reader()
+    .read_to_end(&mut archive)
+    .with_context(|| format!("failed to download {}", url))?;
+  verify_signature(&archive, &signature, &updater.pubkey)
+    .context("the update would be rejected: its signature cannot be verified with `tauri.conf.json > tauri > updater > pubkey`")?;
+  info!("The signature of the update matches the public key.");
+  Ok(())
+}
+
+/// Validates the updater response as the runtime updater deserializes it, returning the announced version.
+fn read_version(value: &Value) -> Result<Version> {
+  let version = value
+    .get("version")
+    .or_else(|| value.get("name"))
+    .context("the `version` field is missing")?
+    .as_str()
+    .context("the `version` field is not a string")?;
+  let version = Version::parse(version.trim_start_matches('v'))
+    .with_context(|| format!("the `version` field `{}` is not a semver version", version))?;
+  match value.get("notes") {
+    None | Some(Value::Null) | Some(Value::String(_)) => (),
+    Some(_) => bail!("the `notes` field is not a string"),
+  }
+  match value.get("pub_date") {
+    None | Some(Value::Null) => (),
+    Some(Value::String(date)) if is_rfc3339(date) => (),
+    Some(date) => bail!("the `pub_date` field {} is not a RFC 3339 date", date),
+  }
+
+  match value.get("platforms") {
+    Some(Value::Object(platforms)) => {
+      for (key, platform) in platforms {
+        read_url_and_signature(platform)
+          .with_context(|| format!("invalid `platforms > {}`", key))?;
+      }
+    }
+    Some(Value::Null) | None => {
+      read_url_and_signature(value)?;
+    }
+    Some(_) => bail!("the `platforms` field is not an object"),
+  }
+  Ok(version)
+}
+
+/// The `url` and `signature` of the platform, from the `platforms` of a static JSON or the dynamic response.
+fn read_platform(value: &Value, json_target: &str) -> Result<(String, String)> {
+  match value.get("platforms").and_then(Value::as_object) {
+    Some(platforms) => {
+      let platform = platforms.get(json_target).with_context(|| {
+        format!(
+          "the update would fail: the response has no `{}` platform, the platforms are {}",
+          json_target,
+          platforms
+            .keys()
+            .map(|key| format!("`{}`", key))
+            .collect::<Vec<_>>()
+            .join(", ")
+        )
+      })?;
+      read_url_and_signature(platform)
+    }
+    None => read_url_and_signature(value),
+  }
+}
+
+fn read_url_and_signature(value: &Value) -> Result<(String, String)> {
+  let url = value
+    .get("url")
+    .context("the `url` field is missing")?
+    .as_str()
+    .context("the `url` field is not a string")?;
+  url::Url::parse(url).with_context(|| format!("the `url` field `{}` is not a URL", url))?;
+  let signature = value
+    .get("signature")
+    .context("the `signature` field is missing")?
+    .as_str()
+    .context("the `signature` field is not a string")?;
+  Ok((url.into(), signature.into()))
+}
+
+/// Whether the date is a RFC 3339 date time, e.g. `2022-09-01T12:00:00Z`.
+fn is_rfc3339(date: &str) -> bool {
+  regex::Regex::new(r"^\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})$")
+    .unwrap()
+    .is_match(date)
+}
+
+/// Verifies the signature of the update as the runtime updater does.
+fn verify_signature(archive: &[u8], signature: &str, pubkey: &str) -> Result<()> {
+  let pubkey = String::from_utf8(base64::decode(pubkey)?)?;
+  let public_key = minisign::PublicKeyBox::from_string(&pubkey)?.into_public_key()?;
+  let signature = String::from_utf8(base64::decode(signature)?)?;
+  let signature = minisign::SignatureBox::from_string(&signature)?;
+  minisign::verify(
+    &public_key,
+    &signature,
+    Cursor::new(archive),
+    true,
+    false,
+    true,
+  )?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{is_rfc3339, read_platform, read_version};
+  use serde_json::json;
+
+  #[test]
+  fn reads_static_and_dynamic_responses() {
+    let response = json!({
+      "version": "v1.1.0",
+      "pub_date": "2022-09-01T12:00:00Z",
+      "platforms": {
+        "linux-x86_64": { "url": "https://cdn.app.dev/app.AppImage.tar.gz", "signature": "sig" }
+      }
+    });
+    assert_eq!(read_version(&response).unwrap(), "1.1.0".parse().unwrap());
+    assert_eq!(
+      read_platform(&response, "linux-x86_64").unwrap(),
+      (
+        "https://cdn.app.dev/app.AppImage.tar.gz".into(),
+        "sig".into()
+      )
+    );
+    let error = read_platform(&response, "darwin-aarch64")
+      .unwrap_err()
+      .to_string();
+    assert!(error.contains("no `darwin-aarch64` platform"), "{}", error);
+
+    let response =
+      json!({ "name": "1.1.0", "url": "https://cdn.app.dev/app.zip", "signature": "sig" });
+    assert!(read_version(&response).is_ok());
+    assert!(read_platform(&response, "windows-x86_64").is_ok());
+
+    let response = json!({ "version": "1.1.0", "url": "https://cdn.app.dev/app.zip" });
+    assert!(read_version(&response).is_err());
+    let response =
+      json!({ "version": "latest", "url": "https://cdn.app.dev/app.zip", "signature": "sig" });
+    assert!(read_version(&response).is_err());
+  }
+
+  #[test]
+  fn validates_rfc3339_dates() {
+    assert!(is_rfc3339("2022-09-01T12:00:00Z"));
+    assert!(is_rfc3339("2022-09-01T12:00:00.123+02:00"));
+    assert!(!is_rfc3339("2022-09-01"));
+  }
+}
@@ -0,0 +1,3 @@
+package {{reverse-domain app.domain}}.{{snake-case app.name}};
+
+public class MainActivity extends TauriActivity {}
@@ -0,0 +1,12 @@
+package {{ android_package }}
+
+import android.app.Activity
+
+/**
+ * The Android side of the plugin, called from Rust through JNI, e.g. with `Window::with_webview`.
+ */
+class {{ plugin_name_pascal_case }}Plugin(private val activity: Activity) {
+    fun ping(value: String?): String? {
+        return value
+    }
+}
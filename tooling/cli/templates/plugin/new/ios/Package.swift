@@ -0,0 +1,21 @@
+// swift-tools-version:5.3
+
+import PackageDescription
+
+let package = Package(
+    name: "tauri-plugin-{{ plugin_name }}",
+    platforms: [
+        .iOS(.v13),
+    ],
+    products: [
+        .library(
+            name: "tauri-plugin-{{ plugin_name }}",
+            type: .static,
+            targets: ["{{ plugin_name_pascal_case }}Plugin"]),
+    ],
+    targets: [
+        .target(
+            name: "{{ plugin_name_pascal_case }}Plugin",
+            path: "Sources/{{ plugin_name_pascal_case }}Plugin"),
+    ]
+)
@@ -0,0 +1,10 @@
+import Foundation
+
+/// The iOS side of the plugin, exported with the C ABI so it can be called from Rust.
+@_cdecl("tauri_plugin_{{ plugin_name_snake_case }}_ping")
+public func ping(_ value: UnsafePointer<CChar>?) -> UnsafePointer<CChar>? {
+    guard let value = value else {
+        return nil
+    }
+    return UnsafePointer(strdup(String(cString: value)))
+}
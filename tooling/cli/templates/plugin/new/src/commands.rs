@@ -0,0 +1,16 @@
+{{#if license_header}}
+{{ license_header }}
+{{/if}}
+use tauri::{command, AppHandle, Runtime};
+
+use crate::{PingRequest, PingResponse, Result};
+
+#[command]
+pub(crate) async fn ping<R: Runtime>(
+  _app: AppHandle<R>,
+  payload: PingRequest,
+) -> Result<PingResponse> {
+  Ok(PingResponse {
+    value: payload.value,
+  })
+}
@@ -0,0 +1,21 @@
+{{#if license_header}}
+{{ license_header }}
+{{/if}}
+use tauri::{
+  plugin::{Builder, TauriPlugin},
+  Runtime,
+};
+
+mod commands;
+mod error;
+mod models;
+
+pub use error::{Error, Result};
+pub use models::*;
+
+/// Initializes the plugin.
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("{{ plugin_name }}")
+    .invoke_handler(tauri::generate_handler![commands::ping])
+    .build()
+}